@@ -0,0 +1,43 @@
+-- Split Transactions
+-- One transaction can carry several category lines (e.g. a supermarket receipt
+-- that is part groceries, part household). Lines must sum to the transaction amount.
+-- Unsplit transactions keep using transactions.category_id.
+CREATE TABLE IF NOT EXISTS transaction_splits (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_id INTEGER NOT NULL,
+    category_id INTEGER NOT NULL,
+    amount REAL NOT NULL CHECK(amount > 0),
+    memo TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE,
+    FOREIGN KEY (category_id) REFERENCES categories(id)
+);
+
+CREATE INDEX idx_transaction_splits_transaction ON transaction_splits(transaction_id);
+CREATE INDEX idx_transaction_splits_category ON transaction_splits(category_id);
+
+-- Category-level view of transactions: one row per category line.
+-- Unsplit transactions appear once with their own category; split transactions
+-- appear once per split line. Reports should aggregate over this view.
+CREATE VIEW IF NOT EXISTS transaction_category_lines AS
+SELECT
+    t.id AS id,
+    t.date AS date,
+    t.type AS type,
+    t.account_id AS account_id,
+    t.category_id AS category_id,
+    t.amount AS amount,
+    t.memo AS memo
+FROM transactions t
+WHERE NOT EXISTS (SELECT 1 FROM transaction_splits s WHERE s.transaction_id = t.id)
+UNION ALL
+SELECT
+    t.id AS id,
+    t.date AS date,
+    t.type AS type,
+    t.account_id AS account_id,
+    s.category_id AS category_id,
+    s.amount AS amount,
+    COALESCE(s.memo, t.memo) AS memo
+FROM transaction_splits s
+INNER JOIN transactions t ON s.transaction_id = t.id;
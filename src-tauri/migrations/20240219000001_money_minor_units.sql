@@ -0,0 +1,442 @@
+-- Money as integer minor units
+-- Every amount column moves from REAL to INTEGER holding minor units of the
+-- currency it is denominated in (cents for LKR/USD/..., whole yen for JPY).
+-- Account-linked amounts use the account's currency; budgets, net worth
+-- snapshots and unlinked goals/templates use the primary currency.
+-- SQLite cannot change a column type in place, so each table is rebuilt.
+
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+-- _currency_factor (code, factor) is filled by the app before migrating, from
+-- the same supported-currency list that decides decimals at runtime.
+-- Unknown codes get 2 decimal places, as they do in the app.
+CREATE TEMP TABLE _account_factor AS
+SELECT a.id AS account_id, COALESCE(cf.factor, 100) AS factor
+FROM accounts a
+LEFT JOIN _currency_factor cf ON cf.code = UPPER(a.currency);
+
+CREATE TEMP TABLE _primary_factor AS
+SELECT COALESCE((
+    SELECT factor FROM _currency_factor
+    WHERE code = UPPER(COALESCE((SELECT value FROM app_settings WHERE key = 'primary_currency'), 'LKR'))
+), 100) AS factor;
+
+DROP VIEW IF EXISTS transaction_category_lines;
+
+-- accounts
+CREATE TABLE accounts_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    group_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    initial_balance INTEGER NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'LKR',
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (group_id) REFERENCES account_groups(id)
+);
+INSERT INTO accounts_new (id, group_id, name, initial_balance, currency, created_at)
+SELECT a.id, a.group_id, a.name, CAST(ROUND(a.initial_balance * f.factor) AS INTEGER), a.currency, a.created_at
+FROM accounts a JOIN _account_factor f ON f.account_id = a.id;
+DROP TABLE accounts;
+ALTER TABLE accounts_new RENAME TO accounts;
+
+-- transactions
+CREATE TABLE transactions_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date TEXT NOT NULL,
+    type TEXT NOT NULL CHECK(type IN ('INCOME', 'EXPENSE', 'TRANSFER')),
+    amount INTEGER NOT NULL CHECK(amount > 0),
+    account_id INTEGER NOT NULL,
+    to_account_id INTEGER, -- NULL for income/expense, filled for transfers
+    category_id INTEGER,
+    memo TEXT,
+    photo_path TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    import_batch_id TEXT,
+    FOREIGN KEY (account_id) REFERENCES accounts(id),
+    FOREIGN KEY (to_account_id) REFERENCES accounts(id),
+    FOREIGN KEY (category_id) REFERENCES categories(id)
+);
+INSERT INTO transactions_new (id, date, type, amount, account_id, to_account_id, category_id, memo, photo_path, created_at, import_batch_id)
+SELECT t.id, t.date, t.type, CAST(ROUND(t.amount * f.factor) AS INTEGER), t.account_id, t.to_account_id,
+       t.category_id, t.memo, t.photo_path, t.created_at, t.import_batch_id
+FROM transactions t JOIN _account_factor f ON f.account_id = t.account_id;
+DROP TABLE transactions;
+ALTER TABLE transactions_new RENAME TO transactions;
+CREATE INDEX idx_transactions_date ON transactions(date);
+
+-- journal_entries (each entry in its own account's currency)
+CREATE TABLE journal_entries_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_id INTEGER NOT NULL,
+    account_id INTEGER NOT NULL,
+    debit INTEGER NOT NULL DEFAULT 0 CHECK(debit >= 0),
+    credit INTEGER NOT NULL DEFAULT 0 CHECK(credit >= 0),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE,
+    FOREIGN KEY (account_id) REFERENCES accounts(id),
+    CHECK ((debit > 0 AND credit = 0) OR (credit > 0 AND debit = 0))
+);
+INSERT INTO journal_entries_new (id, transaction_id, account_id, debit, credit, created_at)
+SELECT j.id, j.transaction_id, j.account_id,
+       CAST(ROUND(j.debit * f.factor) AS INTEGER), CAST(ROUND(j.credit * f.factor) AS INTEGER), j.created_at
+FROM journal_entries j JOIN _account_factor f ON f.account_id = j.account_id;
+DROP TABLE journal_entries;
+ALTER TABLE journal_entries_new RENAME TO journal_entries;
+CREATE INDEX idx_journal_account ON journal_entries(account_id);
+CREATE INDEX idx_journal_transaction ON journal_entries(transaction_id);
+
+-- transaction_splits (transaction's currency)
+CREATE TABLE transaction_splits_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    transaction_id INTEGER NOT NULL,
+    category_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL CHECK(amount > 0),
+    memo TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE,
+    FOREIGN KEY (category_id) REFERENCES categories(id)
+);
+INSERT INTO transaction_splits_new (id, transaction_id, category_id, amount, memo, created_at)
+SELECT s.id, s.transaction_id, s.category_id, CAST(ROUND(s.amount * f.factor) AS INTEGER), s.memo, s.created_at
+FROM transaction_splits s
+JOIN transactions t ON t.id = s.transaction_id
+JOIN _account_factor f ON f.account_id = t.account_id;
+DROP TABLE transaction_splits;
+ALTER TABLE transaction_splits_new RENAME TO transaction_splits;
+CREATE INDEX idx_transaction_splits_transaction ON transaction_splits(transaction_id);
+CREATE INDEX idx_transaction_splits_category ON transaction_splits(category_id);
+
+-- budgets (primary currency)
+CREATE TABLE budgets_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    category_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL CHECK(amount > 0),
+    period TEXT NOT NULL CHECK(period IN ('MONTHLY', 'YEARLY')),
+    start_date TEXT NOT NULL,
+    FOREIGN KEY (category_id) REFERENCES categories(id)
+);
+INSERT INTO budgets_new (id, category_id, amount, period, start_date)
+SELECT b.id, b.category_id, CAST(ROUND(b.amount * (SELECT factor FROM _primary_factor)) AS INTEGER), b.period, b.start_date
+FROM budgets b;
+DROP TABLE budgets;
+ALTER TABLE budgets_new RENAME TO budgets;
+
+-- recurring_transactions
+CREATE TABLE recurring_transactions_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    description TEXT,
+    transaction_type TEXT NOT NULL CHECK(transaction_type IN ('INCOME', 'EXPENSE', 'TRANSFER')),
+    amount INTEGER NOT NULL CHECK(amount > 0),
+    account_id INTEGER NOT NULL,
+    to_account_id INTEGER,
+    category_id INTEGER,
+    frequency TEXT NOT NULL CHECK(frequency IN ('DAILY', 'WEEKLY', 'MONTHLY', 'YEARLY', 'CUSTOM')),
+    interval_days INTEGER DEFAULT 1 CHECK(interval_days > 0),
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    next_execution_date TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1 CHECK(is_active IN (0, 1)),
+    last_executed_date TEXT,
+    execution_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    amount_mode TEXT NOT NULL DEFAULT 'FIXED' CHECK(amount_mode IN ('FIXED', 'VARIABLE')),
+    resume_date TEXT,
+    active_months TEXT,
+    auto_approve INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (account_id) REFERENCES accounts(id),
+    FOREIGN KEY (to_account_id) REFERENCES accounts(id),
+    FOREIGN KEY (category_id) REFERENCES categories(id)
+);
+INSERT INTO recurring_transactions_new (
+    id, name, description, transaction_type, amount, account_id, to_account_id, category_id,
+    frequency, interval_days, start_date, end_date, next_execution_date, is_active,
+    last_executed_date, execution_count, created_at, amount_mode, resume_date, active_months, auto_approve
+)
+SELECT r.id, r.name, r.description, r.transaction_type, CAST(ROUND(r.amount * f.factor) AS INTEGER),
+       r.account_id, r.to_account_id, r.category_id, r.frequency, r.interval_days, r.start_date, r.end_date,
+       r.next_execution_date, r.is_active, r.last_executed_date, r.execution_count, r.created_at,
+       r.amount_mode, r.resume_date, r.active_months, r.auto_approve
+FROM recurring_transactions r JOIN _account_factor f ON f.account_id = r.account_id;
+DROP TABLE recurring_transactions;
+ALTER TABLE recurring_transactions_new RENAME TO recurring_transactions;
+CREATE INDEX idx_recurring_next_execution ON recurring_transactions(next_execution_date, is_active);
+CREATE INDEX idx_recurring_active ON recurring_transactions(is_active);
+
+-- recurring_execution_log (recurring's account currency)
+CREATE TABLE recurring_execution_log_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    recurring_id INTEGER NOT NULL,
+    execution_date TEXT NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('SUCCESS', 'SKIPPED', 'FAILED', 'VARIABLE_PENDING')),
+    amount INTEGER,
+    transaction_id INTEGER,
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (recurring_id) REFERENCES recurring_transactions(id) ON DELETE CASCADE,
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE SET NULL
+);
+INSERT INTO recurring_execution_log_new (id, recurring_id, execution_date, status, amount, transaction_id, notes, created_at)
+SELECT l.id, l.recurring_id, l.execution_date, l.status,
+       CASE WHEN l.amount IS NULL THEN NULL
+            ELSE CAST(ROUND(l.amount * COALESCE(f.factor, (SELECT factor FROM _primary_factor))) AS INTEGER) END,
+       l.transaction_id, l.notes, l.created_at
+FROM recurring_execution_log l
+LEFT JOIN recurring_transactions r ON r.id = l.recurring_id
+LEFT JOIN _account_factor f ON f.account_id = r.account_id;
+DROP TABLE recurring_execution_log;
+ALTER TABLE recurring_execution_log_new RENAME TO recurring_execution_log;
+CREATE INDEX idx_exec_log_recurring ON recurring_execution_log(recurring_id, execution_date DESC);
+
+-- installment_plans
+CREATE TABLE installment_plans_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    total_amount INTEGER NOT NULL CHECK(total_amount > 0),
+    num_installments INTEGER NOT NULL CHECK(num_installments > 0),
+    amount_per_installment INTEGER NOT NULL CHECK(amount_per_installment > 0),
+    account_id INTEGER NOT NULL,
+    category_id INTEGER NOT NULL,
+    start_date TEXT NOT NULL, -- ISO 8601 format
+    frequency TEXT NOT NULL CHECK(frequency IN ('MONTHLY', 'WEEKLY', 'DAILY')),
+    next_due_date TEXT NOT NULL,
+    installments_paid INTEGER NOT NULL DEFAULT 0,
+    total_paid INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'ACTIVE' CHECK(status IN ('ACTIVE', 'COMPLETED', 'CANCELLED')),
+    memo TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE RESTRICT,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE RESTRICT
+);
+INSERT INTO installment_plans_new (
+    id, name, total_amount, num_installments, amount_per_installment, account_id, category_id,
+    start_date, frequency, next_due_date, installments_paid, total_paid, status, memo, created_at, updated_at
+)
+SELECT p.id, p.name, CAST(ROUND(p.total_amount * f.factor) AS INTEGER), p.num_installments,
+       CAST(ROUND(p.amount_per_installment * f.factor) AS INTEGER), p.account_id, p.category_id,
+       p.start_date, p.frequency, p.next_due_date, p.installments_paid,
+       CAST(ROUND(p.total_paid * f.factor) AS INTEGER), p.status, p.memo, p.created_at, p.updated_at
+FROM installment_plans p JOIN _account_factor f ON f.account_id = p.account_id;
+DROP TABLE installment_plans;
+ALTER TABLE installment_plans_new RENAME TO installment_plans;
+CREATE INDEX idx_installment_plans_status ON installment_plans(status);
+CREATE INDEX idx_installment_plans_next_due ON installment_plans(next_due_date);
+
+-- installment_payments (plan's account currency)
+CREATE TABLE installment_payments_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    installment_plan_id INTEGER NOT NULL,
+    transaction_id INTEGER NOT NULL,
+    installment_number INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    due_date TEXT NOT NULL,
+    paid_date TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (installment_plan_id) REFERENCES installment_plans(id) ON DELETE CASCADE,
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE,
+    UNIQUE(installment_plan_id, installment_number)
+);
+INSERT INTO installment_payments_new (id, installment_plan_id, transaction_id, installment_number, amount, due_date, paid_date, created_at)
+SELECT ip.id, ip.installment_plan_id, ip.transaction_id, ip.installment_number,
+       CAST(ROUND(ip.amount * f.factor) AS INTEGER), ip.due_date, ip.paid_date, ip.created_at
+FROM installment_payments ip
+JOIN installment_plans p ON p.id = ip.installment_plan_id
+JOIN _account_factor f ON f.account_id = p.account_id;
+DROP TABLE installment_payments;
+ALTER TABLE installment_payments_new RENAME TO installment_payments;
+CREATE INDEX idx_installment_payments_plan ON installment_payments(installment_plan_id);
+
+-- transaction_templates (account currency, primary when no account)
+CREATE TABLE transaction_templates_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    transaction_type TEXT NOT NULL CHECK(transaction_type IN ('INCOME', 'EXPENSE', 'TRANSFER')),
+    amount INTEGER NOT NULL CHECK(amount >= 0),
+    account_id INTEGER,
+    to_account_id INTEGER,
+    category_id INTEGER,
+    memo TEXT,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    last_used_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE SET NULL,
+    FOREIGN KEY (to_account_id) REFERENCES accounts(id) ON DELETE SET NULL,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE SET NULL
+);
+INSERT INTO transaction_templates_new (
+    id, name, transaction_type, amount, account_id, to_account_id, category_id, memo,
+    use_count, last_used_at, created_at, updated_at
+)
+SELECT tt.id, tt.name, tt.transaction_type,
+       CAST(ROUND(tt.amount * COALESCE(f.factor, (SELECT factor FROM _primary_factor))) AS INTEGER),
+       tt.account_id, tt.to_account_id, tt.category_id, tt.memo, tt.use_count, tt.last_used_at,
+       tt.created_at, tt.updated_at
+FROM transaction_templates tt LEFT JOIN _account_factor f ON f.account_id = tt.account_id;
+DROP TABLE transaction_templates;
+ALTER TABLE transaction_templates_new RENAME TO transaction_templates;
+CREATE INDEX idx_templates_type ON transaction_templates(transaction_type);
+CREATE INDEX idx_templates_use_count ON transaction_templates(use_count DESC);
+
+-- credit_card_settings (card account currency)
+CREATE TABLE credit_card_settings_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL UNIQUE,
+    credit_limit INTEGER NOT NULL DEFAULT 0 CHECK(credit_limit >= 0),
+    statement_day INTEGER NOT NULL CHECK(statement_day BETWEEN 1 AND 28),
+    payment_due_day INTEGER NOT NULL CHECK(payment_due_day BETWEEN 1 AND 28),
+    minimum_payment_percentage REAL NOT NULL DEFAULT 5.0 CHECK(minimum_payment_percentage BETWEEN 0 AND 100),
+    auto_settlement_enabled INTEGER NOT NULL DEFAULT 0, -- 0=false, 1=true
+    settlement_account_id INTEGER, -- Bank account for auto-settlement
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    FOREIGN KEY (settlement_account_id) REFERENCES accounts(id) ON DELETE SET NULL
+);
+INSERT INTO credit_card_settings_new (
+    id, account_id, credit_limit, statement_day, payment_due_day, minimum_payment_percentage,
+    auto_settlement_enabled, settlement_account_id, created_at, updated_at
+)
+SELECT c.id, c.account_id, CAST(ROUND(c.credit_limit * f.factor) AS INTEGER), c.statement_day,
+       c.payment_due_day, c.minimum_payment_percentage, c.auto_settlement_enabled,
+       c.settlement_account_id, c.created_at, c.updated_at
+FROM credit_card_settings c JOIN _account_factor f ON f.account_id = c.account_id;
+DROP TABLE credit_card_settings;
+ALTER TABLE credit_card_settings_new RENAME TO credit_card_settings;
+CREATE INDEX idx_cc_settings_account ON credit_card_settings(account_id);
+
+-- credit_card_statements (card account currency)
+CREATE TABLE credit_card_statements_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    credit_card_id INTEGER NOT NULL, -- references credit_card_settings.id
+    statement_date TEXT NOT NULL,
+    due_date TEXT NOT NULL,
+    cycle_start_date TEXT NOT NULL,
+    cycle_end_date TEXT NOT NULL,
+    opening_balance INTEGER NOT NULL DEFAULT 0,
+    total_charges INTEGER NOT NULL DEFAULT 0,
+    total_payments INTEGER NOT NULL DEFAULT 0,
+    closing_balance INTEGER NOT NULL DEFAULT 0,
+    minimum_payment INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'OPEN' CHECK(status IN ('OPEN', 'CLOSED', 'PAID', 'PARTIAL', 'OVERDUE')),
+    paid_amount INTEGER NOT NULL DEFAULT 0,
+    paid_date TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (credit_card_id) REFERENCES credit_card_settings(id) ON DELETE CASCADE
+);
+INSERT INTO credit_card_statements_new (
+    id, credit_card_id, statement_date, due_date, cycle_start_date, cycle_end_date,
+    opening_balance, total_charges, total_payments, closing_balance, minimum_payment,
+    status, paid_amount, paid_date, created_at
+)
+SELECT s.id, s.credit_card_id, s.statement_date, s.due_date, s.cycle_start_date, s.cycle_end_date,
+       CAST(ROUND(s.opening_balance * f.factor) AS INTEGER), CAST(ROUND(s.total_charges * f.factor) AS INTEGER),
+       CAST(ROUND(s.total_payments * f.factor) AS INTEGER), CAST(ROUND(s.closing_balance * f.factor) AS INTEGER),
+       CAST(ROUND(s.minimum_payment * f.factor) AS INTEGER), s.status,
+       CAST(ROUND(s.paid_amount * f.factor) AS INTEGER), s.paid_date, s.created_at
+FROM credit_card_statements s
+JOIN credit_card_settings c ON c.id = s.credit_card_id
+JOIN _account_factor f ON f.account_id = c.account_id;
+DROP TABLE credit_card_statements;
+ALTER TABLE credit_card_statements_new RENAME TO credit_card_statements;
+CREATE INDEX idx_cc_statements_card ON credit_card_statements(credit_card_id);
+CREATE INDEX idx_cc_statements_dates ON credit_card_statements(cycle_start_date, cycle_end_date);
+CREATE INDEX idx_cc_statements_status ON credit_card_statements(status);
+
+-- net_worth_snapshots (primary currency)
+CREATE TABLE net_worth_snapshots_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    snapshot_date TEXT NOT NULL,
+    total_assets INTEGER NOT NULL DEFAULT 0,
+    total_liabilities INTEGER NOT NULL DEFAULT 0,
+    net_worth INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+INSERT INTO net_worth_snapshots_new (id, snapshot_date, total_assets, total_liabilities, net_worth, created_at)
+SELECT n.id, n.snapshot_date,
+       CAST(ROUND(n.total_assets * p.factor) AS INTEGER),
+       CAST(ROUND(n.total_liabilities * p.factor) AS INTEGER),
+       CAST(ROUND(n.net_worth * p.factor) AS INTEGER), n.created_at
+FROM net_worth_snapshots n, _primary_factor p;
+DROP TABLE net_worth_snapshots;
+ALTER TABLE net_worth_snapshots_new RENAME TO net_worth_snapshots;
+CREATE UNIQUE INDEX idx_networth_date ON net_worth_snapshots(snapshot_date);
+
+-- savings_goals (linked account currency, primary when unlinked)
+CREATE TEMP TABLE _goal_factor AS
+SELECT g.id AS goal_id, COALESCE(f.factor, (SELECT factor FROM _primary_factor)) AS factor
+FROM savings_goals g LEFT JOIN _account_factor f ON f.account_id = g.linked_account_id;
+
+CREATE TABLE savings_goals_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    target_amount INTEGER NOT NULL,
+    target_date TEXT,
+    linked_account_id INTEGER REFERENCES accounts(id) ON DELETE SET NULL,
+    color TEXT NOT NULL DEFAULT '#6B7280',
+    icon TEXT NOT NULL DEFAULT 'target',
+    status TEXT NOT NULL DEFAULT 'ACTIVE' CHECK(status IN ('ACTIVE','PAUSED','COMPLETED','ARCHIVED')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+INSERT INTO savings_goals_new (id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at)
+SELECT g.id, g.name, CAST(ROUND(g.target_amount * gf.factor) AS INTEGER), g.target_date, g.linked_account_id,
+       g.color, g.icon, g.status, g.created_at, g.updated_at
+FROM savings_goals g JOIN _goal_factor gf ON gf.goal_id = g.id;
+DROP TABLE savings_goals;
+ALTER TABLE savings_goals_new RENAME TO savings_goals;
+
+-- goal_contributions (goal's currency)
+CREATE TABLE goal_contributions_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    goal_id INTEGER NOT NULL REFERENCES savings_goals(id) ON DELETE CASCADE,
+    amount INTEGER NOT NULL,
+    contribution_date TEXT NOT NULL,
+    note TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    transaction_id INTEGER REFERENCES transactions(id) ON DELETE SET NULL,
+    contribution_type TEXT NOT NULL DEFAULT 'MANUAL' CHECK(contribution_type IN ('MANUAL', 'TRANSACTION', 'WITHDRAWAL'))
+);
+INSERT INTO goal_contributions_new (id, goal_id, amount, contribution_date, note, created_at, transaction_id, contribution_type)
+SELECT c.id, c.goal_id,
+       CAST(ROUND(c.amount * COALESCE(gf.factor, (SELECT factor FROM _primary_factor))) AS INTEGER),
+       c.contribution_date, c.note, c.created_at, c.transaction_id, c.contribution_type
+FROM goal_contributions c LEFT JOIN _goal_factor gf ON gf.goal_id = c.goal_id;
+DROP TABLE goal_contributions;
+ALTER TABLE goal_contributions_new RENAME TO goal_contributions;
+CREATE INDEX idx_goal_contributions_transaction ON goal_contributions(transaction_id);
+CREATE INDEX idx_goal_contributions_goal ON goal_contributions(goal_id);
+
+CREATE VIEW transaction_category_lines AS
+SELECT
+    t.id AS id,
+    t.date AS date,
+    t.type AS type,
+    t.account_id AS account_id,
+    t.category_id AS category_id,
+    t.amount AS amount,
+    t.memo AS memo
+FROM transactions t
+WHERE NOT EXISTS (SELECT 1 FROM transaction_splits s WHERE s.transaction_id = t.id)
+UNION ALL
+SELECT
+    t.id AS id,
+    t.date AS date,
+    t.type AS type,
+    t.account_id AS account_id,
+    s.category_id AS category_id,
+    s.amount AS amount,
+    COALESCE(s.memo, t.memo) AS memo
+FROM transaction_splits s
+INNER JOIN transactions t ON s.transaction_id = t.id;
+
+DROP TABLE _goal_factor;
+DROP TABLE _primary_factor;
+DROP TABLE _account_factor;
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
@@ -0,0 +1,16 @@
+-- OFX/QFX import: bank-assigned transaction ids + remembered account links
+
+-- FITID from the bank statement; unique per account so re-imports can't duplicate
+ALTER TABLE transactions ADD COLUMN external_id TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_external_id
+    ON transactions(account_id, external_id)
+    WHERE external_id IS NOT NULL;
+
+-- OFX account (BANKID:ACCTID) -> app account, filled in on first import
+CREATE TABLE IF NOT EXISTS ofx_account_links (
+    account_key TEXT PRIMARY KEY,
+    account_id INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
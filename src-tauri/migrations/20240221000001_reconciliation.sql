@@ -0,0 +1,28 @@
+-- Bank reconciliation: cleared/reconciled status + per-account sessions
+
+CREATE TABLE IF NOT EXISTS reconciliations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL,
+    statement_date TEXT NOT NULL,
+    statement_balance INTEGER NOT NULL, -- minor units of the account currency
+    status TEXT NOT NULL DEFAULT 'IN_PROGRESS' CHECK(status IN ('IN_PROGRESS', 'COMPLETED')),
+    cleared_balance INTEGER,            -- recorded when the session is finished
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    completed_at TEXT,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_reconciliations_account ON reconciliations(account_id, statement_date DESC);
+
+-- Only one open session per account
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reconciliations_open
+    ON reconciliations(account_id)
+    WHERE status = 'IN_PROGRESS';
+
+ALTER TABLE transactions ADD COLUMN status TEXT NOT NULL DEFAULT 'UNCLEARED'
+    CHECK(status IN ('UNCLEARED', 'CLEARED', 'RECONCILED'));
+
+ALTER TABLE transactions ADD COLUMN reconciliation_id INTEGER
+    REFERENCES reconciliations(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_status ON transactions(account_id, status);
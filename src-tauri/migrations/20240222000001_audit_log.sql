@@ -0,0 +1,28 @@
+-- Append-only history of changes to transactions, accounts, categories,
+-- budgets, goals and recurring rules
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT NOT NULL CHECK(entity_type IN ('TRANSACTION', 'ACCOUNT', 'CATEGORY', 'BUDGET', 'GOAL', 'RECURRING')),
+    entity_id INTEGER NOT NULL,
+    action TEXT NOT NULL CHECK(action IN ('CREATE', 'UPDATE', 'DELETE', 'REVERT')),
+    before_json TEXT,                -- row state before the change (NULL for CREATE)
+    after_json TEXT,                 -- row state after the change (NULL for DELETE)
+    reverted_entry_id INTEGER,       -- REVERT entries: the entry that was undone
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id, id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_created ON audit_log(created_at);
+
+CREATE TRIGGER IF NOT EXISTS audit_log_no_update
+BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS audit_log_no_delete
+BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
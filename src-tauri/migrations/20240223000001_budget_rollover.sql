@@ -0,0 +1,7 @@
+-- Envelope-style budgets: unspent or overspent amounts carry into the next period
+
+ALTER TABLE budgets ADD COLUMN rollover_enabled INTEGER NOT NULL DEFAULT 0;
+
+-- Largest amount (either direction) carried between periods, in minor units
+-- of the primary currency. NULL = no cap.
+ALTER TABLE budgets ADD COLUMN rollover_cap INTEGER CHECK(rollover_cap IS NULL OR rollover_cap > 0);
@@ -0,0 +1,32 @@
+-- More budget periods: WEEKLY, BIWEEKLY, QUARTERLY and CUSTOM (an explicit
+-- start_date..end_date window). Repeating periods are anchored on start_date.
+-- SQLite cannot alter a CHECK constraint, so the table is rebuilt.
+
+PRAGMA foreign_keys = OFF;
+
+BEGIN;
+
+CREATE TABLE budgets_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    category_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL CHECK(amount > 0),
+    period TEXT NOT NULL CHECK(period IN ('WEEKLY', 'BIWEEKLY', 'MONTHLY', 'QUARTERLY', 'YEARLY', 'CUSTOM')),
+    start_date TEXT NOT NULL,
+    end_date TEXT, -- last day of a CUSTOM budget (inclusive)
+    rollover_enabled INTEGER NOT NULL DEFAULT 0,
+    rollover_cap INTEGER CHECK(rollover_cap IS NULL OR rollover_cap > 0),
+    FOREIGN KEY (category_id) REFERENCES categories(id),
+    CHECK((period = 'CUSTOM') = (end_date IS NOT NULL)),
+    CHECK(end_date IS NULL OR end_date >= start_date)
+);
+
+INSERT INTO budgets_new (id, category_id, amount, period, start_date, rollover_enabled, rollover_cap)
+SELECT id, category_id, amount, period, start_date, rollover_enabled, rollover_cap
+FROM budgets;
+
+DROP TABLE budgets;
+ALTER TABLE budgets_new RENAME TO budgets;
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
@@ -0,0 +1,19 @@
+-- Interest-bearing installment plans. total_amount stays the financed principal;
+-- apr is an annual percentage, processing_fee is in minor units and is charged
+-- with the first installment. When liability_account_id is set, principal is
+-- paid down as a transfer into that account instead of being booked as an expense.
+ALTER TABLE installment_plans ADD COLUMN apr REAL NOT NULL DEFAULT 0 CHECK(apr >= 0);
+ALTER TABLE installment_plans ADD COLUMN interest_method TEXT NOT NULL DEFAULT 'FLAT'
+    CHECK(interest_method IN ('FLAT', 'REDUCING_BALANCE'));
+ALTER TABLE installment_plans ADD COLUMN processing_fee INTEGER NOT NULL DEFAULT 0 CHECK(processing_fee >= 0);
+ALTER TABLE installment_plans ADD COLUMN liability_account_id INTEGER REFERENCES accounts(id) ON DELETE RESTRICT;
+ALTER TABLE installment_plans ADD COLUMN interest_category_id INTEGER REFERENCES categories(id) ON DELETE RESTRICT;
+
+-- Per-payment breakdown; interest and fee are posted as a separate expense
+ALTER TABLE installment_payments ADD COLUMN principal INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE installment_payments ADD COLUMN interest INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE installment_payments ADD COLUMN fee INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE installment_payments ADD COLUMN interest_transaction_id INTEGER
+    REFERENCES transactions(id) ON DELETE SET NULL;
+
+UPDATE installment_payments SET principal = amount;
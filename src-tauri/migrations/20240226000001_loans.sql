@@ -0,0 +1,44 @@
+-- Loans and mortgages. A loan is attached to a LIABILITY account; rate is an
+-- annual percentage compounded monthly and payments fall on payment_day each
+-- month. emi starts as the annuity payment for principal over term_months and
+-- is recalculated when a prepayment is applied with REDUCE_EMI.
+CREATE TABLE IF NOT EXISTS loans (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL UNIQUE,
+    payment_account_id INTEGER NOT NULL,
+    interest_category_id INTEGER,
+    principal INTEGER NOT NULL CHECK(principal > 0),
+    annual_rate REAL NOT NULL CHECK(annual_rate >= 0),
+    term_months INTEGER NOT NULL CHECK(term_months > 0),
+    start_date TEXT NOT NULL,
+    payment_day INTEGER NOT NULL CHECK(payment_day BETWEEN 1 AND 28),
+    emi INTEGER NOT NULL CHECK(emi > 0),
+    status TEXT NOT NULL DEFAULT 'ACTIVE' CHECK(status IN ('ACTIVE', 'CLOSED')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    FOREIGN KEY (payment_account_id) REFERENCES accounts(id) ON DELETE RESTRICT,
+    FOREIGN KEY (interest_category_id) REFERENCES categories(id) ON DELETE SET NULL
+);
+
+-- Each payment is a principal transfer into the loan account plus, for
+-- scheduled payments, an interest expense. Deleting the principal transaction
+-- removes the payment and restores the outstanding balance.
+CREATE TABLE IF NOT EXISTS loan_payments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    loan_id INTEGER NOT NULL,
+    transaction_id INTEGER NOT NULL,
+    interest_transaction_id INTEGER,
+    kind TEXT NOT NULL CHECK(kind IN ('SCHEDULED', 'PREPAYMENT')),
+    payment_number INTEGER, -- NULL for prepayments
+    payment_date TEXT NOT NULL,
+    principal INTEGER NOT NULL DEFAULT 0,
+    interest INTEGER NOT NULL DEFAULT 0,
+    strategy TEXT CHECK(strategy IN ('REDUCE_TERM', 'REDUCE_EMI')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (loan_id) REFERENCES loans(id) ON DELETE CASCADE,
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE,
+    FOREIGN KEY (interest_transaction_id) REFERENCES transactions(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_loan_payments_loan ON loan_payments(loan_id, payment_date);
@@ -0,0 +1,14 @@
+-- Credit card interest and fees. apr is an annual percentage; late_fee and
+-- annual_fee are in the card currency's minor units. Once a statement's due
+-- date passes it is assessed exactly once: an unpaid remainder accrues interest
+-- for one billing cycle and a payment below the minimum adds the late fee.
+ALTER TABLE credit_card_settings ADD COLUMN apr REAL NOT NULL DEFAULT 0 CHECK(apr >= 0);
+ALTER TABLE credit_card_settings ADD COLUMN late_fee INTEGER NOT NULL DEFAULT 0 CHECK(late_fee >= 0);
+ALTER TABLE credit_card_settings ADD COLUMN annual_fee INTEGER NOT NULL DEFAULT 0 CHECK(annual_fee >= 0);
+ALTER TABLE credit_card_settings ADD COLUMN annual_fee_month INTEGER CHECK(annual_fee_month BETWEEN 1 AND 12);
+ALTER TABLE credit_card_settings ADD COLUMN fee_category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL;
+ALTER TABLE credit_card_settings ADD COLUMN last_annual_fee_date TEXT;
+
+ALTER TABLE credit_card_statements ADD COLUMN finance_charge INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE credit_card_statements ADD COLUMN late_fee INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE credit_card_statements ADD COLUMN assessed_at TEXT;
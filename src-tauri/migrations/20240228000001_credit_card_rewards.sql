@@ -0,0 +1,42 @@
+-- Credit card cashback and points. CASHBACK rule rates are a percentage of
+-- spend; POINTS rule rates are points per whole unit of spend, each point worth
+-- point_value in the card currency. Reward amounts are stored as integer units:
+-- minor currency units for CASHBACK, whole points for POINTS.
+ALTER TABLE credit_card_settings ADD COLUMN reward_type TEXT NOT NULL DEFAULT 'CASHBACK'
+    CHECK(reward_type IN ('CASHBACK', 'POINTS'));
+ALTER TABLE credit_card_settings ADD COLUMN point_value REAL NOT NULL DEFAULT 0.01 CHECK(point_value >= 0);
+
+-- A NULL category is the base rate for spending no other rule covers. Several
+-- rules for one category form tiers: spend above tier_threshold (minor units,
+-- per billing cycle) earns that rule's rate. cycle_cap limits a rule's reward
+-- per cycle.
+CREATE TABLE IF NOT EXISTS credit_card_reward_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    credit_card_id INTEGER NOT NULL, -- references credit_card_settings.id
+    category_id INTEGER,
+    rate REAL NOT NULL CHECK(rate >= 0),
+    tier_threshold INTEGER NOT NULL DEFAULT 0 CHECK(tier_threshold >= 0),
+    cycle_cap INTEGER CHECK(cycle_cap >= 0),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (credit_card_id) REFERENCES credit_card_settings(id) ON DELETE CASCADE,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE,
+    UNIQUE(credit_card_id, category_id, tier_threshold)
+);
+
+-- Accruals are recorded when a statement is generated; redemptions post an
+-- INCOME transaction and are undone if that transaction is deleted.
+CREATE TABLE IF NOT EXISTS credit_card_rewards (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    credit_card_id INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK(kind IN ('ACCRUAL', 'REDEMPTION')),
+    units INTEGER NOT NULL CHECK(units > 0),
+    statement_id INTEGER,
+    transaction_id INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (credit_card_id) REFERENCES credit_card_settings(id) ON DELETE CASCADE,
+    FOREIGN KEY (statement_id) REFERENCES credit_card_statements(id) ON DELETE CASCADE,
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_cc_reward_rules_card ON credit_card_reward_rules(credit_card_id);
+CREATE INDEX idx_cc_rewards_card ON credit_card_rewards(credit_card_id);
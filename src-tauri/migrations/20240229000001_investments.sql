@@ -0,0 +1,64 @@
+-- Investment holdings. An investment account is an ASSET account whose book
+-- balance is its cash plus the cost basis of the securities it holds; net
+-- worth revalues the securities at the latest price on or before each date.
+CREATE TABLE IF NOT EXISTS securities (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    symbol TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Manually maintained closing prices, per unit in the security's currency
+CREATE TABLE IF NOT EXISTS security_prices (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    security_id INTEGER NOT NULL,
+    date TEXT NOT NULL,
+    price REAL NOT NULL CHECK(price >= 0),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(security_id, date),
+    FOREIGN KEY (security_id) REFERENCES securities(id) ON DELETE CASCADE
+);
+
+-- cash_account_id is where purchases are paid from and proceeds and dividends
+-- are paid to. When NULL the cash is held in the investment account itself.
+CREATE TABLE IF NOT EXISTS investment_accounts (
+    account_id INTEGER PRIMARY KEY,
+    cash_account_id INTEGER,
+    cost_basis_method TEXT NOT NULL DEFAULT 'FIFO' CHECK(cost_basis_method IN ('FIFO', 'AVERAGE')),
+    income_category_id INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    FOREIGN KEY (cash_account_id) REFERENCES accounts(id) ON DELETE SET NULL,
+    FOREIGN KEY (income_category_id) REFERENCES categories(id) ON DELETE SET NULL
+);
+
+-- amount is the cash side in minor units: cost including fees for BUY, net
+-- proceeds for SELL and the payout for DIVIDEND. Lots are not stored; they are
+-- rebuilt by replaying these rows in (date, id) order.
+CREATE TABLE IF NOT EXISTS investment_transactions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    account_id INTEGER NOT NULL,
+    security_id INTEGER NOT NULL,
+    type TEXT NOT NULL CHECK(type IN ('BUY', 'SELL', 'DIVIDEND', 'SPLIT')),
+    date TEXT NOT NULL,
+    quantity REAL NOT NULL DEFAULT 0,
+    price REAL NOT NULL DEFAULT 0,
+    fees INTEGER NOT NULL DEFAULT 0,
+    amount INTEGER NOT NULL DEFAULT 0,
+    split_ratio REAL,
+    realized_gain INTEGER NOT NULL DEFAULT 0,
+    cash_account_id INTEGER,
+    transaction_id INTEGER,
+    gain_transaction_id INTEGER,
+    memo TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (account_id) REFERENCES investment_accounts(account_id) ON DELETE CASCADE,
+    FOREIGN KEY (security_id) REFERENCES securities(id) ON DELETE RESTRICT,
+    FOREIGN KEY (cash_account_id) REFERENCES accounts(id) ON DELETE SET NULL,
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE SET NULL,
+    FOREIGN KEY (gain_transaction_id) REFERENCES transactions(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_investment_transactions_position ON investment_transactions(account_id, security_id, date);
+CREATE INDEX idx_security_prices_date ON security_prices(security_id, date);
@@ -0,0 +1,5 @@
+-- Snapshots are converted to the primary currency at the rates effective on
+-- the snapshot date. The per-currency figures and the rates used are kept as
+-- JSON: [{"currency", "assets", "liabilities", "rate"}], amounts in minor units
+-- of that currency and rate NULL where none was available.
+ALTER TABLE net_worth_snapshots ADD COLUMN currency_breakdown TEXT;
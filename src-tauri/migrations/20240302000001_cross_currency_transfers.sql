@@ -0,0 +1,32 @@
+-- Destination side of a transfer, in minor units of the destination account's
+-- currency. Transfers between currencies carry the amount actually received;
+-- the implied rate is to_amount / amount. NULL means the amount unchanged.
+ALTER TABLE transactions ADD COLUMN to_amount INTEGER;
+
+-- Existing transfers were journalled with the same value on both sides
+UPDATE transactions SET to_amount = (
+    SELECT je.debit FROM journal_entries je
+    WHERE je.transaction_id = transactions.id
+      AND je.account_id = transactions.to_account_id
+      AND je.debit > 0
+    ORDER BY je.id
+    LIMIT 1
+)
+WHERE type = 'TRANSFER';
+
+-- FX gain or loss of each cross-currency transfer, in minor units of the
+-- primary currency: the value received less the value sent, each at the
+-- market rate on the transfer date. Rebuilt whenever those rates change;
+-- transfers with no rate for either side have no row.
+CREATE TABLE IF NOT EXISTS fx_postings (
+    transaction_id INTEGER PRIMARY KEY,
+    date TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    source_value INTEGER NOT NULL,
+    destination_value INTEGER NOT NULL,
+    gain INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_fx_postings_date ON fx_postings(date);
@@ -0,0 +1,36 @@
+-- Rules gain conditions beyond the memo pattern and actions beyond the
+-- category. Rebuilt so category_id can be NULL for tag- or memo-only rules.
+-- An empty match_pattern matches any memo. Amount bounds are inclusive and in
+-- major units of the transaction's own currency; memo_rewrite replaces the
+-- memo, with $1-style capture references for regex rules.
+CREATE TABLE categorization_rules_new (
+    id TEXT PRIMARY KEY,
+    match_pattern TEXT NOT NULL DEFAULT '',
+    match_type TEXT NOT NULL, -- 'exact', 'contains', 'starts_with', 'regex'
+    category_id TEXT,
+    priority INTEGER DEFAULT 0,
+    min_amount REAL,
+    max_amount REAL,
+    account_id INTEGER,
+    transaction_type TEXT CHECK(transaction_type IN ('INCOME', 'EXPENSE', 'TRANSFER')),
+    memo_rewrite TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+INSERT INTO categorization_rules_new (id, match_pattern, match_type, category_id, priority, created_at, updated_at)
+SELECT id, match_pattern, match_type, category_id, priority, created_at, updated_at
+FROM categorization_rules;
+
+DROP TABLE categorization_rules;
+ALTER TABLE categorization_rules_new RENAME TO categorization_rules;
+
+CREATE TABLE IF NOT EXISTS categorization_rule_tags (
+    rule_id TEXT NOT NULL,
+    tag_id INTEGER NOT NULL,
+    PRIMARY KEY (rule_id, tag_id),
+    FOREIGN KEY (rule_id) REFERENCES categorization_rules(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
@@ -0,0 +1,32 @@
+-- Payees (merchants) as entities of their own. Aliases are the other
+-- spellings that resolve to a payee, e.g. 'KEELLS SUPER' for 'Keells'.
+-- The default category and tags apply to new transactions that name the
+-- payee without a category of their own.
+CREATE TABLE IF NOT EXISTS payees (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    default_category_id INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (default_category_id) REFERENCES categories(id) ON DELETE SET NULL
+);
+
+CREATE TABLE IF NOT EXISTS payee_aliases (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    payee_id INTEGER NOT NULL,
+    alias TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    FOREIGN KEY (payee_id) REFERENCES payees(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS payee_tags (
+    payee_id INTEGER NOT NULL,
+    tag_id INTEGER NOT NULL,
+    PRIMARY KEY (payee_id, tag_id),
+    FOREIGN KEY (payee_id) REFERENCES payees(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+ALTER TABLE transactions ADD COLUMN payee_id INTEGER REFERENCES payees(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_payee_aliases_payee ON payee_aliases(payee_id);
+CREATE INDEX IF NOT EXISTS idx_transactions_payee ON transactions(payee_id);
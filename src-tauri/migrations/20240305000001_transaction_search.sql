@@ -0,0 +1,123 @@
+-- Full-text index over the words a transaction can be found by: its memo
+-- (and split memos), payee, account names, category names and tag names.
+-- Rows are keyed by transaction id and kept in step by the triggers below.
+CREATE VIEW IF NOT EXISTS transaction_search_source AS
+SELECT
+    t.id,
+    COALESCE(t.memo, '') || COALESCE(' ' || (
+        SELECT GROUP_CONCAT(s.memo, ' ') FROM transaction_splits s WHERE s.transaction_id = t.id
+    ), '') AS memo,
+    COALESCE(p.name, '') AS payee,
+    COALESCE(a.name, '') || COALESCE(' ' || ta.name, '') AS account,
+    COALESCE((
+        SELECT GROUP_CONCAT(c.name, ' ') FROM categories c
+        WHERE c.id IN (SELECT category_id FROM transaction_category_lines l WHERE l.id = t.id)
+    ), '') AS category,
+    COALESCE((
+        SELECT GROUP_CONCAT(tg.name, ' ') FROM transaction_tags tt
+        INNER JOIN tags tg ON tt.tag_id = tg.id
+        WHERE tt.transaction_id = t.id
+    ), '') AS tags
+FROM transactions t
+LEFT JOIN payees p ON t.payee_id = p.id
+LEFT JOIN accounts a ON t.account_id = a.id
+LEFT JOIN accounts ta ON t.to_account_id = ta.id;
+
+CREATE VIRTUAL TABLE IF NOT EXISTS transaction_search USING fts5(
+    memo, payee, account, category, tags,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+SELECT id, memo, payee, account, category, tags FROM transaction_search_source;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_insert AFTER INSERT ON transactions
+BEGIN
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_update AFTER UPDATE ON transactions
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = OLD.id;
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_delete AFTER DELETE ON transactions
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_tag_insert AFTER INSERT ON transaction_tags
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = NEW.transaction_id;
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id = NEW.transaction_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_tag_delete AFTER DELETE ON transaction_tags
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = OLD.transaction_id;
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id = OLD.transaction_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_split_insert AFTER INSERT ON transaction_splits
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = NEW.transaction_id;
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id = NEW.transaction_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_split_delete AFTER DELETE ON transaction_splits
+BEGIN
+    DELETE FROM transaction_search WHERE rowid = OLD.transaction_id;
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id = OLD.transaction_id;
+END;
+
+-- Renames reach every transaction that shows the name
+CREATE TRIGGER IF NOT EXISTS transaction_search_account_rename AFTER UPDATE OF name ON accounts
+BEGIN
+    DELETE FROM transaction_search WHERE rowid IN (
+        SELECT id FROM transactions WHERE account_id = NEW.id OR to_account_id = NEW.id
+    );
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id IN (
+        SELECT id FROM transactions WHERE account_id = NEW.id OR to_account_id = NEW.id
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_category_rename AFTER UPDATE OF name ON categories
+BEGIN
+    DELETE FROM transaction_search WHERE rowid IN (
+        SELECT id FROM transaction_category_lines WHERE category_id = NEW.id
+    );
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id IN (
+        SELECT id FROM transaction_category_lines WHERE category_id = NEW.id
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_tag_rename AFTER UPDATE OF name ON tags
+BEGIN
+    DELETE FROM transaction_search WHERE rowid IN (
+        SELECT transaction_id FROM transaction_tags WHERE tag_id = NEW.id
+    );
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id IN (
+        SELECT transaction_id FROM transaction_tags WHERE tag_id = NEW.id
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS transaction_search_payee_rename AFTER UPDATE OF name ON payees
+BEGIN
+    DELETE FROM transaction_search WHERE rowid IN (
+        SELECT id FROM transactions WHERE payee_id = NEW.id
+    );
+    INSERT INTO transaction_search (rowid, memo, payee, account, category, tags)
+    SELECT id, memo, payee, account, category, tags FROM transaction_search_source WHERE id IN (
+        SELECT id FROM transactions WHERE payee_id = NEW.id
+    );
+END;
@@ -0,0 +1,17 @@
+-- Named transaction filters ("views"). The filter is a TransactionFilter as
+-- JSON; its date_range expression (e.g. 'last 3 months', 'this financial
+-- year') is resolved to dates each time the view is used.
+CREATE TABLE IF NOT EXISTS saved_filters (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    filter TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Export templates and budgets can be scoped to a saved filter
+ALTER TABLE export_templates ADD COLUMN saved_filter_id INTEGER REFERENCES saved_filters(id) ON DELETE SET NULL;
+ALTER TABLE budgets ADD COLUMN saved_filter_id INTEGER REFERENCES saved_filters(id) ON DELETE SET NULL;
+
+-- First month (1-12) of the financial year, for 'this financial year' ranges
+INSERT OR IGNORE INTO app_settings (key, value) VALUES ('financial_year_start_month', '1');
@@ -0,0 +1,3 @@
+-- Import duplicate checks look transactions up by date, type and amount
+-- instead of loading every transaction first
+CREATE INDEX IF NOT EXISTS idx_transactions_duplicate ON transactions(date, type, amount);
@@ -0,0 +1,39 @@
+-- Cleared/reconciled status per account side. A transfer is reconciled
+-- separately in each account it touches; sides without a row are UNCLEARED.
+
+CREATE TABLE IF NOT EXISTS transaction_account_status (
+    transaction_id INTEGER NOT NULL,
+    account_id INTEGER NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('CLEARED', 'RECONCILED')),
+    reconciliation_id INTEGER,
+    PRIMARY KEY (transaction_id, account_id),
+    FOREIGN KEY (transaction_id) REFERENCES transactions(id) ON DELETE CASCADE,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE,
+    FOREIGN KEY (reconciliation_id) REFERENCES reconciliations(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_account_status_account
+    ON transaction_account_status(account_id, status);
+
+-- Carry the old per-transaction status over to every side. Only the side
+-- whose account was reconciled stays locked; the other side of a transfer
+-- becomes CLEARED so it can still be reconciled in its own account.
+INSERT OR IGNORE INTO transaction_account_status (transaction_id, account_id, status, reconciliation_id)
+SELECT DISTINCT t.id, je.account_id,
+       CASE WHEN t.status = 'RECONCILED' AND (r.account_id IS NULL OR r.account_id = je.account_id)
+            THEN 'RECONCILED' ELSE 'CLEARED' END,
+       CASE WHEN t.status = 'RECONCILED' AND r.account_id = je.account_id
+            THEN t.reconciliation_id END
+FROM transactions t
+INNER JOIN journal_entries je ON je.transaction_id = t.id
+LEFT JOIN reconciliations r ON t.reconciliation_id = r.id
+WHERE t.status != 'UNCLEARED';
+
+DROP INDEX IF EXISTS idx_transactions_status;
+ALTER TABLE transactions DROP COLUMN status;
+
+-- transactions.reconciliation_id stays behind as a dead column: SQLite cannot
+-- DROP a column that carries a foreign key, and rebuilding transactions would
+-- mean recreating every table and trigger that references it. Nothing reads
+-- or writes it any more; transaction_account_status.reconciliation_id is the
+-- only link from a transaction side to its reconciliation.
@@ -0,0 +1,457 @@
+// File: src-tauri/src/bin/money_manager-cli.rs
+// Headless access to the Money Manager database for scripting (cron imports,
+// nightly reports, backups). Uses the same migrations and command logic as the app.
+use app_lib::commands::export::{
+    export_transactions_csv_internal, export_transactions_qif_internal,
+    pub_export_full_backup_internal, ExportFilter,
+};
+use app_lib::commands::import::execute_import_internal;
+use app_lib::commands::recurring::process_recurring_transactions_internal;
+use app_lib::commands::scheduled_backup::{perform_backup, restore_from_zip_backup_internal};
+use app_lib::commands::settings::restore_from_backup_internal;
+use app_lib::commands::transactions::{
+    create_transaction_internal, get_transactions_filtered_internal,
+};
+use app_lib::db;
+use app_lib::models::import::{ColumnMapping, ImportOptions};
+use app_lib::models::transactions::{CreateTransactionInput, TransactionFilter, TransactionPage};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Same directory Tauri resolves for `app_data_dir()` (the bundle identifier)
+const APP_IDENTIFIER: &str = "com.miththiran.money-manager";
+
+#[derive(Parser)]
+#[command(name = "money_manager-cli", version, about = "Command-line access to the Money Manager database")]
+struct Cli {
+    /// App data directory holding money_manager.db (defaults to the desktop app's)
+    #[arg(long, env = "MONEY_MANAGER_DATA_DIR", global = true)]
+    data_dir: Option<PathBuf>,
+
+    /// Read the master password of an encrypted database from the first line
+    /// of stdin. Otherwise MONEY_MANAGER_PASSWORD is used, or it is prompted for.
+    #[arg(long, global = true)]
+    password_stdin: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Add or list transactions
+    #[command(subcommand)]
+    Tx(TxCommand),
+    /// Create all recurring transactions that are due
+    Recurring {
+        /// Treat this date (YYYY-MM-DD) as today
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Import transactions from a CSV file
+    Import(ImportArgs),
+    /// Export transactions
+    Export(ExportArgs),
+    /// Create or restore backups
+    #[command(subcommand)]
+    Backup(BackupCommand),
+}
+
+#[derive(Subcommand)]
+enum TxCommand {
+    /// Add a transaction
+    Add {
+        #[arg(long)]
+        date: Option<String>,
+        #[arg(long = "type", value_parser = ["INCOME", "EXPENSE", "TRANSFER"], ignore_case = true)]
+        transaction_type: String,
+        #[arg(long)]
+        amount: f64,
+        /// Account id or name
+        #[arg(long)]
+        account: String,
+        /// Destination account id or name (transfers)
+        #[arg(long)]
+        to_account: Option<String>,
+        /// Amount received, for transfers between accounts in different currencies
+        #[arg(long)]
+        to_amount: Option<f64>,
+        /// Category id or name
+        #[arg(long)]
+        category: Option<String>,
+        /// Payee name or alias; unknown names create a payee
+        #[arg(long)]
+        payee: Option<String>,
+        #[arg(long)]
+        memo: Option<String>,
+    },
+    /// List transactions, newest first
+    List {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Words and terms such as amount>100 tag:travel -category:Rent
+        #[arg(long)]
+        search: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Args)]
+struct FilterArgs {
+    /// Start date (YYYY-MM-DD, inclusive)
+    #[arg(long)]
+    from: Option<String>,
+    /// End date (YYYY-MM-DD, inclusive)
+    #[arg(long)]
+    to: Option<String>,
+    /// Account id or name
+    #[arg(long)]
+    account: Option<String>,
+    #[arg(long = "type", value_parser = ["INCOME", "EXPENSE", "TRANSFER"], ignore_case = true)]
+    transaction_type: Option<String>,
+    /// Saved filter id or name; the options above narrow it further
+    #[arg(long)]
+    view: Option<String>,
+}
+
+#[derive(Args)]
+struct ImportArgs {
+    file: PathBuf,
+    /// Account id or name the rows are booked to
+    #[arg(long)]
+    account: String,
+    #[arg(long, default_value_t = 0)]
+    date_col: usize,
+    #[arg(long, default_value_t = 1)]
+    amount_col: usize,
+    #[arg(long)]
+    memo_col: Option<usize>,
+    #[arg(long)]
+    category_col: Option<usize>,
+    #[arg(long)]
+    payee_col: Option<usize>,
+    #[arg(long, default_value = "YYYY-MM-DD")]
+    date_format: String,
+    /// Treat negative amounts as expenses and positive ones as income
+    #[arg(long)]
+    negative_as_expense: bool,
+    #[arg(long)]
+    skip_duplicates: bool,
+    #[arg(long)]
+    create_missing_categories: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Qif,
+    /// Full JSON backup of every table
+    Json,
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    #[arg(long, value_enum, default_value = "csv")]
+    format: ExportFormat,
+    #[command(flatten)]
+    filter: FilterArgs,
+    /// Output file (stdout when omitted)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum BackupCommand {
+    /// Write a zip backup into a directory
+    Create {
+        dir: PathBuf,
+        #[arg(long)]
+        include_photos: bool,
+    },
+    /// Restore from a .zip or .json backup, replacing all data
+    Restore {
+        file: PathBuf,
+        /// Required, since restoring deletes the current data
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    let data_dir = match cli.data_dir {
+        Some(dir) => dir,
+        None => dirs::data_dir()
+            .ok_or_else(|| "Could not determine the data directory; pass --data-dir".to_string())?
+            .join(APP_IDENTIFIER),
+    };
+
+    let pool = open_database(&data_dir, cli.password_stdin)?;
+
+    // Listings, exports and backups only read, so they use a reader connection
+    match cli.command {
+        Command::Tx(TxCommand::Add {
+            date,
+            transaction_type,
+            amount,
+            account,
+            to_account,
+            to_amount,
+            category,
+            payee,
+            memo,
+        }) => {
+            let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+            let input = CreateTransactionInput {
+                date: date.unwrap_or_else(today),
+                transaction_type: transaction_type.to_uppercase(),
+                amount,
+                account_id: resolve_id(&conn, "accounts", "account", &account)?,
+                to_account_id: to_account.map(|a| resolve_id(&conn, "accounts", "account", &a)).transpose()?,
+                to_amount,
+                category_id: category.map(|c| resolve_id(&conn, "categories", "category", &c)).transpose()?,
+                payee_id: None,
+                payee,
+                memo,
+                tag_ids: None,
+                goal_allocations: None,
+                goal_withdrawals: None,
+                splits: None,
+            };
+            let id = create_transaction_internal(&mut conn, input)?;
+            println!("Created transaction {}", id);
+        }
+        Command::Tx(TxCommand::List {
+            filter,
+            search,
+            limit,
+            json,
+        }) => {
+            let conn = pool.read()?;
+            // Searches stay ranked by relevance, so only plain listings are limited in SQL
+            let page = search.is_none().then_some(TransactionPage {
+                after: None,
+                limit: Some(limit as i64),
+            });
+            let transaction_filter = TransactionFilter {
+                start_date: filter.from,
+                end_date: filter.to,
+                date_range: None,
+                saved_filter_id: filter.view.map(|v| resolve_id(&conn, "saved_filters", "saved filter", &v)).transpose()?,
+                transaction_type: filter.transaction_type.map(|t| t.to_uppercase()),
+                account_id: filter.account.map(|a| resolve_id(&conn, "accounts", "account", &a)).transpose()?,
+                category_id: None,
+                payee_id: None,
+                search_query: search,
+                include_subcategories: None,
+                tag_ids: None,
+            };
+            let mut transactions = get_transactions_filtered_internal(&conn, transaction_filter, page)?;
+            transactions.truncate(limit);
+
+            if json {
+                let out = serde_json::to_string_pretty(&transactions)
+                    .map_err(|e| format!("Failed to serialize: {}", e))?;
+                println!("{}", out);
+            } else {
+                for t in &transactions {
+                    let account = match &t.to_account_name {
+                        Some(to) => format!("{} -> {}", t.account_name, to),
+                        None => t.account_name.clone(),
+                    };
+                    println!(
+                        "{:>6}  {}  {:<8}  {:>14}  {:<24}  {:<18}  {}",
+                        t.transaction.id,
+                        t.transaction.date,
+                        t.transaction.transaction_type,
+                        t.transaction.amount.to_string(),
+                        account,
+                        t.category_name.clone().unwrap_or_default(),
+                        t.transaction.memo.clone().unwrap_or_default()
+                    );
+                }
+            }
+        }
+        Command::Recurring { date } => {
+            let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+            let today = match date {
+                Some(d) => chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d")
+                    .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", d))?,
+                None => chrono::Local::now().naive_local().date(),
+            };
+            let created = process_recurring_transactions_internal(&mut conn, today)?;
+            println!("Created {} recurring transaction(s)", created.len());
+        }
+        Command::Import(args) => {
+            let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+            let account_id = resolve_id(&conn, "accounts", "account", &args.account)?;
+            let mapping = ColumnMapping {
+                date_col: args.date_col,
+                amount_col: args.amount_col,
+                credit_col: None,
+                type_col: None,
+                account_col: None,
+                category_col: args.category_col,
+                memo_col: args.memo_col,
+                payee_col: args.payee_col,
+                date_format: args.date_format,
+                negative_as_expense: args.negative_as_expense,
+            };
+            let options = ImportOptions {
+                skip_duplicates: args.skip_duplicates,
+                create_missing_categories: args.create_missing_categories,
+                default_account_id: account_id,
+                account_mapping: HashMap::new(),
+                category_mapping: load_category_mapping(&conn)?,
+            };
+            let result = execute_import_internal(&mut conn, &args.file.to_string_lossy(), mapping, options)?;
+            println!(
+                "Imported {}, skipped {}, errors {} (batch {})",
+                result.imported, result.skipped, result.errors, result.batch_id
+            );
+        }
+        Command::Export(args) => {
+            let conn = pool.read()?;
+            let filter = ExportFilter {
+                start_date: args.filter.from,
+                end_date: args.filter.to,
+                transaction_type: args.filter.transaction_type.map(|t| t.to_uppercase()),
+                account_id: args.filter.account.map(|a| resolve_id(&conn, "accounts", "account", &a)).transpose()?,
+                category_id: None,
+                saved_filter_id: args.filter.view.map(|v| resolve_id(&conn, "saved_filters", "saved filter", &v)).transpose()?,
+                columns: None,
+                include_pie_chart: None,
+                include_histogram: None,
+            };
+            let content = match args.format {
+                ExportFormat::Csv => export_transactions_csv_internal(&conn, Some(filter))?,
+                ExportFormat::Qif => export_transactions_qif_internal(&conn, Some(filter))?,
+                ExportFormat::Json => pub_export_full_backup_internal(&conn)?,
+            };
+            match args.output {
+                Some(path) => {
+                    std::fs::write(&path, content)
+                        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                    println!("Wrote {}", path.display());
+                }
+                None => print!("{}", content),
+            }
+        }
+        Command::Backup(BackupCommand::Create { dir, include_photos }) => {
+            let conn = pool.read()?;
+            let result = perform_backup(&conn, &dir.to_string_lossy(), include_photos, &data_dir)?;
+            println!("Backup written to {} ({} bytes)", result.file_path, result.file_size_bytes);
+        }
+        Command::Backup(BackupCommand::Restore { file, yes }) => {
+            if !yes {
+                return Err("Restoring replaces all existing data; re-run with --yes to confirm".to_string());
+            }
+            let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+            let is_zip = file
+                .extension()
+                .map(|e| e.eq_ignore_ascii_case("zip"))
+                .unwrap_or(false);
+            let transactions_restored = if is_zip {
+                restore_from_zip_backup_internal(&mut conn, &file.to_string_lossy(), &data_dir)?
+                    .transactions_restored
+            } else {
+                let json = std::fs::read_to_string(&file)
+                    .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+                restore_from_backup_internal(&mut conn, &json)?.transactions_restored
+            };
+            println!("Restore complete: {} transaction(s)", transactions_restored);
+        }
+    }
+
+    Ok(())
+}
+
+/// Open the database the same way the app does, asking for the master
+/// password when the encryption config says it is encrypted.
+fn open_database(data_dir: &Path, password_stdin: bool) -> Result<db::DbPool, String> {
+    let db_path = data_dir.join("money_manager.db");
+    if !db_path.exists() {
+        return Err(format!("No database found at {}", db_path.display()));
+    }
+
+    let config = db::encryption::read_config(data_dir);
+    match config {
+        Some(config) if config.encrypted => {
+            let password = read_password(password_stdin)?;
+            if !db::encryption::verify_password(&password, &config.password_verify_hash) {
+                return Err("Incorrect master password".to_string());
+            }
+            let key = db::encryption::derive_key(&password, &config.salt)?;
+            db::init_database_encrypted(&db_path, &key)
+                .map_err(|e| format!("Failed to unlock database: {}", e))
+        }
+        _ => db::init_database_unencrypted(&db_path)
+            .map_err(|e| format!("Failed to open database: {}", e)),
+    }
+}
+
+/// The master password from stdin, the environment, or a terminal prompt.
+/// It is never taken as an argument, where it would show up in `ps`.
+fn read_password(from_stdin: bool) -> Result<String, String> {
+    if from_stdin {
+        let mut line = String::new();
+        std::io::stdin()
+            .read_line(&mut line)
+            .map_err(|e| format!("Failed to read password: {}", e))?;
+        return Ok(line.trim_end_matches(['\r', '\n']).to_string());
+    }
+    if let Ok(password) = std::env::var("MONEY_MANAGER_PASSWORD") {
+        return Ok(password);
+    }
+    rpassword::prompt_password("Master password: ").map_err(|e| format!("Failed to read password: {}", e))
+}
+
+/// Accept either a numeric id or a (case-insensitive) name. `label` names
+/// the kind of record in the error message.
+fn resolve_id(conn: &Connection, table: &str, label: &str, value: &str) -> Result<i64, String> {
+    if let Ok(id) = value.parse::<i64>() {
+        return Ok(id);
+    }
+
+    conn.query_row(
+        &format!("SELECT id FROM {} WHERE name = ?1 COLLATE NOCASE ORDER BY id LIMIT 1", table),
+        [value],
+        |row| row.get(0),
+    )
+    .map_err(|_| format!("No {} named '{}'", label, value))
+}
+
+/// CSV category names map onto existing categories by exact name
+fn load_category_mapping(conn: &Connection) -> Result<HashMap<String, i64>, String> {
+    let mut stmt = conn
+        .prepare("SELECT name, id FROM categories")
+        .map_err(|e| format!("Query error: {}", e))?;
+    let mapping = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+    Ok(mapping)
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
@@ -1,5 +1,8 @@
 // File: src-tauri/src/commands/accounts.rs
+use crate::commands::audit::{log_change, snapshot};
+use crate::commands::currencies::currency_decimals;
 use crate::models::account::{Account, AccountGroup, AccountWithBalance, CreateAccountInput};
+use crate::models::money::Money;
 use crate::AppState;
 use rusqlite::params;
 use tauri::State;
@@ -44,12 +47,13 @@ pub fn get_accounts(state: State<'_, AppState>) -> Result<Vec<Account>, String>
 
     let accounts = stmt
         .query_map([], |row| {
+            let currency: String = row.get(4)?;
             Ok(Account {
                 id: row.get(0)?,
                 group_id: row.get(1)?,
                 name: row.get(2)?,
-                initial_balance: row.get(3)?,
-                currency: row.get(4)?,
+                initial_balance: Money::new(row.get(3)?, currency_decimals(&currency)),
+                currency,
                 created_at: row.get(5)?,
             })
         })
@@ -71,7 +75,7 @@ pub fn get_accounts_with_balance(
         .prepare(
             "SELECT a.id, a.group_id, a.name, a.initial_balance, a.currency, a.created_at,
                     ag.name as group_name, ag.type as group_type,
-                    CAST(COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0) AS REAL) as journal_balance
+                    COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0) as journal_balance
              FROM accounts a
              INNER JOIN account_groups ag ON a.group_id = ag.id
              LEFT JOIN journal_entries je ON je.account_id = a.id
@@ -82,9 +86,10 @@ pub fn get_accounts_with_balance(
 
     let accounts = stmt
         .query_map([], |row| {
-            let initial_balance: f64 = row.get(3)?;
-            let journal_balance: f64 = row.get(8)?;
-            let balance = initial_balance + journal_balance;
+            let currency: String = row.get(4)?;
+            let decimals = currency_decimals(&currency);
+            let initial_balance = Money::new(row.get(3)?, decimals);
+            let journal_balance = Money::new(row.get(8)?, decimals);
 
             Ok(AccountWithBalance {
                 account: Account {
@@ -92,10 +97,10 @@ pub fn get_accounts_with_balance(
                     group_id: row.get(1)?,
                     name: row.get(2)?,
                     initial_balance,
-                    currency: row.get(4)?,
+                    currency,
                     created_at: row.get(5)?,
                 },
-                current_balance: (balance * 100.0).round() / 100.0,
+                current_balance: initial_balance + journal_balance,
             })
         })
         .map_err(|e| format!("Failed to fetch accounts: {}", e))?
@@ -112,7 +117,13 @@ pub fn create_account(
 ) -> Result<Account, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    create_account_internal(&conn, input)
+}
 
+pub fn create_account_internal(
+    conn: &rusqlite::Connection,
+    input: CreateAccountInput,
+) -> Result<Account, String> {
     // Validate group exists
     let group_exists: bool = conn
         .query_row(
@@ -128,14 +139,16 @@ pub fn create_account(
     }
 
     let currency = input.currency.unwrap_or_else(|| "LKR".to_string());
+    let initial_balance = Money::from_major(input.initial_balance, currency_decimals(&currency));
 
     conn.execute(
         "INSERT INTO accounts (group_id, name, initial_balance, currency) VALUES (?1, ?2, ?3, ?4)",
-        params![input.group_id, input.name, input.initial_balance, currency],
+        params![input.group_id, input.name, initial_balance, currency],
     )
     .map_err(|e| format!("Failed to create account: {}", e))?;
 
     let account_id = conn.last_insert_rowid();
+    log_change(conn, "ACCOUNT", account_id, "CREATE", None)?;
 
     let account = conn
         .query_row(
@@ -144,12 +157,13 @@ pub fn create_account(
              WHERE a.id = ?1",
             params![account_id],
             |row| {
+                let currency: String = row.get(4)?;
                 Ok(Account {
                     id: row.get(0)?,
                     group_id: row.get(1)?,
                     name: row.get(2)?,
-                    initial_balance: row.get(3)?,
-                    currency: row.get(4)?,
+                    initial_balance: Money::new(row.get(3)?, currency_decimals(&currency)),
+                    currency,
                     created_at: row.get(5)?,
                 })
             },
@@ -169,6 +183,48 @@ pub fn update_account(
 ) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    update_account_internal(&conn, id, name, initial_balance, currency)
+}
+
+pub fn update_account_internal(
+    conn: &rusqlite::Connection,
+    id: i64,
+    name: Option<String>,
+    initial_balance: Option<f64>,
+    currency: Option<String>,
+) -> Result<(), String> {
+    let (old_currency, old_initial_minor): (String, i64) = conn
+        .query_row(
+            "SELECT currency, initial_balance FROM accounts WHERE id = ?1",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|_| "Account not found".to_string())?;
+
+    let old_decimals = currency_decimals(&old_currency);
+    let new_decimals = currency
+        .as_deref()
+        .map(currency_decimals)
+        .unwrap_or(old_decimals);
+
+    // Amounts are stored in the currency's minor units, so existing entries
+    // cannot be reinterpreted under a currency with a different precision.
+    if new_decimals != old_decimals {
+        let entry_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM journal_entries WHERE account_id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .unwrap_or(0);
+
+        if entry_count > 0 {
+            return Err(
+                "Cannot change to a currency with different decimal places once the account has transactions"
+                    .to_string(),
+            );
+        }
+    }
 
     let mut set_clauses: Vec<String> = Vec::new();
 
@@ -177,7 +233,15 @@ pub fn update_account(
     }
 
     if let Some(b) = initial_balance {
-        set_clauses.push(format!("initial_balance = {}", b));
+        set_clauses.push(format!(
+            "initial_balance = {}",
+            Money::from_major(b, new_decimals).minor()
+        ));
+    } else if new_decimals != old_decimals {
+        set_clauses.push(format!(
+            "initial_balance = {}",
+            Money::new(old_initial_minor, old_decimals).rescale(new_decimals).minor()
+        ));
     }
 
     if let Some(ref c) = currency {
@@ -194,6 +258,8 @@ pub fn update_account(
         id
     );
 
+    let before = snapshot(conn, "ACCOUNT", id)?;
+
     let rows = conn
         .execute(&query, [])
         .map_err(|e| format!("Failed to update account: {}", e))?;
@@ -202,14 +268,17 @@ pub fn update_account(
         return Err("Account not found".to_string());
     }
 
-    Ok(())
+    log_change(conn, "ACCOUNT", id, "UPDATE", before)
 }
 
 #[tauri::command]
 pub fn delete_account(state: State<'_, AppState>, account_id: i64) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    delete_account_internal(&conn, account_id)
+}
 
+pub fn delete_account_internal(conn: &rusqlite::Connection, account_id: i64) -> Result<(), String> {
     // Check for existing transactions
     let txn_count: i64 = conn
         .query_row(
@@ -226,6 +295,8 @@ pub fn delete_account(state: State<'_, AppState>, account_id: i64) -> Result<(),
         ));
     }
 
+    let before = snapshot(conn, "ACCOUNT", account_id)?;
+
     let rows = conn
         .execute("DELETE FROM accounts WHERE id = ?1", params![account_id])
         .map_err(|e| format!("Failed to delete account: {}", e))?;
@@ -234,5 +305,5 @@ pub fn delete_account(state: State<'_, AppState>, account_id: i64) -> Result<(),
         return Err("Account not found".to_string());
     }
 
-    Ok(())
+    log_change(conn, "ACCOUNT", account_id, "DELETE", before)
 }
@@ -1,35 +1,228 @@
 // File: src-tauri/src/commands/advanced.rs
-use crate::models::advanced::{CategorizationRule, CreateCategorizationRuleInput, ExportTemplate, CreateExportTemplateInput};
+use crate::commands::audit::{log_change, snapshot};
+use crate::commands::currencies::currency_decimals;
+use crate::commands::import::load_categories;
+use crate::commands::reconciliation::TRANSACTION_STATUS_SQL;
+use crate::models::advanced::{
+    ApplyRulesInput, CategorizationRule, CreateCategorizationRuleInput, CreateExportTemplateInput,
+    ExportTemplate, RuleApplicationResult, RuleChange,
+};
+use crate::models::money::Money;
 use crate::AppState;
+use regex::Regex;
 use rusqlite::params;
 use tauri::State;
 
 // ===================== CATEGORIZATION RULES =====================
 
+const RULE_SELECT: &str = "SELECT id, match_pattern, match_type, category_id, priority, min_amount, max_amount,
+        account_id, transaction_type, memo_rewrite, created_at, updated_at
+     FROM categorization_rules";
+
+/// The parts of a transaction a rule can test
+pub struct RuleSubject<'a> {
+    pub memo: &'a str,
+    /// Major units of the transaction's own currency
+    pub amount: f64,
+    pub account_id: Option<i64>,
+    pub transaction_type: &'a str,
+}
+
+/// Combined actions of every matching rule: category and memo rewrite come
+/// from the highest-priority rule that sets one, tags from all of them.
+#[derive(Debug, Default)]
+pub struct RuleOutcome {
+    pub rule_ids: Vec<String>,
+    pub category_id: Option<i64>,
+    pub memo: Option<String>,
+    pub tag_ids: Vec<i64>,
+}
+
+fn row_to_rule(row: &rusqlite::Row) -> rusqlite::Result<CategorizationRule> {
+    Ok(CategorizationRule {
+        id: row.get(0)?,
+        match_pattern: row.get(1)?,
+        match_type: row.get(2)?,
+        category_id: row.get(3)?,
+        priority: row.get(4)?,
+        min_amount: row.get(5)?,
+        max_amount: row.get(6)?,
+        account_id: row.get(7)?,
+        transaction_type: row.get(8)?,
+        memo_rewrite: row.get(9)?,
+        tag_ids: Vec::new(),
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
+
+/// All rules with their tags, highest priority first
+pub(crate) fn load_categorization_rules(conn: &rusqlite::Connection) -> Result<Vec<CategorizationRule>, String> {
+    let mut stmt = conn
+        .prepare(&format!("{} ORDER BY priority DESC, created_at DESC", RULE_SELECT))
+        .map_err(|e| format!("Query error: {}", e))?;
+    let mut rules = stmt
+        .query_map([], row_to_rule)
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    let mut stmt = conn
+        .prepare("SELECT rule_id, tag_id FROM categorization_rule_tags ORDER BY tag_id")
+        .map_err(|e| format!("Query error: {}", e))?;
+    let tags = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+    for (rule_id, tag_id) in tags {
+        if let Some(rule) = rules.iter_mut().find(|r| r.id == rule_id) {
+            rule.tag_ids.push(tag_id);
+        }
+    }
+
+    Ok(rules)
+}
+
+fn rule_matches(rule: &CategorizationRule, subject: &RuleSubject) -> bool {
+    if rule.account_id.is_some_and(|id| subject.account_id != Some(id))
+        || rule.transaction_type.as_deref().is_some_and(|t| t != subject.transaction_type)
+        || rule.min_amount.is_some_and(|min| subject.amount < min)
+        || rule.max_amount.is_some_and(|max| subject.amount > max)
+    {
+        return false;
+    }
+    if rule.match_pattern.is_empty() {
+        return true;
+    }
+
+    let text_lower = subject.memo.to_lowercase();
+    let pattern_lower = rule.match_pattern.to_lowercase();
+    match rule.match_type.as_str() {
+        "exact" => text_lower == pattern_lower,
+        "contains" => text_lower.contains(&pattern_lower),
+        "starts_with" => text_lower.starts_with(&pattern_lower),
+        "regex" => Regex::new(&rule.match_pattern).is_ok_and(|re| re.is_match(subject.memo)),
+        _ => false,
+    }
+}
+
+/// The memo a matching rule rewrites to; regex rules may reference their captures as $1, $name
+fn rewritten_memo(rule: &CategorizationRule, memo: &str) -> Option<String> {
+    let rewrite = rule.memo_rewrite.as_deref()?;
+    if rule.match_type != "regex" || rule.match_pattern.is_empty() {
+        return Some(rewrite.to_string());
+    }
+    let captures = Regex::new(&rule.match_pattern).ok()?.captures(memo)?;
+    let mut expanded = String::new();
+    captures.expand(rewrite, &mut expanded);
+    Some(expanded)
+}
+
+/// Run `rules` (highest priority first) against a transaction
+pub(crate) fn evaluate_categorization_rules(
+    rules: &[CategorizationRule],
+    subject: &RuleSubject,
+) -> Option<RuleOutcome> {
+    let mut outcome = RuleOutcome::default();
+    for rule in rules.iter().filter(|r| rule_matches(r, subject)) {
+        outcome.rule_ids.push(rule.id.clone());
+        if outcome.category_id.is_none() {
+            outcome.category_id = rule.category_id.as_deref().and_then(|id| id.parse().ok());
+        }
+        if outcome.memo.is_none() {
+            outcome.memo = rewritten_memo(rule, subject.memo);
+        }
+        for tag_id in &rule.tag_ids {
+            if !outcome.tag_ids.contains(tag_id) {
+                outcome.tag_ids.push(*tag_id);
+            }
+        }
+    }
+    (!outcome.rule_ids.is_empty()).then_some(outcome)
+}
+
+/// Category of the highest-priority matching rule that sets an existing one.
+/// Imports only take the category; tags and memo rewrites apply on entry.
+pub(crate) fn apply_categorization_rules(
+    subject: &RuleSubject,
+    rules: &[CategorizationRule],
+    categories: &[(i64, String)],
+) -> Option<(i64, String)> {
+    rules.iter().filter(|r| rule_matches(r, subject)).find_map(|rule| {
+        let cat_id = rule.category_id.as_deref()?.parse::<i64>().ok()?;
+        categories.iter().find(|(id, _)| *id == cat_id).cloned()
+    })
+}
+
+/// Blank category and memo fields from the editor mean "not set"
+fn normalize_rule_input(mut input: CreateCategorizationRuleInput) -> Result<CreateCategorizationRuleInput, String> {
+    input.category_id = input.category_id.filter(|c| !c.trim().is_empty());
+    input.memo_rewrite = input.memo_rewrite.filter(|m| !m.trim().is_empty());
+    input.transaction_type = input.transaction_type.filter(|t| !t.is_empty());
+
+    match input.match_type.as_str() {
+        "exact" | "contains" | "starts_with" => {}
+        "regex" => {
+            Regex::new(&input.match_pattern).map_err(|e| format!("Invalid regular expression: {}", e))?;
+        }
+        other => return Err(format!("Unknown match type: {}", other)),
+    }
+    if let Some(txn_type) = &input.transaction_type {
+        if !matches!(txn_type.as_str(), "INCOME" | "EXPENSE" | "TRANSFER") {
+            return Err("Invalid transaction type".to_string());
+        }
+    }
+    if let (Some(min), Some(max)) = (input.min_amount, input.max_amount) {
+        if min > max {
+            return Err("Minimum amount cannot exceed the maximum amount".to_string());
+        }
+    }
+
+    let has_condition = !input.match_pattern.trim().is_empty()
+        || input.min_amount.is_some()
+        || input.max_amount.is_some()
+        || input.account_id.is_some()
+        || input.transaction_type.is_some();
+    if !has_condition {
+        return Err("A rule needs a match pattern or another condition".to_string());
+    }
+    let has_action = input.category_id.is_some()
+        || input.memo_rewrite.is_some()
+        || input.tag_ids.as_ref().is_some_and(|t| !t.is_empty());
+    if !has_action {
+        return Err("A rule must set a category, tags or a memo".to_string());
+    }
+
+    Ok(input)
+}
+
+fn save_rule_tags(conn: &rusqlite::Connection, rule_id: &str, tag_ids: &[i64]) -> Result<(), String> {
+    conn.execute("DELETE FROM categorization_rule_tags WHERE rule_id = ?1", params![rule_id])
+        .map_err(|e| format!("Failed to clear rule tags: {}", e))?;
+    for tag_id in tag_ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO categorization_rule_tags (rule_id, tag_id) VALUES (?1, ?2)",
+            params![rule_id, tag_id],
+        )
+        .map_err(|e| format!("Failed to associate tag: {}", e))?;
+    }
+    Ok(())
+}
+
+fn get_rule(conn: &rusqlite::Connection, id: &str) -> Result<CategorizationRule, String> {
+    load_categorization_rules(conn)?
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| "Rule not found".to_string())
+}
+
 #[tauri::command]
 pub fn get_categorization_rules(state: State<'_, AppState>) -> Result<Vec<CategorizationRule>, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, match_pattern, match_type, category_id, priority, created_at, updated_at 
-         FROM categorization_rules ORDER BY priority DESC, created_at DESC"
-    ).map_err(|e| format!("Query error: {}", e))?;
-
-    let rules = stmt.query_map([], |row| {
-        Ok(CategorizationRule {
-            id: row.get(0)?,
-            match_pattern: row.get(1)?,
-            match_type: row.get(2)?,
-            category_id: row.get(3)?,
-            priority: row.get(4)?,
-            created_at: row.get(5)?,
-            updated_at: row.get(6)?,
-        })
-    }).map_err(|e| format!("Execute error: {}", e))?
-    .collect::<Result<Vec<_>, _>>().map_err(|e| format!("Read error: {}", e))?;
-
-    Ok(rules)
+    load_categorization_rules(&conn)
 }
 
 #[tauri::command]
@@ -38,32 +231,33 @@ pub fn create_categorization_rule(
     input: CreateCategorizationRuleInput,
 ) -> Result<CategorizationRule, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    conn.execute(
-        "INSERT INTO categorization_rules (id, match_pattern, match_type, category_id, priority)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
+    let input = normalize_rule_input(input)?;
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    tx.execute(
+        "INSERT INTO categorization_rules (id, match_pattern, match_type, category_id, priority,
+            min_amount, max_amount, account_id, transaction_type, memo_rewrite)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
         params![
             input.id,
             input.match_pattern,
             input.match_type,
             input.category_id,
-            input.priority
+            input.priority,
+            input.min_amount,
+            input.max_amount,
+            input.account_id,
+            input.transaction_type,
+            input.memo_rewrite
         ],
     ).map_err(|e| format!("Insert error: {}", e))?;
+    save_rule_tags(&tx, &input.id, input.tag_ids.as_deref().unwrap_or(&[]))?;
 
-    let mut stmt = conn.prepare("SELECT created_at, updated_at FROM categorization_rules WHERE id = ?1").unwrap();
-    let (created_at, updated_at): (String, String) = stmt.query_row(params![input.id], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+    tx.commit().map_err(|e| format!("Commit error: {}", e))?;
 
-    Ok(CategorizationRule {
-        id: input.id,
-        match_pattern: input.match_pattern,
-        match_type: input.match_type,
-        category_id: input.category_id,
-        priority: input.priority,
-        created_at,
-        updated_at,
-    })
+    get_rule(&conn, &input.id)
 }
 
 #[tauri::command]
@@ -72,33 +266,40 @@ pub fn update_categorization_rule(
     input: CreateCategorizationRuleInput,
 ) -> Result<CategorizationRule, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    conn.execute(
+    let input = normalize_rule_input(input)?;
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    let updated = tx.execute(
         "UPDATE categorization_rules 
-         SET match_pattern = ?1, match_type = ?2, category_id = ?3, priority = ?4, updated_at = CURRENT_TIMESTAMP
-         WHERE id = ?5",
+         SET match_pattern = ?1, match_type = ?2, category_id = ?3, priority = ?4,
+             min_amount = ?5, max_amount = ?6, account_id = ?7, transaction_type = ?8,
+             memo_rewrite = ?9, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?10",
         params![
             input.match_pattern,
             input.match_type,
             input.category_id,
             input.priority,
+            input.min_amount,
+            input.max_amount,
+            input.account_id,
+            input.transaction_type,
+            input.memo_rewrite,
             input.id
         ],
     ).map_err(|e| format!("Update error: {}", e))?;
+    if updated == 0 {
+        return Err("Rule not found".to_string());
+    }
+    if let Some(tag_ids) = &input.tag_ids {
+        save_rule_tags(&tx, &input.id, tag_ids)?;
+    }
 
-    let mut stmt = conn.prepare("SELECT created_at, updated_at FROM categorization_rules WHERE id = ?1").unwrap();
-    let (created_at, updated_at): (String, String) = stmt.query_row(params![input.id], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+    tx.commit().map_err(|e| format!("Commit error: {}", e))?;
 
-    Ok(CategorizationRule {
-        id: input.id,
-        match_pattern: input.match_pattern,
-        match_type: input.match_type,
-        category_id: input.category_id,
-        priority: input.priority,
-        created_at,
-        updated_at,
-    })
+    get_rule(&conn, &input.id)
 }
 
 #[tauri::command]
@@ -112,6 +313,165 @@ pub fn delete_categorization_rule(state: State<'_, AppState>, id: String) -> Res
     Ok(())
 }
 
+/// Re-run the rules over existing transactions. With `dry_run` nothing is
+/// written and the result previews what would change.
+#[tauri::command]
+pub fn rerun_categorization_rules(
+    state: State<'_, AppState>,
+    input: ApplyRulesInput,
+) -> Result<RuleApplicationResult, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    rerun_categorization_rules_internal(&mut conn, input)
+}
+
+/// (id, date, type, amount, account, category, memo, status, has splits)
+type RuleCandidate = (i64, String, String, Money, i64, Option<i64>, Option<String>, String, bool);
+
+pub fn rerun_categorization_rules_internal(
+    conn: &mut rusqlite::Connection,
+    input: ApplyRulesInput,
+) -> Result<RuleApplicationResult, String> {
+    let rules = load_categorization_rules(conn)?;
+    let categories = load_categories(conn)?;
+    let include_categorized = input.include_categorized.unwrap_or(false);
+
+    let candidates: Vec<RuleCandidate> = {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT t.id, t.date, t.type, t.amount, a.currency, t.account_id, t.category_id, t.memo,
+                        {}, EXISTS(SELECT 1 FROM transaction_splits s WHERE s.transaction_id = t.id)
+                 FROM transactions t
+                 INNER JOIN accounts a ON t.account_id = a.id
+                 WHERE (?1 IS NULL OR t.date >= ?1)
+                   AND (?2 IS NULL OR t.date <= ?2)
+                   AND (?3 IS NULL OR t.account_id = ?3)
+                 ORDER BY t.date, t.id",
+                TRANSACTION_STATUS_SQL
+            ))
+            .map_err(|e| format!("Query error: {}", e))?;
+        let rows = stmt.query_map(params![input.start_date, input.end_date, input.account_id], |row| {
+            let amount = Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(4)?));
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                amount,
+                row.get(5)?,
+                row.get(6)?,
+                row.get(7)?,
+                row.get(8)?,
+                row.get(9)?,
+            ))
+        })
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+        rows
+    };
+
+    let mut result = RuleApplicationResult {
+        dry_run: input.dry_run,
+        scanned: 0,
+        locked: 0,
+        changes: Vec::new(),
+    };
+
+    for (id, date, txn_type, amount, account_id, category_id, memo, status, has_splits) in candidates {
+        let categorized = category_id.is_some() || has_splits;
+        if categorized && !include_categorized {
+            continue;
+        }
+        result.scanned += 1;
+
+        let subject = RuleSubject {
+            memo: memo.as_deref().unwrap_or(""),
+            amount: amount.to_major(),
+            account_id: Some(account_id),
+            transaction_type: &txn_type,
+        };
+        let Some(outcome) = evaluate_categorization_rules(&rules, &subject) else {
+            continue;
+        };
+
+        // Transfers and split transactions carry no header category
+        let new_category = outcome
+            .category_id
+            .filter(|id| txn_type != "TRANSFER" && !has_splits && Some(*id) != category_id)
+            .and_then(|id| categories.iter().find(|(c, _)| *c == id).cloned());
+        let new_memo = outcome.memo.filter(|m| Some(m) != memo.as_ref());
+
+        let mut stmt = conn
+            .prepare_cached("SELECT tag_id FROM transaction_tags WHERE transaction_id = ?1")
+            .map_err(|e| format!("Query error: {}", e))?;
+        let current_tags = stmt
+            .query_map(params![id], |row| row.get::<_, i64>(0))
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?;
+        let added_tag_ids: Vec<i64> = outcome
+            .tag_ids
+            .into_iter()
+            .filter(|t| !current_tags.contains(t))
+            .collect();
+
+        if new_category.is_none() && new_memo.is_none() && added_tag_ids.is_empty() {
+            continue;
+        }
+        if status == "RECONCILED" {
+            result.locked += 1;
+            continue;
+        }
+
+        result.changes.push(RuleChange {
+            transaction_id: id,
+            date,
+            amount,
+            memo,
+            rule_ids: outcome.rule_ids,
+            old_category_id: category_id,
+            new_category_id: new_category.as_ref().map(|(id, _)| *id),
+            new_category_name: new_category.map(|(_, name)| name),
+            new_memo,
+            added_tag_ids,
+        });
+    }
+
+    if input.dry_run || result.changes.is_empty() {
+        return Ok(result);
+    }
+
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+    for change in &result.changes {
+        let before = snapshot(&tx, "TRANSACTION", change.transaction_id)?;
+        if let Some(category_id) = change.new_category_id {
+            tx.execute(
+                "UPDATE transactions SET category_id = ?1 WHERE id = ?2",
+                params![category_id, change.transaction_id],
+            )
+            .map_err(|e| format!("Failed to update transaction: {}", e))?;
+        }
+        if let Some(memo) = &change.new_memo {
+            tx.execute(
+                "UPDATE transactions SET memo = ?1 WHERE id = ?2",
+                params![memo, change.transaction_id],
+            )
+            .map_err(|e| format!("Failed to update transaction: {}", e))?;
+        }
+        for tag_id in &change.added_tag_ids {
+            tx.execute(
+                "INSERT OR IGNORE INTO transaction_tags (transaction_id, tag_id) VALUES (?1, ?2)",
+                params![change.transaction_id, tag_id],
+            )
+            .map_err(|e| format!("Failed to associate tag: {}", e))?;
+        }
+        log_change(&tx, "TRANSACTION", change.transaction_id, "UPDATE", before)?;
+    }
+    tx.commit().map_err(|e| format!("Failed to commit rule changes: {}", e))?;
+
+    Ok(result)
+}
+
 // ===================== EXPORT TEMPLATES =====================
 
 #[tauri::command]
@@ -120,7 +480,7 @@ pub fn get_export_templates(state: State<'_, AppState>) -> Result<Vec<ExportTemp
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, name, columns, filters, format, created_at, updated_at 
+        "SELECT id, name, columns, filters, format, created_at, updated_at, saved_filter_id
          FROM export_templates ORDER BY name ASC"
     ).map_err(|e| format!("Query error: {}", e))?;
 
@@ -130,6 +490,7 @@ pub fn get_export_templates(state: State<'_, AppState>) -> Result<Vec<ExportTemp
             name: row.get(1)?,
             columns: row.get(2)?,
             filters: row.get(3)?,
+            saved_filter_id: row.get(7)?,
             format: row.get(4)?,
             created_at: row.get(5)?,
             updated_at: row.get(6)?,
@@ -149,14 +510,15 @@ pub fn create_export_template(
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     conn.execute(
-        "INSERT INTO export_templates (id, name, columns, filters, format)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
+        "INSERT INTO export_templates (id, name, columns, filters, format, saved_filter_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
         params![
             input.id,
             input.name,
             input.columns,
             input.filters,
-            input.format
+            input.format,
+            input.saved_filter_id
         ],
     ).map_err(|e| format!("Insert error: {}", e))?;
 
@@ -168,6 +530,7 @@ pub fn create_export_template(
         name: input.name,
         columns: input.columns,
         filters: input.filters,
+        saved_filter_id: input.saved_filter_id,
         format: input.format,
         created_at,
         updated_at,
@@ -184,13 +547,15 @@ pub fn update_export_template(
 
     conn.execute(
         "UPDATE export_templates 
-         SET name = ?1, columns = ?2, filters = ?3, format = ?4, updated_at = CURRENT_TIMESTAMP
-         WHERE id = ?5",
+         SET name = ?1, columns = ?2, filters = ?3, format = ?4, saved_filter_id = ?5,
+             updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?6",
         params![
             input.name,
             input.columns,
             input.filters,
             input.format,
+            input.saved_filter_id,
             input.id
         ],
     ).map_err(|e| format!("Update error: {}", e))?;
@@ -203,6 +568,7 @@ pub fn update_export_template(
         name: input.name,
         columns: input.columns,
         filters: input.filters,
+        saved_filter_id: input.saved_filter_id,
         format: input.format,
         created_at,
         updated_at,
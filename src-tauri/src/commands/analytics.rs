@@ -1,4 +1,9 @@
 // File: src-tauri/src/commands/analytics.rs
+use crate::commands::currencies::{currency_decimals, RateConverter};
+use crate::commands::networth::calc_net_worth_at;
+use crate::commands::saved_filters::saved_filter_condition;
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
 use crate::models::transactions::CategorySpending;
 use crate::models::analytics::{
     AccountBalanceHistory, NetWorthHistory,
@@ -9,17 +14,21 @@ use chrono::{Datelike, Local, NaiveDate};
 use rusqlite::params;
 use tauri::State;
 
+/// ((category id, name), date, currency, minor amount, transaction count)
+type CategoryDayRow = ((i64, String), String, String, i64, i64);
+
 // ======================== NET WORTH ========================
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_net_worth_history(
     state: State<'_, AppState>,
     months: i64,
 ) -> Result<Vec<NetWorthHistory>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
     let today = Local::now().date_naive();
+    let currency = crate::commands::currencies::get_primary_currency_internal(&conn)?;
     let mut history = Vec::new();
 
     for m in (0..months).rev() {
@@ -34,13 +43,17 @@ pub fn get_net_worth_history(
         let end_of_month_str = end_of_month.format("%Y-%m-%d").to_string();
         let month_name = end_of_month.format("%b %Y").to_string();
 
-        let (assets, liabilities) = calc_net_worth_at(&conn, Some(&end_of_month_str))?;
+        // Each month-end is valued at the rates effective on that date
+        let valuation = calc_net_worth_at(&conn, Some(&end_of_month_str))?;
 
         history.push(NetWorthHistory {
             month: month_name,
-            assets,
-            liabilities,
-            net_worth: assets - liabilities,
+            assets: valuation.assets,
+            liabilities: valuation.liabilities,
+            net_worth: valuation.assets - valuation.liabilities,
+            currency: currency.clone(),
+            by_currency: valuation.by_currency,
+            missing_rates: valuation.missing_rates,
         });
     }
 
@@ -49,20 +62,23 @@ pub fn get_net_worth_history(
 
 // ======================== ACCOUNT BALANCE ========================
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_account_balance_history(
     state: State<'_, AppState>,
     account_id: i64,
     days: i64,
 ) -> Result<Vec<AccountBalanceHistory>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
-    let initial_balance: f64 = conn
+    let initial_balance = conn
         .query_row(
-            "SELECT initial_balance FROM accounts WHERE id = ?1",
+            "SELECT initial_balance, currency FROM accounts WHERE id = ?1",
             params![account_id],
-            |row| row.get(0),
+            |row| {
+                let currency: String = row.get(1)?;
+                Ok(Money::new(row.get(0)?, currency_decimals(&currency)))
+            },
         )
         .map_err(|e| format!("Account not found: {}", e))?;
 
@@ -73,10 +89,10 @@ pub fn get_account_balance_history(
         let target_date = today - chrono::Duration::days(d);
         let date_str = target_date.format("%Y-%m-%d").to_string();
 
-        let journal_balance: f64 = conn
+        let journal_balance: i64 = conn
             .query_row(
                 r#"
-                SELECT CAST(COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0) AS REAL)
+                SELECT COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0)
                 FROM journal_entries je
                 JOIN transactions t ON je.transaction_id = t.id
                 WHERE je.account_id = ?1 AND t.date <= ?2
@@ -84,9 +100,9 @@ pub fn get_account_balance_history(
                 params![account_id, date_str],
                 |row| row.get(0),
             )
-            .unwrap_or(0.0);
+            .unwrap_or(0);
 
-        let balance = (initial_balance + journal_balance * 100.0).round() / 100.0;
+        let balance = initial_balance + Money::new(journal_balance, initial_balance.decimals());
 
         history.push(AccountBalanceHistory {
             date: date_str,
@@ -99,118 +115,172 @@ pub fn get_account_balance_history(
 
 // ======================== CATEGORIES ========================
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_top_categories(
     state: State<'_, AppState>,
     start_date: String,
     end_date: String,
     limit: i64,
+    saved_filter_id: Option<i64>, // Only the saved filter's transactions
 ) -> Result<Vec<CategorySpending>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
-    let total_expense: f64 = conn
-        .query_row(
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let scope = saved_filter_condition(&conn, saved_filter_id, true)?;
+
+    let expense_rows = daily_rows::<i64>(
+        &conn,
+        &format!(
             r#"
-            SELECT COALESCE(SUM(amount), 0)
-            FROM transactions
-            WHERE type = 'EXPENSE' AND date >= ?1 AND date <= ?2
+            SELECT 0, t.date, a.currency, SUM(t.amount)
+            FROM transactions t
+            JOIN accounts a ON t.account_id = a.id
+            WHERE t.type = 'EXPENSE' AND t.date >= ?1 AND t.date <= ?2{}
+            GROUP BY t.date, a.currency
             "#,
-            params![start_date, end_date],
-            |row| row.get(0),
-        )
-        .unwrap_or(0.0);
-
-    if total_expense == 0.0 {
+            scope
+        ),
+        params![start_date, end_date],
+    )?;
+    let total_expense = converter
+        .sum_by(expense_rows)
+        .pop()
+        .map(|t| t.total)
+        .unwrap_or(converter.zero());
+
+    if total_expense.is_zero() {
         return Ok(Vec::new());
     }
 
+    // Grouped by day and currency so each line converts at its own date's rate
     let mut stmt = conn
-        .prepare(
+        .prepare(&format!(
             r#"
             SELECT
                 COALESCE(p.id, c.id) as category_id,
                 COALESCE(p.name, c.name) as category_name,
+                t.date,
+                a.currency,
                 SUM(t.amount) as total_amount,
-                COUNT(t.id) as transaction_count
-            FROM transactions t
+                COUNT(DISTINCT t.id) as transaction_count
+            FROM transaction_category_lines t
+            JOIN accounts a ON t.account_id = a.id
             JOIN categories c ON t.category_id = c.id
             LEFT JOIN categories p ON c.parent_id = p.id
-            WHERE t.type = 'EXPENSE' AND t.date >= ?1 AND t.date <= ?2
-            GROUP BY category_id, category_name
-            ORDER BY total_amount DESC
-            LIMIT ?3
+            WHERE t.type = 'EXPENSE' AND t.date >= ?1 AND t.date <= ?2{}
+            GROUP BY category_id, t.date, a.currency
             "#,
-        )
+            scope
+        ))
         .map_err(|e| format!("Query error: {}", e))?;
 
-    let spending = stmt
-        .query_map(params![start_date, end_date, limit], |row| {
-            let amount: f64 = row.get(2)?;
-            let percentage = (amount / total_expense * 10000.0).round() / 100.0;
-            Ok(CategorySpending {
-                category_id: row.get(0)?,
-                category_name: row.get(1)?,
-                total_amount: amount,
-                transaction_count: row.get(3)?,
-                percentage,
-            })
+    let rows: Vec<CategoryDayRow> = stmt
+        .query_map(params![start_date, end_date], |row| {
+            Ok(((row.get(0)?, row.get(1)?), row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
         })
         .map_err(|e| format!("Execution error: {}", e))?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| format!("Read error: {}", e))?;
 
+    let mut counts: Vec<(i64, i64)> = Vec::new();
+    for ((category_id, _), _, _, _, count) in &rows {
+        match counts.iter_mut().find(|(id, _)| id == category_id) {
+            Some((_, total)) => *total += count,
+            None => counts.push((*category_id, *count)),
+        }
+    }
+
+    let totals = converter.sum_by(
+        rows.into_iter()
+            .map(|(key, date, currency, amount, _)| (key, date, currency, amount))
+            .collect(),
+    );
+
+    let mut spending: Vec<CategorySpending> = totals
+        .into_iter()
+        .map(|t| {
+            let (category_id, category_name) = t.key;
+            CategorySpending {
+                category_id,
+                category_name,
+                total_amount: t.total,
+                transaction_count: counts
+                    .iter()
+                    .find(|(id, _)| *id == category_id)
+                    .map(|(_, count)| *count)
+                    .unwrap_or(0),
+                percentage: (t.total.ratio(total_expense) * 10000.0).round() / 100.0,
+                currency: converter.target.clone(),
+                by_currency: t.by_currency,
+                missing_rates: t.missing_rates,
+            }
+        })
+        .collect();
+
+    spending.sort_by_key(|s| std::cmp::Reverse(s.total_amount));
+    spending.truncate(limit.max(0) as usize);
+
     Ok(spending)
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_subcategory_breakdown(
     state: State<'_, AppState>,
     parent_category_id: i64,
     start_date: String,
     end_date: String,
+    saved_filter_id: Option<i64>, // Only the saved filter's transactions
 ) -> Result<Vec<SubCategorySpending>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
-    let mut stmt = conn
-        .prepare(
+    let rows = daily_rows(
+        &conn,
+        &format!(
             r#"
-            SELECT c.id, c.name, SUM(t.amount) as total_amount
-            FROM transactions t
+            SELECT c.id, t.date, a.currency, SUM(t.amount)
+            FROM transaction_category_lines t
+            JOIN accounts a ON t.account_id = a.id
             JOIN categories c ON t.category_id = c.id
             WHERE t.type = 'EXPENSE'
               AND (c.id = ?1 OR c.parent_id = ?1)
-              AND t.date >= ?2 AND t.date <= ?3
-            GROUP BY c.id, c.name
-            ORDER BY total_amount DESC
+              AND t.date >= ?2 AND t.date <= ?3{}
+            GROUP BY c.id, t.date, a.currency
             "#,
-        )
-        .map_err(|e| format!("Query error: {}", e))?;
-
-    let breakdown = stmt
-        .query_map(params![parent_category_id, start_date, end_date], |row| {
-            Ok(SubCategorySpending {
-                category_id: row.get(0)?,
-                category_name: row.get(1)?,
-                total_amount: row.get(2)?,
-            })
-        })
-        .map_err(|e| format!("Execution error: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Read error: {}", e))?;
+            saved_filter_condition(&conn, saved_filter_id, true)?
+        ),
+        params![parent_category_id, start_date, end_date],
+    )?;
+
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let mut breakdown = Vec::new();
+
+    for total in converter.sum_by(rows) {
+        let category_name: String = conn
+            .query_row("SELECT name FROM categories WHERE id = ?1", params![total.key], |row| row.get(0))
+            .map_err(|e| format!("Read error: {}", e))?;
+
+        breakdown.push(SubCategorySpending {
+            category_id: total.key,
+            category_name,
+            total_amount: total.total,
+            missing_rates: total.missing_rates,
+        });
+    }
+    breakdown.sort_by_key(|s| std::cmp::Reverse(s.total_amount));
 
     Ok(breakdown)
 }
 
 // ======================== DASHBOARD ========================
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_analytics_dashboard(
     state: State<'_, AppState>,
 ) -> Result<AnalyticsDashboardData, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
     let today = Local::now().date_naive();
     let current_month_start =
@@ -218,139 +288,164 @@ pub fn get_analytics_dashboard(
     let end_date = today.format("%Y-%m-%d").to_string();
 
     // 1. Current Net Worth
-    let (assets, liabilities) = calc_net_worth_at(&conn, None)?;
+    let valuation = calc_net_worth_at(&conn, None)?;
+    let (assets, liabilities) = (valuation.assets, valuation.liabilities);
     let net_worth = assets - liabilities;
+    let mut missing_rates = valuation.missing_rates;
 
-    // 2. This month's income & expense
-    let this_month_income: f64 = conn
-        .query_row(
-            "SELECT COALESCE(SUM(amount), 0) FROM transactions WHERE type = 'INCOME' AND date >= ?1",
-            params![current_month_start],
-            |row| row.get(0),
-        )
-        .unwrap_or(0.0);
+    let mut converter = RateConverter::to_primary(&conn)?;
 
-    let this_month_expense: f64 = conn
-        .query_row(
-            "SELECT COALESCE(SUM(amount), 0) FROM transactions WHERE type = 'EXPENSE' AND date >= ?1",
-            params![current_month_start],
-            |row| row.get(0),
-        )
-        .unwrap_or(0.0);
+    // 2. This month's income & expense
+    let flow_rows = daily_rows::<String>(
+        &conn,
+        r#"
+        SELECT t.type, t.date, a.currency, SUM(t.amount)
+        FROM transactions t
+        JOIN accounts a ON t.account_id = a.id
+        WHERE t.type IN ('INCOME', 'EXPENSE') AND t.date >= ?1
+        GROUP BY t.type, t.date, a.currency
+        "#,
+        params![current_month_start],
+    )?;
+    let flows = converter.sum_by(flow_rows);
+    let flow_total = |kind: &str| {
+        flows
+            .iter()
+            .find(|t| t.key == kind)
+            .map(|t| t.total)
+            .unwrap_or(converter.zero())
+    };
 
-    // 3. Top categories this month (reusing logic but inline)
-    let top_categories = {
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT COALESCE(p.name, c.name) as name, SUM(t.amount) as amount
-            FROM transactions t
-            JOIN categories c ON t.category_id = c.id
-            LEFT JOIN categories p ON c.parent_id = p.id
-            WHERE t.type = 'EXPENSE' AND t.date >= ?1
-            GROUP BY name
-            ORDER BY amount DESC
-            LIMIT 5
-            "#
-        ).unwrap();
-
-        stmt.query_map(params![current_month_start], |row| {
-            let name: String = row.get(0)?;
-            let amount: f64 = row.get(1)?;
-            let percentage = if this_month_expense > 0.0 {
-                (amount / this_month_expense * 100.0).round()
-            } else {
-                0.0
-            };
-            Ok(DashboardCategory { name, amount, percentage })
+    let this_month_income = flow_total("INCOME");
+    let this_month_expense = flow_total("EXPENSE");
+
+    // 3. Top categories this month
+    let category_rows = daily_rows(
+        &conn,
+        r#"
+        SELECT COALESCE(p.name, c.name), t.date, a.currency, SUM(t.amount)
+        FROM transaction_category_lines t
+        JOIN accounts a ON t.account_id = a.id
+        JOIN categories c ON t.category_id = c.id
+        LEFT JOIN categories p ON c.parent_id = p.id
+        WHERE t.type = 'EXPENSE' AND t.date >= ?1
+        GROUP BY COALESCE(p.name, c.name), t.date, a.currency
+        "#,
+        params![current_month_start],
+    )?;
+    let mut top_categories: Vec<DashboardCategory> = converter
+        .sum_by(category_rows)
+        .into_iter()
+        .map(|t| DashboardCategory {
+            name: t.key,
+            amount: t.total,
+            percentage: (t.total.ratio(this_month_expense) * 100.0).round(),
         })
-        .unwrap()
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap_or_default()
-    };
+        .collect();
+    top_categories.sort_by_key(|s| std::cmp::Reverse(s.amount));
+    top_categories.truncate(5);
 
     // 4. Daily spending (last 30 days)
     let start_30_days = (today - chrono::Duration::days(30)).format("%Y-%m-%d").to_string();
     let daily_spending = {
-        let mut stmt = conn.prepare(
+        let spending_rows = daily_rows::<String>(
+            &conn,
             r#"
-            SELECT date, SUM(amount)
-            FROM transactions
-            WHERE type = 'EXPENSE' AND date >= ?1 AND date <= ?2
-            GROUP BY date
-            ORDER BY date ASC
-            "#
-        ).unwrap();
-
-        let mut daily_map = std::collections::HashMap::new();
-        let _ = stmt.query_map(params![start_30_days, end_date], |row| {
-            let date: String = row.get(0)?;
-            let amount: f64 = row.get(1)?;
-            daily_map.insert(date, amount);
-            Ok(())
-        }).unwrap().collect::<Result<Vec<_>, _>>();
+            SELECT t.date, t.date, a.currency, SUM(t.amount)
+            FROM transactions t
+            JOIN accounts a ON t.account_id = a.id
+            WHERE t.type = 'EXPENSE' AND t.date >= ?1 AND t.date <= ?2
+            GROUP BY t.date, a.currency
+            "#,
+            params![start_30_days, end_date],
+        )?;
+        let daily = converter.sum_by(spending_rows);
 
         let mut series = Vec::new();
         for d in (0..=30).rev() {
             let date = (today - chrono::Duration::days(d)).format("%Y-%m-%d").to_string();
-            let amount = *daily_map.get(&date).unwrap_or(&0.0);
+            let amount = daily
+                .iter()
+                .find(|t| t.key == date)
+                .map(|t| t.total)
+                .unwrap_or(converter.zero());
             series.push(DashboardDailySummary { date, amount });
         }
         series
     };
 
+    for missing in converter.missing_rates() {
+        if !missing_rates.iter().any(|m| m.currency == missing.currency) {
+            missing_rates.push(missing);
+        }
+    }
+
     Ok(AnalyticsDashboardData {
         net_worth,
         total_assets: assets,
         total_liabilities: liabilities,
         this_month_income,
         this_month_expense,
-        savings_rate: if this_month_income > 0.0 {
-            ((this_month_income - this_month_expense) / this_month_income * 100.0).max(0.0).round()
-        } else {
-            0.0
-        },
+        savings_rate: ((this_month_income - this_month_expense).ratio(this_month_income) * 100.0)
+            .max(0.0)
+            .round(),
         top_categories,
         daily_spending,
+        currency: converter.target.clone(),
+        missing_rates,
     })
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_year_over_year_comparison(
     state: State<'_, AppState>,
     year: i32,
 ) -> Result<Vec<YearOverYearComparison>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
     let prev_year = year - 1;
+    let mut converter = RateConverter::to_primary(&conn)?;
+
+    let rows = daily_rows::<String>(
+        &conn,
+        r#"
+        SELECT strftime('%Y-%m', t.date), t.date, a.currency, SUM(t.amount)
+        FROM transactions t
+        JOIN accounts a ON t.account_id = a.id
+        WHERE t.type = 'EXPENSE' AND strftime('%Y', t.date) IN (?1, ?2)
+        GROUP BY t.date, a.currency
+        "#,
+        params![year.to_string(), prev_year.to_string()],
+    )?;
+    let monthly = converter.sum_by(rows);
+
     let mut comparison = Vec::new();
 
     for month in 1..=12 {
-        let month_str = format!("{:02}", month);
         let month_name = chrono::Month::try_from(month as u8)
             .unwrap()
             .name()
             .to_string();
 
-        let current_amount: f64 = conn
-            .query_row(
-                "SELECT COALESCE(SUM(amount), 0) FROM transactions WHERE type = 'EXPENSE' AND strftime('%Y', date) = ?1 AND strftime('%m', date) = ?2",
-                params![year.to_string(), month_str],
-                |row| row.get(0),
-            )
-            .unwrap_or(0.0);
+        let mut missing_rates = Vec::new();
+        let mut expense_in = |year: i32| -> Money {
+            let key = format!("{}-{:02}", year, month);
+            match monthly.iter().find(|t| t.key == key) {
+                Some(t) => {
+                    missing_rates.extend(t.missing_rates.iter().cloned());
+                    t.total
+                }
+                None => converter.zero(),
+            }
+        };
 
-        let previous_amount: f64 = conn
-            .query_row(
-                "SELECT COALESCE(SUM(amount), 0) FROM transactions WHERE type = 'EXPENSE' AND strftime('%Y', date) = ?1 AND strftime('%m', date) = ?2",
-                params![prev_year.to_string(), month_str],
-                |row| row.get(0),
-            )
-            .unwrap_or(0.0);
+        let current_amount = expense_in(year);
+        let previous_amount = expense_in(prev_year);
 
-        let percentage_change = if previous_amount > 0.0 {
-            ((current_amount - previous_amount) / previous_amount * 10000.0).round() / 100.0
-        } else if current_amount > 0.0 {
+        let percentage_change = if previous_amount.is_positive() {
+            ((current_amount - previous_amount).ratio(previous_amount) * 10000.0).round() / 100.0
+        } else if current_amount.is_positive() {
             100.0
         } else {
             0.0
@@ -361,6 +456,7 @@ pub fn get_year_over_year_comparison(
             current_year_amount: current_amount,
             previous_year_amount: previous_amount,
             percentage_change,
+            missing_rates,
         });
     }
 
@@ -369,64 +465,22 @@ pub fn get_year_over_year_comparison(
 
 // ======================== HELPERS ========================
 
-fn calc_net_worth_at(
+/// Runs a query returning `(key, date, currency, minor amount)` rows for
+/// `RateConverter::sum_by`.
+fn daily_rows<K: rusqlite::types::FromSql>(
     conn: &rusqlite::Connection,
-    as_of_date: Option<&str>,
-) -> Result<(f64, f64), String> {
-    let mut stmt = if let Some(_date) = as_of_date {
-        conn.prepare(
-            r#"
-            SELECT a.initial_balance, ag.type as account_type,
-                   CAST(COALESCE(
-                       (SELECT SUM(je2.debit) - SUM(je2.credit)
-                        FROM journal_entries je2
-                        JOIN transactions t2 ON je2.transaction_id = t2.id
-                        WHERE je2.account_id = a.id AND t2.date <= ?1),
-                   0) AS REAL) as journal_balance
-            FROM accounts a
-            JOIN account_groups ag ON a.group_id = ag.id
-            GROUP BY a.id
-            "#,
-        ).unwrap()
-    } else {
-        conn.prepare(
-            r#"
-            SELECT a.initial_balance, ag.type as account_type,
-                   CAST(COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0) AS REAL) as journal_balance
-            FROM accounts a
-            JOIN account_groups ag ON a.group_id = ag.id
-            LEFT JOIN journal_entries je ON je.account_id = a.id
-            GROUP BY a.id
-            "#,
-        ).unwrap()
-    };
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<(K, String, String, i64)>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| format!("Query error: {}", e))?;
 
-    let rows = if let Some(date) = as_of_date {
-        stmt.query_map(params![date], |row| {
-            Ok((row.get::<_, f64>(0)?, row.get::<_, String>(1)?, row.get::<_, f64>(2)?))
-        }).unwrap().collect::<Result<Vec<_>, _>>().unwrap()
-    } else {
-        stmt.query_map([], |row| {
-            Ok((row.get::<_, f64>(0)?, row.get::<_, String>(1)?, row.get::<_, f64>(2)?))
-        }).unwrap().collect::<Result<Vec<_>, _>>().unwrap()
-    };
-
-    let mut assets = 0.0_f64;
-    let mut liabilities = 0.0_f64;
-
-    for (initial, acc_type, journal) in rows {
-        let balance = initial + journal;
-        match acc_type.as_str() {
-            "ASSET" => assets += balance,
-            "LIABILITY" => liabilities += (-balance).max(0.0),
-            _ => {}
-        }
-    }
+    let rows = stmt
+        .query_map(params, |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+        .map_err(|e| format!("Execution error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
 
-    Ok((
-        (assets * 100.0).round() / 100.0,
-        (liabilities * 100.0).round() / 100.0,
-    ))
+    Ok(rows)
 }
 
 fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
@@ -441,25 +495,27 @@ fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
 
 #[derive(Debug, serde::Serialize)]
 pub struct AnalyticsDashboardData {
-    pub net_worth: f64,
-    pub total_assets: f64,
-    pub total_liabilities: f64,
-    pub this_month_income: f64,
-    pub this_month_expense: f64,
+    pub net_worth: Money,
+    pub total_assets: Money,
+    pub total_liabilities: Money,
+    pub this_month_income: Money,
+    pub this_month_expense: Money,
     pub savings_rate: f64,
     pub top_categories: Vec<DashboardCategory>,
     pub daily_spending: Vec<DashboardDailySummary>,
+    pub currency: String,
+    pub missing_rates: Vec<MissingRate>,
 }
 
 #[derive(Debug, serde::Serialize)]
 pub struct DashboardDailySummary {
     pub date: String,
-    pub amount: f64,
+    pub amount: Money,
 }
 
 #[derive(Debug, serde::Serialize)]
 pub struct DashboardCategory {
     pub name: String,
-    pub amount: f64,
+    pub amount: Money,
     pub percentage: f64,
 }
@@ -0,0 +1,802 @@
+// File: src-tauri/src/commands/audit.rs
+use crate::commands::accounts::{create_account_internal, delete_account_internal, update_account_internal};
+use crate::commands::budgets::{create_budget_internal, delete_budget_internal, update_budget_internal};
+use crate::commands::categories::{
+    create_category_internal, delete_category_internal, update_category_internal,
+};
+use crate::commands::currencies::{account_decimals, currency_decimals, primary_decimals};
+use crate::commands::goals::{create_goal_internal, delete_goal_internal, goal_decimals, update_goal_internal};
+use crate::commands::recurring::{
+    create_recurring_transaction_internal, delete_recurring_transaction_internal,
+    update_recurring_transaction_internal,
+};
+use crate::commands::transactions::{
+    apply_transaction_update, delete_transaction_internal, insert_transaction,
+};
+use crate::models::account::CreateAccountInput;
+use crate::models::audit::{AuditEntry, AuditFilter};
+use crate::models::budget::{CreateBudgetInput, UpdateBudgetInput};
+use crate::models::category::CreateCategoryInput;
+use crate::models::goal::{CreateGoalInput, UpdateGoalInput};
+use crate::models::money::Money;
+use crate::models::recurring::{CreateRecurringTransactionInput, UpdateRecurringTransactionInput};
+use crate::models::transactions::{CreateTransactionInput, TransactionSplitInput, UpdateTransactionInput};
+use crate::AppState;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{params, OptionalExtension};
+use serde_json::{Map, Value};
+use std::cell::Cell;
+use tauri::State;
+
+fn entity_table(entity_type: &str) -> Result<&'static str, String> {
+    match entity_type {
+        "TRANSACTION" => Ok("transactions"),
+        "ACCOUNT" => Ok("accounts"),
+        "CATEGORY" => Ok("categories"),
+        "BUDGET" => Ok("budgets"),
+        "GOAL" => Ok("savings_goals"),
+        "RECURRING" => Ok("recurring_transactions"),
+        _ => Err(format!("Unknown entity type: {}", entity_type)),
+    }
+}
+
+/// Run a query and return each row as a JSON object keyed by column name
+fn query_json_rows(conn: &rusqlite::Connection, sql: &str, id: i64) -> Result<Vec<Value>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| format!("Query error: {}", e))?;
+    let names: Vec<String> = stmt.column_names().iter().map(|n| n.to_string()).collect();
+
+    let rows = stmt
+        .query_map(params![id], |row| {
+            let mut object = Map::new();
+            for (i, name) in names.iter().enumerate() {
+                let value = match row.get_ref(i)? {
+                    ValueRef::Null | ValueRef::Blob(_) => Value::Null,
+                    ValueRef::Integer(n) => Value::from(n),
+                    ValueRef::Real(f) => Value::from(f),
+                    ValueRef::Text(t) => Value::from(String::from_utf8_lossy(t).into_owned()),
+                };
+                object.insert(name.clone(), value);
+            }
+            Ok(Value::Object(object))
+        })
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    Ok(rows)
+}
+
+/// Current state of a record as JSON (amounts in minor units), or None if it
+/// does not exist. Transactions also carry their tag ids and split lines.
+pub fn snapshot(
+    conn: &rusqlite::Connection,
+    entity_type: &str,
+    entity_id: i64,
+) -> Result<Option<Value>, String> {
+    let table = entity_table(entity_type)?;
+    let mut row = match query_json_rows(conn, &format!("SELECT * FROM {} WHERE id = ?1", table), entity_id)?
+        .pop()
+    {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    if entity_type == "TRANSACTION" {
+        let tag_ids: Vec<Value> = query_json_rows(
+            conn,
+            "SELECT tag_id FROM transaction_tags WHERE transaction_id = ?1 ORDER BY tag_id",
+            entity_id,
+        )?
+        .into_iter()
+        .map(|r| r["tag_id"].clone())
+        .collect();
+        let splits = query_json_rows(
+            conn,
+            "SELECT category_id, amount, memo FROM transaction_splits WHERE transaction_id = ?1 ORDER BY id",
+            entity_id,
+        )?;
+        row["tag_ids"] = Value::Array(tag_ids);
+        row["splits"] = Value::Array(splits);
+    }
+
+    Ok(Some(row))
+}
+
+thread_local! {
+    /// Set while a revert replays a change; the revert writes its own entry
+    static REVERTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Append an entry to the audit log. The after-state is read from the
+/// database, so call this once the change has been written.
+pub fn log_change(
+    conn: &rusqlite::Connection,
+    entity_type: &str,
+    entity_id: i64,
+    action: &str,
+    before: Option<Value>,
+) -> Result<(), String> {
+    if REVERTING.with(Cell::get) {
+        return Ok(());
+    }
+
+    let after = if action == "DELETE" {
+        None
+    } else {
+        snapshot(conn, entity_type, entity_id)?
+    };
+
+    conn.execute(
+        "INSERT INTO audit_log (entity_type, entity_id, action, before_json, after_json)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            entity_type,
+            entity_id,
+            action,
+            before.map(|v| v.to_string()),
+            after.map(|v| v.to_string())
+        ],
+    )
+    .map_err(|e| format!("Failed to write audit log: {}", e))?;
+
+    Ok(())
+}
+
+/// app_settings key holding the id of the last audit entry written before a
+/// restore or clear. Ids in those entries may now point at different records.
+const RESTORE_BOUNDARY_KEY: &str = "audit_restore_boundary";
+
+/// Close off the existing history after the data it describes was replaced.
+/// Call this inside the restore or clear transaction.
+pub fn mark_restore_boundary(conn: &rusqlite::Connection) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at)
+         VALUES (?1, (SELECT COALESCE(MAX(id), 0) FROM audit_log), datetime('now'))
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![RESTORE_BOUNDARY_KEY],
+    )
+    .map_err(|e| format!("Failed to record audit restore boundary: {}", e))?;
+
+    Ok(())
+}
+
+fn restore_boundary(conn: &rusqlite::Connection) -> Result<i64, String> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            params![RESTORE_BOUNDARY_KEY],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(value.and_then(|v| v.parse().ok()).unwrap_or(0))
+}
+
+const AUDIT_SELECT: &str =
+    "SELECT a.id, a.entity_type, a.entity_id, a.action, a.before_json, a.after_json,
+            a.reverted_entry_id, a.created_at,
+            EXISTS(SELECT 1 FROM audit_log r WHERE r.reverted_entry_id = a.id)
+     FROM audit_log a";
+
+fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<AuditEntry> {
+    let parse = |json: Option<String>| json.and_then(|j| serde_json::from_str(&j).ok());
+    Ok(AuditEntry {
+        id: row.get(0)?,
+        entity_type: row.get(1)?,
+        entity_id: row.get(2)?,
+        action: row.get(3)?,
+        before: parse(row.get(4)?),
+        after: parse(row.get(5)?),
+        reverted_entry_id: row.get(6)?,
+        created_at: row.get(7)?,
+        is_reverted: row.get(8)?,
+    })
+}
+
+fn get_entry(conn: &rusqlite::Connection, audit_id: i64) -> Result<AuditEntry, String> {
+    conn.query_row(&format!("{} WHERE a.id = ?1", AUDIT_SELECT), params![audit_id], row_to_entry)
+        .optional()
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| "Audit entry not found".to_string())
+}
+
+/// History for one record (entity_type + entity_id) or for everything in a
+/// date range, newest first.
+#[tauri::command]
+pub fn get_audit_history(
+    state: State<'_, AppState>,
+    filter: AuditFilter,
+) -> Result<Vec<AuditEntry>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    get_audit_history_internal(&conn, filter)
+}
+
+pub fn get_audit_history_internal(
+    conn: &rusqlite::Connection,
+    filter: AuditFilter,
+) -> Result<Vec<AuditEntry>, String> {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut values: Vec<SqlValue> = Vec::new();
+
+    if let Some(entity_type) = filter.entity_type {
+        entity_table(&entity_type)?;
+        conditions.push("a.entity_type = ?");
+        values.push(SqlValue::Text(entity_type));
+    }
+    if let Some(entity_id) = filter.entity_id {
+        conditions.push("a.entity_id = ?");
+        values.push(SqlValue::Integer(entity_id));
+    }
+    if let Some(start_date) = filter.start_date {
+        conditions.push("date(a.created_at) >= ?");
+        values.push(SqlValue::Text(start_date));
+    }
+    if let Some(end_date) = filter.end_date {
+        conditions.push("date(a.created_at) <= ?");
+        values.push(SqlValue::Text(end_date));
+    }
+
+    let mut query = AUDIT_SELECT.to_string();
+    if !conditions.is_empty() {
+        query.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+    }
+    query.push_str(&format!(" ORDER BY a.id DESC LIMIT {}", filter.limit.unwrap_or(500).max(1)));
+
+    let mut stmt = conn.prepare(&query).map_err(|e| format!("Query error: {}", e))?;
+    let entries = stmt
+        .query_map(rusqlite::params_from_iter(values), row_to_entry)
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    Ok(entries)
+}
+
+/// Undo one logged change by replaying the previous state through the
+/// regular create/update/delete commands, so their validation still applies.
+/// Only the latest change to a record can be reverted, and nothing logged
+/// before the last restore or clear.
+#[tauri::command]
+pub fn revert_audit_entry(state: State<'_, AppState>, audit_id: i64) -> Result<AuditEntry, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    revert_audit_entry_internal(&mut conn, audit_id)
+}
+
+pub fn revert_audit_entry_internal(
+    conn: &mut rusqlite::Connection,
+    audit_id: i64,
+) -> Result<AuditEntry, String> {
+    let entry = get_entry(conn, audit_id)?;
+
+    if entry.action == "REVERT" {
+        return Err("A revert cannot be reverted; revert the change it made instead".to_string());
+    }
+    if entry.is_reverted {
+        return Err("This change has already been reverted".to_string());
+    }
+    if entry.id <= restore_boundary(conn)? {
+        return Err("This change was made before the data was restored or cleared and can no longer be reverted".to_string());
+    }
+
+    let newer: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM audit_log
+             WHERE entity_type = ?1 AND entity_id = ?2 AND id > ?3 AND action != 'REVERT'",
+            params![entry.entity_type, entry.entity_id, entry.id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+    if newer > 0 {
+        return Err("The record has changed since; revert the later changes first".to_string());
+    }
+
+    // The replayed commands would log their own entries; the revert is
+    // recorded as a single REVERT entry instead, in the same transaction.
+    // A recreated record has a new id, and its history starts here.
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+    REVERTING.with(|r| r.set(true));
+    let replayed = replay_previous_state(&tx, &entry);
+    REVERTING.with(|r| r.set(false));
+    let result_id = replayed?;
+
+    let result = snapshot(&tx, &entry.entity_type, result_id)?;
+    tx.execute(
+        "INSERT INTO audit_log (entity_type, entity_id, action, before_json, after_json, reverted_entry_id)
+         VALUES (?1, ?2, 'REVERT', ?3, ?4, ?5)",
+        params![
+            entry.entity_type,
+            result_id,
+            entry.after.map(|v| v.to_string()),
+            result.map(|v| v.to_string()),
+            entry.id
+        ],
+    )
+    .map_err(|e| format!("Failed to write audit log: {}", e))?;
+    let revert_id = tx.last_insert_rowid();
+
+    tx.commit().map_err(|e| format!("Failed to commit revert: {}", e))?;
+
+    get_entry(conn, revert_id)
+}
+
+/// Undo `entry` through the regular commands. Returns the id of the record
+/// it leaves behind, which is new when a deleted record is recreated.
+fn replay_previous_state(conn: &rusqlite::Connection, entry: &AuditEntry) -> Result<i64, String> {
+    let empty = Value::Null;
+    let before = entry.before.as_ref().unwrap_or(&empty);
+    let after = entry.after.as_ref().unwrap_or(&empty);
+
+    match entry.action.as_str() {
+        "CREATE" => {
+            delete_entity(conn, &entry.entity_type, entry.entity_id)?;
+            Ok(entry.entity_id)
+        }
+        "UPDATE" => {
+            restore_entity(conn, &entry.entity_type, entry.entity_id, before, after)?;
+            Ok(entry.entity_id)
+        }
+        "DELETE" => recreate_entity(conn, &entry.entity_type, before),
+        other => Err(format!("Cannot revert action {}", other)),
+    }
+}
+
+fn text(v: &Value, key: &str) -> Option<String> {
+    v.get(key).and_then(|x| x.as_str()).map(|s| s.to_string())
+}
+
+fn int(v: &Value, key: &str) -> Option<i64> {
+    v.get(key).and_then(|x| x.as_i64())
+}
+
+fn required_text(v: &Value, key: &str) -> Result<String, String> {
+    text(v, key).ok_or_else(|| format!("Audit entry is missing '{}'", key))
+}
+
+fn required_int(v: &Value, key: &str) -> Result<i64, String> {
+    int(v, key).ok_or_else(|| format!("Audit entry is missing '{}'", key))
+}
+
+fn major(v: &Value, key: &str, decimals: u32) -> Result<f64, String> {
+    Ok(Money::new(required_int(v, key)?, decimals).to_major())
+}
+
+/// A transfer's recorded amount received, in major units of its destination account
+fn received_major(conn: &rusqlite::Connection, v: &Value) -> Result<Option<f64>, String> {
+    match (int(v, "to_account_id"), int(v, "to_amount")) {
+        (Some(to_account_id), Some(_)) => major(v, "to_amount", account_decimals(conn, to_account_id)?).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn changed(before: &Value, after: &Value, key: &str) -> bool {
+    before.get(key) != after.get(key)
+}
+
+/// Text field to pass to an update command, where "" clears the column
+fn changed_text(before: &Value, after: &Value, key: &str) -> Option<String> {
+    changed(before, after, key).then(|| text(before, key).unwrap_or_default())
+}
+
+fn cannot_clear(field: &str) -> String {
+    format!("Cannot revert: clearing '{}' is not supported by the update command", field)
+}
+
+fn split_inputs(v: &Value, decimals: u32) -> Result<Vec<TransactionSplitInput>, String> {
+    let lines = v.get("splits").and_then(|s| s.as_array()).cloned().unwrap_or_default();
+    lines
+        .iter()
+        .map(|line| {
+            Ok(TransactionSplitInput {
+                category_id: required_int(line, "category_id")?,
+                amount: major(line, "amount", decimals)?,
+                memo: text(line, "memo"),
+            })
+        })
+        .collect()
+}
+
+fn tag_ids(v: &Value) -> Vec<i64> {
+    v.get("tag_ids")
+        .and_then(|t| t.as_array())
+        .map(|ids| ids.iter().filter_map(|id| id.as_i64()).collect())
+        .unwrap_or_default()
+}
+
+fn delete_entity(conn: &rusqlite::Connection, entity_type: &str, id: i64) -> Result<(), String> {
+    match entity_type {
+        "TRANSACTION" => delete_transaction_internal(conn, id),
+        "ACCOUNT" => delete_account_internal(conn, id),
+        "CATEGORY" => delete_category_internal(conn, id),
+        "BUDGET" => delete_budget_internal(conn, id),
+        "GOAL" => delete_goal_internal(conn, id),
+        "RECURRING" => delete_recurring_transaction_internal(conn, id),
+        other => Err(format!("Unknown entity type: {}", other)),
+    }
+}
+
+/// Put back the fields that differ between `before` and `after`
+fn restore_entity(
+    conn: &rusqlite::Connection,
+    entity_type: &str,
+    id: i64,
+    before: &Value,
+    after: &Value,
+) -> Result<(), String> {
+    match entity_type {
+        "TRANSACTION" => {
+            let decimals = account_decimals(conn, required_int(before, "account_id")?)?;
+            let splits_changed = changed(before, after, "splits");
+            let payee_changed = changed(before, after, "payee_id");
+            let category_id = if changed(before, after, "category_id") {
+                match int(before, "category_id") {
+                    Some(category_id) => Some(category_id),
+                    None if !split_inputs(before, decimals)?.is_empty() => None,
+                    None => return Err(cannot_clear("category")),
+                }
+            } else {
+                None
+            };
+            apply_transaction_update(
+                conn,
+                UpdateTransactionInput {
+                    id,
+                    date: changed(before, after, "date").then(|| text(before, "date")).flatten(),
+                    amount: None,
+                    to_amount: if changed(before, after, "to_amount") {
+                        received_major(conn, before)?
+                    } else {
+                        None
+                    },
+                    category_id,
+                    payee_id: if payee_changed { int(before, "payee_id") } else { None },
+                    // An empty payee name clears the link
+                    payee: (payee_changed && int(before, "payee_id").is_none()).then(String::new),
+                    memo: changed_text(before, after, "memo"),
+                    tag_ids: changed(before, after, "tag_ids").then(|| tag_ids(before)),
+                    splits: if splits_changed { Some(split_inputs(before, decimals)?) } else { None },
+                },
+            )
+        }
+        "ACCOUNT" => {
+            let decimals = currency_decimals(&required_text(before, "currency")?);
+            update_account_internal(
+                conn,
+                id,
+                changed(before, after, "name").then(|| text(before, "name")).flatten(),
+                if changed(before, after, "initial_balance") {
+                    Some(major(before, "initial_balance", decimals)?)
+                } else {
+                    None
+                },
+                changed(before, after, "currency").then(|| text(before, "currency")).flatten(),
+            )
+        }
+        "CATEGORY" => {
+            let parent_id = if changed(before, after, "parent_id") {
+                Some(int(before, "parent_id").ok_or_else(|| cannot_clear("parent"))?)
+            } else {
+                None
+            };
+            update_category_internal(
+                conn,
+                id,
+                changed(before, after, "name").then(|| text(before, "name")).flatten(),
+                parent_id,
+            )
+        }
+        "BUDGET" => update_budget_internal(
+            conn,
+            UpdateBudgetInput {
+                id,
+                amount: if changed(before, after, "amount") {
+                    Some(major(before, "amount", primary_decimals(conn))?)
+                } else {
+                    None
+                },
+                start_date: changed(before, after, "start_date").then(|| text(before, "start_date")).flatten(),
+                end_date: changed(before, after, "end_date").then(|| text(before, "end_date")).flatten(),
+                rollover_enabled: changed(before, after, "rollover_enabled")
+                    .then(|| int(before, "rollover_enabled").map(|v| v != 0))
+                    .flatten(),
+                rollover_cap: if changed(before, after, "rollover_cap") {
+                    // 0 removes the cap
+                    Some(match int(before, "rollover_cap") {
+                        Some(cap) => Money::new(cap, primary_decimals(conn)).to_major(),
+                        None => 0.0,
+                    })
+                } else {
+                    None
+                },
+                // 0 removes the saved filter
+                saved_filter_id: changed(before, after, "saved_filter_id")
+                    .then(|| int(before, "saved_filter_id").unwrap_or(0)),
+            },
+        ),
+        "GOAL" => {
+            let decimals = goal_decimals(conn, id)?;
+            update_goal_internal(
+                conn,
+                UpdateGoalInput {
+                    id,
+                    name: changed(before, after, "name").then(|| text(before, "name")).flatten(),
+                    target_amount: if changed(before, after, "target_amount") {
+                        Some(major(before, "target_amount", decimals)?)
+                    } else {
+                        None
+                    },
+                    target_date: changed_text(before, after, "target_date"),
+                    color: changed(before, after, "color").then(|| text(before, "color")).flatten(),
+                    icon: changed(before, after, "icon").then(|| text(before, "icon")).flatten(),
+                },
+            )
+            .map(|_| ())
+        }
+        "RECURRING" => {
+            let decimals = account_decimals(conn, required_int(before, "account_id")?)?;
+            let end_date = if changed(before, after, "end_date") {
+                Some(text(before, "end_date").ok_or_else(|| cannot_clear("end date"))?)
+            } else {
+                None
+            };
+            update_recurring_transaction_internal(
+                conn,
+                UpdateRecurringTransactionInput {
+                    id,
+                    name: changed(before, after, "name").then(|| text(before, "name")).flatten(),
+                    description: changed_text(before, after, "description"),
+                    amount: if changed(before, after, "amount") {
+                        Some(major(before, "amount", decimals)?)
+                    } else {
+                        None
+                    },
+                    frequency: changed(before, after, "frequency").then(|| text(before, "frequency")).flatten(),
+                    interval_days: changed(before, after, "interval_days").then(|| int(before, "interval_days")).flatten(),
+                    end_date,
+                    amount_mode: changed(before, after, "amount_mode").then(|| text(before, "amount_mode")).flatten(),
+                    resume_date: changed_text(before, after, "resume_date"),
+                    active_months: changed_text(before, after, "active_months"),
+                    auto_approve: changed(before, after, "auto_approve")
+                        .then(|| int(before, "auto_approve").map(|v| v != 0))
+                        .flatten(),
+                },
+            )
+        }
+        other => Err(format!("Unknown entity type: {}", other)),
+    }
+}
+
+/// Create the record again from its last known state. Returns the new id.
+fn recreate_entity(conn: &rusqlite::Connection, entity_type: &str, before: &Value) -> Result<i64, String> {
+    match entity_type {
+        "TRANSACTION" => {
+            let account_id = required_int(before, "account_id")?;
+            let decimals = account_decimals(conn, account_id)?;
+            let splits = split_inputs(before, decimals)?;
+            insert_transaction(
+                conn,
+                CreateTransactionInput {
+                    date: required_text(before, "date")?,
+                    transaction_type: required_text(before, "type")?,
+                    amount: major(before, "amount", decimals)?,
+                    account_id,
+                    to_account_id: int(before, "to_account_id"),
+                    to_amount: received_major(conn, before)?,
+                    category_id: int(before, "category_id"),
+                    payee_id: int(before, "payee_id"),
+                    payee: None,
+                    memo: text(before, "memo"),
+                    tag_ids: Some(tag_ids(before)),
+                    goal_allocations: None,
+                    goal_withdrawals: None,
+                    splits: (!splits.is_empty()).then_some(splits),
+                },
+            )
+        }
+        "ACCOUNT" => {
+            let currency = required_text(before, "currency")?;
+            create_account_internal(
+                conn,
+                CreateAccountInput {
+                    group_id: required_int(before, "group_id")?,
+                    name: required_text(before, "name")?,
+                    initial_balance: major(before, "initial_balance", currency_decimals(&currency))?,
+                    currency: Some(currency),
+                },
+            )
+            .map(|account| account.id)
+        }
+        "CATEGORY" => create_category_internal(
+            conn,
+            CreateCategoryInput {
+                parent_id: int(before, "parent_id"),
+                name: required_text(before, "name")?,
+                category_type: required_text(before, "type")?,
+            },
+        )
+        .map(|category| category.id),
+        "BUDGET" => {
+            let decimals = primary_decimals(conn);
+            create_budget_internal(
+                conn,
+                CreateBudgetInput {
+                    category_id: required_int(before, "category_id")?,
+                    amount: major(before, "amount", decimals)?,
+                    period: required_text(before, "period")?,
+                    start_date: required_text(before, "start_date")?,
+                    end_date: text(before, "end_date"),
+                    rollover_enabled: int(before, "rollover_enabled").map(|v| v != 0),
+                    rollover_cap: int(before, "rollover_cap").map(|cap| Money::new(cap, decimals).to_major()),
+                    saved_filter_id: int(before, "saved_filter_id"),
+                },
+            )
+        }
+        "GOAL" => {
+            let linked_account_id = int(before, "linked_account_id");
+            let decimals = match linked_account_id {
+                Some(account_id) => account_decimals(conn, account_id)?,
+                None => primary_decimals(conn),
+            };
+            create_goal_internal(
+                conn,
+                CreateGoalInput {
+                    name: required_text(before, "name")?,
+                    target_amount: major(before, "target_amount", decimals)?,
+                    target_date: text(before, "target_date"),
+                    linked_account_id,
+                    color: text(before, "color"),
+                    icon: text(before, "icon"),
+                },
+            )
+            .map(|goal| goal.id)
+        }
+        "RECURRING" => {
+            let account_id = required_int(before, "account_id")?;
+            let decimals = account_decimals(conn, account_id)?;
+            let recurring_id = create_recurring_transaction_internal(
+                conn,
+                CreateRecurringTransactionInput {
+                    name: required_text(before, "name")?,
+                    description: text(before, "description"),
+                    transaction_type: required_text(before, "transaction_type")?,
+                    amount: major(before, "amount", decimals)?,
+                    account_id,
+                    to_account_id: int(before, "to_account_id"),
+                    category_id: int(before, "category_id"),
+                    frequency: required_text(before, "frequency")?,
+                    interval_days: int(before, "interval_days"),
+                    start_date: required_text(before, "start_date")?,
+                    end_date: text(before, "end_date"),
+                    amount_mode: text(before, "amount_mode"),
+                    active_months: text(before, "active_months"),
+                    auto_approve: int(before, "auto_approve").map(|v| v != 0),
+                },
+            )?;
+
+            // Carry over the schedule so past occurrences are not generated again
+            conn.execute(
+                "UPDATE recurring_transactions
+                 SET next_execution_date = ?1, last_executed_date = ?2, execution_count = ?3,
+                     is_active = ?4, resume_date = ?5
+                 WHERE id = ?6",
+                params![
+                    required_text(before, "next_execution_date")?,
+                    text(before, "last_executed_date"),
+                    int(before, "execution_count").unwrap_or(0),
+                    int(before, "is_active").unwrap_or(1),
+                    text(before, "resume_date"),
+                    recurring_id
+                ],
+            )
+            .map_err(|e| format!("Failed to restore schedule: {}", e))?;
+
+            Ok(recurring_id)
+        }
+        other => Err(format!("Unknown entity type: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::settings::restore_from_backup_internal;
+
+    #[test]
+    fn entries_from_before_a_restore_cannot_be_reverted() {
+        let dir = std::env::temp_dir().join(format!("mm-audit-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = crate::db::init_database_unencrypted(&dir.join("money_manager.db")).unwrap();
+        let mut conn = pool.lock().unwrap();
+
+        let account = |conn: &rusqlite::Connection, name: &str| {
+            create_account_internal(
+                conn,
+                CreateAccountInput {
+                    group_id: 1,
+                    name: name.into(),
+                    initial_balance: 0.0,
+                    currency: None,
+                },
+            )
+            .unwrap()
+            .id
+        };
+        let latest_entry = |conn: &rusqlite::Connection| {
+            conn.query_row("SELECT MAX(id) FROM audit_log", [], |row| row.get::<_, i64>(0))
+                .unwrap()
+        };
+
+        let old_id = account(&conn, "Before Restore");
+        let old_entry = latest_entry(&conn);
+
+        let backup = r#"{"version": "1.0", "data": {"accounts": [], "categories": [], "transactions": [], "budgets": []}}"#;
+        restore_from_backup_internal(&mut conn, backup).unwrap();
+
+        // The restored database hands out the same id to a different account
+        let mut new_id = 0;
+        while new_id < old_id {
+            new_id = account(&conn, "After Restore");
+        }
+        assert_eq!(new_id, old_id);
+        let new_entry = latest_entry(&conn);
+
+        assert!(revert_audit_entry_internal(&mut conn, old_entry).is_err());
+        let count = |conn: &rusqlite::Connection| {
+            conn.query_row("SELECT COUNT(*) FROM accounts WHERE id = ?1", params![new_id], |row| row.get::<_, i64>(0))
+                .unwrap()
+        };
+        assert_eq!(count(&conn), 1);
+
+        revert_audit_entry_internal(&mut conn, new_entry).unwrap();
+        assert_eq!(count(&conn), 0);
+
+        drop(conn);
+        pool.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_revert_writes_one_entry() {
+        let dir = std::env::temp_dir().join(format!("mm-audit-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = crate::db::init_database_unencrypted(&dir.join("money_manager.db")).unwrap();
+        let mut conn = pool.lock().unwrap();
+
+        let account_id = create_account_internal(
+            &conn,
+            CreateAccountInput {
+                group_id: 1,
+                name: "Original".into(),
+                initial_balance: 0.0,
+                currency: None,
+            },
+        )
+        .unwrap()
+        .id;
+        update_account_internal(&conn, account_id, Some("Renamed".into()), None, None).unwrap();
+        let (update_entry, entries_before) = conn
+            .query_row("SELECT MAX(id), COUNT(*) FROM audit_log", [], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+            })
+            .unwrap();
+
+        let revert = revert_audit_entry_internal(&mut conn, update_entry).unwrap();
+        assert_eq!(revert.action, "REVERT");
+        assert_eq!(revert.reverted_entry_id, Some(update_entry));
+
+        let entries_after: i64 = conn.query_row("SELECT COUNT(*) FROM audit_log", [], |row| row.get(0)).unwrap();
+        assert_eq!(entries_after, entries_before + 1);
+        let name: String = conn
+            .query_row("SELECT name FROM accounts WHERE id = ?1", params![account_id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(name, "Original");
+
+        drop(conn);
+        pool.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,5 +1,7 @@
 // File: src-tauri/src/commands/bills.rs
+use crate::commands::currencies::currency_decimals;
 use crate::models::bill::UpcomingBill;
+use crate::models::money::Money;
 use crate::AppState;
 use chrono::{Datelike, Duration, NaiveDate};
 use rusqlite::params;
@@ -15,9 +17,9 @@ pub fn get_upcoming_bills(
     let pool = crate::get_db(&state)?;
 
     // Auto-resume any paused items whose resume_date has arrived (before locking conn)
-    let _ = crate::commands::recurring::check_and_resume(pool.as_ref());
+    let _ = crate::commands::recurring::check_and_resume(&pool);
     // Auto-execute all due items with auto_approve=1 and FIXED amount
-    let _ = crate::commands::recurring::process_auto_approvals(pool.as_ref());
+    let _ = crate::commands::recurring::process_auto_approvals(&pool);
 
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
@@ -37,7 +39,7 @@ pub fn get_upcoming_bills(
             SELECT rt.id, rt.name, rt.amount, rt.transaction_type, rt.next_execution_date,
                    a.name as account_name,
                    c.name as category_name,
-                   rt.amount_mode, rt.active_months
+                   rt.amount_mode, rt.active_months, a.currency
             FROM recurring_transactions rt
             INNER JOIN accounts a ON rt.account_id = a.id
             LEFT JOIN categories c ON rt.category_id = c.id
@@ -56,12 +58,13 @@ pub fn get_upcoming_bills(
             let days_until = (due_date - today).num_days();
             let amount_mode: String = row.get(7)?;
             let active_months: Option<String> = row.get(8)?;
+            let currency: String = row.get(9)?;
 
             Ok((UpcomingBill {
                 source: "RECURRING".to_string(),
                 source_id: row.get(0)?,
                 name: row.get(1)?,
-                amount: row.get(2)?,
+                amount: Money::new(row.get(2)?, currency_decimals(&currency)),
                 due_date: due_date_str,
                 days_until_due: days_until,
                 transaction_type: row.get(3)?,
@@ -91,7 +94,8 @@ pub fn get_upcoming_bills(
             SELECT ip.id, ip.name, ip.amount_per_installment, ip.next_due_date,
                    ip.installments_paid, ip.num_installments,
                    a.name as account_name,
-                   c.name as category_name
+                   c.name as category_name,
+                   a.currency
             FROM installment_plans ip
             INNER JOIN accounts a ON ip.account_id = a.id
             INNER JOIN categories c ON ip.category_id = c.id
@@ -109,12 +113,13 @@ pub fn get_upcoming_bills(
             let days_until = (due_date - today).num_days();
             let paid: i32 = row.get(4)?;
             let total: i32 = row.get(5)?;
+            let currency: String = row.get(8)?;
 
             Ok(UpcomingBill {
                 source: "INSTALLMENT".to_string(),
                 source_id: row.get(0)?,
                 name: row.get(1)?,
-                amount: row.get(2)?,
+                amount: Money::new(row.get(2)?, currency_decimals(&currency)),
                 due_date: due_date_str,
                 days_until_due: days_until,
                 transaction_type: "EXPENSE".to_string(),
@@ -1,35 +1,55 @@
 // File: src-tauri/src/commands/budgets.rs
+use crate::commands::audit::{log_change, snapshot};
+use crate::commands::currencies::{primary_decimals, RateConverter};
+use crate::commands::saved_filters::saved_filter_condition;
 use crate::models::budget::{
-    Budget, BudgetAlert, BudgetStatus, CreateBudgetInput, UpdateBudgetInput,
+    Budget, BudgetAlert, BudgetPeriod, BudgetStatus, CreateBudgetInput, UpdateBudgetInput,
 };
+use crate::models::money::Money;
 use crate::AppState;
-use chrono::{Datelike, NaiveDate};
+use chrono::{Days, Duration, Months, NaiveDate};
 use rusqlite::params;
 use tauri::State;
 
+const BUDGET_COLUMNS: &str =
+    "b.id, b.category_id, b.amount, b.period, b.start_date, b.rollover_enabled, b.rollover_cap, b.end_date,
+     b.saved_filter_id";
+
+const BUDGET_PERIODS: [&str; 6] = ["WEEKLY", "BIWEEKLY", "MONTHLY", "QUARTERLY", "YEARLY", "CUSTOM"];
+
+fn row_to_budget(row: &rusqlite::Row, decimals: u32) -> rusqlite::Result<Budget> {
+    Ok(Budget {
+        id: row.get(0)?,
+        category_id: row.get(1)?,
+        amount: Money::new(row.get(2)?, decimals),
+        period: row.get(3)?,
+        start_date: row.get(4)?,
+        rollover_enabled: row.get(5)?,
+        rollover_cap: row.get::<_, Option<i64>>(6)?.map(|cap| Money::new(cap, decimals)),
+        end_date: row.get(7)?,
+        saved_filter_id: row.get(8)?,
+    })
+}
+
 #[tauri::command]
 pub fn get_budgets(state: State<'_, AppState>) -> Result<Vec<Budget>, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let mut stmt = conn
-        .prepare(
-            "SELECT id, category_id, amount, period, start_date 
-             FROM budgets 
-             ORDER BY start_date DESC",
-        )
+        .prepare(&format!(
+            "SELECT {} 
+             FROM budgets b 
+             ORDER BY b.start_date DESC",
+            BUDGET_COLUMNS
+        ))
         .map_err(|e| format!("Query error: {}", e))?;
 
+    // Budgets are kept in the primary currency
+    let decimals = primary_decimals(&conn);
+
     let budgets = stmt
-        .query_map([], |row| {
-            Ok(Budget {
-                id: row.get(0)?,
-                category_id: row.get(1)?,
-                amount: row.get(2)?,
-                period: row.get(3)?,
-                start_date: row.get(4)?,
-            })
-        })
+        .query_map([], |row| row_to_budget(row, decimals))
         .map_err(|e| format!("Execute error: {}", e))?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| format!("Read error: {}", e))?;
@@ -44,17 +64,30 @@ pub fn create_budget(
 ) -> Result<i64, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    create_budget_internal(&conn, input)
+}
 
+pub fn create_budget_internal(
+    conn: &rusqlite::Connection,
+    input: CreateBudgetInput,
+) -> Result<i64, String> {
     // Validate period
-    if input.period != "MONTHLY" && input.period != "YEARLY" {
-        return Err("Period must be MONTHLY or YEARLY".to_string());
+    if !BUDGET_PERIODS.contains(&input.period.as_str()) {
+        return Err(format!("Period must be one of {}", BUDGET_PERIODS.join(", ")));
     }
 
     // Validate amount
-    if input.amount <= 0.0 {
+    let decimals = primary_decimals(conn);
+    let amount = Money::from_major(input.amount, decimals);
+    if !amount.is_positive() {
         return Err("Budget amount must be greater than zero".to_string());
     }
 
+    let rollover_cap = input
+        .rollover_cap
+        .map(|cap| Money::from_major(cap, decimals))
+        .filter(|cap| cap.is_positive());
+
     // Validate category exists
     let category_exists: bool = conn
         .query_row(
@@ -68,15 +101,29 @@ pub fn create_budget(
         return Err("Category does not exist".to_string());
     }
 
+    if let Some(saved_filter_id) = input.saved_filter_id {
+        ensure_saved_filter_exists(conn, saved_filter_id)?;
+    }
+
     // Validate date format
-    NaiveDate::parse_from_str(&input.start_date, "%Y-%m-%d")
+    let start_date = NaiveDate::parse_from_str(&input.start_date, "%Y-%m-%d")
         .map_err(|_| "Invalid date format. Use YYYY-MM-DD".to_string())?;
 
+    // CUSTOM budgets cover an explicit window; the others repeat from start_date
+    let end_date = if input.period == "CUSTOM" {
+        let end_date = input.end_date.as_deref().ok_or("CUSTOM budgets require an end date")?;
+        validate_end_date(start_date, end_date)?;
+        Some(end_date)
+    } else {
+        None
+    };
+
     // Check if budget already exists for this category and period
     let existing: bool = conn
         .query_row(
-            "SELECT COUNT(id) FROM budgets WHERE category_id = ?1 AND period = ?2 AND start_date = ?3",
-            params![input.category_id, input.period, input.start_date],
+            "SELECT COUNT(id) FROM budgets
+             WHERE category_id = ?1 AND period = ?2 AND start_date = ?3 AND saved_filter_id IS ?4",
+            params![input.category_id, input.period, input.start_date, input.saved_filter_id],
             |row| row.get::<_, i64>(0),
         )
         .unwrap_or(0) > 0;
@@ -86,12 +133,26 @@ pub fn create_budget(
     }
 
     conn.execute(
-        "INSERT INTO budgets (category_id, amount, period, start_date) VALUES (?1, ?2, ?3, ?4)",
-        params![input.category_id, input.amount, input.period, input.start_date],
+        "INSERT INTO budgets (category_id, amount, period, start_date, end_date, rollover_enabled, rollover_cap,
+                              saved_filter_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            input.category_id,
+            amount,
+            input.period,
+            input.start_date,
+            end_date,
+            input.rollover_enabled.unwrap_or(false),
+            rollover_cap,
+            input.saved_filter_id
+        ],
     )
     .map_err(|e| format!("Failed to create budget: {}", e))?;
 
-    Ok(conn.last_insert_rowid())
+    let budget_id = conn.last_insert_rowid();
+    log_change(conn, "BUDGET", budget_id, "CREATE", None)?;
+
+    Ok(budget_id)
 }
 
 #[tauri::command]
@@ -101,27 +162,30 @@ pub fn update_budget(
 ) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    update_budget_internal(&conn, input)
+}
 
+pub fn update_budget_internal(
+    conn: &rusqlite::Connection,
+    input: UpdateBudgetInput,
+) -> Result<(), String> {
     // Check if budget exists
-    let exists: bool = conn
+    let (period, current_start, current_end): (String, String, Option<String>) = conn
         .query_row(
-            "SELECT COUNT(id) FROM budgets WHERE id = ?1",
+            "SELECT period, start_date, end_date FROM budgets WHERE id = ?1",
             params![input.id],
-            |row| row.get::<_, i64>(0),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
         )
-        .unwrap_or(0) > 0;
-
-    if !exists {
-        return Err("Budget not found".to_string());
-    }
+        .map_err(|_| "Budget not found".to_string())?;
 
     let mut updates = Vec::new();
 
     if let Some(amount) = input.amount {
-        if amount <= 0.0 {
+        let amount = Money::from_major(amount, primary_decimals(conn));
+        if !amount.is_positive() {
             return Err("Budget amount must be greater than zero".to_string());
         }
-        updates.push(format!("amount = {}", amount));
+        updates.push(format!("amount = {}", amount.minor()));
     }
 
     if let Some(start_date) = &input.start_date {
@@ -130,6 +194,43 @@ pub fn update_budget(
         updates.push(format!("start_date = '{}'", start_date));
     }
 
+    if let Some(end_date) = &input.end_date {
+        if period != "CUSTOM" {
+            return Err("Only CUSTOM budgets have an end date".to_string());
+        }
+        updates.push(format!("end_date = '{}'", end_date));
+    }
+
+    if period == "CUSTOM" && (input.start_date.is_some() || input.end_date.is_some()) {
+        let start_date = input.start_date.as_deref().unwrap_or(&current_start);
+        let end_date = input.end_date.as_deref().or(current_end.as_deref()).unwrap_or(start_date);
+        let start_date = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
+            .map_err(|_| "Invalid start date in database".to_string())?;
+        validate_end_date(start_date, end_date)?;
+    }
+
+    if let Some(rollover_enabled) = input.rollover_enabled {
+        updates.push(format!("rollover_enabled = {}", if rollover_enabled { 1 } else { 0 }));
+    }
+
+    if let Some(cap) = input.rollover_cap {
+        let cap = Money::from_major(cap, primary_decimals(conn));
+        if cap.is_positive() {
+            updates.push(format!("rollover_cap = {}", cap.minor()));
+        } else {
+            updates.push("rollover_cap = NULL".to_string());
+        }
+    }
+
+    if let Some(saved_filter_id) = input.saved_filter_id {
+        if saved_filter_id > 0 {
+            ensure_saved_filter_exists(conn, saved_filter_id)?;
+            updates.push(format!("saved_filter_id = {}", saved_filter_id));
+        } else {
+            updates.push("saved_filter_id = NULL".to_string());
+        }
+    }
+
     if updates.is_empty() {
         return Err("No fields to update".to_string());
     }
@@ -140,16 +241,23 @@ pub fn update_budget(
         input.id
     );
 
+    let before = snapshot(conn, "BUDGET", input.id)?;
+
     conn.execute(&query, [])
         .map_err(|e| format!("Failed to update budget: {}", e))?;
 
-    Ok(())
+    log_change(conn, "BUDGET", input.id, "UPDATE", before)
 }
 
 #[tauri::command]
 pub fn delete_budget(state: State<'_, AppState>, budget_id: i64) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    delete_budget_internal(&conn, budget_id)
+}
+
+pub fn delete_budget_internal(conn: &rusqlite::Connection, budget_id: i64) -> Result<(), String> {
+    let before = snapshot(conn, "BUDGET", budget_id)?;
 
     let rows_affected = conn
         .execute("DELETE FROM budgets WHERE id = ?1", params![budget_id])
@@ -159,7 +267,7 @@ pub fn delete_budget(state: State<'_, AppState>, budget_id: i64) -> Result<(), S
         return Err("Budget not found".to_string());
     }
 
-    Ok(())
+    log_change(conn, "BUDGET", budget_id, "DELETE", before)
 }
 
 #[tauri::command]
@@ -173,97 +281,202 @@ pub fn get_budget_status(
     get_budget_status_internal(&conn, budget_id)
 }
 
+fn ensure_saved_filter_exists(conn: &rusqlite::Connection, saved_filter_id: i64) -> Result<(), String> {
+    let exists = conn
+        .query_row(
+            "SELECT COUNT(*) FROM saved_filters WHERE id = ?1",
+            params![saved_filter_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap_or(0)
+        > 0;
+    if !exists {
+        return Err("Saved filter does not exist".to_string());
+    }
+    Ok(())
+}
+
+fn validate_end_date(start_date: NaiveDate, end_date: &str) -> Result<(), String> {
+    let end_date = NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
+        .map_err(|_| "Invalid end date format. Use YYYY-MM-DD".to_string())?;
+    if end_date < start_date {
+        return Err("End date must not be before the start date".to_string());
+    }
+    Ok(())
+}
+
+/// Start of the `n`-th period of a budget anchored at its start date.
+/// Month arithmetic clamps to the end of shorter months. A CUSTOM budget has
+/// a single period; "period 1" is the day after its end date.
+fn period_start(budget: &Budget, anchor: NaiveDate, n: u32) -> Result<NaiveDate, String> {
+    let start = match budget.period.as_str() {
+        "WEEKLY" => anchor.checked_add_days(Days::new(7 * n as u64)),
+        "BIWEEKLY" => anchor.checked_add_days(Days::new(14 * n as u64)),
+        "MONTHLY" => anchor.checked_add_months(Months::new(n)),
+        "QUARTERLY" => anchor.checked_add_months(Months::new(3 * n)),
+        "YEARLY" => anchor.checked_add_months(Months::new(12 * n)),
+        "CUSTOM" if n == 0 => Some(anchor),
+        "CUSTOM" => {
+            let end_date = budget.end_date.as_deref().ok_or("CUSTOM budget has no end date")?;
+            NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
+                .map_err(|_| "Invalid end date in database".to_string())?
+                .checked_add_days(Days::new(1))
+        }
+        _ => return Err("Invalid budget period".to_string()),
+    };
+    start.ok_or_else(|| "Budget period out of range".to_string())
+}
+
+/// Amount carried into the next period, limited to the cap in either direction
+fn carry_over(budget: &Budget, closing_balance: Money) -> Money {
+    let decimals = budget.amount.decimals();
+    if !budget.rollover_enabled {
+        return Money::zero(decimals);
+    }
+    let carried = match budget.rollover_cap {
+        Some(cap) => closing_balance.clamp(-cap, cap),
+        None => closing_balance,
+    };
+    carried.rescale(decimals)
+}
+
 fn get_budget_status_internal(
     conn: &rusqlite::Connection,
     budget_id: i64,
 ) -> Result<BudgetStatus, String> {
     // Get budget details
-    let mut stmt = conn.prepare(
-        "SELECT b.id, b.category_id, b.amount, b.period, b.start_date, c.name as category_name
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, c.name as category_name
          FROM budgets b
          INNER JOIN categories c ON b.category_id = c.id
          WHERE b.id = ?1",
-    ).map_err(|e| format!("Query error: {}", e))?;
+        BUDGET_COLUMNS
+    )).map_err(|e| format!("Query error: {}", e))?;
+
+    let decimals = primary_decimals(conn);
 
     let (budget, category_name) = stmt.query_row(params![budget_id], |row| {
-        Ok((
-            Budget {
-                id: row.get(0)?,
-                category_id: row.get(1)?,
-                amount: row.get(2)?,
-                period: row.get(3)?,
-                start_date: row.get(4)?,
-            },
-            row.get::<_, String>(5)?,
-        ))
+        Ok((row_to_budget(row, decimals)?, row.get::<_, String>(9)?))
     }).map_err(|_| "Budget not found".to_string())?;
 
-    // Calculate date range for the budget period
-    let start_date = NaiveDate::parse_from_str(&budget.start_date, "%Y-%m-%d")
+    let anchor = NaiveDate::parse_from_str(&budget.start_date, "%Y-%m-%d")
         .map_err(|_| "Invalid start date in database".to_string())?;
 
-    let end_date = match budget.period.as_str() {
-        "MONTHLY" => {
-            let next_month = if start_date.month() == 12 {
-                NaiveDate::from_ymd_opt(start_date.year() + 1, 1, start_date.day())
-            } else {
-                NaiveDate::from_ymd_opt(start_date.year(), start_date.month() + 1, start_date.day())
-            };
-            next_month.unwrap_or(start_date)
-        }
-        "YEARLY" => {
-            NaiveDate::from_ymd_opt(start_date.year() + 1, start_date.month(), start_date.day())
-                .unwrap_or(start_date)
-        }
-        _ => return Err("Invalid budget period".to_string()),
-    };
-
+    // Budgets repeat from their start date; find the period containing today
+    // (the first one if the budget has not started yet, the only one for CUSTOM)
     let today = chrono::Local::now().naive_local().date();
-    let days_remaining = (end_date - today).num_days().max(0);
-
-    // Calculate actual spending (including subcategories)
-    let spent_amount: f64 = conn.query_row(
-        "SELECT CAST(COALESCE(SUM(t.amount), 0) AS REAL) as spent_amount
-         FROM transactions t
+    let mut current: u32 = 0;
+    if budget.period != "CUSTOM" {
+        while period_start(&budget, anchor, current + 1)? <= today {
+            current += 1;
+        }
+    }
+    let start_date = period_start(&budget, anchor, current)?;
+    let end_date = period_start(&budget, anchor, current + 1)?;
+
+    // Days left in the window, not counting any before it starts
+    let days_remaining = (end_date - today.max(start_date)).num_days().max(0);
+
+    // Daily spending since the first period (including subcategories and split lines),
+    // converted to the primary currency at each day's rate. A saved filter narrows it
+    // further; the budget's periods replace the filter's own dates.
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.date, t.date, a.currency, COALESCE(SUM(t.amount), 0) as spent_amount
+         FROM transaction_category_lines t
+         INNER JOIN accounts a ON t.account_id = a.id
          INNER JOIN categories c ON t.category_id = c.id
          WHERE t.type = 'EXPENSE'
            AND t.date >= ?1 AND t.date < ?2
-           AND (c.id = ?3 OR c.parent_id = ?4)",
-        params![
-            budget.start_date,
-            end_date.format("%Y-%m-%d").to_string(),
-            budget.category_id,
-            budget.category_id
-        ],
-        |row| row.get(0),
-    ).unwrap_or(0.0);
+           AND (c.id = ?3 OR c.parent_id = ?4){}
+         GROUP BY t.date, a.currency",
+        saved_filter_condition(conn, budget.saved_filter_id, false)?
+    )).map_err(|e| format!("Query error: {}", e))?;
+
+    let rows: Vec<(String, String, String, i64)> = stmt
+        .query_map(
+            params![
+                budget.start_date,
+                end_date.format("%Y-%m-%d").to_string(),
+                budget.category_id,
+                budget.category_id
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    let mut converter = RateConverter::to_primary(conn)?;
+    let daily_spending = converter.sum_by(rows);
+
+    let spent_between = |from: NaiveDate, to: NaiveDate| -> Money {
+        let (from, to) = (from.format("%Y-%m-%d").to_string(), to.format("%Y-%m-%d").to_string());
+        daily_spending
+            .iter()
+            .filter(|day| day.key >= from && day.key < to)
+            .map(|day| day.total)
+            .fold(Money::zero(decimals), |total, spent| total + spent)
+    };
+
+    // Close every past period in order, carrying the balance forward
+    let mut history = Vec::new();
+    let mut carried_amount = Money::zero(decimals);
+    for n in 0..current {
+        let from = period_start(&budget, anchor, n)?;
+        let to = period_start(&budget, anchor, n + 1)?;
+        let available = budget.amount + carried_amount;
+        let spent = spent_between(from, to);
+        let closing_balance = available - spent;
+        let carried_out = carry_over(&budget, closing_balance);
+
+        history.push(BudgetPeriod {
+            period_start: from.format("%Y-%m-%d").to_string(),
+            period_end: (to - Duration::days(1)).format("%Y-%m-%d").to_string(),
+            budgeted: budget.amount,
+            carried_in: carried_amount,
+            available,
+            spent,
+            closing_balance,
+            carried_out,
+        });
+        carried_amount = carried_out;
+    }
 
-    let remaining_amount = budget.amount - spent_amount;
-    let percentage_used = if budget.amount > 0.0 {
-        (spent_amount / budget.amount) * 100.0
+    let available_amount = budget.amount + carried_amount;
+    let spent_amount = spent_between(start_date, end_date);
+    let remaining_amount = available_amount - spent_amount;
+    // With nothing left to spend, the envelope is already fully used
+    let percentage_used = if available_amount.is_positive() {
+        spent_amount.ratio(available_amount) * 100.0
     } else {
-        0.0
+        100.0 + spent_amount.ratio(budget.amount) * 100.0
     };
 
     // Calculate daily averages
     let days_elapsed = (today - start_date).num_days().max(1);
-    let daily_average_spent = spent_amount / days_elapsed as f64;
+    let daily_average_spent = spent_amount.div_round(days_elapsed);
     let daily_budget_remaining = if days_remaining > 0 {
-        remaining_amount / days_remaining as f64
+        remaining_amount.div_round(days_remaining)
     } else {
-        0.0
+        Money::zero(remaining_amount.decimals())
     };
 
     Ok(BudgetStatus {
         budget: budget.clone(),
         category_name,
+        period_start: start_date.format("%Y-%m-%d").to_string(),
+        period_end: (end_date - Duration::days(1)).format("%Y-%m-%d").to_string(),
+        carried_amount,
+        available_amount,
         spent_amount,
         remaining_amount,
         percentage_used,
         days_remaining,
         daily_average_spent,
         daily_budget_remaining,
-        is_over_budget: spent_amount > budget.amount,
+        is_over_budget: spent_amount > available_amount,
+        history,
+        missing_rates: converter.missing_rates(),
     })
 }
 
@@ -297,9 +510,12 @@ pub fn get_all_budget_statuses(
 #[tauri::command]
 pub fn get_budget_alerts(state: State<'_, AppState>) -> Result<Vec<BudgetAlert>, String> {
     let statuses = get_all_budget_statuses(state)?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
 
     let alerts: Vec<BudgetAlert> = statuses
         .into_iter()
+        // Only windows that are running now (CUSTOM budgets can be over or not yet started)
+        .filter(|status| status.period_start <= today && today <= status.period_end)
         .filter_map(|status| {
             let alert_level = if status.percentage_used >= 120.0 {
                 Some("CRITICAL")
@@ -314,7 +530,7 @@ pub fn get_budget_alerts(state: State<'_, AppState>) -> Result<Vec<BudgetAlert>,
             alert_level.map(|level| BudgetAlert {
                 budget_id: status.budget.id,
                 category_name: status.category_name,
-                budget_amount: status.budget.amount,
+                budget_amount: status.available_amount,
                 spent_amount: status.spent_amount,
                 percentage_used: status.percentage_used,
                 alert_level: level.to_string(),
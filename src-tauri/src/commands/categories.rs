@@ -1,4 +1,5 @@
 // File: src-tauri/src/commands/categories.rs
+use crate::commands::audit::{log_change, snapshot};
 use crate::models::category::{Category, CategoryWithChildren, CreateCategoryInput};
 use crate::AppState;
 use rusqlite::params;
@@ -83,7 +84,13 @@ pub fn create_category(
 ) -> Result<Category, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    create_category_internal(&conn, input)
+}
 
+pub fn create_category_internal(
+    conn: &rusqlite::Connection,
+    input: CreateCategoryInput,
+) -> Result<Category, String> {
     // Validate type
     if input.category_type != "INCOME" && input.category_type != "EXPENSE" {
         return Err("Category type must be INCOME or EXPENSE".to_string());
@@ -112,6 +119,7 @@ pub fn create_category(
     .map_err(|e| format!("Failed to create category: {}", e))?;
 
     let cat_id = conn.last_insert_rowid();
+    log_change(conn, "CATEGORY", cat_id, "CREATE", None)?;
 
     let cat = conn
         .query_row(
@@ -140,7 +148,15 @@ pub fn update_category(
 ) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    update_category_internal(&conn, id, name, parent_id)
+}
 
+pub fn update_category_internal(
+    conn: &rusqlite::Connection,
+    id: i64,
+    name: Option<String>,
+    parent_id: Option<i64>,
+) -> Result<(), String> {
     let mut set_clauses: Vec<String> = Vec::new();
 
     if let Some(ref n) = name {
@@ -164,6 +180,8 @@ pub fn update_category(
         id
     );
 
+    let before = snapshot(conn, "CATEGORY", id)?;
+
     let rows = conn
         .execute(&query, [])
         .map_err(|e| format!("Failed to update category: {}", e))?;
@@ -172,14 +190,17 @@ pub fn update_category(
         return Err("Category not found".to_string());
     }
 
-    Ok(())
+    log_change(conn, "CATEGORY", id, "UPDATE", before)
 }
 
 #[tauri::command]
 pub fn delete_category(state: State<'_, AppState>, category_id: i64) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    delete_category_internal(&conn, category_id)
+}
 
+pub fn delete_category_internal(conn: &rusqlite::Connection, category_id: i64) -> Result<(), String> {
     // Check for child categories
     let child_count: i64 = conn
         .query_row(
@@ -225,6 +246,8 @@ pub fn delete_category(state: State<'_, AppState>, category_id: i64) -> Result<(
         return Err("Cannot delete category with active budgets.".to_string());
     }
 
+    let before = snapshot(conn, "CATEGORY", category_id)?;
+
     let rows = conn
         .execute("DELETE FROM categories WHERE id = ?1", params![category_id])
         .map_err(|e| format!("Failed to delete category: {}", e))?;
@@ -233,7 +256,7 @@ pub fn delete_category(state: State<'_, AppState>, category_id: i64) -> Result<(
         return Err("Category not found".to_string());
     }
 
-    Ok(())
+    log_change(conn, "CATEGORY", category_id, "DELETE", before)
 }
 
 #[tauri::command]
@@ -1,11 +1,17 @@
 // File: src-tauri/src/commands/credit_cards.rs
+use crate::commands::currencies::{account_decimals, currency_decimals};
+use crate::commands::transactions::insert_transaction;
 use crate::models::credit_card::{
     CreateCreditCardSettingsInput, CreditCardSettings, CreditCardStatement, CreditCardSummary,
-    CreditCardWithDetails, SettlementInput, StatementTransaction, StatementWithTransactions,
+    CreateRewardRuleInput, CreditCardRewardSummary, CreditCardWithDetails,
+    MinimumPaymentProjection, ProjectionMonth, RedeemRewardsInput, RewardLine, RewardRule,
+    SettlementInput, StatementRewards, StatementTransaction, StatementWithTransactions,
     UpdateCreditCardSettingsInput,
 };
+use crate::models::money::Money;
+use crate::models::transactions::CreateTransactionInput;
 use crate::AppState;
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Local, Months, NaiveDate};
 use rusqlite::{params, OptionalExtension};
 use tauri::State;
 
@@ -56,7 +62,8 @@ pub fn create_credit_card_settings(
         return Err("Payment due day must be between 1 and 28".to_string());
     }
 
-    if input.credit_limit < 0.0 {
+    let credit_limit = Money::from_major(input.credit_limit, account_decimals(&conn, input.account_id)?);
+    if credit_limit.is_negative() {
         return Err("Credit limit cannot be negative".to_string());
     }
 
@@ -87,21 +94,40 @@ pub fn create_credit_card_settings(
     let min_payment_pct = input.minimum_payment_percentage.unwrap_or(5.0);
     let auto_settlement = input.auto_settlement_enabled.unwrap_or(false);
 
+    let decimals = credit_limit.decimals();
+    let apr = input.apr.unwrap_or(0.0);
+    let late_fee = Money::from_major(input.late_fee.unwrap_or(0.0), decimals);
+    let annual_fee = Money::from_major(input.annual_fee.unwrap_or(0.0), decimals);
+    validate_fee_settings(&conn, apr, late_fee, annual_fee, input.annual_fee_month, input.fee_category_id)?;
+
+    let reward_type = input.reward_type.unwrap_or_else(|| "CASHBACK".to_string());
+    let point_value = input.point_value.unwrap_or(0.01);
+    validate_reward_settings(&reward_type, point_value)?;
+
     conn.execute(
         r#"
         INSERT INTO credit_card_settings (
             account_id, credit_limit, statement_day, payment_due_day,
-            minimum_payment_percentage, auto_settlement_enabled, settlement_account_id
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            minimum_payment_percentage, auto_settlement_enabled, settlement_account_id,
+            apr, late_fee, annual_fee, annual_fee_month, fee_category_id,
+            reward_type, point_value
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
         "#,
         params![
             input.account_id,
-            input.credit_limit,
+            credit_limit,
             input.statement_day,
             input.payment_due_day,
             min_payment_pct,
             auto_settlement as i32,
-            input.settlement_account_id
+            input.settlement_account_id,
+            apr,
+            late_fee,
+            annual_fee,
+            input.annual_fee_month,
+            input.fee_category_id,
+            reward_type,
+            point_value
         ],
     ).map_err(|e| format!("Failed to create credit card settings: {}", e))?;
 
@@ -117,23 +143,16 @@ pub fn update_credit_card_settings(
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    let exists: bool = conn.query_row(
-        "SELECT COUNT(id) FROM credit_card_settings WHERE id = ?1",
-        params![input.id],
-        |row| row.get::<_, i64>(0),
-    ).unwrap_or(0) > 0;
-
-    if !exists {
-        return Err("Credit card settings not found".to_string());
-    }
+    let current = get_credit_card_settings_by_id_internal(&conn, input.id)?;
 
     let mut set_clauses: Vec<String> = Vec::new();
 
     if let Some(limit) = input.credit_limit {
-        if limit < 0.0 {
+        let limit = Money::from_major(limit, current.credit_limit.decimals());
+        if limit.is_negative() {
             return Err("Credit limit cannot be negative".to_string());
         }
-        set_clauses.push(format!("credit_limit = {}", limit));
+        set_clauses.push(format!("credit_limit = {}", limit.minor()));
     }
 
     if let Some(day) = input.statement_day {
@@ -182,6 +201,44 @@ pub fn update_credit_card_settings(
         set_clauses.push(format!("settlement_account_id = {}", settlement_id));
     }
 
+    if input.apr.is_some()
+        || input.late_fee.is_some()
+        || input.annual_fee.is_some()
+        || input.annual_fee_month.is_some()
+        || input.fee_category_id.is_some()
+    {
+        let decimals = current.credit_limit.decimals();
+        let apr = input.apr.unwrap_or(current.apr);
+        let late_fee = input
+            .late_fee
+            .map(|f| Money::from_major(f, decimals))
+            .unwrap_or(current.late_fee);
+        let annual_fee = input
+            .annual_fee
+            .map(|f| Money::from_major(f, decimals))
+            .unwrap_or(current.annual_fee);
+        validate_fee_settings(&conn, apr, late_fee, annual_fee, input.annual_fee_month, input.fee_category_id)?;
+
+        set_clauses.push(format!("apr = {}", apr));
+        set_clauses.push(format!("late_fee = {}", late_fee.minor()));
+        set_clauses.push(format!("annual_fee = {}", annual_fee.minor()));
+        if let Some(month) = input.annual_fee_month {
+            set_clauses.push(format!("annual_fee_month = {}", month));
+        }
+        if let Some(category_id) = input.fee_category_id {
+            set_clauses.push(format!("fee_category_id = {}", category_id));
+        }
+    }
+
+    if input.reward_type.is_some() || input.point_value.is_some() {
+        let reward_type = input.reward_type.clone().unwrap_or(current.reward_type.clone());
+        let point_value = input.point_value.unwrap_or(current.point_value);
+        validate_reward_settings(&reward_type, point_value)?;
+
+        set_clauses.push(format!("reward_type = '{}'", reward_type));
+        set_clauses.push(format!("point_value = {}", point_value));
+    }
+
     if set_clauses.is_empty() {
         return Err("No fields to update".to_string());
     }
@@ -242,7 +299,10 @@ fn get_credit_card_settings_by_id_internal(
         r#"
         SELECT id, account_id, credit_limit, statement_day, payment_due_day,
                minimum_payment_percentage, auto_settlement_enabled,
-               settlement_account_id, created_at, updated_at
+               settlement_account_id, created_at, updated_at,
+               apr, late_fee, annual_fee, annual_fee_month, fee_category_id,
+               reward_type, point_value,
+               (SELECT currency FROM accounts WHERE id = credit_card_settings.account_id)
         FROM credit_card_settings
         WHERE id = ?1
         "#,
@@ -264,7 +324,10 @@ pub fn get_credit_card_settings_by_account(
         r#"
         SELECT id, account_id, credit_limit, statement_day, payment_due_day,
                minimum_payment_percentage, auto_settlement_enabled,
-               settlement_account_id, created_at, updated_at
+               settlement_account_id, created_at, updated_at,
+               apr, late_fee, annual_fee, annual_fee_month, fee_category_id,
+               reward_type, point_value,
+               (SELECT currency FROM accounts WHERE id = credit_card_settings.account_id)
         FROM credit_card_settings
         WHERE account_id = ?1
         "#,
@@ -286,7 +349,9 @@ pub fn get_all_credit_cards(
         SELECT ccs.id, ccs.account_id, ccs.credit_limit, ccs.statement_day,
                ccs.payment_due_day, ccs.minimum_payment_percentage,
                ccs.auto_settlement_enabled, ccs.settlement_account_id,
-               ccs.created_at, ccs.updated_at,
+               ccs.created_at, ccs.updated_at, ccs.apr, ccs.late_fee,
+               ccs.annual_fee, ccs.annual_fee_month, ccs.fee_category_id,
+               ccs.reward_type, ccs.point_value, a.currency,
                a.name as account_name,
                sa.name as settlement_account_name
         FROM credit_card_settings ccs
@@ -299,8 +364,8 @@ pub fn get_all_credit_cards(
     let cards_data: Vec<(CreditCardSettings, String, Option<String>)> = stmt.query_map([], |row| {
         Ok((
             row_to_settings(row),
-            row.get(10)?,
-            row.get(11)?,
+            row.get(18)?,
+            row.get(19)?,
         ))
     }).unwrap().filter_map(Result::ok).collect();
 
@@ -338,7 +403,9 @@ pub fn get_credit_card_details(
         SELECT ccs.id, ccs.account_id, ccs.credit_limit, ccs.statement_day,
                ccs.payment_due_day, ccs.minimum_payment_percentage,
                ccs.auto_settlement_enabled, ccs.settlement_account_id,
-               ccs.created_at, ccs.updated_at,
+               ccs.created_at, ccs.updated_at, ccs.apr, ccs.late_fee,
+               ccs.annual_fee, ccs.annual_fee_month, ccs.fee_category_id,
+               ccs.reward_type, ccs.point_value, a.currency,
                a.name as account_name,
                sa.name as settlement_account_name
         FROM credit_card_settings ccs
@@ -351,8 +418,8 @@ pub fn get_credit_card_details(
     let (settings, account_name, settlement_account_name) = stmt.query_row(params![settings_id], |row| {
         Ok((
             row_to_settings(row),
-            row.get::<_, String>(10)?,
-            row.get::<_, Option<String>>(11)?,
+            row.get::<_, String>(18)?,
+            row.get::<_, Option<String>>(19)?,
         ))
     }).map_err(|_| "Credit card not found".to_string())?;
 
@@ -416,8 +483,9 @@ pub fn get_current_cycle_transactions(
 
     let mut stmt = conn.prepare(
         r#"
-        SELECT t.id, t.date, t.type, t.amount, c.name as category_name, t.memo
+        SELECT t.id, t.date, t.type, t.amount, c.name as category_name, t.memo, a.currency
         FROM transactions t
+        INNER JOIN accounts a ON t.account_id = a.id
         LEFT JOIN categories c ON t.category_id = c.id
         WHERE (t.account_id = ?1 OR t.to_account_id = ?2)
           AND t.date >= ?3 AND t.date <= ?4
@@ -437,7 +505,7 @@ pub fn get_current_cycle_transactions(
                 id: row.get(0)?,
                 date: row.get(1)?,
                 transaction_type: row.get(2)?,
-                amount: row.get(3)?,
+                amount: Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(6)?)),
                 category_name: row.get(4)?,
                 memo: row.get(5)?,
             })
@@ -455,7 +523,7 @@ pub fn generate_statement(
     settings_id: i64,
 ) -> Result<CreditCardStatement, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let settings = get_credit_card_settings_by_id_internal(&conn, settings_id)?;
     let today = Local::now().date_naive();
@@ -479,40 +547,54 @@ pub fn generate_statement(
         return Err("Statement already exists for this billing cycle".to_string());
     }
 
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    // Post any interest and fees owed on earlier statements so they land in this cycle
+    assess_card_charges(&tx, &settings, today)?;
+
+    let decimals = settings.credit_limit.decimals();
+
     // Calculate charges
-    let total_charges: f64 = conn.query_row(
-        r#"
-        SELECT COALESCE(SUM(amount), 0.0) as total
-        FROM transactions
-        WHERE account_id = ?1 AND type = 'EXPENSE'
-          AND date >= ?2 AND date <= ?3
-        "#,
-        params![
-            settings.account_id,
-            cycle_start.format("%Y-%m-%d").to_string(),
-            cycle_end.format("%Y-%m-%d").to_string()
-        ],
-        |row| row.get(0),
-    ).unwrap_or(0.0);
+    let total_charges = Money::new(
+        tx.query_row(
+            r#"
+            SELECT COALESCE(SUM(amount), 0) as total
+            FROM transactions
+            WHERE account_id = ?1 AND type = 'EXPENSE'
+              AND date >= ?2 AND date <= ?3
+            "#,
+            params![
+                settings.account_id,
+                cycle_start.format("%Y-%m-%d").to_string(),
+                cycle_end.format("%Y-%m-%d").to_string()
+            ],
+            |row| row.get(0),
+        ).unwrap_or(0),
+        decimals,
+    );
 
-    // Calculate payments
-    let total_payments: f64 = conn.query_row(
-        r#"
-        SELECT COALESCE(SUM(amount), 0.0) as total
-        FROM transactions
-        WHERE to_account_id = ?1 AND type = 'TRANSFER'
-          AND date >= ?2 AND date <= ?3
-        "#,
-        params![
-            settings.account_id,
-            cycle_start.format("%Y-%m-%d").to_string(),
-            cycle_end.format("%Y-%m-%d").to_string()
-        ],
-        |row| row.get(0),
-    ).unwrap_or(0.0);
+    // Calculate payments (the card-side journal entries, already in the card's currency)
+    let total_payments = Money::new(
+        tx.query_row(
+            r#"
+            SELECT COALESCE(SUM(je.debit), 0) as total
+            FROM journal_entries je
+            JOIN transactions t ON je.transaction_id = t.id
+            WHERE t.to_account_id = ?1 AND je.account_id = ?1 AND t.type = 'TRANSFER'
+              AND t.date >= ?2 AND t.date <= ?3
+            "#,
+            params![
+                settings.account_id,
+                cycle_start.format("%Y-%m-%d").to_string(),
+                cycle_end.format("%Y-%m-%d").to_string()
+            ],
+            |row| row.get(0),
+        ).unwrap_or(0),
+        decimals,
+    );
 
     // Get opening balance
-    let prev_statement_balance: Option<f64> = conn.query_row(
+    let prev_statement_balance: Option<i64> = tx.query_row(
         r#"
         SELECT closing_balance FROM credit_card_statements
         WHERE credit_card_id = ?1
@@ -524,17 +606,16 @@ pub fn generate_statement(
     ).optional().map_err(|e| format!("Database error: {}", e))?.flatten();
 
     let opening_balance = match prev_statement_balance {
-        Some(b) => b,
-        None => calculate_balance_before_date(&conn, settings.account_id, &cycle_start)?,
+        Some(b) => Money::new(b, decimals),
+        None => calculate_balance_before_date(&tx, settings.account_id, &cycle_start)?,
     };
 
     let closing_balance = opening_balance + total_charges - total_payments;
-    let minimum_payment =
-        (closing_balance * settings.minimum_payment_percentage / 100.0 * 100.0).round() / 100.0;
+    let minimum_payment = closing_balance.percent(settings.minimum_payment_percentage);
 
     let due_date = compute_due_date(cycle_end, settings.payment_due_day);
 
-    conn.execute(
+    tx.execute(
         r#"
         INSERT INTO credit_card_statements (
             credit_card_id, statement_date, due_date, cycle_start_date, cycle_end_date,
@@ -551,12 +632,27 @@ pub fn generate_statement(
             total_charges,
             total_payments,
             closing_balance,
-            minimum_payment.max(0.0)
+            minimum_payment.max(Money::zero(decimals))
         ],
     ).map_err(|e| format!("Failed to create statement: {}", e))?;
 
-    let statement_id = conn.last_insert_rowid();
-    get_statement_by_id(&conn, statement_id)
+    let statement_id = tx.last_insert_rowid();
+
+    let (_, reward_units) = cycle_rewards(&tx, &settings, cycle_start, cycle_end)?;
+    if reward_units > 0 {
+        tx.execute(
+            r#"
+            INSERT INTO credit_card_rewards (credit_card_id, kind, units, statement_id)
+            VALUES (?1, 'ACCRUAL', ?2, ?3)
+            "#,
+            params![settings_id, reward_units, statement_id],
+        ).map_err(|e| format!("Failed to record rewards: {}", e))?;
+    }
+
+    let statement = get_statement_by_id(&tx, statement_id)?;
+    tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
+
+    Ok(statement)
 }
 
 #[tauri::command]
@@ -571,7 +667,11 @@ pub fn get_statements(
         r#"
         SELECT id, credit_card_id, statement_date, due_date, cycle_start_date,
                cycle_end_date, opening_balance, total_charges, total_payments,
-               closing_balance, minimum_payment, status, paid_amount, paid_date, created_at
+               closing_balance, minimum_payment, status, paid_amount, paid_date, created_at,
+               finance_charge, late_fee, assessed_at,
+               (SELECT a.currency FROM credit_card_settings ccs
+                JOIN accounts a ON ccs.account_id = a.id
+                WHERE ccs.id = credit_card_statements.credit_card_id)
         FROM credit_card_statements
         WHERE credit_card_id = ?1
         ORDER BY cycle_end_date DESC
@@ -595,18 +695,14 @@ pub fn get_statement_with_transactions(
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let statement = get_statement_by_id(&conn, statement_id)?;
-
-    // Get the account_id for this credit card
-    let account_id: i64 = conn.query_row(
-        "SELECT account_id FROM credit_card_settings WHERE id = ?1",
-        params![statement.credit_card_id],
-        |row| row.get(0),
-    ).map_err(|e| format!("Database error: {}", e))?;
+    let settings = get_credit_card_settings_by_id_internal(&conn, statement.credit_card_id)?;
+    let account_id = settings.account_id;
 
     let mut stmt = conn.prepare(
         r#"
-        SELECT t.id, t.date, t.type, t.amount, c.name as category_name, t.memo
+        SELECT t.id, t.date, t.type, t.amount, c.name as category_name, t.memo, a.currency
         FROM transactions t
+        INNER JOIN accounts a ON t.account_id = a.id
         LEFT JOIN categories c ON t.category_id = c.id
         WHERE (t.account_id = ?1 OR t.to_account_id = ?2)
           AND t.date >= ?3 AND t.date <= ?4
@@ -626,16 +722,24 @@ pub fn get_statement_with_transactions(
                 id: row.get(0)?,
                 date: row.get(1)?,
                 transaction_type: row.get(2)?,
-                amount: row.get(3)?,
+                amount: Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(6)?)),
                 category_name: row.get(4)?,
                 memo: row.get(5)?,
             })
         }
     ).unwrap().filter_map(Result::ok).collect();
 
+    let (rewards, _) = cycle_rewards(
+        &conn,
+        &settings,
+        parse_statement_date(&statement.cycle_start_date)?,
+        parse_statement_date(&statement.cycle_end_date)?,
+    )?;
+
     Ok(StatementWithTransactions {
         statement,
         transactions,
+        rewards,
     })
 }
 
@@ -677,19 +781,24 @@ pub fn settle_credit_card(
 
     let payment_amount = match input.amount {
         Some(amt) => {
-            if amt <= 0.0 {
+            let amt = Money::from_major(amt, settings.credit_limit.decimals());
+            if !amt.is_positive() {
                 return Err("Payment amount must be greater than 0".to_string());
             }
             amt
         }
         None => {
-            if balances.total_balance <= 0.0 {
+            if !balances.total_balance.is_positive() {
                 return Err("No outstanding balance to pay".to_string());
             }
             balances.total_balance
         }
     };
 
+    // Sent in the paying account's decimals. The amount is in the card's
+    // currency, so a paying account in another currency is refused.
+    let source_amount = payment_amount.rescale(account_decimals(&conn, input.payment_account_id)?);
+
     let date = input
         .date
         .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
@@ -698,37 +807,31 @@ pub fn settle_credit_card(
 
     let memo = format!("Credit card payment - {}", settings.account_id);
 
-    tx.execute(
-        r#"
-        INSERT INTO transactions (date, type, amount, account_id, to_account_id, memo)
-        VALUES (?1, 'TRANSFER', ?2, ?3, ?4, ?5)
-        "#,
-        params![
-            date,
-            payment_amount,
-            input.payment_account_id,
-            settings.account_id,
-            memo
-        ],
-    ).map_err(|e| format!("Failed to create payment transaction: {}", e))?;
-
-    let transaction_id = tx.last_insert_rowid();
-
-    tx.execute(
-        "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) VALUES (?1, ?2, 0, ?3)",
-        params![transaction_id, input.payment_account_id, payment_amount],
-    ).map_err(|e| format!("Failed to create journal entry: {}", e))?;
-
-    tx.execute(
-        "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) VALUES (?1, ?2, ?3, 0)",
-        params![transaction_id, settings.account_id, payment_amount],
-    ).map_err(|e| format!("Failed to create journal entry: {}", e))?;
+    let transaction_id = insert_transaction(
+        &tx,
+        CreateTransactionInput {
+            date: date.clone(),
+            transaction_type: "TRANSFER".to_string(),
+            amount: source_amount.to_major(),
+            account_id: input.payment_account_id,
+            to_account_id: Some(settings.account_id),
+            to_amount: None,
+            category_id: None,
+            payee_id: None,
+            payee: None,
+            memo: Some(memo),
+            tag_ids: None,
+            goal_allocations: None,
+            goal_withdrawals: None,
+            splits: None,
+        },
+    )?;
 
     tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
 
     // Update statements after committing transaction
     update_statement_payment_status(
-        &pool.lock().unwrap(),
+        &conn,
         input.credit_card_settings_id,
         payment_amount,
         &date,
@@ -737,6 +840,305 @@ pub fn settle_credit_card(
     Ok(transaction_id)
 }
 
+// ======================== INTEREST & FEES ========================
+
+/// Longest minimum-payment projection before it is reported as never paying off
+const MAX_PROJECTION_MONTHS: i32 = 600;
+
+/// Assesses overdue statements and annual fees on every card.
+/// Returns the ids of the charge transactions that were posted.
+#[tauri::command]
+pub fn process_credit_card_charges(state: State<'_, AppState>) -> Result<Vec<i64>, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let today = Local::now().date_naive();
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    let settings_ids: Vec<i64> = {
+        let mut stmt = tx
+            .prepare("SELECT id FROM credit_card_settings")
+            .map_err(|e| format!("Database error: {}", e))?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))
+            .map_err(|e| format!("Database error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Database error: {}", e))?;
+        ids
+    };
+
+    let mut created = Vec::new();
+    for settings_id in settings_ids {
+        let settings = get_credit_card_settings_by_id_internal(&tx, settings_id)?;
+        created.extend(assess_card_charges(&tx, &settings, today)?);
+    }
+
+    tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
+    Ok(created)
+}
+
+/// "What if I pay only the minimum": projects the card balance (or `balance`
+/// if given) month by month at the card's APR and minimum-payment percentage.
+#[tauri::command]
+pub fn project_minimum_payments(
+    state: State<'_, AppState>,
+    settings_id: i64,
+    balance: Option<f64>,
+    minimum_floor: Option<f64>,
+) -> Result<MinimumPaymentProjection, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let settings = get_credit_card_settings_by_id_internal(&conn, settings_id)?;
+    let decimals = settings.credit_limit.decimals();
+
+    let balance = match balance {
+        Some(b) => Money::from_major(b, decimals),
+        None => calculate_card_balances(&conn, &settings)?.total_balance,
+    };
+    let floor = Money::from_major(minimum_floor.unwrap_or(0.0), decimals);
+    if floor.is_negative() {
+        return Err("Minimum payment floor cannot be negative".to_string());
+    }
+
+    Ok(project_minimum(balance, settings.apr, settings.minimum_payment_percentage, floor))
+}
+
+// ======================== REWARDS ========================
+
+#[tauri::command]
+pub fn get_reward_rules(
+    state: State<'_, AppState>,
+    settings_id: i64,
+) -> Result<Vec<RewardRule>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let settings = get_credit_card_settings_by_id_internal(&conn, settings_id)?;
+    fetch_reward_rules(&conn, &settings)
+}
+
+#[tauri::command]
+pub fn create_reward_rule(
+    state: State<'_, AppState>,
+    input: CreateRewardRuleInput,
+) -> Result<RewardRule, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let settings = get_credit_card_settings_by_id_internal(&conn, input.credit_card_id)?;
+    let decimals = settings.credit_limit.decimals();
+
+    if !input.rate.is_finite() || input.rate < 0.0 {
+        return Err("Reward rate cannot be negative".to_string());
+    }
+    let tier_threshold = Money::from_major(input.tier_threshold.unwrap_or(0.0), decimals);
+    if tier_threshold.is_negative() {
+        return Err("Tier threshold cannot be negative".to_string());
+    }
+    let cycle_cap = match input.cycle_cap {
+        Some(cap) if cap < 0.0 => return Err("Reward cap cannot be negative".to_string()),
+        Some(cap) => Some(reward_amount_to_units(&settings, cap)),
+        None => None,
+    };
+
+    if let Some(category_id) = input.category_id {
+        let exists: bool = conn.query_row(
+            "SELECT COUNT(id) FROM categories WHERE id = ?1",
+            params![category_id],
+            |row| row.get::<_, i64>(0),
+        ).unwrap_or(0) > 0;
+
+        if !exists {
+            return Err("Category not found".to_string());
+        }
+    }
+
+    // UNIQUE treats NULL categories as distinct, so check the base rate here
+    let duplicate: bool = conn.query_row(
+        r#"
+        SELECT COUNT(id) FROM credit_card_reward_rules
+        WHERE credit_card_id = ?1 AND category_id IS ?2 AND tier_threshold = ?3
+        "#,
+        params![settings.id, input.category_id, tier_threshold],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+
+    if duplicate {
+        return Err("A reward rule for this category and tier already exists".to_string());
+    }
+
+    conn.execute(
+        r#"
+        INSERT INTO credit_card_reward_rules (credit_card_id, category_id, rate, tier_threshold, cycle_cap)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        "#,
+        params![settings.id, input.category_id, input.rate, tier_threshold, cycle_cap],
+    ).map_err(|e| format!("Failed to create reward rule: {}", e))?;
+
+    let rule_id = conn.last_insert_rowid();
+    fetch_reward_rules(&conn, &settings)?
+        .into_iter()
+        .find(|r| r.id == rule_id)
+        .ok_or_else(|| "Reward rule not found".to_string())
+}
+
+#[tauri::command]
+pub fn delete_reward_rule(state: State<'_, AppState>, rule_id: i64) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let rows_affected = conn.execute("DELETE FROM credit_card_reward_rules WHERE id = ?1", params![rule_id])
+        .map_err(|e| format!("Failed to delete reward rule: {}", e))?;
+
+    if rows_affected == 0 {
+        return Err("Reward rule not found".to_string());
+    }
+
+    Ok(())
+}
+
+/// Converts rewards to money as an INCOME transaction, either as a statement
+/// credit on the card or into another account. Returns the transaction id.
+#[tauri::command]
+pub fn redeem_credit_card_rewards(
+    state: State<'_, AppState>,
+    input: RedeemRewardsInput,
+) -> Result<i64, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let settings = get_credit_card_settings_by_id_internal(&conn, input.credit_card_settings_id)?;
+
+    let units = reward_amount_to_units(&settings, input.amount);
+    if units <= 0 {
+        return Err("Redemption amount must be greater than 0".to_string());
+    }
+
+    let (earned, redeemed) = reward_totals(&conn, settings.id)?;
+    if units > earned - redeemed {
+        return Err(format!(
+            "Only {} in rewards is available to redeem",
+            reward_units_to_amount(&settings, earned - redeemed)
+        ));
+    }
+
+    let account_id = input.account_id.unwrap_or(settings.account_id);
+    let value = reward_units_value(&settings, units).rescale(account_decimals(&conn, account_id)?);
+    if !value.is_positive() {
+        return Err("Redemption is worth less than the smallest currency unit".to_string());
+    }
+
+    let date = input
+        .date
+        .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    let transaction_id = insert_transaction(
+        &tx,
+        CreateTransactionInput {
+            date,
+            transaction_type: "INCOME".to_string(),
+            amount: value.to_major(),
+            account_id,
+            to_account_id: None,
+            to_amount: None,
+            category_id: input.category_id,
+            payee_id: None,
+            payee: None,
+            memo: Some("Credit card rewards redemption".to_string()),
+            tag_ids: None,
+            goal_allocations: None,
+            goal_withdrawals: None,
+            splits: None,
+        },
+    )?;
+
+    tx.execute(
+        r#"
+        INSERT INTO credit_card_rewards (credit_card_id, kind, units, transaction_id)
+        VALUES (?1, 'REDEMPTION', ?2, ?3)
+        "#,
+        params![settings.id, units, transaction_id],
+    ).map_err(|e| format!("Failed to record redemption: {}", e))?;
+
+    tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
+
+    Ok(transaction_id)
+}
+
+#[tauri::command]
+pub fn get_credit_card_reward_summaries(
+    state: State<'_, AppState>,
+) -> Result<Vec<CreditCardRewardSummary>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT ccs.id, ccs.account_id, ccs.credit_limit, ccs.statement_day,
+               ccs.payment_due_day, ccs.minimum_payment_percentage,
+               ccs.auto_settlement_enabled, ccs.settlement_account_id,
+               ccs.created_at, ccs.updated_at, ccs.apr, ccs.late_fee,
+               ccs.annual_fee, ccs.annual_fee_month, ccs.fee_category_id,
+               ccs.reward_type, ccs.point_value, a.currency,
+               a.name as account_name
+        FROM credit_card_settings ccs
+        JOIN accounts a ON ccs.account_id = a.id
+        ORDER BY a.name
+        "#,
+    ).map_err(|e| format!("Database error: {}", e))?;
+
+    let cards_data: Vec<(CreditCardSettings, String)> = stmt.query_map([], |row| {
+        Ok((
+            row_to_settings(row),
+            row.get(18)?,
+        ))
+    }).unwrap().filter_map(Result::ok).collect();
+
+    let today = Local::now().date_naive();
+    let mut summaries = Vec::new();
+
+    for (settings, account_name) in cards_data {
+        let (earned, redeemed) = reward_totals(&conn, settings.id)?;
+
+        // The current cycle only counts until its statement records the accrual
+        let (cycle_start, cycle_end) = compute_current_cycle_dates(today, settings.statement_day);
+        let cycle_closed: bool = conn.query_row(
+            r#"
+            SELECT COUNT(id) FROM credit_card_statements
+            WHERE credit_card_id = ?1 AND cycle_start_date = ?2 AND cycle_end_date = ?3
+            "#,
+            params![
+                settings.id,
+                cycle_start.format("%Y-%m-%d").to_string(),
+                cycle_end.format("%Y-%m-%d").to_string()
+            ],
+            |row| row.get::<_, i64>(0),
+        ).unwrap_or(0) > 0;
+        let pending = if cycle_closed {
+            0
+        } else {
+            cycle_rewards(&conn, &settings, cycle_start, cycle_end)?.1
+        };
+
+        summaries.push(CreditCardRewardSummary {
+            credit_card_id: settings.id,
+            account_id: settings.account_id,
+            account_name,
+            reward_type: settings.reward_type.clone(),
+            earned: reward_units_to_amount(&settings, earned),
+            redeemed: reward_units_to_amount(&settings, redeemed),
+            balance: reward_units_to_amount(&settings, earned - redeemed),
+            balance_value: reward_units_value(&settings, earned - redeemed),
+            current_cycle_accrual: reward_units_to_amount(&settings, pending),
+        });
+    }
+
+    Ok(summaries)
+}
+
 // ======================== DASHBOARD SUMMARY ========================
 
 #[tauri::command]
@@ -751,7 +1153,9 @@ pub fn get_credit_card_summaries(
         SELECT ccs.id, ccs.account_id, ccs.credit_limit, ccs.statement_day,
                ccs.payment_due_day, ccs.minimum_payment_percentage,
                ccs.auto_settlement_enabled, ccs.settlement_account_id,
-               ccs.created_at, ccs.updated_at,
+               ccs.created_at, ccs.updated_at, ccs.apr, ccs.late_fee,
+               ccs.annual_fee, ccs.annual_fee_month, ccs.fee_category_id,
+               ccs.reward_type, ccs.point_value, a.currency,
                a.name as account_name
         FROM credit_card_settings ccs
         JOIN accounts a ON ccs.account_id = a.id
@@ -762,7 +1166,7 @@ pub fn get_credit_card_summaries(
     let cards_data: Vec<(CreditCardSettings, String)> = stmt.query_map([], |row| {
         Ok((
             row_to_settings(row),
-            row.get(10)?,
+            row.get(18)?,
         ))
     }).unwrap().filter_map(Result::ok).collect();
 
@@ -771,11 +1175,11 @@ pub fn get_credit_card_summaries(
     for (settings, account_name) in cards_data {
         let balances = calculate_card_balances(&conn, &settings)?;
 
-        let next_due_row: Option<(String, f64)> = conn.query_row(
+        let next_due_row: Option<(String, i64)> = conn.query_row(
             r#"
             SELECT due_date, closing_balance - paid_amount as remaining
             FROM credit_card_statements
-            WHERE credit_card_id = ?1 AND status IN ('OPEN', 'CLOSED', 'PARTIAL')
+            WHERE credit_card_id = ?1 AND status IN ('OPEN', 'CLOSED', 'PARTIAL', 'OVERDUE')
             ORDER BY due_date ASC
             LIMIT 1
             "#,
@@ -784,7 +1188,7 @@ pub fn get_credit_card_summaries(
         ).optional().unwrap_or(None);
 
         let (next_due_date, next_due_amount) = match next_due_row {
-            Some((d, a)) => (Some(d), Some(a)),
+            Some((d, a)) => (Some(d), Some(Money::new(a, settings.credit_limit.decimals()))),
             None => (None, None),
         };
 
@@ -809,7 +1213,7 @@ pub fn get_credit_card_summaries(
 pub fn process_auto_settlements(state: State<'_, AppState>) -> Result<Vec<i64>, String> {
     let pool = crate::get_db(&state)?;
     // We only need a short lock for queries since we use state.clone() in settle_credit_card
-    let cards_to_settle: Vec<(i64, i64, i64)> = {
+    let cards_to_settle: Vec<(i64, i64, Money)> = {
         let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
         let today = Local::now().date_naive();
         let mut stmt = conn.prepare(
@@ -851,7 +1255,7 @@ pub fn process_auto_settlements(state: State<'_, AppState>) -> Result<Vec<i64>,
                     continue;
                 }
 
-                let remaining: Option<f64> = conn.query_row(
+                let remaining: Option<i64> = conn.query_row(
                     r#"
                     SELECT closing_balance - paid_amount as remaining
                     FROM credit_card_statements
@@ -864,8 +1268,14 @@ pub fn process_auto_settlements(state: State<'_, AppState>) -> Result<Vec<i64>,
                 ).optional().unwrap_or(None).flatten();
 
                 if let Some(amt) = remaining {
-                    if amt > 0.0 {
-                        to_process.push((settings_id, settlement_account_id, (amt * 100.0) as i64));
+                    if amt > 0 {
+                        let decimals = conn.query_row(
+                            "SELECT a.currency FROM credit_card_settings ccs
+                             JOIN accounts a ON ccs.account_id = a.id WHERE ccs.id = ?1",
+                            params![settings_id],
+                            |row| row.get::<_, String>(0),
+                        ).map(|currency| currency_decimals(&currency)).unwrap_or(2);
+                        to_process.push((settings_id, settlement_account_id, Money::new(amt, decimals)));
                     }
                 }
             }
@@ -876,11 +1286,11 @@ pub fn process_auto_settlements(state: State<'_, AppState>) -> Result<Vec<i64>,
     let mut settled_ids = Vec::new();
     let today = Local::now().date_naive();
 
-    for (settings_id, settlement_account_id, amt_cents) in cards_to_settle {
+    for (settings_id, settlement_account_id, amount) in cards_to_settle {
         let input = SettlementInput {
             credit_card_settings_id: settings_id,
             payment_account_id: settlement_account_id,
-            amount: Some((amt_cents as f64) / 100.0),
+            amount: Some(amount.to_major()),
             date: Some(today.format("%Y-%m-%d").to_string()),
         };
 
@@ -897,12 +1307,15 @@ pub fn process_auto_settlements(state: State<'_, AppState>) -> Result<Vec<i64>,
 
 // ======================== HELPER FUNCTIONS ========================
 
+/// Expects the card account's currency in column 17.
 fn row_to_settings(row: &rusqlite::Row) -> CreditCardSettings {
     let auto_settlement_int: i32 = row.get(6).unwrap_or(0);
+    let currency: String = row.get(17).unwrap_or_default();
+    let decimals = currency_decimals(&currency);
     CreditCardSettings {
         id: row.get(0).unwrap_or_default(),
         account_id: row.get(1).unwrap_or_default(),
-        credit_limit: row.get(2).unwrap_or_default(),
+        credit_limit: Money::new(row.get(2).unwrap_or_default(), decimals),
         statement_day: row.get(3).unwrap_or_default(),
         payment_due_day: row.get(4).unwrap_or_default(),
         minimum_payment_percentage: row.get(5).unwrap_or_default(),
@@ -910,10 +1323,21 @@ fn row_to_settings(row: &rusqlite::Row) -> CreditCardSettings {
         settlement_account_id: row.get(7).unwrap_or_default(),
         created_at: row.get(8).unwrap_or_default(),
         updated_at: row.get(9).unwrap_or_default(),
+        apr: row.get(10).unwrap_or_default(),
+        late_fee: Money::new(row.get(11).unwrap_or_default(), decimals),
+        annual_fee: Money::new(row.get(12).unwrap_or_default(), decimals),
+        annual_fee_month: row.get(13).unwrap_or_default(),
+        fee_category_id: row.get(14).unwrap_or_default(),
+        reward_type: row.get(15).unwrap_or_default(),
+        point_value: row.get(16).unwrap_or_default(),
     }
 }
 
+/// Expects the card account's currency in column 18.
 fn row_to_statement(row: &rusqlite::Row) -> CreditCardStatement {
+    let currency: String = row.get(18).unwrap_or_default();
+    let decimals = currency_decimals(&currency);
+    let money = |idx: usize| Money::new(row.get(idx).unwrap_or_default(), decimals);
     CreditCardStatement {
         id: row.get(0).unwrap_or_default(),
         credit_card_id: row.get(1).unwrap_or_default(),
@@ -921,15 +1345,18 @@ fn row_to_statement(row: &rusqlite::Row) -> CreditCardStatement {
         due_date: row.get(3).unwrap_or_default(),
         cycle_start_date: row.get(4).unwrap_or_default(),
         cycle_end_date: row.get(5).unwrap_or_default(),
-        opening_balance: row.get(6).unwrap_or_default(),
-        total_charges: row.get(7).unwrap_or_default(),
-        total_payments: row.get(8).unwrap_or_default(),
-        closing_balance: row.get(9).unwrap_or_default(),
-        minimum_payment: row.get(10).unwrap_or_default(),
+        opening_balance: money(6),
+        total_charges: money(7),
+        total_payments: money(8),
+        closing_balance: money(9),
+        minimum_payment: money(10),
         status: row.get(11).unwrap_or_default(),
-        paid_amount: row.get(12).unwrap_or_default(),
+        paid_amount: money(12),
         paid_date: row.get(13).unwrap_or_default(),
         created_at: row.get(14).unwrap_or_default(),
+        finance_charge: money(15),
+        late_fee: money(16),
+        assessed_at: row.get(17).unwrap_or_default(),
     }
 }
 
@@ -941,7 +1368,11 @@ fn get_statement_by_id(
         r#"
         SELECT id, credit_card_id, statement_date, due_date, cycle_start_date,
                cycle_end_date, opening_balance, total_charges, total_payments,
-               closing_balance, minimum_payment, status, paid_amount, paid_date, created_at
+               closing_balance, minimum_payment, status, paid_amount, paid_date, created_at,
+               finance_charge, late_fee, assessed_at,
+               (SELECT a.currency FROM credit_card_settings ccs
+                JOIN accounts a ON ccs.account_id = a.id
+                WHERE ccs.id = credit_card_statements.credit_card_id)
         FROM credit_card_statements
         WHERE id = ?1
         "#,
@@ -952,11 +1383,11 @@ fn get_statement_by_id(
 }
 
 struct CardBalances {
-    total_balance: f64,
-    outstanding_balance: f64,
-    available_credit: f64,
-    current_cycle_charges: f64,
-    current_cycle_payments: f64,
+    total_balance: Money,
+    outstanding_balance: Money,
+    available_credit: Money,
+    current_cycle_charges: Money,
+    current_cycle_payments: Money,
     utilization_percentage: f64,
 }
 
@@ -964,64 +1395,70 @@ fn calculate_card_balances(
     conn: &rusqlite::Connection,
     settings: &CreditCardSettings,
 ) -> Result<CardBalances, String> {
-    let initial_balance: f64 = conn.query_row(
+    let decimals = settings.credit_limit.decimals();
+    let zero = Money::zero(decimals);
+
+    let initial_balance: i64 = conn.query_row(
         "SELECT initial_balance FROM accounts WHERE id = ?1",
         params![settings.account_id],
         |row| row.get(0),
     ).map_err(|e| format!("Database error: {}", e))?;
 
-    let journal_balance: f64 = conn.query_row(
+    let journal_balance: i64 = conn.query_row(
         r#"
-        SELECT CAST(COALESCE(SUM(debit), 0) - COALESCE(SUM(credit), 0) AS REAL) as balance
+        SELECT COALESCE(SUM(debit), 0) - COALESCE(SUM(credit), 0) as balance
         FROM journal_entries WHERE account_id = ?1
         "#,
         params![settings.account_id],
         |row| row.get(0),
-    ).unwrap_or(0.0);
+    ).unwrap_or(0);
 
-    let raw_balance = initial_balance + journal_balance;
-    let total_balance = -raw_balance;
+    let total_balance = -Money::new(initial_balance + journal_balance, decimals);
 
     let today = Local::now().date_naive();
     let (cycle_start, _cycle_end) = compute_current_cycle_dates(today, settings.statement_day);
 
-    let current_cycle_charges: f64 = conn.query_row(
-        r#"
-        SELECT COALESCE(SUM(amount), 0.0) as total
-        FROM transactions
-        WHERE account_id = ?1 AND type = 'EXPENSE'
-          AND date >= ?2
-        "#,
-        params![settings.account_id, cycle_start.format("%Y-%m-%d").to_string()],
-        |row| row.get(0),
-    ).unwrap_or(0.0);
+    let current_cycle_charges = Money::new(
+        conn.query_row(
+            r#"
+            SELECT COALESCE(SUM(amount), 0) as total
+            FROM transactions
+            WHERE account_id = ?1 AND type = 'EXPENSE'
+              AND date >= ?2
+            "#,
+            params![settings.account_id, cycle_start.format("%Y-%m-%d").to_string()],
+            |row| row.get(0),
+        ).unwrap_or(0),
+        decimals,
+    );
 
-    let current_cycle_payments: f64 = conn.query_row(
-        r#"
-        SELECT COALESCE(SUM(amount), 0.0) as total
-        FROM transactions
-        WHERE to_account_id = ?1 AND type = 'TRANSFER'
-          AND date >= ?2
-        "#,
-        params![settings.account_id, cycle_start.format("%Y-%m-%d").to_string()],
-        |row| row.get(0),
-    ).unwrap_or(0.0);
+    let current_cycle_payments = Money::new(
+        conn.query_row(
+            r#"
+            SELECT COALESCE(SUM(je.debit), 0) as total
+            FROM journal_entries je
+            JOIN transactions t ON je.transaction_id = t.id
+            WHERE t.to_account_id = ?1 AND je.account_id = ?1 AND t.type = 'TRANSFER'
+              AND t.date >= ?2
+            "#,
+            params![settings.account_id, cycle_start.format("%Y-%m-%d").to_string()],
+            |row| row.get(0),
+        ).unwrap_or(0),
+        decimals,
+    );
 
     let outstanding_balance = current_cycle_charges - current_cycle_payments;
-    let available_credit = if settings.credit_limit > 0.0 {
-        (settings.credit_limit - total_balance).max(0.0)
+    let available_credit = if settings.credit_limit.is_positive() {
+        (settings.credit_limit - total_balance).max(zero)
     } else {
-        0.0
-    };
-    let utilization_percentage = if settings.credit_limit > 0.0 {
-        ((total_balance / settings.credit_limit) * 100.0 * 100.0).round() / 100.0
-    } else {
-        0.0
+        zero
     };
+    let utilization_percentage =
+        (total_balance.ratio(settings.credit_limit) * 100.0 * 100.0).round() / 100.0;
 
     Ok(CardBalances {
-        total_balance: total_balance.max(0.0),
-        outstanding_balance: outstanding_balance.max(0.0),
+        total_balance: total_balance.max(zero),
+        outstanding_balance: outstanding_balance.max(zero),
         available_credit,
         current_cycle_charges,
         current_cycle_payments,
@@ -1033,28 +1470,31 @@ fn calculate_balance_before_date(
     conn: &rusqlite::Connection,
     account_id: i64,
     before_date: &NaiveDate,
-) -> Result<f64, String> {
-    let charges: f64 = conn.query_row(
+) -> Result<Money, String> {
+    let decimals = account_decimals(conn, account_id)?;
+
+    let charges: i64 = conn.query_row(
         r#"
-        SELECT COALESCE(SUM(amount), 0.0) as total
+        SELECT COALESCE(SUM(amount), 0) as total
         FROM transactions
         WHERE account_id = ?1 AND type = 'EXPENSE' AND date < ?2
         "#,
         params![account_id, before_date.format("%Y-%m-%d").to_string()],
         |row| row.get(0),
-    ).unwrap_or(0.0);
+    ).unwrap_or(0);
 
-    let payments: f64 = conn.query_row(
+    let payments: i64 = conn.query_row(
         r#"
-        SELECT COALESCE(SUM(amount), 0.0) as total
-        FROM transactions
-        WHERE to_account_id = ?1 AND type = 'TRANSFER' AND date < ?2
+        SELECT COALESCE(SUM(je.debit), 0) as total
+        FROM journal_entries je
+        JOIN transactions t ON je.transaction_id = t.id
+        WHERE t.to_account_id = ?1 AND je.account_id = ?1 AND t.type = 'TRANSFER' AND t.date < ?2
         "#,
         params![account_id, before_date.format("%Y-%m-%d").to_string()],
         |row| row.get(0),
-    ).unwrap_or(0.0);
+    ).unwrap_or(0);
 
-    Ok((charges - payments).max(0.0))
+    Ok(Money::new((charges - payments).max(0), decimals))
 }
 
 fn compute_current_cycle_dates(today: NaiveDate, statement_day: i32) -> (NaiveDate, NaiveDate) {
@@ -1098,6 +1538,461 @@ fn compute_due_date(statement_date: NaiveDate, payment_due_day: i32) -> NaiveDat
     next_month.unwrap_or(statement_date + chrono::Duration::days(30))
 }
 
+fn validate_reward_settings(reward_type: &str, point_value: f64) -> Result<(), String> {
+    if reward_type != "CASHBACK" && reward_type != "POINTS" {
+        return Err("Reward type must be CASHBACK or POINTS".to_string());
+    }
+    if !point_value.is_finite() || point_value < 0.0 {
+        return Err("Point value cannot be negative".to_string());
+    }
+    Ok(())
+}
+
+fn parse_statement_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("Invalid date: {}", e))
+}
+
+/// Reward units are minor currency units for CASHBACK cards and whole points for POINTS cards.
+fn reward_units_to_amount(settings: &CreditCardSettings, units: i64) -> f64 {
+    match settings.reward_type.as_str() {
+        "POINTS" => units as f64,
+        _ => Money::new(units, settings.credit_limit.decimals()).to_major(),
+    }
+}
+
+fn reward_amount_to_units(settings: &CreditCardSettings, amount: f64) -> i64 {
+    match settings.reward_type.as_str() {
+        "POINTS" => amount.round() as i64,
+        _ => Money::from_major(amount, settings.credit_limit.decimals()).minor(),
+    }
+}
+
+fn reward_units_value(settings: &CreditCardSettings, units: i64) -> Money {
+    let decimals = settings.credit_limit.decimals();
+    match settings.reward_type.as_str() {
+        "POINTS" => Money::from_major(units as f64 * settings.point_value, decimals),
+        _ => Money::new(units, decimals),
+    }
+}
+
+/// (earned, redeemed) reward units for a card
+fn reward_totals(conn: &rusqlite::Connection, settings_id: i64) -> Result<(i64, i64), String> {
+    conn.query_row(
+        r#"
+        SELECT COALESCE(SUM(CASE WHEN kind = 'ACCRUAL' THEN units ELSE 0 END), 0),
+               COALESCE(SUM(CASE WHEN kind = 'REDEMPTION' THEN units ELSE 0 END), 0)
+        FROM credit_card_rewards
+        WHERE credit_card_id = ?1
+        "#,
+        params![settings_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).map_err(|e| format!("Database error: {}", e))
+}
+
+fn fetch_reward_rules(
+    conn: &rusqlite::Connection,
+    settings: &CreditCardSettings,
+) -> Result<Vec<RewardRule>, String> {
+    let decimals = settings.credit_limit.decimals();
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT r.id, r.credit_card_id, r.category_id, c.name, r.rate,
+               r.tier_threshold, r.cycle_cap, r.created_at
+        FROM credit_card_reward_rules r
+        LEFT JOIN categories c ON r.category_id = c.id
+        WHERE r.credit_card_id = ?1
+        ORDER BY c.name IS NOT NULL, c.name, r.tier_threshold
+        "#,
+    ).map_err(|e| format!("Database error: {}", e))?;
+
+    let rules = stmt.query_map(params![settings.id], |row| {
+        Ok(RewardRule {
+            id: row.get(0)?,
+            credit_card_id: row.get(1)?,
+            category_id: row.get(2)?,
+            category_name: row.get(3)?,
+            rate: row.get(4)?,
+            tier_threshold: Money::new(row.get(5)?, decimals),
+            cycle_cap: row
+                .get::<_, Option<i64>>(6)?
+                .map(|cap| reward_units_to_amount(settings, cap)),
+            created_at: row.get(7)?,
+        })
+    }).map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(rules)
+}
+
+/// Rewards earned by EXPENSE lines on the card between `start` and `end`.
+/// Interest and fee charges (the card's fee category) never earn rewards.
+fn cycle_rewards(
+    conn: &rusqlite::Connection,
+    settings: &CreditCardSettings,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<(StatementRewards, i64), String> {
+    let decimals = settings.credit_limit.decimals();
+    let rules = fetch_reward_rules(conn, settings)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT l.category_id, c.parent_id, COALESCE(SUM(l.amount), 0)
+        FROM transaction_category_lines l
+        LEFT JOIN categories c ON l.category_id = c.id
+        WHERE l.account_id = ?1 AND l.type = 'EXPENSE'
+          AND l.date >= ?2 AND l.date <= ?3
+          AND (?4 IS NULL OR l.category_id IS NULL OR l.category_id != ?4)
+        GROUP BY l.category_id
+        "#,
+    ).map_err(|e| format!("Database error: {}", e))?;
+
+    let spend: Vec<(Option<i64>, Option<i64>, Money)> = stmt.query_map(
+        params![
+            settings.account_id,
+            start.format("%Y-%m-%d").to_string(),
+            end.format("%Y-%m-%d").to_string(),
+            settings.fee_category_id
+        ],
+        |row| Ok((row.get(0)?, row.get(1)?, Money::new(row.get(2)?, decimals))),
+    ).map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let (lines, units) = compute_rewards(settings, &rules, &spend);
+
+    Ok((
+        StatementRewards {
+            reward_type: settings.reward_type.clone(),
+            total: reward_units_to_amount(settings, units),
+            value: reward_units_value(settings, units),
+            lines,
+        },
+        units,
+    ))
+}
+
+/// Applies reward rules to per-category spend `(category_id, parent_id, amount)`.
+/// Spend earns under its own category's rules, then its parent's, then the
+/// base rules. Within a category each tier's rate applies to the spend between
+/// its threshold and the next one, and is capped at the tier's cycle cap.
+/// Returns the reward lines and the total in reward units.
+pub fn compute_rewards(
+    settings: &CreditCardSettings,
+    rules: &[RewardRule],
+    spend: &[(Option<i64>, Option<i64>, Money)],
+) -> (Vec<RewardLine>, i64) {
+    let decimals = settings.credit_limit.decimals();
+    let has_rules = |category_id: Option<i64>| rules.iter().any(|r| r.category_id == category_id);
+
+    // Total spend per rule category
+    let mut by_key: Vec<(Option<i64>, Money)> = Vec::new();
+    for (category_id, parent_id, amount) in spend {
+        let key = if category_id.is_some() && has_rules(*category_id) {
+            *category_id
+        } else if parent_id.is_some() && has_rules(*parent_id) {
+            *parent_id
+        } else {
+            None
+        };
+
+        match by_key.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, total)) => *total += *amount,
+            None => by_key.push((key, *amount)),
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut total_units = 0;
+
+    for (key, total) in by_key {
+        let mut tiers: Vec<&RewardRule> = rules.iter().filter(|r| r.category_id == key).collect();
+        tiers.sort_by_key(|r| r.tier_threshold);
+
+        for (i, rule) in tiers.iter().enumerate() {
+            let upper = tiers.get(i + 1).map(|next| next.tier_threshold);
+            let portion = match upper {
+                Some(upper) => total.min(upper) - rule.tier_threshold,
+                None => total - rule.tier_threshold,
+            };
+            if !portion.is_positive() {
+                continue;
+            }
+
+            let mut units = match settings.reward_type.as_str() {
+                "POINTS" => (portion.to_major() * rule.rate).floor() as i64,
+                _ => (portion.minor() as f64 * rule.rate / 100.0).floor() as i64,
+            };
+            let cap = rule.cycle_cap.map(|cap| reward_amount_to_units(settings, cap));
+            let capped = cap.is_some_and(|cap| units > cap);
+            if let Some(cap) = cap {
+                units = units.min(cap);
+            }
+            total_units += units;
+
+            lines.push(RewardLine {
+                rule_id: rule.id,
+                category_id: rule.category_id,
+                category_name: rule.category_name.clone(),
+                spend: portion.rescale(decimals),
+                rate: rule.rate,
+                reward: reward_units_to_amount(settings, units),
+                capped,
+            });
+        }
+    }
+
+    (lines, total_units)
+}
+
+fn validate_fee_settings(
+    conn: &rusqlite::Connection,
+    apr: f64,
+    late_fee: Money,
+    annual_fee: Money,
+    annual_fee_month: Option<u32>,
+    fee_category_id: Option<i64>,
+) -> Result<(), String> {
+    if !apr.is_finite() || apr < 0.0 {
+        return Err("APR cannot be negative".to_string());
+    }
+    if late_fee.is_negative() || annual_fee.is_negative() {
+        return Err("Fees cannot be negative".to_string());
+    }
+    if let Some(month) = annual_fee_month {
+        if !(1..=12).contains(&month) {
+            return Err("Annual fee month must be between 1 and 12".to_string());
+        }
+    }
+    if let Some(category_id) = fee_category_id {
+        let exists: bool = conn.query_row(
+            "SELECT COUNT(id) FROM categories WHERE id = ?1",
+            params![category_id],
+            |row| row.get::<_, i64>(0),
+        ).unwrap_or(0) > 0;
+
+        if !exists {
+            return Err("Fee category not found".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Settles every statement whose due date has passed. Without a grace period
+/// the unpaid remainder accrues one billing cycle of interest at the card's
+/// daily rate; paying less than the minimum also adds the late fee and marks
+/// the statement OVERDUE. The annual fee is posted once a year on the
+/// statement day of `annual_fee_month`.
+fn assess_card_charges(
+    conn: &rusqlite::Connection,
+    settings: &CreditCardSettings,
+    today: NaiveDate,
+) -> Result<Vec<i64>, String> {
+    let decimals = settings.credit_limit.decimals();
+    let zero = Money::zero(decimals);
+    let today_str = today.format("%Y-%m-%d").to_string();
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, statement_date, due_date, closing_balance, minimum_payment, paid_amount
+        FROM credit_card_statements
+        WHERE credit_card_id = ?1 AND assessed_at IS NULL AND due_date < ?2
+        ORDER BY due_date ASC
+        "#,
+    ).map_err(|e| format!("Database error: {}", e))?;
+
+    let due: Vec<(i64, String, String, Money, Money, Money)> = stmt.query_map(
+        params![settings.id, today_str],
+        |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                Money::new(row.get(3)?, decimals),
+                Money::new(row.get(4)?, decimals),
+                Money::new(row.get(5)?, decimals),
+            ))
+        },
+    ).map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let mut created = Vec::new();
+
+    for (statement_id, statement_date, due_date, closing_balance, minimum_payment, paid_amount) in due {
+        let unpaid = closing_balance - paid_amount;
+
+        let (status, finance_charge, late_fee) = if !unpaid.is_positive() {
+            ("PAID", zero, zero)
+        } else {
+            let start = NaiveDate::parse_from_str(&statement_date, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid statement date: {}", e))?;
+            let next = start
+                .checked_add_months(Months::new(1))
+                .unwrap_or(start + chrono::Duration::days(30));
+            let cycle_days = (next - start).num_days() as f64;
+            let interest = unpaid.mul_f64(settings.apr / 100.0 / 365.0 * cycle_days, decimals);
+
+            if paid_amount < minimum_payment {
+                ("OVERDUE", interest, settings.late_fee)
+            } else {
+                ("PARTIAL", interest, zero)
+            }
+        };
+
+        if finance_charge.is_positive() {
+            created.push(post_card_charge(
+                conn,
+                settings,
+                &due_date,
+                finance_charge,
+                &format!("Interest charge - statement {}", statement_date),
+            )?);
+        }
+        if late_fee.is_positive() {
+            created.push(post_card_charge(
+                conn,
+                settings,
+                &due_date,
+                late_fee,
+                &format!("Late payment fee - statement {}", statement_date),
+            )?);
+        }
+
+        conn.execute(
+            r#"
+            UPDATE credit_card_statements
+            SET status = ?1, finance_charge = ?2, late_fee = ?3, assessed_at = datetime('now')
+            WHERE id = ?4
+            "#,
+            params![status, finance_charge, late_fee, statement_id],
+        ).map_err(|e| format!("Failed to update statement: {}", e))?;
+    }
+
+    if settings.annual_fee.is_positive() {
+        let created_on = settings.created_at.get(..10).unwrap_or_default();
+        let month = settings
+            .annual_fee_month
+            .or_else(|| created_on.get(5..7).and_then(|m| m.parse().ok()))
+            .unwrap_or(1);
+        let fee_date = make_date(today.year(), month, settings.statement_day)
+            .format("%Y-%m-%d")
+            .to_string();
+
+        let last_charged: Option<String> = conn.query_row(
+            "SELECT last_annual_fee_date FROM credit_card_settings WHERE id = ?1",
+            params![settings.id],
+            |row| row.get(0),
+        ).map_err(|e| format!("Database error: {}", e))?;
+
+        let already_charged = last_charged.is_some_and(|d| d >= fee_date);
+        if fee_date <= today_str && fee_date.as_str() >= created_on && !already_charged {
+            created.push(post_card_charge(
+                conn,
+                settings,
+                &fee_date,
+                settings.annual_fee,
+                &format!("Annual fee {}", today.year()),
+            )?);
+
+            conn.execute(
+                "UPDATE credit_card_settings SET last_annual_fee_date = ?1 WHERE id = ?2",
+                params![fee_date, settings.id],
+            ).map_err(|e| format!("Failed to update credit card settings: {}", e))?;
+        }
+    }
+
+    Ok(created)
+}
+
+fn post_card_charge(
+    conn: &rusqlite::Connection,
+    settings: &CreditCardSettings,
+    date: &str,
+    amount: Money,
+    memo: &str,
+) -> Result<i64, String> {
+    insert_transaction(
+        conn,
+        CreateTransactionInput {
+            date: date.to_string(),
+            transaction_type: "EXPENSE".to_string(),
+            amount: amount.to_major(),
+            account_id: settings.account_id,
+            to_account_id: None,
+            to_amount: None,
+            category_id: settings.fee_category_id,
+            payee_id: None,
+            payee: None,
+            memo: Some(memo.to_string()),
+            tag_ids: None,
+            goal_allocations: None,
+            goal_withdrawals: None,
+            splits: None,
+        },
+    )
+}
+
+/// Pays the larger of `minimum_pct` of the balance and `floor` each month after
+/// interest is added. Stops early when the payment no longer covers interest.
+pub fn project_minimum(
+    balance: Money,
+    apr: f64,
+    minimum_pct: f64,
+    floor: Money,
+) -> MinimumPaymentProjection {
+    let decimals = balance.decimals();
+    let zero = Money::zero(decimals);
+    let smallest = Money::new(1, decimals);
+
+    let mut remaining = balance.max(zero);
+    let mut total_interest = zero;
+    let mut total_paid = zero;
+    let mut months = Vec::new();
+    let mut stalled = false;
+
+    while remaining.is_positive() && (months.len() as i32) < MAX_PROJECTION_MONTHS {
+        let interest = remaining.mul_f64(apr / 1200.0, decimals);
+        remaining += interest;
+
+        let payment = remaining
+            .percent(minimum_pct)
+            .max(floor)
+            .max(smallest)
+            .min(remaining);
+        remaining -= payment;
+        total_interest += interest;
+        total_paid += payment;
+
+        months.push(ProjectionMonth {
+            month: months.len() as i32 + 1,
+            payment,
+            interest,
+            balance: remaining,
+        });
+
+        if payment <= interest && remaining.is_positive() {
+            stalled = true;
+            break;
+        }
+    }
+
+    let pays_off = !stalled && remaining.is_zero();
+
+    MinimumPaymentProjection {
+        starting_balance: balance,
+        apr,
+        minimum_payment_percentage: minimum_pct,
+        minimum_payment_floor: floor,
+        pays_off,
+        months_to_payoff: if pays_off { months.len() as i32 } else { 0 },
+        total_interest,
+        total_paid,
+        months,
+    }
+}
+
 fn make_date(year: i32, month: u32, day: i32) -> NaiveDate {
     NaiveDate::from_ymd_opt(year, month, day as u32).unwrap_or_else(|| {
         NaiveDate::from_ymd_opt(year, month, 28).expect("Day 28 should always be valid")
@@ -1107,32 +2002,37 @@ fn make_date(year: i32, month: u32, day: i32) -> NaiveDate {
 fn update_statement_payment_status(
     conn: &rusqlite::Connection,
     settings_id: i64,
-    payment_amount: f64,
+    payment_amount: Money,
     paid_date: &str,
 ) -> Result<(), String> {
     let mut stmt = conn.prepare(
         r#"
         SELECT id, closing_balance, paid_amount
         FROM credit_card_statements
-        WHERE credit_card_id = ?1 AND status IN ('OPEN', 'CLOSED', 'PARTIAL')
+        WHERE credit_card_id = ?1 AND status IN ('OPEN', 'CLOSED', 'PARTIAL', 'OVERDUE')
         ORDER BY due_date ASC
         "#,
     ).map_err(|e| format!("Database error: {}", e))?;
 
-    let statements: Vec<(i64, f64, f64)> = stmt.query_map(params![settings_id], |row| {
-        Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+    let decimals = payment_amount.decimals();
+    let statements: Vec<(i64, Money, Money)> = stmt.query_map(params![settings_id], |row| {
+        Ok((
+            row.get(0)?,
+            Money::new(row.get(1)?, decimals),
+            Money::new(row.get(2)?, decimals),
+        ))
     }).unwrap().filter_map(Result::ok).collect();
 
     let mut remaining_payment = payment_amount;
 
     for (stmt_id, closing_balance, already_paid) in statements {
-        if remaining_payment <= 0.0 {
+        if !remaining_payment.is_positive() {
             break;
         }
 
         let stmt_remaining = closing_balance - already_paid;
 
-        if stmt_remaining <= 0.0 {
+        if !stmt_remaining.is_positive() {
             continue;
         }
 
@@ -1140,7 +2040,7 @@ fn update_statement_payment_status(
         let new_paid = already_paid + apply_amount;
         remaining_payment -= apply_amount;
 
-        let new_status = if (new_paid - closing_balance).abs() < 0.01 {
+        let new_status = if new_paid == closing_balance {
             "PAID"
         } else {
             "PARTIAL"
@@ -1158,3 +2058,94 @@ fn update_statement_payment_status(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimum_payments_with_floor_pay_off() {
+        let projection = project_minimum(Money::new(100000, 2), 24.0, 2.0, Money::new(2500, 2));
+
+        assert!(projection.pays_off);
+        assert_eq!(projection.months[0].interest, Money::new(2000, 2));
+        assert_eq!(projection.months[0].payment, Money::new(2500, 2));
+        assert_eq!(
+            projection.total_paid,
+            projection.starting_balance + projection.total_interest
+        );
+        assert_eq!(projection.months_to_payoff, projection.months.len() as i32);
+    }
+
+    fn rule(id: i64, category_id: Option<i64>, rate: f64, threshold: i64, cap: Option<f64>) -> RewardRule {
+        RewardRule {
+            id,
+            credit_card_id: 1,
+            category_id,
+            category_name: None,
+            rate,
+            tier_threshold: Money::new(threshold, 2),
+            cycle_cap: cap,
+            created_at: String::new(),
+        }
+    }
+
+    fn card(reward_type: &str) -> CreditCardSettings {
+        CreditCardSettings {
+            id: 1,
+            account_id: 1,
+            credit_limit: Money::zero(2),
+            statement_day: 1,
+            payment_due_day: 20,
+            minimum_payment_percentage: 5.0,
+            auto_settlement_enabled: false,
+            settlement_account_id: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            apr: 0.0,
+            late_fee: Money::zero(2),
+            annual_fee: Money::zero(2),
+            annual_fee_month: None,
+            fee_category_id: None,
+            reward_type: reward_type.to_string(),
+            point_value: 0.01,
+        }
+    }
+
+    #[test]
+    fn rewards_apply_tiers_caps_and_parent_categories() {
+        let rules = vec![
+            rule(1, None, 1.0, 0, None),
+            rule(2, Some(10), 5.0, 0, Some(20.0)),
+            rule(3, Some(20), 2.0, 0, None),
+            rule(4, Some(20), 4.0, 50000, None),
+        ];
+        let spend = vec![
+            (Some(10), None, Money::new(100000, 2)),     // 5% capped at 20.00
+            (Some(21), Some(20), Money::new(80000, 2)), // 2% on 500, 4% on 300
+            (Some(30), None, Money::new(10000, 2)),     // base 1%
+        ];
+
+        let (lines, units) = compute_rewards(&card("CASHBACK"), &rules, &spend);
+
+        assert_eq!(units, 2000 + 1000 + 1200 + 100);
+        assert!(lines.iter().find(|l| l.rule_id == 2).unwrap().capped);
+        assert_eq!(lines.iter().find(|l| l.rule_id == 4).unwrap().spend, Money::new(30000, 2));
+    }
+
+    #[test]
+    fn points_are_earned_per_whole_unit() {
+        let rules = vec![rule(1, None, 2.0, 0, None)];
+        let (_, units) = compute_rewards(&card("POINTS"), &rules, &[(None, None, Money::new(12399, 2))]);
+
+        assert_eq!(units, 247);
+    }
+
+    #[test]
+    fn minimum_below_interest_never_pays_off() {
+        let projection = project_minimum(Money::new(100000, 2), 36.0, 2.0, Money::zero(2));
+
+        assert!(!projection.pays_off);
+        assert_eq!(projection.months_to_payoff, 0);
+    }
+}
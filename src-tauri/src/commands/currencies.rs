@@ -1,68 +1,123 @@
 // File: src-tauri/src/commands/currencies.rs
 use crate::models::currency::{
-    CurrencyConversion, ExchangeRate, ExchangeRateSummary, SetExchangeRateInput, SupportedCurrency,
+    CurrencyAmount, CurrencyConversion, ExchangeRate, ExchangeRateSummary, FxGainReport,
+    FxPosting, MissingRate, SetExchangeRateInput, SupportedCurrency,
 };
+use crate::models::money::Money;
 use crate::AppState;
 use rusqlite::{params, OptionalExtension};
 use serde::Serialize;
+use std::collections::HashMap;
 use tauri::State;
 
 // ======================== SUPPORTED CURRENCIES ========================
 
+/// (code, name, symbol, decimal places). Decimal places define the minor unit
+/// that amounts in that currency are stored in (see `models::money::Money`).
+pub const SUPPORTED_CURRENCIES: &[(&str, &str, &str, u32)] = &[
+    ("LKR", "Sri Lankan Rupee", "Rs.", 2),
+    ("USD", "US Dollar", "$", 2),
+    ("EUR", "Euro", "€", 2),
+    ("GBP", "British Pound", "£", 2),
+    ("INR", "Indian Rupee", "₹", 2),
+    ("AUD", "Australian Dollar", "A$", 2),
+    ("CAD", "Canadian Dollar", "C$", 2),
+    ("JPY", "Japanese Yen", "¥", 0),
+    ("SGD", "Singapore Dollar", "S$", 2),
+    ("AED", "UAE Dirham", "د.إ", 2),
+];
+
 #[tauri::command]
 pub fn get_supported_currencies() -> Result<Vec<SupportedCurrency>, String> {
-    Ok(vec![
-        SupportedCurrency {
-            code: "LKR".to_string(),
-            name: "Sri Lankan Rupee".to_string(),
-            symbol: "Rs.".to_string(),
-        },
-        SupportedCurrency {
-            code: "USD".to_string(),
-            name: "US Dollar".to_string(),
-            symbol: "$".to_string(),
-        },
-        SupportedCurrency {
-            code: "EUR".to_string(),
-            name: "Euro".to_string(),
-            symbol: "€".to_string(),
-        },
-        SupportedCurrency {
-            code: "GBP".to_string(),
-            name: "British Pound".to_string(),
-            symbol: "£".to_string(),
-        },
-        SupportedCurrency {
-            code: "INR".to_string(),
-            name: "Indian Rupee".to_string(),
-            symbol: "₹".to_string(),
-        },
-        SupportedCurrency {
-            code: "AUD".to_string(),
-            name: "Australian Dollar".to_string(),
-            symbol: "A$".to_string(),
-        },
-        SupportedCurrency {
-            code: "CAD".to_string(),
-            name: "Canadian Dollar".to_string(),
-            symbol: "C$".to_string(),
-        },
-        SupportedCurrency {
-            code: "JPY".to_string(),
-            name: "Japanese Yen".to_string(),
-            symbol: "¥".to_string(),
-        },
-        SupportedCurrency {
-            code: "SGD".to_string(),
-            name: "Singapore Dollar".to_string(),
-            symbol: "S$".to_string(),
-        },
-        SupportedCurrency {
-            code: "AED".to_string(),
-            name: "UAE Dirham".to_string(),
-            symbol: "د.إ".to_string(),
-        },
-    ])
+    Ok(SUPPORTED_CURRENCIES
+        .iter()
+        .map(|(code, name, symbol, decimals)| SupportedCurrency {
+            code: code.to_string(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            decimals: *decimals,
+        })
+        .collect())
+}
+
+/// Decimal places for a currency code. Unknown currencies default to 2.
+pub fn currency_decimals(code: &str) -> u32 {
+    let code = code.trim().to_uppercase();
+    SUPPORTED_CURRENCIES
+        .iter()
+        .find(|(c, _, _, _)| *c == code)
+        .map(|(_, _, _, decimals)| *decimals)
+        .unwrap_or(2)
+}
+
+/// Largest number of decimal places among supported currencies. Aggregates that
+/// span accounts in different currencies are summed at this scale.
+pub fn common_decimals() -> u32 {
+    SUPPORTED_CURRENCIES
+        .iter()
+        .map(|(_, _, _, decimals)| *decimals)
+        .max()
+        .unwrap_or(2)
+}
+
+/// SQL expression lifting a minor-unit `amount` column to `common_decimals()`,
+/// given the column holding its currency code. Use with `SUM(...)` when rows
+/// may come from accounts in different currencies.
+pub fn common_scale_sql(amount: &str, currency: &str) -> String {
+    let common = common_decimals();
+    let cases: Vec<String> = SUPPORTED_CURRENCIES
+        .iter()
+        .filter(|(_, _, _, decimals)| *decimals != common)
+        .map(|(code, _, _, decimals)| format!("WHEN '{}' THEN {}", code, 10_i64.pow(common - decimals)))
+        .collect();
+
+    let default_factor = 10_i64.pow(common.saturating_sub(2));
+    if cases.is_empty() && default_factor == 1 {
+        return amount.to_string();
+    }
+
+    format!(
+        "({} * CASE UPPER({}) {} ELSE {} END)",
+        amount,
+        currency,
+        cases.join(" "),
+        default_factor
+    )
+}
+
+/// SQL expression rendering a minor-unit `amount` column as major-unit text
+/// (e.g. 123450 → "1234.50"), for LIKE searches on amounts.
+pub fn major_text_sql(amount: &str, currency: &str) -> String {
+    let common = common_decimals();
+    format!(
+        "printf('%.{}f', {} / {}.0)",
+        common,
+        common_scale_sql(amount, currency),
+        10_i64.pow(common)
+    )
+}
+
+/// Decimal places of the currency an account is held in.
+pub fn account_decimals(conn: &rusqlite::Connection, account_id: i64) -> Result<u32, String> {
+    Ok(currency_decimals(&account_currency(conn, account_id)?))
+}
+
+/// Currency an account is held in.
+pub fn account_currency(conn: &rusqlite::Connection, account_id: i64) -> Result<String, String> {
+    conn.query_row(
+        "SELECT currency FROM accounts WHERE id = ?1",
+        params![account_id],
+        |row| row.get(0),
+    )
+    .map_err(|_| "Account does not exist".to_string())
+}
+
+/// Decimal places of the primary currency. Budgets, net worth snapshots and
+/// goals without a linked account are stored in the primary currency.
+pub fn primary_decimals(conn: &rusqlite::Connection) -> u32 {
+    get_primary_currency_internal(conn)
+        .map(|code| currency_decimals(&code))
+        .unwrap_or(2)
 }
 
 // ======================== PRIMARY CURRENCY ========================
@@ -81,14 +136,25 @@ pub fn set_primary_currency(
     currency_code: String,
 ) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let code = currency_code.trim().to_uppercase();
     if code.is_empty() || code.len() != 3 {
         return Err("Currency code must be a 3-letter code (e.g., LKR, USD)".to_string());
     }
 
-    conn.execute(
+    // Rescaling, the setting and the FX postings change together or not at all
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    // Amounts denominated in the primary currency are stored in its minor units,
+    // so a change in decimal places has to rescale them.
+    let old_decimals = primary_decimals(&tx);
+    let new_decimals = currency_decimals(&code);
+    if old_decimals != new_decimals {
+        rescale_primary_amounts(&tx, old_decimals, new_decimals)?;
+    }
+
+    tx.execute(
         r#"
         INSERT INTO app_settings (key, value, updated_at)
         VALUES ('primary_currency', ?1, datetime('now'))
@@ -97,6 +163,47 @@ pub fn set_primary_currency(
         params![code],
     ).map_err(|e| format!("Failed to set primary currency: {}", e))?;
 
+    refresh_fx_postings(&tx, None, None)?;
+
+    tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_rate_base_currency(state: State<'_, AppState>) -> Result<String, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    Ok(rate_base_currency(&conn))
+}
+
+/// Set the currency cross rates are triangulated through when a pair has no
+/// stored rate of its own, e.g. EUR for rates imported from the ECB.
+#[tauri::command]
+pub fn set_rate_base_currency(
+    state: State<'_, AppState>,
+    currency_code: String,
+) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let code = currency_code.trim().to_uppercase();
+    if code.len() != 3 {
+        return Err("Currency code must be a 3-letter code (e.g., EUR, USD)".to_string());
+    }
+
+    conn.execute(
+        r#"
+        INSERT INTO app_settings (key, value, updated_at)
+        VALUES ('rate_base_currency', ?1, datetime('now'))
+        ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = datetime('now')
+        "#,
+        params![code],
+    ).map_err(|e| format!("Failed to set base currency: {}", e))?;
+
+    refresh_fx_postings(&conn, None, None)?;
+
     Ok(())
 }
 
@@ -143,8 +250,11 @@ pub fn set_exchange_rate(
         "#,
         params![from, to, input.rate, input.effective_date],
     ).map_err(|e| format!("Failed to set exchange rate: {}", e))?;
+    let rate_id = conn.last_insert_rowid();
+
+    refresh_fx_postings(&conn, Some(&input.effective_date), None)?;
 
-    Ok(conn.last_insert_rowid())
+    Ok(rate_id)
 }
 
 #[tauri::command]
@@ -225,6 +335,7 @@ fn row_to_exchange_rate(row: &rusqlite::Row) -> rusqlite::Result<ExchangeRate> {
         effective_date: row.get(4)?,
         created_at: row.get(5)?,
         updated_at: row.get(6)?,
+        via_currency: None,
     })
 }
 
@@ -233,12 +344,18 @@ pub fn delete_exchange_rate(state: State<'_, AppState>, rate_id: i64) -> Result<
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    let rows_affected = conn.execute("DELETE FROM exchange_rates WHERE id = ?1", params![rate_id])
+    let effective_date: String = conn
+        .query_row(
+            "SELECT effective_date FROM exchange_rates WHERE id = ?1",
+            params![rate_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| "Exchange rate not found".to_string())?;
+
+    conn.execute("DELETE FROM exchange_rates WHERE id = ?1", params![rate_id])
         .map_err(|e| format!("Failed to delete exchange rate: {}", e))?;
 
-    if rows_affected == 0 {
-        return Err("Exchange rate not found".to_string());
-    }
+    refresh_fx_postings(&conn, Some(&effective_date), None)?;
 
     Ok(())
 }
@@ -262,13 +379,14 @@ pub fn convert_amount(
 
     let from = from_currency.trim().to_uppercase();
     let to = to_currency.trim().to_uppercase();
+    let original = Money::from_major(amount, currency_decimals(&from));
 
     if from == to {
         return Ok(CurrencyConversion {
             from_currency: from,
             to_currency: to,
-            original_amount: amount,
-            converted_amount: amount,
+            original_amount: original,
+            converted_amount: original,
             rate_used: 1.0,
             rate_date: date,
         });
@@ -276,19 +394,14 @@ pub fn convert_amount(
 
     let rate = get_exchange_rate_internal(&conn, &from, &to, &date)?;
 
-    let converted = amount * rate.rate;
-
-    let converted_rounded = if to == "JPY" {
-        converted.round()
-    } else {
-        (converted * 100.0).round() / 100.0
-    };
+    // Rounded to the target currency's own precision (e.g. whole yen for JPY)
+    let converted = original.mul_f64(rate.rate, currency_decimals(&to));
 
     Ok(CurrencyConversion {
         from_currency: from,
         to_currency: to,
-        original_amount: amount,
-        converted_amount: converted_rounded,
+        original_amount: original,
+        converted_amount: converted,
         rate_used: rate.rate,
         rate_date: rate.effective_date,
     })
@@ -296,13 +409,20 @@ pub fn convert_amount(
 
 // ======================== RATE SUMMARIES ========================
 
+/// A pair whose latest rate is older than this many days is reported as stale
+const STALE_RATE_DAYS: i64 = 30;
+
 #[tauri::command]
 pub fn get_exchange_rate_summaries(
     state: State<'_, AppState>,
+    stale_after_days: Option<i64>,
 ) -> Result<Vec<ExchangeRateSummary>, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
+    let stale_after_days = stale_after_days.unwrap_or(STALE_RATE_DAYS);
+    let today = chrono::Local::now().date_naive();
+
     let mut stmt = conn.prepare(
         r#"
         SELECT
@@ -326,12 +446,18 @@ pub fn get_exchange_rate_summaries(
     ).map_err(|e| format!("Query error: {}", e))?;
 
     let summaries = stmt.query_map([], |row| {
+        let latest_date: String = row.get(3)?;
+        let days_since_update = chrono::NaiveDate::parse_from_str(&latest_date, "%Y-%m-%d")
+            .map(|d| (today - d).num_days())
+            .unwrap_or(i64::MAX);
         Ok(ExchangeRateSummary {
             from_currency: row.get(0)?,
             to_currency: row.get(1)?,
             latest_rate: row.get(2)?,
-            latest_date: row.get(3)?,
+            latest_date,
             rate_count: row.get(4)?,
+            days_since_update,
+            is_stale: days_since_update > stale_after_days,
         })
     }).unwrap().filter_map(Result::ok).collect();
 
@@ -357,7 +483,7 @@ pub fn convert_balances_to_primary(
             a.name,
             a.currency,
             a.initial_balance,
-            CAST(COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0) AS REAL) as journal_balance
+            COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0) as journal_balance
         FROM accounts a
         LEFT JOIN journal_entries je ON je.account_id = a.id
         GROUP BY a.id
@@ -365,7 +491,7 @@ pub fn convert_balances_to_primary(
         "#,
     ).map_err(|e| format!("Query error: {}", e))?;
 
-    let rows: Vec<(i64, String, String, f64, f64)> = stmt.query_map([], |row| {
+    let rows: Vec<(i64, String, String, i64, i64)> = stmt.query_map([], |row| {
         Ok((
             row.get(0)?,
             row.get(1)?,
@@ -376,19 +502,16 @@ pub fn convert_balances_to_primary(
     }).unwrap().filter_map(Result::ok).collect();
 
     let mut results = Vec::new();
+    let primary_dec = currency_decimals(&primary);
 
     for (id, name, currency, initial_balance, journal_balance) in rows {
-        let original_balance = initial_balance + journal_balance;
+        let original_balance = Money::new(initial_balance + journal_balance, currency_decimals(&currency));
 
         let (converted_balance, rate_used) = if currency == primary {
             (original_balance, 1.0)
         } else {
             match get_exchange_rate_internal(&conn, &currency, &primary, &today) {
-                Ok(rate) => {
-                    let converted = original_balance * rate.rate;
-                    let rounded = (converted * 100.0).round() / 100.0;
-                    (rounded, rate.rate)
-                }
+                Ok(rate) => (original_balance.mul_f64(rate.rate, primary_dec), rate.rate),
                 Err(_) => (original_balance, 0.0),
             }
         };
@@ -407,9 +530,295 @@ pub fn convert_balances_to_primary(
     Ok(results)
 }
 
+// ======================== HISTORICAL CONVERSION ========================
+
+/// Converts amounts into one target currency at the rate effective on each
+/// amount's own date. Lookups are cached per (currency, date), and amounts
+/// with no rate are recorded instead of being mixed into the totals.
+pub struct RateConverter<'c> {
+    conn: &'c rusqlite::Connection,
+    pub target: String,
+    pub decimals: u32,
+    rates: HashMap<(String, String), Option<f64>>,
+    missing: Vec<MissingRate>,
+}
+
+impl<'c> RateConverter<'c> {
+    pub fn to_primary(conn: &'c rusqlite::Connection) -> Result<Self, String> {
+        let target = get_primary_currency_internal(conn)?;
+        Ok(RateConverter {
+            conn,
+            decimals: currency_decimals(&target),
+            target,
+            rates: HashMap::new(),
+            missing: Vec::new(),
+        })
+    }
+
+    pub fn zero(&self) -> Money {
+        Money::zero(self.decimals)
+    }
+
+    /// Rate from `currency` to the target effective on `date`, if one exists.
+    pub fn rate(&mut self, currency: &str, date: &str) -> Option<f64> {
+        if currency == self.target {
+            return Some(1.0);
+        }
+
+        let key = (currency.to_string(), date.to_string());
+        if !self.rates.contains_key(&key) {
+            let rate = get_exchange_rate_internal(self.conn, currency, &self.target, date)
+                .ok()
+                .map(|r| r.rate);
+            self.rates.insert(key.clone(), rate);
+        }
+
+        let rate = self.rates[&key];
+        if rate.is_none() {
+            self.record_missing(currency, date);
+        }
+        rate
+    }
+
+    /// `amount` in the target currency, or None when no rate was effective on `date`.
+    pub fn convert(&mut self, amount: Money, currency: &str, date: &str) -> Option<Money> {
+        self.rate(currency, date)
+            .map(|rate| amount.mul_f64(rate, self.decimals))
+    }
+
+    /// Sums `(key, date, currency, minor amount)` rows per key, converting each
+    /// row at its own date's rate. Keys keep the order they first appear in.
+    pub fn sum_by<K: PartialEq>(&mut self, rows: Vec<(K, String, String, i64)>) -> Vec<ConvertedTotal<K>> {
+        let mut totals: Vec<ConvertedTotal<K>> = Vec::new();
+
+        for (key, date, currency, amount) in rows {
+            let index = match totals.iter().position(|t| t.key == key) {
+                Some(index) => index,
+                None => {
+                    totals.push(ConvertedTotal {
+                        key,
+                        total: self.zero(),
+                        by_currency: Vec::new(),
+                        missing_rates: Vec::new(),
+                    });
+                    totals.len() - 1
+                }
+            };
+
+            let amount = Money::new(amount, currency_decimals(&currency));
+            let converted = self.convert(amount, &currency, &date);
+            let entry = &mut totals[index];
+            add_currency_amount(&mut entry.by_currency, &currency, amount);
+            match converted {
+                Some(converted) => entry.total += converted,
+                None => record_missing_rate(&mut entry.missing_rates, &currency, &self.target, &date),
+            }
+        }
+
+        totals
+    }
+
+    pub fn missing_rates(&self) -> Vec<MissingRate> {
+        self.missing.clone()
+    }
+
+    fn record_missing(&mut self, currency: &str, date: &str) {
+        let target = self.target.clone();
+        record_missing_rate(&mut self.missing, currency, &target, date);
+    }
+}
+
+/// A converted total from `RateConverter::sum_by`
+pub struct ConvertedTotal<K> {
+    pub key: K,
+    pub total: Money,
+    pub by_currency: Vec<CurrencyAmount>,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Notes that `currency` had no rate to `to_currency` on `date`.
+pub fn record_missing_rate(missing: &mut Vec<MissingRate>, currency: &str, to_currency: &str, date: &str) {
+    match missing.iter_mut().find(|m| m.currency == currency) {
+        Some(entry) => {
+            if date < entry.first_date.as_str() {
+                entry.first_date = date.to_string();
+            }
+            if date > entry.last_date.as_str() {
+                entry.last_date = date.to_string();
+            }
+            entry.occurrences += 1;
+        }
+        None => missing.push(MissingRate {
+            currency: currency.to_string(),
+            to_currency: to_currency.to_string(),
+            first_date: date.to_string(),
+            last_date: date.to_string(),
+            occurrences: 1,
+        }),
+    }
+}
+
+/// Adds to the running original-currency total for `currency`.
+pub fn add_currency_amount(totals: &mut Vec<CurrencyAmount>, currency: &str, amount: Money) {
+    match totals.iter_mut().find(|t| t.currency == currency) {
+        Some(total) => total.amount += amount,
+        None => totals.push(CurrencyAmount {
+            currency: currency.to_string(),
+            amount,
+        }),
+    }
+}
+
+// ======================== FX GAINS ========================
+
+/// Rebuild the FX postings of cross-currency transfers dated on or after
+/// `since`, or of the single transfer `transaction_id`; everything when both
+/// are None. Called whenever a transfer or a rate it was valued at changes.
+pub fn refresh_fx_postings(
+    conn: &rusqlite::Connection,
+    since: Option<&str>,
+    transaction_id: Option<i64>,
+) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM fx_postings
+         WHERE (?1 IS NULL OR date >= ?1) AND (?2 IS NULL OR transaction_id = ?2)",
+        params![since, transaction_id],
+    )
+    .map_err(|e| format!("Failed to clear FX postings: {}", e))?;
+
+    let transfers: Vec<(i64, String, String, i64, String, Option<i64>)> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.id, t.date, a.currency, t.amount, ta.currency, t.to_amount
+                 FROM transactions t
+                 INNER JOIN accounts a ON t.account_id = a.id
+                 INNER JOIN accounts ta ON t.to_account_id = ta.id
+                 WHERE t.type = 'TRANSFER' AND a.currency <> ta.currency
+                   AND (?1 IS NULL OR t.date >= ?1) AND (?2 IS NULL OR t.id = ?2)",
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+        let rows = stmt
+            .query_map(params![since, transaction_id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+            })
+            .map_err(|e| format!("Execution error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?;
+        rows
+    };
+
+    let mut converter = RateConverter::to_primary(conn)?;
+    for (id, date, currency, amount, to_currency, to_amount) in transfers {
+        let amount = Money::new(amount, currency_decimals(&currency));
+        let to_amount = match to_amount {
+            Some(minor) => Money::new(minor, currency_decimals(&to_currency)),
+            None => amount.rescale(currency_decimals(&to_currency)),
+        };
+        let sent = converter.convert(amount, &currency, &date);
+        let received = converter.convert(to_amount, &to_currency, &date);
+        let (Some(sent), Some(received)) = (sent, received) else {
+            continue;
+        };
+
+        conn.execute(
+            "INSERT INTO fx_postings (transaction_id, date, currency, source_value, destination_value, gain)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![id, date, converter.target, sent, received, (received - sent).rescale(converter.decimals)],
+        )
+        .map_err(|e| format!("Failed to record FX posting: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// FX gains and losses on cross-currency transfers within a period, in the
+/// primary currency. Transfers that could not be valued are reported in
+/// `missing_rates`.
+#[tauri::command(async)]
+pub fn get_fx_gains(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<FxGainReport, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.read()?;
+
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let target_decimals = converter.decimals;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT t.id, t.date, t.account_id, a.name, a.currency, t.amount,
+                    t.to_account_id, ta.name, ta.currency, t.to_amount,
+                    fx.source_value, fx.destination_value, fx.gain
+             FROM transactions t
+             INNER JOIN accounts a ON t.account_id = a.id
+             INNER JOIN accounts ta ON t.to_account_id = ta.id
+             LEFT JOIN fx_postings fx ON fx.transaction_id = t.id
+             WHERE t.type = 'TRANSFER' AND a.currency <> ta.currency
+               AND t.date >= ?1 AND t.date <= ?2
+             ORDER BY t.date, t.id",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let rows: Vec<(FxPosting, bool)> = stmt
+        .query_map(params![start_date, end_date], |row| {
+            let currency: String = row.get(4)?;
+            let to_currency: String = row.get(8)?;
+            let amount = Money::new(row.get(5)?, currency_decimals(&currency));
+            let to_amount = match row.get::<_, Option<i64>>(9)? {
+                Some(minor) => Money::new(minor, currency_decimals(&to_currency)),
+                None => amount.rescale(currency_decimals(&to_currency)),
+            };
+            let source_value: Option<i64> = row.get(10)?;
+            Ok((
+                FxPosting {
+                    transaction_id: row.get(0)?,
+                    date: row.get(1)?,
+                    account_id: row.get(2)?,
+                    account_name: row.get(3)?,
+                    amount,
+                    account_currency: currency,
+                    to_account_id: row.get(6)?,
+                    to_account_name: row.get(7)?,
+                    to_amount,
+                    to_account_currency: to_currency,
+                    implied_rate: to_amount.ratio(amount),
+                    source_value: Money::new(source_value.unwrap_or(0), target_decimals),
+                    destination_value: Money::new(row.get::<_, Option<i64>>(11)?.unwrap_or(0), target_decimals),
+                    gain: Money::new(row.get::<_, Option<i64>>(12)?.unwrap_or(0), target_decimals),
+                },
+                source_value.is_some(),
+            ))
+        })
+        .map_err(|e| format!("Execution error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    let mut postings = Vec::new();
+    for (posting, valued) in rows {
+        if valued {
+            postings.push(posting);
+        } else {
+            // Let the converter record whichever side has no rate
+            converter.convert(posting.amount, &posting.account_currency, &posting.date);
+            converter.convert(posting.to_amount, &posting.to_account_currency, &posting.date);
+        }
+    }
+
+    Ok(FxGainReport {
+        start_date,
+        end_date,
+        currency: converter.target.clone(),
+        total_gain: postings.iter().map(|p| p.gain).fold(converter.zero(), |total, gain| total + gain),
+        postings,
+        missing_rates: converter.missing_rates(),
+    })
+}
+
 // ======================== INTERNAL HELPERS ========================
 
-fn get_exchange_rate_internal(
+pub fn get_exchange_rate_internal(
     conn: &rusqlite::Connection,
     from: &str,
     to: &str,
@@ -427,26 +836,49 @@ fn get_exchange_rate_internal(
             effective_date: date.to_string(),
             created_at: String::new(),
             updated_at: String::new(),
+            via_currency: None,
         });
     }
 
-    let mut direct_stmt = conn.prepare(
-        r#"
-        SELECT id, from_currency, to_currency, rate, effective_date, created_at, updated_at
-        FROM exchange_rates
-        WHERE from_currency = ?1 AND to_currency = ?2 AND effective_date <= ?3
-        ORDER BY effective_date DESC
-        LIMIT 1
-        "#,
-    ).unwrap();
-
-    let direct = direct_stmt.query_row(params![from, to, date], row_to_exchange_rate).optional().unwrap_or(None);
-
-    if let Some(rate) = direct {
+    if let Some(rate) = stored_pair_rate(conn, &from, &to, date)? {
         return Ok(rate);
     }
 
-    let mut inverse_stmt = conn.prepare(
+    // Cross rate through the base currency, dated by its older leg
+    let base = rate_base_currency(conn);
+    if base != from && base != to {
+        if let (Some(first), Some(second)) = (
+            stored_pair_rate(conn, &from, &base, date)?,
+            stored_pair_rate(conn, &base, &to, date)?,
+        ) {
+            return Ok(ExchangeRate {
+                id: 0,
+                from_currency: from,
+                to_currency: to,
+                rate: first.rate * second.rate,
+                effective_date: first.effective_date.min(second.effective_date),
+                created_at: String::new(),
+                updated_at: String::new(),
+                via_currency: Some(base),
+            });
+        }
+    }
+
+    Err(format!(
+        "No exchange rate found for {} → {} on or before {}",
+        from, to, date
+    ))
+}
+
+/// Latest stored rate for the pair on or before `date`, trying the inverse
+/// pair when the direct one has never been entered.
+fn stored_pair_rate(
+    conn: &rusqlite::Connection,
+    from: &str,
+    to: &str,
+    date: &str,
+) -> Result<Option<ExchangeRate>, String> {
+    let mut stmt = conn.prepare(
         r#"
         SELECT id, from_currency, to_currency, rate, effective_date, created_at, updated_at
         FROM exchange_rates
@@ -456,33 +888,84 @@ fn get_exchange_rate_internal(
         "#,
     ).unwrap();
 
-    let inverse = inverse_stmt.query_row(params![to, from, date], row_to_exchange_rate).optional().unwrap_or(None);
+    let direct = stmt.query_row(params![from, to, date], row_to_exchange_rate).optional().unwrap_or(None);
 
-    if let Some(mut rate) = inverse {
-        if rate.rate <= 0.0 {
-            return Err("Stored rate is invalid (zero or negative)".to_string());
-        }
+    if let Some(rate) = direct {
+        return Ok(Some(rate));
+    }
 
-        rate.from_currency = from;
-        rate.to_currency = to;
-        rate.rate = 1.0 / rate.rate;
+    let inverse = stmt.query_row(params![to, from, date], row_to_exchange_rate).optional().unwrap_or(None);
 
-        return Ok(rate);
+    match inverse {
+        Some(rate) if rate.rate <= 0.0 => Err("Stored rate is invalid (zero or negative)".to_string()),
+        Some(mut rate) => {
+            rate.from_currency = from.to_string();
+            rate.to_currency = to.to_string();
+            rate.rate = 1.0 / rate.rate;
+            Ok(Some(rate))
+        }
+        None => Ok(None),
     }
+}
 
-    Err(format!(
-        "No exchange rate found for {} → {} on or before {}",
-        from, to, date
-    ))
+/// Currency cross rates are triangulated through. Defaults to the primary currency.
+pub fn rate_base_currency(conn: &rusqlite::Connection) -> String {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'rate_base_currency'",
+        [],
+        |row| row.get(0),
+    )
+    .optional()
+    .unwrap_or(None)
+    .unwrap_or_else(|| get_primary_currency_internal(conn).unwrap_or_else(|_| "LKR".to_string()))
 }
 
-fn get_primary_currency_internal(conn: &rusqlite::Connection) -> Result<String, String> {
+pub fn get_primary_currency_internal(conn: &rusqlite::Connection) -> Result<String, String> {
     let mut stmt = conn.prepare("SELECT value FROM app_settings WHERE key = 'primary_currency'").unwrap();
     let val: Option<String> = stmt.query_row([], |row| row.get(0)).optional().unwrap_or(None);
 
     Ok(val.unwrap_or_else(|| "LKR".to_string()))
 }
 
+/// Rescale every amount stored in primary-currency minor units.
+fn rescale_primary_amounts(
+    conn: &rusqlite::Connection,
+    old_decimals: u32,
+    new_decimals: u32,
+) -> Result<(), String> {
+    let expr = |column: &str| {
+        if new_decimals > old_decimals {
+            format!("{} * {}", column, 10_i64.pow(new_decimals - old_decimals))
+        } else {
+            format!("CAST(ROUND({} / {}.0) AS INTEGER)", column, 10_i64.pow(old_decimals - new_decimals))
+        }
+    };
+
+    let statements = [
+        format!(
+            "UPDATE budgets SET amount = {}, rollover_cap = {}",
+            expr("amount"), expr("rollover_cap")
+        ),
+        format!(
+            "UPDATE net_worth_snapshots SET total_assets = {}, total_liabilities = {}, net_worth = {}",
+            expr("total_assets"), expr("total_liabilities"), expr("net_worth")
+        ),
+        format!(
+            "UPDATE goal_contributions SET amount = {} WHERE goal_id IN (SELECT id FROM savings_goals WHERE linked_account_id IS NULL)",
+            expr("amount")
+        ),
+        format!("UPDATE savings_goals SET target_amount = {} WHERE linked_account_id IS NULL", expr("target_amount")),
+        format!("UPDATE transaction_templates SET amount = {} WHERE account_id IS NULL", expr("amount")),
+    ];
+
+    for sql in &statements {
+        conn.execute(sql, [])
+            .map_err(|e| format!("Failed to rescale amounts for new primary currency: {}", e))?;
+    }
+
+    Ok(())
+}
+
 // ======================== ADDITIONAL STRUCTS ========================
 
 #[derive(Debug, Serialize)]
@@ -490,8 +973,8 @@ pub struct ConvertedBalance {
     pub account_id: i64,
     pub account_name: String,
     pub original_currency: String,
-    pub original_balance: f64,
+    pub original_balance: Money,
     pub primary_currency: String,
-    pub converted_balance: f64,
+    pub converted_balance: Money,
     pub rate_used: f64, // 0.0 means no rate found
 }
@@ -1,8 +1,16 @@
 // File: src-tauri/src/commands/export.rs
-use crate::models::transactions::TransactionWithDetails;
+use crate::commands::currencies::{currency_decimals, primary_decimals, RateConverter};
+use crate::commands::goals::GOAL_CURRENCY_SQL;
+use crate::commands::reconciliation::TRANSACTION_STATUS_SQL;
+use crate::commands::saved_filters::saved_filter_condition;
+use crate::commands::transactions::{load_splits_for_transactions, page_sql, received_amount};
+use crate::models::money::Money;
+use crate::models::transactions::{TransactionCursor, TransactionPage, TransactionWithDetails};
 use crate::AppState;
 use rust_xlsxwriter::{Color, Format, Workbook};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufWriter, Write};
 use tauri::State;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -12,21 +20,37 @@ pub struct ExportFilter {
     pub transaction_type: Option<String>,
     pub account_id: Option<i64>,
     pub category_id: Option<i64>,
+    pub saved_filter_id: Option<i64>, // Also limits the export to a saved filter's transactions
     pub columns: Option<Vec<String>>,
     pub include_pie_chart: Option<bool>,
     pub include_histogram: Option<bool>,
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn export_transactions_csv(
     state: State<'_, AppState>,
     filter: Option<ExportFilter>,
 ) -> Result<String, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
+    export_transactions_csv_internal(&conn, filter)
+}
 
-    let transactions = get_export_transactions(&conn, filter.clone())?;
+pub fn export_transactions_csv_internal(
+    conn: &rusqlite::Connection,
+    filter: Option<ExportFilter>,
+) -> Result<String, String> {
+    let mut csv = Vec::new();
+    write_transactions_csv(conn, filter.as_ref(), &mut csv)?;
+    String::from_utf8(csv).map_err(|e| format!("Invalid CSV output: {}", e))
+}
 
+/// Write the filtered transactions as CSV, one batch at a time
+fn write_transactions_csv<W: Write>(
+    conn: &rusqlite::Connection,
+    filter: Option<&ExportFilter>,
+    out: &mut W,
+) -> Result<(), String> {
     let default_cols = vec![
         "Date".to_string(),
         "Type".to_string(),
@@ -36,47 +60,132 @@ pub fn export_transactions_csv(
         "Amount".to_string(),
         "Memo".to_string(),
     ];
-    let cols = filter.as_ref().and_then(|f| f.columns.clone()).unwrap_or(default_cols);
+    let cols = filter.and_then(|f| f.columns.clone()).unwrap_or(default_cols);
+    let write_error = |e: std::io::Error| format!("Failed to write CSV: {}", e);
 
     // CSV Header
-    let mut csv = cols.join(",") + "\n";
-
-    // CSV Rows
-    for txn in transactions {
-        let mut row_vals = Vec::new();
-        for col in &cols {
-            let val = match col.as_str() {
-                "Date" => txn.transaction.date.clone(),
-                "Type" => txn.transaction.transaction_type.clone(),
-                "Account" => txn.account_name.clone(),
-                "To Account" => txn.to_account_name.clone().unwrap_or_default(),
-                "Category" => txn.category_name.clone().unwrap_or_default(),
-                "Amount" => format!("{:.2}", txn.transaction.amount),
-                "Memo" => txn.transaction.memo.clone().unwrap_or_default().replace('\"', "\"\""),
-                _ => String::new(),
-            };
-            row_vals.push(format!("\"{}\"", val));
+    writeln!(out, "{}", cols.join(",")).map_err(write_error)?;
+
+    // CSV Rows (split transactions produce one row per category line)
+    for_each_export_transaction(conn, filter, None, |txn| {
+        for (category, amount, memo) in category_lines(&txn) {
+            let mut row_vals = Vec::new();
+            for col in &cols {
+                let val = match col.as_str() {
+                    "Date" => txn.transaction.date.clone(),
+                    "Type" => txn.transaction.transaction_type.clone(),
+                    "Account" => txn.account_name.clone(),
+                    "To Account" => txn.to_account_name.clone().unwrap_or_default(),
+                    "Category" => category.clone(),
+                    "Payee" => txn.payee_name.clone().unwrap_or_default().replace('\"', "\"\""),
+                    "Amount" => amount.to_string(),
+                    "Memo" => memo.replace('\"', "\"\""),
+                    _ => String::new(),
+                };
+                row_vals.push(format!("\"{}\"", val));
+            }
+            writeln!(out, "{}", row_vals.join(",")).map_err(write_error)?;
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command(async)]
+pub fn export_transactions_qif(
+    state: State<'_, AppState>,
+    filter: Option<ExportFilter>,
+) -> Result<String, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.read()?;
+    export_transactions_qif_internal(&conn, filter)
+}
+
+pub fn export_transactions_qif_internal(
+    conn: &rusqlite::Connection,
+    filter: Option<ExportFilter>,
+) -> Result<String, String> {
+    let accounts = get_qif_accounts(conn)?;
+    let category_paths = get_category_paths(conn)?;
+    let path_of = |id: Option<i64>| id.and_then(|id| category_paths.get(&id).cloned()).unwrap_or_default();
+
+    // account id -> QIF records, in account order. Transactions are visited
+    // newest first; each account's records are reversed at the end.
+    let mut records: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+
+    for_each_export_transaction(conn, filter.as_ref(), None, |txn| {
+        let t = &txn.transaction;
+        let date = qif_date(&t.date);
+        let memo = t.memo.clone().unwrap_or_default().replace(['\r', '\n'], " ");
+
+        if t.transaction_type == "TRANSFER" {
+            let Some(to_id) = t.to_account_id else { return Ok(()) };
+            let to_name = txn.to_account_name.clone().unwrap_or_default();
+            let to_decimals = accounts.get(&to_id).map(|a| a.2).unwrap_or(t.amount.decimals());
+            let to_amount = t.to_amount.unwrap_or_else(|| t.amount.rescale(to_decimals));
+
+            records.entry(t.account_id).or_default().push(qif_record(
+                &date, -t.amount, &memo, &format!("[{}]", to_name), &[],
+            ));
+            records.entry(to_id).or_default().push(qif_record(
+                &date, to_amount, &memo, &format!("[{}]", txn.account_name), &[],
+            ));
+            return Ok(());
+        }
+
+        let signed = if t.transaction_type == "EXPENSE" { -t.amount } else { t.amount };
+        let splits: Vec<(String, Money, String)> = txn
+            .splits
+            .iter()
+            .map(|s| {
+                let amount = if t.transaction_type == "EXPENSE" { -s.amount } else { s.amount };
+                (path_of(Some(s.category_id)), amount, s.memo.clone().unwrap_or_default())
+            })
+            .collect();
+
+        records.entry(t.account_id).or_default().push(qif_record(
+            &date, signed, &memo, &path_of(t.category_id), &splits,
+        ));
+        Ok(())
+    })?;
+
+    let mut qif = String::new();
+    for (account_id, mut entries) in records {
+        entries.reverse(); // QIF readers expect oldest first
+        let (name, qif_type, _) = accounts
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_else(|| (String::new(), "Bank", 2));
+        qif.push_str(&format!("!Account\nN{}\nT{}\n^\n!Type:{}\n", name, qif_type, qif_type));
+        for entry in entries {
+            qif.push_str(&entry);
         }
-        csv.push_str(&row_vals.join(","));
-        csv.push('\n');
     }
 
-    Ok(csv)
+    Ok(qif)
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn export_transactions_excel(
     state: State<'_, AppState>,
     filter: Option<ExportFilter>,
 ) -> Result<Vec<u8>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
-    let transactions = get_export_transactions(&conn, filter.clone())?;
+    let mut workbook = build_transactions_workbook(&conn, filter.as_ref())?;
+    let buf = workbook.save_to_buffer().map_err(|e| e.to_string())?;
+    Ok(buf)
+}
 
+/// Lay out the Excel report. Rows are flushed to disk as they are written,
+/// so each section streams its transactions instead of collecting them first.
+fn build_transactions_workbook(
+    conn: &rusqlite::Connection,
+    filter: Option<&ExportFilter>,
+) -> Result<Workbook, String> {
     let mut workbook = Workbook::new();
     let worksheet = workbook
-        .add_worksheet()
+        .add_worksheet_with_constant_memory()
         .set_name("Report")
         .map_err(|e| e.to_string())?;
 
@@ -164,27 +273,41 @@ pub fn export_transactions_excel(
         .map_err(|e| e.to_string())?;
 
     let mut current_row = 4;
+    // Chart totals are in the primary currency; accounts may hold others.
+    let account_currencies = get_account_currencies(conn)?;
+    let mut converter = RateConverter::to_primary(conn)?;
+    let mut type_totals: BTreeMap<String, Money> = BTreeMap::new();
 
     macro_rules! write_section {
-        ($title:expr, $txns:expr, $h_fmt:expr, $c_fmt:expr, $bg_dark:expr) => {
-            if !$txns.is_empty() {
-                worksheet
-                    .merge_range(current_row, 0, current_row, 6, $title, $h_fmt)
-                    .map_err(|e| e.to_string())?;
-                current_row += 1;
-
-                let headers = [
-                    "Date", "Type", "Account", "To Account", "Category", "Amount", "Memo",
-                ];
-                for (i, h) in headers.iter().enumerate() {
+        ($title:expr, $txn_type:expr, $h_fmt:expr, $c_fmt:expr, $bg_dark:expr) => {
+            let mut line_count = 0;
+            for_each_export_transaction(conn, filter, Some($txn_type), |txn| {
+                if line_count == 0 {
                     worksheet
-                        .write_string_with_format(current_row, i as u16, *h, $c_fmt)
+                        .merge_range(current_row, 0, current_row, 6, $title, $h_fmt)
                         .map_err(|e| e.to_string())?;
+                    current_row += 1;
+
+                    let headers = [
+                        "Date", "Type", "Account", "To Account", "Category", "Amount", "Memo",
+                    ];
+                    for (i, h) in headers.iter().enumerate() {
+                        worksheet
+                            .write_string_with_format(current_row, i as u16, *h, $c_fmt)
+                            .map_err(|e| e.to_string())?;
+                    }
+                    current_row += 1;
+                }
+                let currency = account_currencies
+                    .get(&txn.transaction.account_id)
+                    .cloned()
+                    .unwrap_or_else(|| converter.target.clone());
+                if let Some(amount) = converter.convert(txn.transaction.amount, &currency, &txn.transaction.date) {
+                    *type_totals.entry($txn_type.to_string()).or_insert(converter.zero()) += amount;
                 }
-                current_row += 1;
 
-                for (i, txn) in $txns.iter().enumerate() {
-                    let is_dark = i % 2 == 1;
+                for (category, amount, memo) in category_lines(&txn) {
+                    let is_dark = line_count % 2 == 1;
                     let bg_color = if is_dark {
                         $bg_dark.clone()
                     } else {
@@ -219,72 +342,55 @@ pub fn export_transactions_excel(
                         .write_string_with_format(current_row, 3, to_account, &row_fmt)
                         .map_err(|e| e.to_string())?;
 
-                    let category = txn.category_name.as_deref().unwrap_or("");
                     worksheet
-                        .write_string_with_format(current_row, 4, category, &row_fmt)
+                        .write_string_with_format(current_row, 4, &category, &row_fmt)
                         .map_err(|e| e.to_string())?;
 
                     worksheet
-                        .write_number_with_format(current_row, 5, txn.transaction.amount, &num_fmt)
+                        .write_number_with_format(current_row, 5, amount.to_major(), &num_fmt)
                         .map_err(|e| e.to_string())?;
 
-                    let memo = txn.transaction.memo.as_deref().unwrap_or("");
                     worksheet
-                        .write_string_with_format(current_row, 6, memo, &row_fmt)
+                        .write_string_with_format(current_row, 6, &memo, &row_fmt)
                         .map_err(|e| e.to_string())?;
 
                     current_row += 1;
+                    line_count += 1;
                 }
+                Ok(())
+            })?;
+            if line_count > 0 {
                 current_row += 2; // Spacing after section
             }
         };
     }
 
-    let incomes: Vec<_> = transactions
-        .iter()
-        .filter(|t| t.transaction.transaction_type == "INCOME")
-        .collect();
-    let expenses: Vec<_> = transactions
-        .iter()
-        .filter(|t| t.transaction.transaction_type == "EXPENSE")
-        .collect();
-    let transfers: Vec<_> = transactions
-        .iter()
-        .filter(|t| t.transaction.transaction_type == "TRANSFER")
-        .collect();
-
     write_section!(
         "INCOME TRANSACTIONS",
-        incomes,
+        "INCOME",
         &income_header,
         &income_col_header,
         &c_inc_dark
     );
     write_section!(
         "EXPENSE TRANSACTIONS",
-        expenses,
+        "EXPENSE",
         &expense_header,
         &expense_col_header,
         &c_exp_dark
     );
     write_section!(
         "TRANSFER TRANSACTIONS",
-        transfers,
+        "TRANSFER",
         &transfer_header,
         &transfer_col_header,
         &c_tra_dark
     );
 
-    let include_pie = filter.as_ref().and_then(|f| f.include_pie_chart).unwrap_or(false);
-    let include_hist = filter.as_ref().and_then(|f| f.include_histogram).unwrap_or(false);
+    let include_pie = filter.and_then(|f| f.include_pie_chart).unwrap_or(false);
+    let include_hist = filter.and_then(|f| f.include_histogram).unwrap_or(false);
 
     if include_pie || include_hist {
-        let mut type_totals: std::collections::BTreeMap<String, f64> = std::collections::BTreeMap::new();
-        
-        for t in &transactions {
-            *type_totals.entry(t.transaction.transaction_type.clone()).or_insert(0.0) += t.transaction.amount;
-        }
-
         if include_pie && !type_totals.is_empty() {
             let data_start_row = current_row;
             worksheet.write_string(current_row, 0, "Type").unwrap();
@@ -293,7 +399,7 @@ pub fn export_transactions_excel(
             
             for (t_type, amt) in &type_totals {
                 worksheet.write_string(current_row, 0, t_type).unwrap();
-                worksheet.write_number(current_row, 1, *amt).unwrap();
+                worksheet.write_number(current_row, 1, amt.to_major()).unwrap();
                 current_row += 1;
             }
             
@@ -315,7 +421,7 @@ pub fn export_transactions_excel(
             
             for (t_type, amt) in &type_totals {
                 worksheet.write_string(current_row, 0, t_type).unwrap();
-                worksheet.write_number(current_row, 1, *amt).unwrap();
+                worksheet.write_number(current_row, 1, amt.to_major()).unwrap();
                 current_row += 1;
             }
             
@@ -332,90 +438,139 @@ pub fn export_transactions_excel(
 
     let _ = current_row; // Silence unused assignment warning for the last section
 
-    let buf = workbook.save_to_buffer().map_err(|e| e.to_string())?;
-    Ok(buf)
+    Ok(workbook)
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn export_transactions_json(
     state: State<'_, AppState>,
     filter: Option<ExportFilter>,
 ) -> Result<String, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
+
+    let mut json = Vec::new();
+    write_transactions_json(&conn, filter.as_ref(), &mut json)?;
+    String::from_utf8(json).map_err(|e| format!("Invalid JSON output: {}", e))
+}
 
-    let transactions = get_export_transactions(&conn, filter.clone())?;
+/// Write the filtered transactions with their tags, journal entries and photo
+/// metadata as a pretty-printed JSON array, one element at a time
+fn write_transactions_json<W: Write>(
+    conn: &rusqlite::Connection,
+    filter: Option<&ExportFilter>,
+    out: &mut W,
+) -> Result<(), String> {
+    let write_error = |e: std::io::Error| format!("Failed to write JSON: {}", e);
+    let mut first = true;
 
-    let mut enriched = Vec::new();
+    write!(out, "[").map_err(write_error)?;
 
-    for txn in transactions {
+    for_each_export_transaction(conn, filter, None, |txn| {
         // Fetch tags
-        let mut stmt = conn.prepare(
+        let mut stmt = conn.prepare_cached(
             "SELECT t.id, t.name, t.color, t.created_at
              FROM tags t
              INNER JOIN transaction_tags tt ON t.id = tt.tag_id
              WHERE tt.transaction_id = ?1"
-        ).unwrap();
+        ).map_err(|e| format!("Query error: {}", e))?;
         let tags: Vec<serde_json::Value> = stmt.query_map([txn.transaction.id], |row| {
             Ok(serde_json::json!({
                 "id": row.get::<_, i64>(0)?,
                 "name": row.get::<_, String>(1)?,
                 "color": row.get::<_, String>(2)?,
             }))
-        }).unwrap().filter_map(Result::ok).collect();
+        }).map_err(|e| format!("Execute error: {}", e))?.filter_map(Result::ok).collect();
 
         // Fetch journal entries
-        let mut stmt = conn.prepare(
-            "SELECT id, account_id, debit, credit, created_at 
-             FROM journal_entries WHERE transaction_id = ?1"
-        ).unwrap();
+        let mut stmt = conn.prepare_cached(
+            "SELECT je.id, je.account_id, je.debit, je.credit, je.created_at, a.currency
+             FROM journal_entries je
+             JOIN accounts a ON je.account_id = a.id
+             WHERE je.transaction_id = ?1"
+        ).map_err(|e| format!("Query error: {}", e))?;
         let journal_entries: Vec<serde_json::Value> = stmt.query_map([txn.transaction.id], |row| {
+            let decimals = currency_decimals(&row.get::<_, String>(5)?);
             Ok(serde_json::json!({
                 "id": row.get::<_, i64>(0)?,
                 "account_id": row.get::<_, i64>(1)?,
-                "debit": row.get::<_, f64>(2)?,
-                "credit": row.get::<_, f64>(3)?,
+                "debit": Money::new(row.get(2)?, decimals),
+                "credit": Money::new(row.get(3)?, decimals),
                 "created_at": row.get::<_, String>(4)?,
             }))
-        }).unwrap().filter_map(Result::ok).collect();
+        }).map_err(|e| format!("Execute error: {}", e))?.filter_map(Result::ok).collect();
 
         // Fetch photos metadata
-        let mut stmt = conn.prepare(
-            "SELECT id, file_path, file_size_bytes, mime_type, original_name, created_at
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, filename, created_at
              FROM transaction_photos WHERE transaction_id = ?1"
-        ).unwrap();
+        ).map_err(|e| format!("Query error: {}", e))?;
         let photos: Vec<serde_json::Value> = stmt.query_map([txn.transaction.id], |row| {
             Ok(serde_json::json!({
                 "id": row.get::<_, i64>(0)?,
-                "file_path": row.get::<_, String>(1)?,
-                "file_size_bytes": row.get::<_, i64>(2)?,
-                "mime_type": row.get::<_, String>(3)?,
-                "original_name": row.get::<_, String>(4)?,
-                "created_at": row.get::<_, String>(5)?,
+                "filename": row.get::<_, String>(1)?,
+                "created_at": row.get::<_, String>(2)?,
             }))
-        }).unwrap().filter_map(Result::ok).collect();
+        }).map_err(|e| format!("Execute error: {}", e))?.filter_map(Result::ok).collect();
 
-        enriched.push(serde_json::json!({
+        let element = serde_json::to_string_pretty(&serde_json::json!({
             "transaction": txn.transaction,
             "account_name": txn.account_name,
             "to_account_name": txn.to_account_name,
             "category_name": txn.category_name,
+            "payee_name": txn.payee_name,
+            "splits": txn.splits,
             "tags": tags,
             "journal_entries": journal_entries,
             "photos_metadata": photos,
-        }));
+        }))
+        .map_err(|e| format!("Failed to serialize: {}", e))?;
+
+        // Indent each element as it would sit inside the pretty-printed array
+        let separator = if first { "" } else { "," };
+        first = false;
+        write!(out, "{}\n  {}", separator, element.replace('\n', "\n  ")).map_err(write_error)
+    })?;
+
+    write!(out, "{}]", if first { "" } else { "\n" }).map_err(write_error)
+}
+
+/// Write a CSV, JSON or Excel export straight to `path` instead of returning its content
+#[tauri::command(async)]
+pub fn export_transactions_to_file(
+    state: State<'_, AppState>,
+    filter: Option<ExportFilter>,
+    format: String,
+    path: String,
+) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.read()?;
+
+    if matches!(format.as_str(), "excel" | "xlsx") {
+        let mut workbook = build_transactions_workbook(&conn, filter.as_ref())?;
+        return workbook
+            .save(&path)
+            .map_err(|e| format!("Failed to write {}: {}", path, e));
     }
 
-    let result = serde_json::to_string_pretty(&enriched)
-        .map_err(|e| format!("Failed to serialize: {}", e))?;
+    if !matches!(format.as_str(), "csv" | "json") {
+        return Err(format!("Unsupported export format: {}", format));
+    }
 
-    Ok(result)
+    let file = std::fs::File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut out = BufWriter::new(file);
+    if format == "csv" {
+        write_transactions_csv(&conn, filter.as_ref(), &mut out)?;
+    } else {
+        write_transactions_json(&conn, filter.as_ref(), &mut out)?;
+    }
+    out.flush().map_err(|e| format!("Failed to write {}: {}", path, e))
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn export_full_backup(state: State<'_, AppState>) -> Result<String, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
     pub_export_full_backup_internal(&conn)
 }
@@ -424,7 +579,12 @@ pub fn export_full_backup(state: State<'_, AppState>) -> Result<String, String>
 pub fn pub_export_full_backup_internal(conn: &rusqlite::Connection) -> Result<String, String> {
     let accounts = get_all_accounts(conn)?;
     let categories = get_all_categories(conn)?;
-    let transactions = get_export_transactions(conn, None)?;
+    let mut transactions = Vec::new();
+    for_each_export_transaction(conn, None, None, |txn| {
+        let value = serde_json::to_value(&txn).map_err(|e| format!("Failed to serialize: {}", e))?;
+        transactions.push(value);
+        Ok(())
+    })?;
     let budgets = get_all_budgets(conn)?;
 
     let mut stmt = conn.prepare("SELECT id, name, color, created_at FROM tags ORDER BY id")
@@ -455,16 +615,38 @@ pub fn pub_export_full_backup_internal(conn: &rusqlite::Connection) -> Result<St
         .filter_map(Result::ok)
         .collect();
 
+    // Amounts in the backup are written in major units, as they always have been
     let mut stmt = conn.prepare(
-        "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at 
-         FROM savings_goals ORDER BY id"
+        "SELECT s.transaction_id, s.category_id, s.amount, s.memo, a.currency
+         FROM transaction_splits s
+         JOIN transactions t ON s.transaction_id = t.id
+         JOIN accounts a ON t.account_id = a.id
+         ORDER BY s.id"
     ).map_err(|e| format!("Query error: {}", e))?;
+    let transaction_splits: Vec<serde_json::Value> = stmt
+        .query_map([], |row| {
+            Ok(serde_json::json!({
+                "transaction_id": row.get::<_, i64>(0)?,
+                "category_id": row.get::<_, i64>(1)?,
+                "amount": Money::new(row.get(2)?, currency_decimals(&row.get::<_, String>(4)?)),
+                "memo": row.get::<_, Option<String>>(3)?
+            }))
+        })
+        .unwrap()
+        .filter_map(Result::ok)
+        .collect();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at, {}
+         FROM savings_goals ORDER BY id",
+        GOAL_CURRENCY_SQL
+    )).map_err(|e| format!("Query error: {}", e))?;
     let savings_goals: Vec<serde_json::Value> = stmt
         .query_map([], |row| {
             Ok(serde_json::json!({
                 "id": row.get::<_, i64>(0)?,
                 "name": row.get::<_, String>(1)?,
-                "target_amount": row.get::<_, f64>(2)?,
+                "target_amount": Money::new(row.get(2)?, currency_decimals(&row.get::<_, String>(10)?)),
                 "target_date": row.get::<_, Option<String>>(3)?,
                 "linked_account_id": row.get::<_, Option<i64>>(4)?,
                 "color": row.get::<_, String>(5)?,
@@ -478,16 +660,19 @@ pub fn pub_export_full_backup_internal(conn: &rusqlite::Connection) -> Result<St
         .filter_map(Result::ok)
         .collect();
 
-    let mut stmt = conn.prepare(
-        "SELECT id, goal_id, amount, contribution_date, note, created_at 
-         FROM goal_contributions ORDER BY id"
-    ).map_err(|e| format!("Query error: {}", e))?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT gc.id, gc.goal_id, gc.amount, gc.contribution_date, gc.note, gc.created_at, {}
+         FROM goal_contributions gc
+         JOIN savings_goals ON gc.goal_id = savings_goals.id
+         ORDER BY gc.id",
+        GOAL_CURRENCY_SQL
+    )).map_err(|e| format!("Query error: {}", e))?;
     let goal_contributions: Vec<serde_json::Value> = stmt
         .query_map([], |row| {
             Ok(serde_json::json!({
                 "id": row.get::<_, i64>(0)?,
                 "goal_id": row.get::<_, i64>(1)?,
-                "amount": row.get::<_, f64>(2)?,
+                "amount": Money::new(row.get(2)?, currency_decimals(&row.get::<_, String>(6)?)),
                 "contribution_date": row.get::<_, String>(3)?,
                 "note": row.get::<_, Option<String>>(4)?,
                 "created_at": row.get::<_, String>(5)?
@@ -507,6 +692,7 @@ pub fn pub_export_full_backup_internal(conn: &rusqlite::Connection) -> Result<St
             "budgets": budgets,
             "tags": tags,
             "transaction_tags": transaction_tags,
+            "transaction_splits": transaction_splits,
             "savings_goals": savings_goals,
             "goal_contributions": goal_contributions
         }
@@ -518,80 +704,266 @@ pub fn pub_export_full_backup_internal(conn: &rusqlite::Connection) -> Result<St
     Ok(result)
 }
 
-// Helper function to get transactions with details
-fn get_export_transactions(
+/// Transactions loaded per query while streaming an export
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+/// Visit the filtered transactions newest first, optionally of one type only.
+/// They are read in keyset batches so an export never holds the whole table.
+fn for_each_export_transaction<F>(
     conn: &rusqlite::Connection,
-    filter: Option<ExportFilter>,
-) -> Result<Vec<TransactionWithDetails>, String> {
-    let mut query = String::from(
+    filter: Option<&ExportFilter>,
+    transaction_type: Option<&str>,
+    mut visit: F,
+) -> Result<(), String>
+where
+    F: FnMut(TransactionWithDetails) -> Result<(), String>,
+{
+    let mut base_query = format!(
         "SELECT 
             t.id, t.date, t.type, t.amount, t.account_id, t.to_account_id, 
             t.category_id, t.memo, t.photo_path, t.created_at,
             a.name as account_name,
             ta.name as to_account_name,
             c.name as category_name,
-            (SELECT COUNT(*) FROM transaction_photos tp WHERE tp.transaction_id = t.id) as photo_count
+            (SELECT COUNT(*) FROM transaction_photos tp WHERE tp.transaction_id = t.id) as photo_count,
+            a.currency,
+            {},
+            t.to_amount,
+            ta.currency,
+            t.payee_id,
+            p.name as payee_name
          FROM transactions t
          INNER JOIN accounts a ON t.account_id = a.id
          LEFT JOIN accounts ta ON t.to_account_id = ta.id
          LEFT JOIN categories c ON t.category_id = c.id
+         LEFT JOIN payees p ON t.payee_id = p.id
          WHERE 1=1",
+        TRANSACTION_STATUS_SQL
     );
 
-    if let Some(ref f) = filter {
+    if let Some(f) = filter {
         if let Some(ref start) = f.start_date {
-            query.push_str(&format!(" AND t.date >= '{}'", start));
+            base_query.push_str(&format!(" AND t.date >= '{}'", start));
         }
         if let Some(ref end) = f.end_date {
-            query.push_str(&format!(" AND t.date <= '{}'", end));
+            base_query.push_str(&format!(" AND t.date <= '{}'", end));
         }
         if let Some(ref t_type) = f.transaction_type {
-            query.push_str(&format!(" AND t.type = '{}'", t_type));
+            base_query.push_str(&format!(" AND t.type = '{}'", t_type));
         }
         if let Some(acc_id) = f.account_id {
-            query.push_str(&format!(
+            base_query.push_str(&format!(
                 " AND (t.account_id = {} OR t.to_account_id = {})",
                 acc_id, acc_id
             ));
         }
         if let Some(cat_id) = f.category_id {
-            query.push_str(&format!(" AND t.category_id = {}", cat_id));
+            base_query.push_str(&format!(
+                " AND t.id IN (SELECT id FROM transaction_category_lines WHERE category_id = {})",
+                cat_id
+            ));
+        }
+        base_query.push_str(&saved_filter_condition(conn, f.saved_filter_id, true)?);
+    }
+    if let Some(t_type) = transaction_type {
+        base_query.push_str(&format!(" AND t.type = '{}'", t_type));
+    }
+
+    let mut page = TransactionPage {
+        after: None,
+        limit: Some(EXPORT_BATCH_SIZE),
+    };
+
+    loop {
+        let (page_condition, limit, page_values) = page_sql(Some(&page))?;
+        let query = format!("{}{} ORDER BY t.date DESC, t.id DESC{}", base_query, page_condition, limit);
+
+        let mut stmt = conn
+            .prepare(&query)
+            .map_err(|e| format!("Query error: {}", e))?;
+
+        let mut transactions: Vec<TransactionWithDetails> = stmt
+            .query_map(rusqlite::params_from_iter(page_values), |row| {
+                let amount = Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(14)?));
+                Ok(TransactionWithDetails {
+                    transaction: crate::models::transactions::Transaction {
+                        id: row.get(0)?,
+                        date: row.get(1)?,
+                        transaction_type: row.get(2)?,
+                        amount,
+                        account_id: row.get(4)?,
+                        to_account_id: row.get(5)?,
+                        to_amount: received_amount(amount, row.get(16)?, row.get(17)?),
+                        category_id: row.get(6)?,
+                        payee_id: row.get(18)?,
+                        memo: row.get(7)?,
+                        photo_path: row.get(8)?,
+                        created_at: row.get(9)?,
+                        status: row.get(15)?,
+                    },
+                    account_name: row.get(10)?,
+                    to_account_name: row.get(11)?,
+                    category_name: row.get(12)?,
+                    payee_name: row.get(19)?,
+                    snippet: None,
+                    photo_count: row.get(13)?,
+                    tags: Vec::new(),
+                    splits: Vec::new(),
+                })
+            })
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?;
+
+        let ids: Vec<i64> = transactions.iter().map(|t| t.transaction.id).collect();
+        let mut splits = load_splits_for_transactions(conn, &ids)?;
+        for txn in &mut transactions {
+            txn.splits = splits.remove(&txn.transaction.id).unwrap_or_default();
+        }
+
+        let is_last_batch = (transactions.len() as i64) < EXPORT_BATCH_SIZE;
+        page.after = transactions.last().map(|t| TransactionCursor {
+            date: t.transaction.date.clone(),
+            id: t.transaction.id,
+        });
+
+        for txn in transactions {
+            visit(txn)?;
+        }
+
+        if is_last_batch {
+            return Ok(());
         }
     }
+}
+
+// Helper to expand a transaction into (category, amount, memo) lines.
+// Unsplit transactions yield a single line; split transactions yield one per split.
+fn category_lines(txn: &TransactionWithDetails) -> Vec<(String, Money, String)> {
+    let txn_memo = txn.transaction.memo.clone().unwrap_or_default();
+
+    if txn.splits.is_empty() {
+        return vec![(
+            txn.category_name.clone().unwrap_or_default(),
+            txn.transaction.amount,
+            txn_memo,
+        )];
+    }
 
-    query.push_str(" ORDER BY t.date DESC, t.created_at DESC");
+    txn.splits
+        .iter()
+        .map(|s| {
+            (
+                s.category_name.clone().unwrap_or_default(),
+                s.amount,
+                s.memo.clone().unwrap_or_else(|| txn_memo.clone()),
+            )
+        })
+        .collect()
+}
 
+// Helper to render one QIF record (D/T/M/L lines, then S/$/E per split)
+fn qif_record(date: &str, amount: Money, memo: &str, category: &str, splits: &[(String, Money, String)]) -> String {
+    let mut record = format!("D{}\nT{}\n", date, amount);
+    if !memo.is_empty() {
+        record.push_str(&format!("M{}\n", memo));
+    }
+    if !category.is_empty() {
+        record.push_str(&format!("L{}\n", category));
+    }
+    for (split_category, split_amount, split_memo) in splits {
+        record.push_str(&format!("S{}\n", split_category));
+        if !split_memo.is_empty() {
+            record.push_str(&format!("E{}\n", split_memo.replace(['\r', '\n'], " ")));
+        }
+        record.push_str(&format!("${}\n", split_amount));
+    }
+    record.push_str("^\n");
+    record
+}
+
+fn qif_date(date: &str) -> String {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.format("%m/%d/%Y").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+// Helper to map accounts to (name, QIF account type, decimals)
+fn get_qif_accounts(
+    conn: &rusqlite::Connection,
+) -> Result<HashMap<i64, (String, &'static str, u32)>, String> {
     let mut stmt = conn
-        .prepare(&query)
+        .prepare(
+            "SELECT a.id, a.name, a.currency, g.name, g.type
+             FROM accounts a
+             INNER JOIN account_groups g ON a.group_id = g.id",
+        )
         .map_err(|e| format!("Query error: {}", e))?;
 
-    let transactions = stmt
+    let accounts = stmt
         .query_map([], |row| {
-            Ok(TransactionWithDetails {
-                transaction: crate::models::transactions::Transaction {
-                    id: row.get(0)?,
-                    date: row.get(1)?,
-                    transaction_type: row.get(2)?,
-                    amount: row.get(3)?,
-                    account_id: row.get(4)?,
-                    to_account_id: row.get(5)?,
-                    category_id: row.get(6)?,
-                    memo: row.get(7)?,
-                    photo_path: row.get(8)?,
-                    created_at: row.get(9)?,
-                },
-                account_name: row.get(10)?,
-                to_account_name: row.get(11)?,
-                category_name: row.get(12)?,
-                photo_count: row.get(13)?,
-                tags: Vec::new(),
-            })
+            let currency: String = row.get(2)?;
+            let group_name: String = row.get(3)?;
+            let group_type: String = row.get(4)?;
+            let qif_type = if group_type == "LIABILITY" {
+                "CCard"
+            } else if group_name.eq_ignore_ascii_case("Cash") {
+                "Cash"
+            } else {
+                "Bank"
+            };
+            Ok((row.get::<_, i64>(0)?, (row.get::<_, String>(1)?, qif_type, currency_decimals(&currency))))
         })
         .map_err(|e| format!("Execute error: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
+        .collect::<Result<HashMap<_, _>, _>>()
         .map_err(|e| format!("Read error: {}", e))?;
 
-    Ok(transactions)
+    Ok(accounts)
+}
+
+// Helper to map accounts to their currency code
+fn get_account_currencies(conn: &rusqlite::Connection) -> Result<HashMap<i64, String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT id, currency FROM accounts")
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let currencies = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    Ok(currencies)
+}
+
+// Helper to build "Parent:Child" paths for every category
+fn get_category_paths(conn: &rusqlite::Connection) -> Result<HashMap<i64, String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT id, parent_id, name FROM categories")
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let categories: HashMap<i64, (Option<i64>, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    let paths = categories
+        .iter()
+        .map(|(id, (parent_id, name))| {
+            let mut parts = vec![name.clone()];
+            let mut next = *parent_id;
+            // Depth guard in case of a parent cycle
+            while let Some((parent, parent_name)) = next.and_then(|p| categories.get(&p)).filter(|_| parts.len() < 16) {
+                parts.push(parent_name.clone());
+                next = *parent;
+            }
+            parts.reverse();
+            (*id, parts.join(":"))
+        })
+        .collect();
+
+    Ok(paths)
 }
 
 // Helper to get all accounts
@@ -602,11 +974,12 @@ fn get_all_accounts(conn: &rusqlite::Connection) -> Result<serde_json::Value, St
 
     let accounts: Vec<serde_json::Value> = stmt
         .query_map([], |row| {
+            let currency: String = row.get(4)?;
             Ok(serde_json::json!({
                 "id": row.get::<_, i64>(0)?,
                 "group_id": row.get::<_, i64>(1)?,
                 "name": row.get::<_, String>(2)?,
-                "initial_balance": row.get::<_, f64>(3)?,
+                "initial_balance": Money::new(row.get(3)?, currency_decimals(&currency)),
                 "currency": row.get::<_, String>(4)?,
                 "created_at": row.get::<_, String>(5)?
             }))
@@ -643,17 +1016,24 @@ fn get_all_categories(conn: &rusqlite::Connection) -> Result<serde_json::Value,
 // Helper to get all budgets
 fn get_all_budgets(conn: &rusqlite::Connection) -> Result<serde_json::Value, String> {
     let mut stmt = conn
-        .prepare("SELECT id, category_id, amount, period, start_date FROM budgets ORDER BY id")
+        .prepare(
+            "SELECT id, category_id, amount, period, start_date, rollover_enabled, rollover_cap, end_date
+             FROM budgets ORDER BY id",
+        )
         .map_err(|e| format!("Query error: {}", e))?;
 
+    let decimals = primary_decimals(conn);
     let budgets: Vec<serde_json::Value> = stmt
         .query_map([], |row| {
             Ok(serde_json::json!({
                 "id": row.get::<_, i64>(0)?,
                 "category_id": row.get::<_, i64>(1)?,
-                "amount": row.get::<_, f64>(2)?,
+                "amount": Money::new(row.get(2)?, decimals),
                 "period": row.get::<_, String>(3)?,
-                "start_date": row.get::<_, String>(4)?
+                "start_date": row.get::<_, String>(4)?,
+                "rollover_enabled": row.get::<_, bool>(5)?,
+                "rollover_cap": row.get::<_, Option<i64>>(6)?.map(|cap| Money::new(cap, decimals)),
+                "end_date": row.get::<_, Option<String>>(7)?
             }))
         })
         .map_err(|e| format!("Execute error: {}", e))?
@@ -1,5 +1,8 @@
 // File: src-tauri/src/commands/goals.rs
+use crate::commands::audit::{log_change, snapshot};
+use crate::commands::currencies::{account_decimals, currency_decimals, primary_decimals};
 use crate::models::goal::*;
+use crate::models::money::Money;
 use crate::AppState;
 use chrono::{Duration, NaiveDate};
 use rusqlite::params;
@@ -14,21 +17,23 @@ pub fn create_goal(
 ) -> Result<SavingsGoal, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    create_goal_internal(&conn, input)
+}
 
-    if input.target_amount <= 0.0 {
-        return Err("Target amount must be greater than zero".to_string());
-    }
+pub fn create_goal_internal(
+    conn: &rusqlite::Connection,
+    input: CreateGoalInput,
+) -> Result<SavingsGoal, String> {
+    // Goals are held in the linked account's currency, or the primary currency
+    let decimals = match input.linked_account_id {
+        Some(account_id) => account_decimals(conn, account_id)
+            .map_err(|_| "Linked account does not exist".to_string())?,
+        None => primary_decimals(conn),
+    };
 
-    // Validate linked account if provided
-    if let Some(account_id) = input.linked_account_id {
-        let exists: bool = conn.query_row(
-            "SELECT COUNT(id) FROM accounts WHERE id = ?1",
-            params![account_id],
-            |row| row.get::<_, i64>(0),
-        ).unwrap_or(0) > 0;
-        if !exists {
-            return Err("Linked account does not exist".to_string());
-        }
+    let target_amount = Money::from_major(input.target_amount, decimals);
+    if !target_amount.is_positive() {
+        return Err("Target amount must be greater than zero".to_string());
     }
 
     let color = input.color.unwrap_or_else(|| "#6B7280".to_string());
@@ -37,11 +42,12 @@ pub fn create_goal(
     conn.execute(
         "INSERT INTO savings_goals (name, target_amount, target_date, linked_account_id, color, icon)
          VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        params![input.name, input.target_amount, input.target_date, input.linked_account_id, color, icon],
+        params![input.name, target_amount, input.target_date, input.linked_account_id, color, icon],
     ).map_err(|e| format!("Failed to create goal: {}", e))?;
 
     let goal_id = conn.last_insert_rowid();
-    get_goal_by_id(&conn, goal_id)
+    log_change(conn, "GOAL", goal_id, "CREATE", None)?;
+    get_goal_by_id(conn, goal_id)
 }
 
 #[tauri::command]
@@ -54,16 +60,22 @@ pub fn get_goals(
 
     let goals: Vec<SavingsGoal> = if let Some(status) = &status_filter {
         let mut stmt = conn.prepare(
-            "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at
-             FROM savings_goals WHERE status = ?1 ORDER BY created_at DESC",
+            &format!(
+                "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at, {}
+                 FROM savings_goals WHERE status = ?1 ORDER BY created_at DESC",
+                GOAL_CURRENCY_SQL
+            ),
         ).unwrap();
         stmt.query_map(params![status], row_to_goal).unwrap().filter_map(Result::ok).collect()
     } else {
         let mut stmt = conn.prepare(
-            "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at
-             FROM savings_goals ORDER BY
-             CASE status WHEN 'ACTIVE' THEN 1 WHEN 'PAUSED' THEN 2 WHEN 'COMPLETED' THEN 3 WHEN 'ARCHIVED' THEN 4 END,
-             created_at DESC",
+            &format!(
+                "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at, {}
+                 FROM savings_goals ORDER BY
+                 CASE status WHEN 'ACTIVE' THEN 1 WHEN 'PAUSED' THEN 2 WHEN 'COMPLETED' THEN 3 WHEN 'ARCHIVED' THEN 4 END,
+                 created_at DESC",
+                GOAL_CURRENCY_SQL
+            ),
         ).unwrap();
         stmt.query_map([], row_to_goal).unwrap().filter_map(Result::ok).collect()
     };
@@ -76,13 +88,15 @@ pub fn get_goals(
                 "SELECT a.name, a.initial_balance +
                         COALESCE((SELECT SUM(je.debit - je.credit) FROM journal_entries je
                                   INNER JOIN transactions t ON je.transaction_id = t.id
-                                  WHERE je.account_id = a.id), 0.0) as balance
+                                  WHERE je.account_id = a.id), 0) as balance,
+                        a.currency
                  FROM accounts a WHERE a.id = ?1",
             ).unwrap();
             let row = stmt.query_row(params![account_id], |row| {
+                let currency: String = row.get(2)?;
                 Ok((
                     row.get::<_, String>(0)?,
-                    row.get::<_, f64>(1)?,
+                    Money::new(row.get(1)?, currency_decimals(&currency)),
                 ))
             });
             row.ok().unzip()
@@ -119,9 +133,15 @@ pub fn update_goal(
 ) -> Result<SavingsGoal, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    update_goal_internal(&conn, input)
+}
 
+pub fn update_goal_internal(
+    conn: &rusqlite::Connection,
+    input: UpdateGoalInput,
+) -> Result<SavingsGoal, String> {
     // Verify goal exists
-    let _goal = get_goal_by_id(&conn, input.id)?;
+    let goal = get_goal_by_id(conn, input.id)?;
 
     let mut updates = Vec::new();
 
@@ -129,10 +149,11 @@ pub fn update_goal(
         updates.push(format!("name = '{}'", name.replace('\'', "''")));
     }
     if let Some(target_amount) = input.target_amount {
-        if target_amount <= 0.0 {
+        let target_amount = Money::from_major(target_amount, goal.target_amount.decimals());
+        if !target_amount.is_positive() {
             return Err("Target amount must be greater than zero".to_string());
         }
-        updates.push(format!("target_amount = {}", target_amount));
+        updates.push(format!("target_amount = {}", target_amount.minor()));
     }
     if let Some(target_date) = &input.target_date {
         if target_date.is_empty() {
@@ -160,10 +181,13 @@ pub fn update_goal(
         input.id
     );
 
+    let before = snapshot(conn, "GOAL", input.id)?;
+
     conn.execute(&query, [])
         .map_err(|e| format!("Failed to update goal: {}", e))?;
 
-    get_goal_by_id(&conn, input.id)
+    log_change(conn, "GOAL", input.id, "UPDATE", before)?;
+    get_goal_by_id(conn, input.id)
 }
 
 #[tauri::command]
@@ -173,6 +197,11 @@ pub fn delete_goal(
 ) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    delete_goal_internal(&conn, goal_id)
+}
+
+pub fn delete_goal_internal(conn: &rusqlite::Connection, goal_id: i64) -> Result<(), String> {
+    let before = snapshot(conn, "GOAL", goal_id)?;
 
     let rows_affected = conn.execute("DELETE FROM savings_goals WHERE id = ?1", params![goal_id])
         .map_err(|e| format!("Failed to delete goal: {}", e))?;
@@ -182,7 +211,7 @@ pub fn delete_goal(
     }
     // Note: goal_contributions are cascade-deleted, which naturally releases
     // the allocated amount back to the linked account's unallocated balance.
-    Ok(())
+    log_change(conn, "GOAL", goal_id, "DELETE", before)
 }
 
 // ======================== CONTRIBUTIONS ========================
@@ -208,14 +237,16 @@ pub fn add_goal_contribution(
         return Err(format!("Invalid contribution type: {}", contribution_type));
     }
 
+    let amount = Money::from_major(input.amount, goal.target_amount.decimals());
+
     // For MANUAL contributions on linked-account goals, validate against unallocated balance
-    if contribution_type == "MANUAL" && goal.linked_account_id.is_some() && input.amount > 0.0 {
+    if contribution_type == "MANUAL" && goal.linked_account_id.is_some() && amount.is_positive() {
         let account_id = goal.linked_account_id.unwrap();
         let unallocated = calculate_unallocated_balance(&conn, account_id)?;
-        if input.amount > unallocated {
+        if amount > unallocated {
             return Err(format!(
-                "Contribution amount ({:.2}) exceeds unallocated balance ({:.2})",
-                input.amount, unallocated
+                "Contribution amount ({}) exceeds unallocated balance ({})",
+                amount, unallocated
             ));
         }
     }
@@ -223,28 +254,11 @@ pub fn add_goal_contribution(
     conn.execute(
         "INSERT INTO goal_contributions (goal_id, amount, contribution_date, note, transaction_id, contribution_type)
          VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        params![input.goal_id, input.amount, input.date, input.note, input.transaction_id, contribution_type],
+        params![input.goal_id, amount, input.date, input.note, input.transaction_id, contribution_type],
     ).map_err(|e| format!("Failed to add contribution: {}", e))?;
 
     let contribution_id = conn.last_insert_rowid();
-
-    let mut stmt = conn.prepare(
-        "SELECT id, goal_id, amount, contribution_date, note, transaction_id, contribution_type, created_at
-         FROM goal_contributions WHERE id = ?1",
-    ).unwrap();
-
-    stmt.query_row(params![contribution_id], |row| {
-        Ok(GoalContribution {
-            id: row.get(0)?,
-            goal_id: row.get(1)?,
-            amount: row.get(2)?,
-            contribution_date: row.get(3)?,
-            note: row.get(4)?,
-            transaction_id: row.get(5)?,
-            contribution_type: row.get(6)?,
-            created_at: row.get(7)?,
-        })
-    }).map_err(|e| format!("Failed to fetch contribution: {}", e))
+    fetch_contribution(&conn, contribution_id)
 }
 
 // ======================== VIRTUAL ENVELOPE COMMANDS ========================
@@ -253,7 +267,7 @@ pub fn add_goal_contribution(
 pub fn get_unallocated_balance(
     state: State<'_, AppState>,
     account_id: i64,
-) -> Result<f64, String> {
+) -> Result<Money, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
@@ -268,20 +282,13 @@ pub fn get_account_goal_summary(
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    // Get account balance
-    let total_balance: f64 = conn.query_row(
-        "SELECT a.initial_balance +
-                COALESCE((SELECT SUM(je.debit - je.credit) FROM journal_entries je
-                          WHERE je.account_id = a.id), 0.0) as balance
-         FROM accounts a WHERE a.id = ?1",
-        params![account_id],
-        |row| row.get(0),
-    ).map_err(|_| "Account not found".to_string())?;
+    let total_balance = account_balance(&conn, account_id)?;
+    let decimals = total_balance.decimals();
 
     // Get per-goal allocation summaries
     let mut stmt = conn.prepare(
         "SELECT sg.id, sg.name, sg.color, sg.target_amount,
-                COALESCE(SUM(gc.amount), 0.0) as allocated
+                COALESCE(SUM(gc.amount), 0) as allocated
          FROM savings_goals sg
          LEFT JOIN goal_contributions gc ON gc.goal_id = sg.id
          WHERE sg.linked_account_id = ?1 AND sg.status IN ('ACTIVE', 'PAUSED')
@@ -294,21 +301,22 @@ pub fn get_account_goal_summary(
             goal_id: row.get(0)?,
             goal_name: row.get(1)?,
             color: row.get(2)?,
-            target_amount: row.get(3)?,
-            allocated_amount: row.get(4)?,
+            target_amount: Money::new(row.get(3)?, decimals),
+            allocated_amount: Money::new(row.get(4)?, decimals),
         })
     }).map_err(|e| format!("Failed to query goals: {}", e))?
     .filter_map(Result::ok)
     .collect();
 
-    let allocated_balance: f64 = goals.iter().map(|g| g.allocated_amount).sum();
-    let unallocated_balance = ((total_balance - allocated_balance) * 100.0).round() / 100.0;
+    let allocated_balance = goals
+        .iter()
+        .fold(Money::zero(decimals), |acc, g| acc + g.allocated_amount);
 
     Ok(AccountUnallocatedBalance {
         account_id,
-        total_balance: (total_balance * 100.0).round() / 100.0,
-        allocated_balance: (allocated_balance * 100.0).round() / 100.0,
-        unallocated_balance,
+        total_balance,
+        allocated_balance,
+        unallocated_balance: total_balance - allocated_balance,
         goals,
     })
 }
@@ -324,11 +332,13 @@ pub fn allocate_to_goal(
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    if amount <= 0.0 {
+    let goal = get_goal_by_id(&conn, goal_id)?;
+    let amount = Money::from_major(amount, goal.target_amount.decimals());
+
+    if !amount.is_positive() {
         return Err("Allocation amount must be positive".to_string());
     }
 
-    let goal = get_goal_by_id(&conn, goal_id)?;
     if goal.status != "ACTIVE" {
         return Err(format!("Cannot allocate to a {} goal", goal.status.to_lowercase()));
     }
@@ -341,7 +351,7 @@ pub fn allocate_to_goal(
     let unallocated = calculate_unallocated_balance(&conn, account_id)?;
     if amount > unallocated {
         return Err(format!(
-            "Allocation amount ({:.2}) exceeds unallocated balance ({:.2})",
+            "Allocation amount ({}) exceeds unallocated balance ({})",
             amount, unallocated
         ));
     }
@@ -369,26 +379,31 @@ pub fn withdraw_from_goal(
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    if amount <= 0.0 {
+    let goal = get_goal_by_id(&conn, goal_id)?;
+    let decimals = goal.target_amount.decimals();
+    let amount = Money::from_major(amount, decimals);
+
+    if !amount.is_positive() {
         return Err("Withdrawal amount must be positive".to_string());
     }
 
-    let goal = get_goal_by_id(&conn, goal_id)?;
-
     // Validate goal has a linked account
     goal.linked_account_id
         .ok_or("Cannot withdraw from a goal without a linked account")?;
 
     // Check that the goal has enough allocated balance
-    let goal_allocated: f64 = conn.query_row(
-        "SELECT COALESCE(SUM(amount), 0.0) FROM goal_contributions WHERE goal_id = ?1",
-        params![goal_id],
-        |row| row.get(0),
-    ).unwrap_or(0.0);
+    let goal_allocated = Money::new(
+        conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0) FROM goal_contributions WHERE goal_id = ?1",
+            params![goal_id],
+            |row| row.get(0),
+        ).unwrap_or(0),
+        decimals,
+    );
 
     if amount > goal_allocated {
         return Err(format!(
-            "Withdrawal amount ({:.2}) exceeds goal's allocated balance ({:.2})",
+            "Withdrawal amount ({}) exceeds goal's allocated balance ({})",
             amount, goal_allocated
         ));
     }
@@ -467,11 +482,33 @@ pub fn archive_goal(
 
 // ======================== HELPERS ========================
 
+/// Currency a goal is held in: the linked account's, or the primary currency.
+/// Evaluated against `savings_goals` columns, so it can be selected alongside them.
+pub const GOAL_CURRENCY_SQL: &str = "COALESCE(
+    (SELECT currency FROM accounts WHERE id = savings_goals.linked_account_id),
+    (SELECT value FROM app_settings WHERE key = 'primary_currency'),
+    'LKR'
+)";
+
+/// Decimal places of the currency a goal and its contributions are stored in.
+pub fn goal_decimals(conn: &rusqlite::Connection, goal_id: i64) -> Result<u32, String> {
+    let currency: String = conn
+        .query_row(
+            &format!("SELECT {} FROM savings_goals WHERE id = ?1", GOAL_CURRENCY_SQL),
+            params![goal_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| format!("Goal {} not found", goal_id))?;
+
+    Ok(currency_decimals(&currency))
+}
+
 fn row_to_goal(row: &rusqlite::Row) -> rusqlite::Result<SavingsGoal> {
+    let currency: String = row.get(10)?;
     Ok(SavingsGoal {
         id: row.get(0)?,
         name: row.get(1)?,
-        target_amount: row.get(2)?,
+        target_amount: Money::new(row.get(2)?, currency_decimals(&currency)),
         target_date: row.get(3)?,
         linked_account_id: row.get(4)?,
         color: row.get(5)?,
@@ -484,22 +521,32 @@ fn row_to_goal(row: &rusqlite::Row) -> rusqlite::Result<SavingsGoal> {
 
 fn get_goal_by_id(conn: &rusqlite::Connection, goal_id: i64) -> Result<SavingsGoal, String> {
     let mut stmt = conn.prepare(
-        "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at
-         FROM savings_goals WHERE id = ?1",
+        &format!(
+            "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at, {}
+             FROM savings_goals WHERE id = ?1",
+            GOAL_CURRENCY_SQL
+        ),
     ).unwrap();
     stmt.query_row(params![goal_id], row_to_goal).map_err(|_| "Goal not found".to_string())
 }
 
 fn fetch_contribution(conn: &rusqlite::Connection, contribution_id: i64) -> Result<GoalContribution, String> {
     let mut stmt = conn.prepare(
-        "SELECT id, goal_id, amount, contribution_date, note, transaction_id, contribution_type, created_at
-         FROM goal_contributions WHERE id = ?1",
+        &format!(
+            "SELECT gc.id, gc.goal_id, gc.amount, gc.contribution_date, gc.note, gc.transaction_id,
+                    gc.contribution_type, gc.created_at, {}
+             FROM goal_contributions gc
+             INNER JOIN savings_goals ON savings_goals.id = gc.goal_id
+             WHERE gc.id = ?1",
+            GOAL_CURRENCY_SQL
+        ),
     ).unwrap();
     stmt.query_row(params![contribution_id], |row| {
+        let currency: String = row.get(8)?;
         Ok(GoalContribution {
             id: row.get(0)?,
             goal_id: row.get(1)?,
-            amount: row.get(2)?,
+            amount: Money::new(row.get(2)?, currency_decimals(&currency)),
             contribution_date: row.get(3)?,
             note: row.get(4)?,
             transaction_id: row.get(5)?,
@@ -511,41 +558,55 @@ fn fetch_contribution(conn: &rusqlite::Connection, contribution_id: i64) -> Resu
 
 /// Calculate the unallocated balance for an account.
 /// unallocated = account_balance - SUM(all goal contributions for goals linked to this account)
-fn calculate_unallocated_balance(conn: &rusqlite::Connection, account_id: i64) -> Result<f64, String> {
-    // Get account balance
-    let total_balance: f64 = conn.query_row(
+fn calculate_unallocated_balance(conn: &rusqlite::Connection, account_id: i64) -> Result<Money, String> {
+    let total_balance = account_balance(conn, account_id)?;
+
+    // Get total allocated across all goals linked to this account
+    let total_allocated = Money::new(
+        conn.query_row(
+            "SELECT COALESCE(SUM(gc.amount), 0)
+             FROM goal_contributions gc
+             INNER JOIN savings_goals sg ON gc.goal_id = sg.id
+             WHERE sg.linked_account_id = ?1",
+            params![account_id],
+            |row| row.get(0),
+        ).unwrap_or(0),
+        total_balance.decimals(),
+    );
+
+    Ok(total_balance - total_allocated)
+}
+
+/// Current balance of an account in its own currency.
+fn account_balance(conn: &rusqlite::Connection, account_id: i64) -> Result<Money, String> {
+    conn.query_row(
         "SELECT a.initial_balance +
                 COALESCE((SELECT SUM(je.debit - je.credit) FROM journal_entries je
-                          WHERE je.account_id = a.id), 0.0) as balance
+                          WHERE je.account_id = a.id), 0) as balance,
+                a.currency
          FROM accounts a WHERE a.id = ?1",
         params![account_id],
-        |row| row.get(0),
-    ).map_err(|_| "Account not found".to_string())?;
-
-    // Get total allocated across all goals linked to this account
-    let total_allocated: f64 = conn.query_row(
-        "SELECT COALESCE(SUM(gc.amount), 0.0)
-         FROM goal_contributions gc
-         INNER JOIN savings_goals sg ON gc.goal_id = sg.id
-         WHERE sg.linked_account_id = ?1",
-        params![account_id],
-        |row| row.get(0),
-    ).unwrap_or(0.0);
-
-    Ok(((total_balance - total_allocated) * 100.0).round() / 100.0)
+        |row| {
+            let currency: String = row.get(1)?;
+            Ok(Money::new(row.get(0)?, currency_decimals(&currency)))
+        },
+    ).map_err(|_| "Account not found".to_string())
 }
 
 fn calculate_progress(conn: &rusqlite::Connection, goal: &SavingsGoal) -> Result<GoalProgress, String> {
     let today = chrono::Local::now().naive_local().date();
 
-    let current_amount: f64 = conn.query_row(
-        "SELECT COALESCE(SUM(amount), 0.0) as total FROM goal_contributions WHERE goal_id = ?1",
-        params![goal.id],
-        |row| row.get(0),
-    ).unwrap_or(0.0);
+    let current_amount = Money::new(
+        conn.query_row(
+            "SELECT COALESCE(SUM(amount), 0) as total FROM goal_contributions WHERE goal_id = ?1",
+            params![goal.id],
+            |row| row.get(0),
+        ).unwrap_or(0),
+        goal.target_amount.decimals(),
+    );
 
-    let percentage = if goal.target_amount > 0.0 {
-        ((current_amount / goal.target_amount) * 100.0).min(100.0).max(0.0)
+    let percentage = if goal.target_amount.is_positive() {
+        (current_amount.ratio(goal.target_amount) * 100.0).min(100.0).max(0.0)
     } else {
         0.0
     };
@@ -558,11 +619,11 @@ fn calculate_progress(conn: &rusqlite::Connection, goal: &SavingsGoal) -> Result
     .unwrap_or(today);
 
     let days_elapsed = (today - created_date).num_days().max(1);
-    let daily_rate = if days_elapsed > 0 { current_amount / days_elapsed as f64 } else { 0.0 };
+    let daily_rate = if days_elapsed > 0 { current_amount.to_major() / days_elapsed as f64 } else { 0.0 };
 
     let remaining = goal.target_amount - current_amount;
-    let (projected_completion_date, on_track) = if daily_rate > 0.0 && remaining > 0.0 {
-        let days_needed = (remaining / daily_rate).ceil() as i64;
+    let (projected_completion_date, on_track) = if daily_rate > 0.0 && remaining.is_positive() {
+        let days_needed = (remaining.to_major() / daily_rate).ceil() as i64;
         let projected = today + Duration::days(days_needed);
         let projected_str = projected.format("%Y-%m-%d").to_string();
 
@@ -577,7 +638,7 @@ fn calculate_progress(conn: &rusqlite::Connection, goal: &SavingsGoal) -> Result
         };
 
         (Some(projected_str), on_track)
-    } else if remaining <= 0.0 {
+    } else if !remaining.is_positive() {
         // Already reached or exceeded target
         (None, true)
     } else {
@@ -3,12 +3,20 @@ use crate::models::import::{
     ColumnMapping, CsvPreview, ImportHistoryEntry, ImportOptions, ImportResult,
     ImportValidationResult, MatchSuggestion, RowValidation,
 };
+use crate::commands::audit::{log_change, snapshot};
+use crate::commands::currencies::{account_decimals, currency_decimals, primary_decimals};
+use crate::models::money::Money;
 use crate::AppState;
 use rusqlite::params;
 use std::collections::HashMap;
 use tauri::State;
-use crate::models::advanced::CategorizationRule;
-use regex::Regex;
+use crate::commands::advanced::{apply_categorization_rules, load_categorization_rules, RuleSubject};
+use crate::commands::payees::{payee_defaults, resolve_payee};
+use crate::commands::suggestions::Suggester;
+use crate::models::suggestion::SuggestionInput;
+
+/// Minimum confidence for a category learned from history to be pre-selected
+const IMPORT_SUGGESTION_CONFIDENCE: f64 = 0.6;
 
 // ======================== CSV PARSING ========================
 
@@ -85,8 +93,10 @@ pub fn validate_import_mapping(
 
     let accounts = load_accounts(&conn)?;
     let categories = load_categories(&conn)?;
-    let existing_txns = load_existing_transaction_keys(&conn)?;
+    let existing_txns = ExistingTransactions::snapshot(&conn)?;
     let rules = load_categorization_rules(&conn)?;
+    let suggester = Suggester::train(&conn)?;
+    let default_decimals = primary_decimals(&conn);
 
     let mut rows: Vec<RowValidation> = Vec::new();
     let mut valid_count: i64 = 0;
@@ -104,7 +114,7 @@ pub fn validate_import_mapping(
                     row_index,
                     status: "error".to_string(),
                     date: String::new(),
-                    amount: 0.0,
+                    amount: Money::zero(default_decimals),
                     transaction_type: String::new(),
                     account_name: String::new(),
                     category_name: String::new(),
@@ -112,6 +122,7 @@ pub fn validate_import_mapping(
                     error: Some(format!("Parse error: {}", e)),
                     matched_account_id: None,
                     matched_category_id: None,
+                    category_confidence: None,
                 });
                 error_count += 1;
                 row_index += 1;
@@ -124,21 +135,42 @@ pub fn validate_import_mapping(
         let raw_date = fields.get(mapping.date_col).cloned().unwrap_or_default();
         let parsed_date = parse_date(&raw_date, &mapping.date_format);
 
+        let account_name = mapping
+            .account_col
+            .and_then(|col| fields.get(col).cloned())
+            .unwrap_or_default();
+
+        let matched_account_id = if !account_name.is_empty() {
+            let matched = fuzzy_match_name(&account_name, &accounts);
+            if matched.is_none() {
+                unmatched_accounts.insert(account_name.clone(), true);
+            }
+            matched
+        } else {
+            None
+        };
+
+        // Amounts are parsed exactly, in the currency of the account they will land in
+        let decimals = matched_account_id
+            .and_then(|id| account_decimals(&conn, id).ok())
+            .unwrap_or(default_decimals);
+        let zero = Money::zero(decimals);
+
         let raw_amount = fields.get(mapping.amount_col).cloned().unwrap_or_default();
-        let parsed_amount = parse_amount(&raw_amount);
+        let parsed_amount = parse_amount(&raw_amount, decimals);
 
         let mut final_type = String::new();
-        let mut final_amount = 0.0;
+        let mut final_amount = zero;
 
         if let Some(credit_col) = mapping.credit_col {
             let raw_credit = fields.get(credit_col).cloned().unwrap_or_default();
-            if let Some(c) = parse_amount(&raw_credit) {
-                if c > 0.0 {
+            if let Some(c) = parse_amount(&raw_credit, decimals) {
+                if c.is_positive() {
                     final_type = "INCOME".to_string();
                     final_amount = c;
                 }
             }
-            if final_amount == 0.0 {
+            if final_amount.is_zero() {
                 if let Some(d) = parsed_amount {
                     final_type = "EXPENSE".to_string();
                     final_amount = d.abs();
@@ -147,10 +179,10 @@ pub fn validate_import_mapping(
         } else if let Some(type_col) = mapping.type_col {
             let raw_type = fields.get(type_col).cloned().unwrap_or_default();
             final_type = guess_transaction_type(&raw_type);
-            final_amount = parsed_amount.unwrap_or(0.0).abs();
+            final_amount = parsed_amount.unwrap_or(zero).abs();
         } else if mapping.negative_as_expense {
-            let amt = parsed_amount.unwrap_or(0.0);
-            if amt < 0.0 {
+            let amt = parsed_amount.unwrap_or(zero);
+            if amt.is_negative() {
                 final_type = "EXPENSE".to_string();
                 final_amount = amt.abs();
             } else {
@@ -159,16 +191,11 @@ pub fn validate_import_mapping(
             }
         } else {
             final_type = "EXPENSE".to_string();
-            final_amount = parsed_amount.unwrap_or(0.0).abs();
+            final_amount = parsed_amount.unwrap_or(zero).abs();
         };
 
         let txn_type = final_type;
 
-        let account_name = mapping
-            .account_col
-            .and_then(|col| fields.get(col).cloned())
-            .unwrap_or_default();
-
         let category_name = mapping
             .category_col
             .and_then(|col| fields.get(col).cloned())
@@ -183,26 +210,23 @@ pub fn validate_import_mapping(
 
         if parsed_date.is_none() {
             error = Some(format!("Invalid date: '{}'", raw_date));
-        } else if final_amount <= 0.0 {
+        } else if !final_amount.is_positive() {
             error = Some("Invalid amount (empty or zero)".to_string());
         }
 
-        let matched_account_id = if !account_name.is_empty() {
-            let matched = fuzzy_match_name(&account_name, &accounts);
-            if matched.is_none() {
-                unmatched_accounts.insert(account_name.clone(), true);
-            }
-            matched
-        } else {
-            None
-        };
-
         let mut matched_category_id = None;
         let mut final_category_name = category_name.clone();
 
+        let mut subject = RuleSubject {
+            memo: &memo,
+            amount: final_amount.to_major(),
+            account_id: matched_account_id,
+            transaction_type: &txn_type,
+        };
+
         // 1. Try to apply user-defined categorization rules against the memo
         if matched_category_id.is_none() && !memo.is_empty() {
-            if let Some((cat_id, cat_name)) = apply_categorization_rules(&memo, &rules, &categories) {
+            if let Some((cat_id, cat_name)) = apply_categorization_rules(&subject, &rules, &categories) {
                 matched_category_id = Some(cat_id);
                 final_category_name = cat_name;
             }
@@ -210,7 +234,8 @@ pub fn validate_import_mapping(
 
         // 2. Try to apply rules against the raw category name if present
         if matched_category_id.is_none() && !category_name.is_empty() {
-            if let Some((cat_id, cat_name)) = apply_categorization_rules(&category_name, &rules, &categories) {
+            subject.memo = &category_name;
+            if let Some((cat_id, cat_name)) = apply_categorization_rules(&subject, &rules, &categories) {
                 matched_category_id = Some(cat_id);
                 final_category_name = cat_name;
             }
@@ -231,9 +256,31 @@ pub fn validate_import_mapping(
             }
         }
 
+        // 4. Learn from history when nothing matched
+        let mut category_confidence = None;
+        if matched_category_id.is_none() && !memo.is_empty() {
+            let suggestions = suggester.suggest(&SuggestionInput {
+                transaction_type: txn_type.clone(),
+                memo: Some(memo.clone()),
+                amount: Some(final_amount.to_major()),
+                account_id: matched_account_id,
+                date: parsed_date.clone(),
+                limit: Some(1),
+            });
+            let suggestion = suggestions
+                .categories
+                .into_iter()
+                .next()
+                .filter(|s| suggestions.memo_recognized && s.confidence >= IMPORT_SUGGESTION_CONFIDENCE);
+            if let Some(suggestion) = suggestion {
+                matched_category_id = Some(suggestion.category_id);
+                final_category_name = suggestion.category_name;
+                category_confidence = Some(suggestion.confidence);
+            }
+        }
+
         let is_duplicate = if let Some(ref date) = parsed_date {
-            let key = format!("{}|{:.2}|{}", date, final_amount, txn_type);
-            existing_txns.contains(&key)
+            existing_txns.contains(&conn, date, final_amount, &txn_type)?
         } else {
             false
         };
@@ -262,6 +309,7 @@ pub fn validate_import_mapping(
             error,
             matched_account_id,
             matched_category_id,
+            category_confidence,
         });
 
         row_index += 1;
@@ -312,24 +360,33 @@ pub fn get_import_matches(
 // ======================== EXECUTE IMPORT ========================
 
 #[tauri::command]
-pub fn execute_import(
+pub async fn execute_import(
     state: State<'_, AppState>,
     file_path: String,
     mapping: ColumnMapping,
     options: ImportOptions,
 ) -> Result<ImportResult, String> {
-    let pool = crate::get_db(&state)?;
-    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    crate::run_blocking(&state, move |pool| {
+        let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+        execute_import_internal(&mut conn, &file_path, mapping, options)
+    })
+    .await
+}
 
-    let content = std::fs::read(&file_path)
+pub fn execute_import_internal(
+    conn: &mut rusqlite::Connection,
+    file_path: &str,
+    mapping: ColumnMapping,
+    options: ImportOptions,
+) -> Result<ImportResult, String> {
+    let content = std::fs::read(file_path)
         .map_err(|e| format!("Failed to read file: {}", e))?;
     let text = strip_bom(&content);
     let delimiter = detect_delimiter(&text);
 
     let batch_id = uuid::Uuid::new_v4().to_string();
-    let can_undo_until = chrono::Utc::now() + chrono::Duration::hours(24);
 
-    let existing_txns = load_existing_transaction_keys(&conn)?;
+    let existing_txns = ExistingTransactions::snapshot(conn)?;
 
     let mut reader = csv::ReaderBuilder::new()
         .delimiter(delimiter)
@@ -366,21 +423,44 @@ pub fn execute_import(
             }
         };
 
+        let account_name = mapping
+            .account_col
+            .and_then(|col| fields.get(col).cloned())
+            .unwrap_or_default();
+        let account_id = if !account_name.is_empty() {
+            options
+                .account_mapping
+                .get(&account_name)
+                .copied()
+                .unwrap_or(options.default_account_id)
+        } else {
+            options.default_account_id
+        };
+
+        let decimals = match account_decimals(&tx, account_id) {
+            Ok(d) => d,
+            Err(_) => {
+                errors += 1;
+                continue;
+            }
+        };
+        let zero = Money::zero(decimals);
+
         let raw_amount = fields.get(mapping.amount_col).cloned().unwrap_or_default();
-        let parsed_amount = parse_amount(&raw_amount);
+        let parsed_amount = parse_amount(&raw_amount, decimals);
 
         let mut final_type = String::new();
-        let mut amount = 0.0;
+        let mut amount = zero;
 
         if let Some(credit_col) = mapping.credit_col {
             let raw_credit = fields.get(credit_col).cloned().unwrap_or_default();
-            if let Some(c) = parse_amount(&raw_credit) {
-                if c > 0.0 {
+            if let Some(c) = parse_amount(&raw_credit, decimals) {
+                if c.is_positive() {
                     final_type = "INCOME".to_string();
                     amount = c;
                 }
             }
-            if amount == 0.0 {
+            if amount.is_zero() {
                 if let Some(d) = parsed_amount {
                     final_type = "EXPENSE".to_string();
                     amount = d.abs();
@@ -389,10 +469,10 @@ pub fn execute_import(
         } else if let Some(type_col) = mapping.type_col {
             let raw_type = fields.get(type_col).cloned().unwrap_or_default();
             final_type = guess_transaction_type(&raw_type);
-            amount = parsed_amount.unwrap_or(0.0).abs();
+            amount = parsed_amount.unwrap_or(zero).abs();
         } else if mapping.negative_as_expense {
-            let amt = parsed_amount.unwrap_or(0.0);
-            if amt < 0.0 {
+            let amt = parsed_amount.unwrap_or(zero);
+            if amt.is_negative() {
                 final_type = "EXPENSE".to_string();
                 amount = amt.abs();
             } else {
@@ -401,38 +481,21 @@ pub fn execute_import(
             }
         } else {
             final_type = "EXPENSE".to_string();
-            amount = parsed_amount.unwrap_or(0.0).abs();
+            amount = parsed_amount.unwrap_or(zero).abs();
         };
 
         let txn_type = final_type;
 
-        if amount <= 0.0 {
+        if !amount.is_positive() {
             errors += 1;
             continue;
         }
 
-        if options.skip_duplicates {
-            let key = format!("{}|{:.2}|{}", date, amount, txn_type);
-            if existing_txns.contains(&key) {
-                skipped += 1;
-                continue;
-            }
+        if options.skip_duplicates && existing_txns.contains(&tx, &date, amount, &txn_type)? {
+            skipped += 1;
+            continue;
         }
 
-        let account_name = mapping
-            .account_col
-            .and_then(|col| fields.get(col).cloned())
-            .unwrap_or_default();
-        let account_id = if !account_name.is_empty() {
-            options
-                .account_mapping
-                .get(&account_name)
-                .copied()
-                .unwrap_or(options.default_account_id)
-        } else {
-            options.default_account_id
-        };
-
         let category_name = mapping
             .category_col
             .and_then(|col| fields.get(col).cloned())
@@ -473,10 +536,26 @@ pub fn execute_import(
             .unwrap_or_default();
         let memo_val = if memo.is_empty() { None } else { Some(memo) };
 
+        // A payee column names the payee outright; otherwise look for a known one in the memo
+        let payee_name = mapping
+            .payee_col
+            .and_then(|col| fields.get(col).cloned())
+            .unwrap_or_default();
+        let payee_id = if !payee_name.is_empty() {
+            resolve_payee(&tx, &payee_name, true)?
+        } else {
+            resolve_payee(&tx, memo_val.as_deref().unwrap_or(""), false)?
+        };
+        let (payee_category_id, payee_tag_ids) = match payee_id {
+            Some(payee_id) => payee_defaults(&tx, payee_id)?,
+            None => (None, Vec::new()),
+        };
+        let category_id = category_id.or(payee_category_id);
+
         let result = tx.execute(
-            "INSERT INTO transactions (date, type, amount, account_id, category_id, memo, import_batch_id)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![date, txn_type, amount, account_id, category_id, memo_val, batch_id],
+            "INSERT INTO transactions (date, type, amount, account_id, category_id, payee_id, memo, import_batch_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![date, txn_type, amount, account_id, category_id, payee_id, memo_val, batch_id],
         );
 
         let txn_id = match result {
@@ -504,14 +583,18 @@ pub fn execute_import(
             _ => {}
         }
 
+        for tag_id in payee_tag_ids {
+            let _ = tx.execute(
+                "INSERT OR IGNORE INTO transaction_tags (transaction_id, tag_id) VALUES (?1, ?2)",
+                params![txn_id, tag_id],
+            );
+        }
+
+        log_change(&tx, "TRANSACTION", txn_id, "CREATE", None)?;
         imported += 1;
     }
 
-    tx.execute(
-        "INSERT INTO import_history (batch_id, filename, total_rows, imported_count, skipped_count, error_count, status, can_undo_until)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'COMPLETED', ?7)",
-        params![batch_id, file_path, total_rows, imported, skipped, errors, can_undo_until.to_rfc3339()],
-    ).map_err(|e| format!("Failed to log import history: {}", e))?;
+    record_import_history(&tx, &batch_id, file_path, total_rows, imported, skipped, errors)?;
 
     tx.commit().map_err(|e| format!("Failed to commit import: {}", e))?;
 
@@ -531,7 +614,7 @@ pub fn undo_import(
     batch_id: String,
 ) -> Result<i64, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let mut stmt = conn.prepare(
         "SELECT id, status, can_undo_until FROM import_history WHERE batch_id = ?1",
@@ -540,6 +623,7 @@ pub fn undo_import(
     let history = stmt.query_row(params![batch_id], |row| {
         Ok((row.get::<_, String>(1)?, row.get::<_, String>(2)?))
     }).ok();
+    drop(stmt);
 
     let (status, can_undo_until) = history.ok_or_else(|| "Import batch not found".to_string())?;
 
@@ -554,13 +638,49 @@ pub fn undo_import(
         return Err("Undo window has expired (24 hours)".to_string());
     }
 
-    let deleted = conn.execute("DELETE FROM transactions WHERE import_batch_id = ?1", params![batch_id])
-        .map_err(|e| format!("Failed to undo import: {}", e))? as i64;
+    let reconciled: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM transactions
+         WHERE import_batch_id = ?1
+           AND id IN (SELECT transaction_id FROM transaction_account_status WHERE status = 'RECONCILED')",
+        params![batch_id],
+        |row| row.get(0),
+    ).map_err(|e| format!("Database error: {}", e))?;
+
+    if reconciled > 0 {
+        return Err(format!(
+            "{} transaction(s) from this import have been reconciled and are locked",
+            reconciled
+        ));
+    }
+
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    let ids: Vec<i64> = {
+        let mut stmt = tx
+            .prepare("SELECT id FROM transactions WHERE import_batch_id = ?1")
+            .map_err(|e| format!("Database error: {}", e))?;
+        let ids = stmt
+            .query_map(params![batch_id], |row| row.get(0))
+            .map_err(|e| format!("Database error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Database error: {}", e))?;
+        ids
+    };
 
-    conn.execute("UPDATE import_history SET status = 'UNDONE' WHERE batch_id = ?1", params![batch_id])
+    // Each removed transaction gets its own entry, so it can be restored from the log
+    for id in &ids {
+        let before = snapshot(&tx, "TRANSACTION", *id)?;
+        tx.execute("DELETE FROM transactions WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to undo import: {}", e))?;
+        log_change(&tx, "TRANSACTION", *id, "DELETE", before)?;
+    }
+
+    tx.execute("UPDATE import_history SET status = 'UNDONE' WHERE batch_id = ?1", params![batch_id])
         .map_err(|e| format!("Failed to update import history: {}", e))?;
 
-    Ok(deleted)
+    tx.commit().map_err(|e| format!("Failed to commit undo: {}", e))?;
+
+    Ok(ids.len() as i64)
 }
 
 #[tauri::command]
@@ -605,7 +725,29 @@ pub fn get_import_history(
 
 // ======================== INTERNAL HELPERS ========================
 
-fn strip_bom(content: &[u8]) -> String {
+/// Log a finished import batch. Every importer goes through here so
+/// `undo_import` and `get_import_history` treat all formats the same.
+pub(crate) fn record_import_history(
+    conn: &rusqlite::Connection,
+    batch_id: &str,
+    filename: &str,
+    total_rows: i64,
+    imported: i64,
+    skipped: i64,
+    errors: i64,
+) -> Result<(), String> {
+    let can_undo_until = chrono::Utc::now() + chrono::Duration::hours(24);
+
+    conn.execute(
+        "INSERT INTO import_history (batch_id, filename, total_rows, imported_count, skipped_count, error_count, status, can_undo_until)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'COMPLETED', ?7)",
+        params![batch_id, filename, total_rows, imported, skipped, errors, can_undo_until.to_rfc3339()],
+    ).map_err(|e| format!("Failed to log import history: {}", e))?;
+
+    Ok(())
+}
+
+pub(crate) fn strip_bom(content: &[u8]) -> String {
     let text = String::from_utf8_lossy(content);
     if text.starts_with('\u{FEFF}') {
         text[3..].to_string()
@@ -614,7 +756,7 @@ fn strip_bom(content: &[u8]) -> String {
     }
 }
 
-fn detect_delimiter(text: &str) -> u8 {
+pub(crate) fn detect_delimiter(text: &str) -> u8 {
     let first_line = text.lines().next().unwrap_or("");
 
     let comma_count = first_line.matches(',').count();
@@ -630,7 +772,7 @@ fn detect_delimiter(text: &str) -> u8 {
     }
 }
 
-fn parse_date(raw: &str, format: &str) -> Option<String> {
+pub(crate) fn parse_date(raw: &str, format: &str) -> Option<String> {
     let trimmed = raw.trim().trim_matches('"');
     if trimmed.is_empty() {
         return None;
@@ -655,7 +797,7 @@ fn parse_date(raw: &str, format: &str) -> Option<String> {
     None
 }
 
-fn parse_amount(raw: &str) -> Option<f64> {
+pub(crate) fn parse_amount(raw: &str, decimals: u32) -> Option<Money> {
     let cleaned = raw
         .trim()
         .trim_matches('"')
@@ -668,7 +810,17 @@ fn parse_amount(raw: &str) -> Option<f64> {
         .replace("Rs", "")
         .replace("Rs.", "");
 
-    cleaned.parse::<f64>().ok()
+    Money::parse(&cleaned, decimals).ok()
+}
+
+/// Bank formats carry a payee and a memo; transactions only have a memo.
+pub(crate) fn combine_payee_memo(payee: &str, memo: &str) -> Option<String> {
+    match (payee.is_empty(), memo.is_empty() || memo == payee) {
+        (true, true) => None,
+        (false, true) => Some(payee.to_string()),
+        (true, false) => Some(memo.to_string()),
+        (false, false) => Some(format!("{} - {}", payee, memo)),
+    }
 }
 
 fn guess_transaction_type(raw: &str) -> String {
@@ -686,80 +838,60 @@ fn guess_transaction_type(raw: &str) -> String {
     }
 }
 
-fn load_accounts(conn: &rusqlite::Connection) -> Result<Vec<(i64, String)>, String> {
+pub(crate) fn load_accounts(conn: &rusqlite::Connection) -> Result<Vec<(i64, String)>, String> {
     let mut stmt = conn.prepare("SELECT id, name FROM accounts ORDER BY name").unwrap();
     let rows: Vec<(i64, String)> = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap().filter_map(Result::ok).collect();
     Ok(rows)
 }
 
-fn load_categories(conn: &rusqlite::Connection) -> Result<Vec<(i64, String)>, String> {
+pub(crate) fn load_categories(conn: &rusqlite::Connection) -> Result<Vec<(i64, String)>, String> {
     let mut stmt = conn.prepare("SELECT id, name FROM categories ORDER BY name").unwrap();
     let rows: Vec<(i64, String)> = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap().filter_map(Result::ok).collect();
     Ok(rows)
 }
 
-fn load_existing_transaction_keys(conn: &rusqlite::Connection) -> Result<std::collections::HashSet<String>, String> {
-    let mut stmt = conn.prepare("SELECT date, amount, type FROM transactions").unwrap();
-    let rows: std::collections::HashSet<String> = stmt.query_map([], |row| {
-        let date: String = row.get(0)?;
-        let amount: f64 = row.get(1)?;
-        let txn_type: String = row.get(2)?;
-        Ok(format!("{}|{:.2}|{}", date, amount, txn_type))
-    }).unwrap().filter_map(Result::ok).collect();
-    Ok(rows)
-}
-
-fn load_categorization_rules(conn: &rusqlite::Connection) -> Result<Vec<CategorizationRule>, String> {
-    let mut stmt = conn.prepare(
-        "SELECT id, match_pattern, match_type, category_id, priority, created_at, updated_at 
-         FROM categorization_rules ORDER BY priority DESC"
-    ).map_err(|e| format!("Query error: {}", e))?;
-
-    let rules = stmt.query_map([], |row| {
-        Ok(CategorizationRule {
-            id: row.get(0)?,
-            match_pattern: row.get(1)?,
-            match_type: row.get(2)?,
-            category_id: row.get(3)?,
-            priority: row.get(4)?,
-            created_at: row.get(5)?,
-            updated_at: row.get(6)?,
-        })
-    }).unwrap().filter_map(Result::ok).collect();
-    
-    Ok(rules)
+/// Transactions that were in the database when an import started, looked up
+/// by date, type and amount through `idx_transactions_duplicate` rather than
+/// loaded up front. Rows the import adds itself are not counted, so repeated
+/// lines within one file all import.
+pub(crate) struct ExistingTransactions {
+    max_id: i64,
 }
 
-fn apply_categorization_rules(text: &str, rules: &[CategorizationRule], categories: &[(i64, String)]) -> Option<(i64, String)> {
-    let text_lower = text.to_lowercase();
-    
-    for rule in rules {
-        let matched = match rule.match_type.as_str() {
-            "exact" => text_lower == rule.match_pattern.to_lowercase(),
-            "contains" => text_lower.contains(&rule.match_pattern.to_lowercase()),
-            "starts_with" => text_lower.starts_with(&rule.match_pattern.to_lowercase()),
-            "regex" => {
-                if let Ok(re) = Regex::new(&rule.match_pattern) {
-                    re.is_match(text)
-                } else {
-                    false
-                }
-            },
-            _ => false,
-        };
+impl ExistingTransactions {
+    pub(crate) fn snapshot(conn: &rusqlite::Connection) -> Result<Self, String> {
+        let max_id = conn
+            .query_row("SELECT COALESCE(MAX(id), 0) FROM transactions", [], |row| row.get(0))
+            .map_err(|e| format!("Query error: {}", e))?;
+        Ok(ExistingTransactions { max_id })
+    }
 
-        if matched {
-            if let Ok(cat_id) = rule.category_id.parse::<i64>() {
-                if let Some((_, name)) = categories.iter().find(|(id, _)| *id == cat_id) {
-                    return Some((cat_id, name.clone()));
-                }
-            }
-        }
+    /// Whether a transaction with the same date, type and amount already
+    /// existed, in an account whose currency has the amount's decimals
+    pub(crate) fn contains(
+        &self,
+        conn: &rusqlite::Connection,
+        date: &str,
+        amount: Money,
+        txn_type: &str,
+    ) -> Result<bool, String> {
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT a.currency FROM transactions t
+                 INNER JOIN accounts a ON t.account_id = a.id
+                 WHERE t.date = ?1 AND t.type = ?2 AND t.amount = ?3 AND t.id <= ?4",
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+        let currencies = stmt
+            .query_map(params![date, txn_type, amount.minor(), self.max_id], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?;
+        Ok(currencies.iter().any(|currency| currency_decimals(currency) == amount.decimals()))
     }
-    None
 }
 
-fn fuzzy_match_name(input: &str, items: &[(i64, String)]) -> Option<i64> {
+pub(crate) fn fuzzy_match_name(input: &str, items: &[(i64, String)]) -> Option<i64> {
     let input_lower = input.trim().to_lowercase();
 
     for (id, name) in items {
@@ -778,7 +910,7 @@ fn fuzzy_match_name(input: &str, items: &[(i64, String)]) -> Option<i64> {
     None
 }
 
-fn find_best_match(input: &str, items: &[(i64, String)]) -> Option<(i64, String, f64)> {
+pub(crate) fn find_best_match(input: &str, items: &[(i64, String)]) -> Option<(i64, String, f64)> {
     let input_lower = input.trim().to_lowercase();
 
     for (id, name) in items {
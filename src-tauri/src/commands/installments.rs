@@ -1,13 +1,31 @@
 // File: src-tauri/src/commands/installments.rs
 use crate::models::installment::{
-    CreateInstallmentPlan, InstallmentPayment, InstallmentPaymentDetails, InstallmentPlan,
-    InstallmentPlanWithDetails,
+    AmortizationRow, CreateInstallmentPlan, InstallmentPayment, InstallmentPaymentDetails,
+    InstallmentPlan, InstallmentPlanWithDetails,
 };
+use crate::commands::currencies::{account_decimals, currency_decimals};
+use crate::commands::transactions::insert_transaction;
+use crate::models::money::Money;
+use crate::models::transactions::CreateTransactionInput;
 use crate::AppState;
 use chrono::{Duration, NaiveDate};
 use rusqlite::params;
 use tauri::State;
 
+const PLAN_COLUMNS: &str = r#"
+    id, name, total_amount, num_installments, amount_per_installment,
+    account_id, category_id, start_date, frequency, next_due_date,
+    installments_paid, total_paid, status, memo, created_at, updated_at,
+    apr, interest_method, processing_fee, liability_account_id, interest_category_id,
+    (SELECT currency FROM accounts WHERE id = installment_plans.account_id)
+"#;
+
+const PAYMENT_COLUMNS: &str = r#"
+    id, installment_plan_id, transaction_id, installment_number,
+    amount, due_date, paid_date, created_at,
+    principal, interest, fee, interest_transaction_id
+"#;
+
 #[tauri::command]
 pub fn create_installment_plan(
     state: State<'_, AppState>,
@@ -16,26 +34,37 @@ pub fn create_installment_plan(
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    if plan.total_amount <= 0.0 {
-        return Err("Total amount must be greater than 0".to_string());
-    }
     if plan.num_installments <= 0 {
         return Err("Number of installments must be greater than 0".to_string());
     }
 
-    let amount_per_installment =
-        (plan.total_amount / plan.num_installments as f64 * 100.0).round() / 100.0;
+    let decimals = account_decimals(&conn, plan.account_id)?;
+    let total_amount = Money::from_major(plan.total_amount, decimals);
+    if !total_amount.is_positive() {
+        return Err("Total amount must be greater than 0".to_string());
+    }
 
-    let account_exists: bool = conn.query_row(
-        "SELECT COUNT(id) FROM accounts WHERE id = ?1",
-        params![plan.account_id],
-        |row| row.get::<_, i64>(0),
-    ).unwrap_or(0) > 0;
+    let apr = plan.apr.unwrap_or(0.0);
+    if !apr.is_finite() || apr < 0.0 {
+        return Err("APR cannot be negative".to_string());
+    }
 
-    if !account_exists {
-        return Err("Account not found".to_string());
+    let processing_fee = Money::from_major(plan.processing_fee.unwrap_or(0.0), decimals);
+    if processing_fee.is_negative() {
+        return Err("Processing fee cannot be negative".to_string());
     }
 
+    let interest_method = plan
+        .interest_method
+        .clone()
+        .unwrap_or_else(|| "FLAT".to_string());
+
+    let account_currency: String = conn.query_row(
+        "SELECT currency FROM accounts WHERE id = ?1",
+        params![plan.account_id],
+        |row| row.get(0),
+    ).map_err(|_| "Account not found".to_string())?;
+
     let category_exists: bool = conn.query_row(
         "SELECT COUNT(id) FROM categories WHERE id = ?1",
         params![plan.category_id],
@@ -46,18 +75,67 @@ pub fn create_installment_plan(
         return Err("Category not found".to_string());
     }
 
-    let next_due_date = calculate_next_due_date(&plan.start_date, &plan.frequency, 1)?;
+    if let Some(liability_id) = plan.liability_account_id {
+        if liability_id == plan.account_id {
+            return Err("Liability account must differ from the paying account".to_string());
+        }
+
+        let (group_type, currency): (String, String) = conn.query_row(
+            r#"
+            SELECT g.type, a.currency
+            FROM accounts a
+            JOIN account_groups g ON a.group_id = g.id
+            WHERE a.id = ?1
+            "#,
+            params![liability_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).map_err(|_| "Liability account not found".to_string())?;
+
+        if group_type != "LIABILITY" {
+            return Err("Principal can only be posted against a liability account".to_string());
+        }
+        if currency != account_currency {
+            return Err("Liability account must use the same currency as the paying account".to_string());
+        }
+    }
+
+    if let Some(interest_category_id) = plan.interest_category_id {
+        let exists: bool = conn.query_row(
+            "SELECT COUNT(id) FROM categories WHERE id = ?1",
+            params![interest_category_id],
+            |row| row.get::<_, i64>(0),
+        ).unwrap_or(0) > 0;
+
+        if !exists {
+            return Err("Interest category not found".to_string());
+        }
+    }
+
+    let schedule = build_schedule(
+        total_amount,
+        apr,
+        &interest_method,
+        &plan.frequency,
+        plan.num_installments,
+        processing_fee,
+        &plan.start_date,
+    )?;
+
+    // The regular installment, i.e. the first one without the processing fee
+    let amount_per_installment = schedule[0].payment - schedule[0].fee;
+    let next_due_date = schedule[0].due_date.clone();
 
     conn.execute(
         r#"
         INSERT INTO installment_plans (
             name, total_amount, num_installments, amount_per_installment,
-            account_id, category_id, start_date, frequency, next_due_date, memo
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            account_id, category_id, start_date, frequency, next_due_date, memo,
+            apr, interest_method, processing_fee, liability_account_id, interest_category_id
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
         "#,
         params![
             plan.name,
-            plan.total_amount,
+            total_amount,
             plan.num_installments,
             amount_per_installment,
             plan.account_id,
@@ -65,7 +143,12 @@ pub fn create_installment_plan(
             plan.start_date,
             plan.frequency,
             next_due_date,
-            plan.memo
+            plan.memo,
+            apr,
+            interest_method,
+            processing_fee,
+            plan.liability_account_id,
+            plan.interest_category_id
         ],
     ).map_err(|e| e.to_string())?;
 
@@ -89,16 +172,10 @@ fn get_installment_plan_internal(
     conn: &rusqlite::Connection,
     plan_id: i64,
 ) -> Result<InstallmentPlan, String> {
-    let mut stmt = conn.prepare(
-        r#"
-        SELECT 
-            id, name, total_amount, num_installments, amount_per_installment,
-            account_id, category_id, start_date, frequency, next_due_date,
-            installments_paid, total_paid, status, memo, created_at, updated_at
-        FROM installment_plans
-        WHERE id = ?1
-        "#,
-    ).map_err(|e| e.to_string())?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM installment_plans WHERE id = ?1",
+        PLAN_COLUMNS
+    )).map_err(|e| e.to_string())?;
 
     stmt.query_row(params![plan_id], row_to_installment_plan)
         .map_err(|_| "Installment plan not found".to_string())
@@ -113,32 +190,19 @@ pub fn get_installment_plans(
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let plans = if let Some(status) = status_filter {
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT 
-                id, name, total_amount, num_installments, amount_per_installment,
-                account_id, category_id, start_date, frequency, next_due_date,
-                installments_paid, total_paid, status, memo, created_at, updated_at
-            FROM installment_plans
-            WHERE status = ?1
-            ORDER BY next_due_date ASC
-            "#,
-        ).unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM installment_plans WHERE status = ?1 ORDER BY next_due_date ASC",
+            PLAN_COLUMNS
+        )).unwrap();
         stmt.query_map(params![status], row_to_installment_plan)
             .unwrap()
             .filter_map(Result::ok)
             .collect()
     } else {
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT 
-                id, name, total_amount, num_installments, amount_per_installment,
-                account_id, category_id, start_date, frequency, next_due_date,
-                installments_paid, total_paid, status, memo, created_at, updated_at
-            FROM installment_plans
-            ORDER BY status ASC, next_due_date ASC
-            "#,
-        ).unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM installment_plans ORDER BY status ASC, next_due_date ASC",
+            PLAN_COLUMNS
+        )).unwrap();
         stmt.query_map([], row_to_installment_plan)
             .unwrap()
             .filter_map(Result::ok)
@@ -170,56 +234,50 @@ pub fn get_installment_plan_with_details(
         |row| row.get(0),
     ).map_err(|e| e.to_string())?;
 
-    let mut stmt = conn.prepare(
-        r#"
-        SELECT 
-            id, installment_plan_id, transaction_id, installment_number,
-            amount, due_date, paid_date, created_at
-        FROM installment_payments
-        WHERE installment_plan_id = ?1
-        ORDER BY installment_number ASC
-        "#,
-    ).map_err(|e| e.to_string())?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM installment_payments WHERE installment_plan_id = ?1 ORDER BY installment_number ASC",
+        PAYMENT_COLUMNS
+    )).map_err(|e| e.to_string())?;
 
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let decimals = plan.total_amount.decimals();
     let payment_details: Vec<InstallmentPaymentDetails> = stmt.query_map(params![plan_id], |row| {
-        let paid_date: String = row.get(6)?;
-        let status = if paid_date <= today {
+        let payment = row_to_payment(row, decimals)?;
+        let status = if payment.paid_date <= today {
             "PAID".to_string()
         } else {
             "PENDING".to_string()
         };
 
         Ok(InstallmentPaymentDetails {
-            installment_number: row.get(3)?,
-            amount: row.get(4)?,
-            due_date: row.get(5)?,
-            paid_date: Some(paid_date.clone()),
+            installment_number: payment.installment_number,
+            amount: payment.amount,
+            due_date: payment.due_date.clone(),
+            paid_date: Some(payment.paid_date.clone()),
             status,
-            payment: InstallmentPayment {
-                id: row.get(0)?,
-                installment_plan_id: row.get(1)?,
-                transaction_id: row.get(2)?,
-                installment_number: row.get(3)?,
-                amount: row.get(4)?,
-                due_date: row.get(5)?,
-                paid_date,
-                created_at: row.get(7)?,
-            },
+            payment,
         })
     }).unwrap().filter_map(Result::ok).collect();
 
-    let remaining_amount = plan.total_amount - plan.total_paid;
-    let remaining_installments = plan.num_installments - plan.installments_paid;
+    let schedule = plan_schedule(&plan)?;
+    let total_interest: Money = schedule.iter().map(|r| r.interest).sum();
+    let total_payable: Money = schedule.iter().map(|r| r.payment).sum();
+    let next_payment_amount = schedule
+        .get(plan.installments_paid as usize)
+        .map(|r| r.payment)
+        .unwrap_or(Money::zero(decimals));
 
     Ok(InstallmentPlanWithDetails {
-        plan: plan.clone(),
+        remaining_amount: total_payable - plan.total_paid,
+        remaining_installments: plan.num_installments - plan.installments_paid,
+        plan,
         payments: payment_details,
         account_name,
         category_name,
-        remaining_amount,
-        remaining_installments,
-        next_payment_amount: plan.amount_per_installment,
+        next_payment_amount,
+        total_interest,
+        total_payable,
+        schedule,
     })
 }
 
@@ -243,49 +301,97 @@ pub fn process_installment_payment(
 
     let today = chrono::Local::now().format("%Y-%m-%d").to_string();
     let next_installment_number = plan.installments_paid + 1;
-    let payment_amount = plan.amount_per_installment;
+    let row = plan_schedule(&plan)?
+        .swap_remove(plan.installments_paid as usize);
 
     let memo = format!(
         "Installment {}/{} for {}",
         next_installment_number, plan.num_installments, plan.name
     );
 
-    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
-
-    tx.execute(
-        r#"
-        INSERT INTO transactions (
-            type, date, account_id, category_id,
-            amount, memo
-        ) VALUES ('EXPENSE', ?1, ?2, ?3, ?4, ?5)
-        "#,
-        params![today, plan.account_id, plan.category_id, payment_amount, memo],
-    ).map_err(|e| e.to_string())?;
+    // Principal pays down the liability when one is linked, otherwise it is the expense itself
+    let (principal_type, principal_category) = match plan.liability_account_id {
+        Some(_) => ("TRANSFER", None),
+        None => ("EXPENSE", Some(plan.category_id)),
+    };
 
-    let transaction_id = tx.last_insert_rowid();
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
 
-    tx.execute(
-        r#"
-        INSERT INTO journal_entries (transaction_id, account_id, debit, credit)
-        VALUES (?1, ?2, 0.0, ?3)
-        "#,
-        params![transaction_id, plan.account_id, payment_amount],
-    ).map_err(|e| e.to_string())?;
+    // The plan is in the paying account's currency. A liability in another
+    // currency has no amount received, so the transfer is refused.
+    let transaction_id = insert_transaction(
+        &tx,
+        CreateTransactionInput {
+            date: today.clone(),
+            transaction_type: principal_type.to_string(),
+            amount: row.principal.to_major(),
+            account_id: plan.account_id,
+            to_account_id: plan.liability_account_id,
+            to_amount: None,
+            category_id: principal_category,
+            payee_id: None,
+            payee: None,
+            memo: Some(memo),
+            tag_ids: None,
+            goal_allocations: None,
+            goal_withdrawals: None,
+            splits: None,
+        },
+    )?;
+
+    let finance_charges = row.interest + row.fee;
+    let interest_transaction_id = if finance_charges.is_positive() {
+        Some(insert_transaction(
+            &tx,
+            CreateTransactionInput {
+                date: today.clone(),
+                transaction_type: "EXPENSE".to_string(),
+                amount: finance_charges.to_major(),
+                account_id: plan.account_id,
+                to_account_id: None,
+                to_amount: None,
+                category_id: Some(plan.interest_category_id.unwrap_or(plan.category_id)),
+                payee_id: None,
+                payee: None,
+                memo: Some(format!(
+                    "Interest for installment {}/{} for {}",
+                    next_installment_number, plan.num_installments, plan.name
+                )),
+                tag_ids: None,
+                goal_allocations: None,
+                goal_withdrawals: None,
+                splits: None,
+            },
+        )?)
+    } else {
+        None
+    };
 
     tx.execute(
         r#"
         INSERT INTO installment_payments (
             installment_plan_id, transaction_id, installment_number,
-            amount, due_date, paid_date
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            amount, due_date, paid_date, principal, interest, fee, interest_transaction_id
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
         "#,
-        params![plan_id, transaction_id, next_installment_number, payment_amount, plan.next_due_date, today],
+        params![
+            plan_id,
+            transaction_id,
+            next_installment_number,
+            row.payment,
+            plan.next_due_date,
+            today,
+            row.principal,
+            row.interest,
+            row.fee,
+            interest_transaction_id
+        ],
     ).map_err(|e| e.to_string())?;
 
     let payment_id = tx.last_insert_rowid();
 
     plan.installments_paid += 1;
-    plan.total_paid += payment_amount;
+    plan.total_paid += row.payment;
 
     let new_status = if plan.installments_paid >= plan.num_installments {
         "COMPLETED"
@@ -316,28 +422,14 @@ pub fn process_installment_payment(
         params![plan.installments_paid, plan.total_paid, next_due_date, new_status, plan_id],
     ).map_err(|e| e.to_string())?;
 
-    let mut stmt = tx.prepare(
-        r#"
-        SELECT 
-            id, installment_plan_id, transaction_id, installment_number,
-            amount, due_date, paid_date, created_at
-        FROM installment_payments
-        WHERE id = ?1
-        "#,
-    ).unwrap();
-
-    let payment = stmt.query_row(params![payment_id], |row| {
-        Ok(InstallmentPayment {
-            id: row.get(0)?,
-            installment_plan_id: row.get(1)?,
-            transaction_id: row.get(2)?,
-            installment_number: row.get(3)?,
-            amount: row.get(4)?,
-            due_date: row.get(5)?,
-            paid_date: row.get(6)?,
-            created_at: row.get(7)?,
-        })
-    }).map_err(|e| e.to_string())?;
+    let mut stmt = tx.prepare(&format!(
+        "SELECT {} FROM installment_payments WHERE id = ?1",
+        PAYMENT_COLUMNS
+    )).unwrap();
+
+    let payment = stmt
+        .query_row(params![payment_id], |row| row_to_payment(row, plan.total_amount.decimals()))
+        .map_err(|e| e.to_string())?;
 
     drop(stmt);
     tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
@@ -413,19 +505,17 @@ pub fn get_upcoming_installments(
         .format("%Y-%m-%d")
         .to_string();
 
-    let mut stmt = conn.prepare(
+    let mut stmt = conn.prepare(&format!(
         r#"
-        SELECT 
-            id, name, total_amount, num_installments, amount_per_installment,
-            account_id, category_id, start_date, frequency, next_due_date,
-            installments_paid, total_paid, status, memo, created_at, updated_at
+        SELECT {}
         FROM installment_plans
         WHERE status = 'ACTIVE'
           AND next_due_date >= ?1
           AND next_due_date <= ?2
         ORDER BY next_due_date ASC
         "#,
-    ).map_err(|e| e.to_string())?;
+        PLAN_COLUMNS
+    )).map_err(|e| e.to_string())?;
 
     let plans: Vec<InstallmentPlan> = stmt.query_map(params![today, future_date], row_to_installment_plan)
         .unwrap()
@@ -435,24 +525,155 @@ pub fn get_upcoming_installments(
     Ok(plans)
 }
 
+/// Schedule for a stored plan, with installments marked PAID, OVERDUE or PENDING.
+fn plan_schedule(plan: &InstallmentPlan) -> Result<Vec<AmortizationRow>, String> {
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let mut schedule = build_schedule(
+        plan.total_amount,
+        plan.apr,
+        &plan.interest_method,
+        &plan.frequency,
+        plan.num_installments,
+        plan.processing_fee,
+        &plan.start_date,
+    )?;
+
+    for row in schedule.iter_mut() {
+        if row.installment_number <= plan.installments_paid {
+            row.status = "PAID".to_string();
+        } else if row.due_date < today {
+            row.status = "OVERDUE".to_string();
+        }
+    }
+
+    Ok(schedule)
+}
+
+/// Splits `principal` plus interest into installments.
+///
+/// FLAT charges `apr` on the original principal for the whole term and spreads
+/// it evenly; REDUCING_BALANCE charges the periodic rate on the outstanding
+/// balance with a level payment. Rounding remainders land on the final
+/// installment, and the processing fee is added to the first.
+pub fn build_schedule(
+    principal: Money,
+    apr: f64,
+    interest_method: &str,
+    frequency: &str,
+    num_installments: i32,
+    processing_fee: Money,
+    start_date: &str,
+) -> Result<Vec<AmortizationRow>, String> {
+    if num_installments <= 0 {
+        return Err("Number of installments must be greater than 0".to_string());
+    }
+
+    let decimals = principal.decimals();
+    let n = num_installments as i64;
+    let rate = apr / 100.0 / periods_per_year(frequency)?;
+
+    let flat_interest = principal.mul_f64(rate * n as f64, decimals);
+    let level_payment = if rate > 0.0 {
+        principal.mul_f64(rate / (1.0 - (1.0 + rate).powi(-num_installments)), decimals)
+    } else {
+        principal.div_round(n)
+    };
+
+    let mut balance = principal;
+    let mut schedule = Vec::with_capacity(num_installments as usize);
+
+    for number in 1..=num_installments {
+        let last = number == num_installments;
+
+        let (principal_part, interest) = match interest_method {
+            "FLAT" => {
+                let interest_part = flat_interest.div_round(n);
+                if last {
+                    (balance, flat_interest - interest_part * (n - 1))
+                } else {
+                    (principal.div_round(n), interest_part)
+                }
+            }
+            "REDUCING_BALANCE" => {
+                let interest = balance.mul_f64(rate, decimals);
+                if last {
+                    (balance, interest)
+                } else {
+                    ((level_payment - interest).min(balance), interest)
+                }
+            }
+            _ => return Err("Invalid interest method".to_string()),
+        };
+
+        balance -= principal_part;
+        let fee = if number == 1 { processing_fee } else { Money::zero(decimals) };
+
+        schedule.push(AmortizationRow {
+            installment_number: number,
+            due_date: calculate_next_due_date(start_date, frequency, number)?,
+            payment: principal_part + interest + fee,
+            principal: principal_part,
+            interest,
+            fee,
+            remaining_balance: balance,
+            status: "PENDING".to_string(),
+        });
+    }
+
+    Ok(schedule)
+}
+
+fn periods_per_year(frequency: &str) -> Result<f64, String> {
+    match frequency {
+        "MONTHLY" => Ok(12.0),
+        "WEEKLY" => Ok(52.0),
+        "DAILY" => Ok(365.0),
+        _ => Err("Invalid frequency".to_string()),
+    }
+}
+
+/// Expects the plan account's currency in column 21.
 fn row_to_installment_plan(row: &rusqlite::Row) -> rusqlite::Result<InstallmentPlan> {
+    let decimals = currency_decimals(&row.get::<_, String>(21)?);
     Ok(InstallmentPlan {
         id: row.get(0)?,
         name: row.get(1)?,
-        total_amount: row.get(2)?,
+        total_amount: Money::new(row.get(2)?, decimals),
         num_installments: row.get(3)?,
-        amount_per_installment: row.get(4)?,
+        amount_per_installment: Money::new(row.get(4)?, decimals),
         account_id: row.get(5)?,
         category_id: row.get(6)?,
         start_date: row.get(7)?,
         frequency: row.get(8)?,
         next_due_date: row.get(9)?,
         installments_paid: row.get(10)?,
-        total_paid: row.get(11)?,
+        total_paid: Money::new(row.get(11)?, decimals),
         status: row.get(12)?,
         memo: row.get(13)?,
         created_at: row.get(14)?,
         updated_at: row.get(15)?,
+        apr: row.get(16)?,
+        interest_method: row.get(17)?,
+        processing_fee: Money::new(row.get(18)?, decimals),
+        liability_account_id: row.get(19)?,
+        interest_category_id: row.get(20)?,
+    })
+}
+
+fn row_to_payment(row: &rusqlite::Row, decimals: u32) -> rusqlite::Result<InstallmentPayment> {
+    Ok(InstallmentPayment {
+        id: row.get(0)?,
+        installment_plan_id: row.get(1)?,
+        transaction_id: row.get(2)?,
+        installment_number: row.get(3)?,
+        amount: Money::new(row.get(4)?, decimals),
+        due_date: row.get(5)?,
+        paid_date: row.get(6)?,
+        created_at: row.get(7)?,
+        principal: Money::new(row.get(8)?, decimals),
+        interest: Money::new(row.get(9)?, decimals),
+        fee: Money::new(row.get(10)?, decimals),
+        interest_transaction_id: row.get(11)?,
     })
 }
 
@@ -473,3 +694,49 @@ fn calculate_next_due_date(
 
     Ok(next_date.format("%Y-%m-%d").to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_schedule_spreads_interest_evenly() {
+        let principal = Money::new(120000, 2);
+        let schedule = build_schedule(
+            principal, 12.0, "FLAT", "MONTHLY", 12, Money::new(2500, 2), "2024-01-01",
+        ).unwrap();
+
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(schedule[0].interest, Money::new(1200, 2));
+        assert_eq!(schedule[0].payment, Money::new(13700, 2));
+        assert_eq!(schedule[1].payment, Money::new(11200, 2));
+        assert!(schedule[11].remaining_balance.is_zero());
+    }
+
+    #[test]
+    fn reducing_balance_schedule_amortizes_to_zero() {
+        let principal = Money::new(1000000, 2);
+        let schedule = build_schedule(
+            principal, 12.0, "REDUCING_BALANCE", "MONTHLY", 12, Money::zero(2), "2024-01-01",
+        ).unwrap();
+
+        // 1% per month on 10,000 over 12 months is a level payment of 888.49
+        assert_eq!(schedule[0].interest, Money::new(10000, 2));
+        assert_eq!(schedule[0].payment, Money::new(88849, 2));
+        assert!(schedule[1].interest < schedule[0].interest);
+        assert!(schedule[11].remaining_balance.is_zero());
+
+        let principal_paid: Money = schedule.iter().map(|r| r.principal).sum();
+        assert_eq!(principal_paid, principal);
+    }
+
+    #[test]
+    fn zero_apr_matches_even_split() {
+        let schedule = build_schedule(
+            Money::new(10000, 2), 0.0, "REDUCING_BALANCE", "WEEKLY", 3, Money::zero(2), "2024-01-01",
+        ).unwrap();
+
+        let payments: Vec<i64> = schedule.iter().map(|r| r.payment.minor()).collect();
+        assert_eq!(payments, vec![3333, 3333, 3334]);
+    }
+}
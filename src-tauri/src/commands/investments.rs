@@ -0,0 +1,1162 @@
+// File: src-tauri/src/commands/investments.rs
+use crate::commands::currencies::{currency_decimals, get_primary_currency_internal, RateConverter};
+use crate::commands::transactions::{delete_transaction_internal, insert_transaction};
+use crate::models::investment::{
+    CreateInvestmentTransactionInput, CreateSecurityInput, Holding, InvestmentAccount,
+    InvestmentGainReport, InvestmentTransaction, Lot, Security, SecurityGain, SecurityPrice,
+    SetInvestmentAccountInput,
+};
+use crate::models::money::Money;
+use crate::models::transactions::CreateTransactionInput;
+use crate::AppState;
+use chrono::NaiveDate;
+use rusqlite::params;
+use tauri::State;
+
+/// Quantities below this are treated as zero, so fractional sells can close a lot.
+const QUANTITY_EPSILON: f64 = 1e-9;
+
+const SECURITY_COLUMNS: &str = r#"
+    s.id, s.symbol, s.name, s.currency,
+    (SELECT price FROM security_prices WHERE security_id = s.id ORDER BY date DESC LIMIT 1),
+    (SELECT MAX(date) FROM security_prices WHERE security_id = s.id),
+    s.created_at
+"#;
+
+const ACCOUNT_COLUMNS: &str = r#"
+    ia.account_id, a.name, a.currency, ia.cash_account_id, ia.cost_basis_method,
+    ia.income_category_id, ia.created_at
+"#;
+
+const TRANSACTION_COLUMNS: &str = r#"
+    it.id, it.account_id, it.security_id, s.symbol, it.type, it.date, it.quantity,
+    it.price, it.fees, it.amount, it.split_ratio, it.realized_gain, it.cash_account_id,
+    it.transaction_id, it.gain_transaction_id, it.memo, it.created_at, a.currency
+"#;
+
+/// One investment transaction as seen by the lot replay.
+#[derive(Debug, Clone)]
+pub struct PositionEvent {
+    pub id: i64,
+    pub kind: String,
+    pub date: String,
+    pub quantity: f64,
+    pub amount: Money,
+    pub split_ratio: Option<f64>,
+}
+
+/// Open lots after a replay, with the gain realized by each SELL.
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub quantity: f64,
+    pub cost_basis: Money,
+    pub lots: Vec<Lot>,
+    pub sell_gains: Vec<(i64, Money)>,
+}
+
+// ======================== SECURITIES & PRICES ========================
+
+#[tauri::command]
+pub fn create_security(
+    state: State<'_, AppState>,
+    input: CreateSecurityInput,
+) -> Result<Security, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let symbol = input.symbol.trim().to_uppercase();
+    if symbol.is_empty() {
+        return Err("Symbol is required".to_string());
+    }
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err("Security name is required".to_string());
+    }
+    let currency = match input.currency {
+        Some(code) => code.trim().to_uppercase(),
+        None => get_primary_currency_internal(&conn)?,
+    };
+
+    conn.execute(
+        "INSERT INTO securities (symbol, name, currency) VALUES (?1, ?2, ?3)",
+        params![symbol, name, currency],
+    )
+    .map_err(|e| {
+        if e.to_string().contains("UNIQUE") {
+            format!("Security '{}' already exists", symbol)
+        } else {
+            format!("Failed to create security: {}", e)
+        }
+    })?;
+
+    fetch_security(&conn, conn.last_insert_rowid())
+}
+
+#[tauri::command]
+pub fn get_securities(state: State<'_, AppState>) -> Result<Vec<Security>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM securities s ORDER BY s.symbol", SECURITY_COLUMNS))
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let securities = stmt
+        .query_map([], row_to_security)
+        .map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(securities)
+}
+
+#[tauri::command]
+pub fn delete_security(state: State<'_, AppState>, security_id: i64) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let in_use: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM investment_transactions WHERE security_id = ?1",
+            params![security_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+    if in_use > 0 {
+        return Err("Cannot delete a security that has investment transactions".to_string());
+    }
+
+    let affected = conn
+        .execute("DELETE FROM securities WHERE id = ?1", params![security_id])
+        .map_err(|e| format!("Failed to delete security: {}", e))?;
+
+    if affected == 0 {
+        return Err("Security not found".to_string());
+    }
+
+    Ok(())
+}
+
+/// Records the closing price for a date, replacing any price already set for it.
+#[tauri::command]
+pub fn set_security_price(
+    state: State<'_, AppState>,
+    security_id: i64,
+    date: String,
+    price: f64,
+) -> Result<SecurityPrice, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    fetch_security(&conn, security_id)?;
+    parse_date(&date)?;
+    if !price.is_finite() || price < 0.0 {
+        return Err("Price cannot be negative".to_string());
+    }
+
+    conn.execute(
+        r#"
+        INSERT INTO security_prices (security_id, date, price) VALUES (?1, ?2, ?3)
+        ON CONFLICT(security_id, date) DO UPDATE SET price = excluded.price
+        "#,
+        params![security_id, date, price],
+    )
+    .map_err(|e| format!("Failed to set price: {}", e))?;
+
+    conn.query_row(
+        "SELECT id, security_id, date, price FROM security_prices WHERE security_id = ?1 AND date = ?2",
+        params![security_id, date],
+        row_to_price,
+    )
+    .map_err(|e| format!("Database error: {}", e))
+}
+
+#[tauri::command]
+pub fn get_security_prices(
+    state: State<'_, AppState>,
+    security_id: i64,
+) -> Result<Vec<SecurityPrice>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, security_id, date, price FROM security_prices
+            WHERE security_id = ?1
+            ORDER BY date DESC
+            "#,
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let prices = stmt
+        .query_map(params![security_id], row_to_price)
+        .map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(prices)
+}
+
+#[tauri::command]
+pub fn delete_security_price(state: State<'_, AppState>, price_id: i64) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let affected = conn
+        .execute("DELETE FROM security_prices WHERE id = ?1", params![price_id])
+        .map_err(|e| format!("Failed to delete price: {}", e))?;
+
+    if affected == 0 {
+        return Err("Price not found".to_string());
+    }
+
+    Ok(())
+}
+
+// ======================== INVESTMENT ACCOUNTS ========================
+
+/// Marks an ASSET account as holding securities, or updates its settings.
+#[tauri::command]
+pub fn set_investment_account(
+    state: State<'_, AppState>,
+    input: SetInvestmentAccountInput,
+) -> Result<InvestmentAccount, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let (group_type, currency): (String, String) = conn
+        .query_row(
+            r#"
+            SELECT g.type, a.currency
+            FROM accounts a
+            JOIN account_groups g ON a.group_id = g.id
+            WHERE a.id = ?1
+            "#,
+            params![input.account_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|_| "Account not found".to_string())?;
+
+    if group_type != "ASSET" {
+        return Err("Investment accounts must be asset accounts".to_string());
+    }
+
+    let cash_account_id = input.cash_account_id.filter(|id| *id != input.account_id);
+    if let Some(cash_id) = cash_account_id {
+        let cash_currency: String = conn
+            .query_row(
+                "SELECT currency FROM accounts WHERE id = ?1",
+                params![cash_id],
+                |row| row.get(0),
+            )
+            .map_err(|_| "Cash account not found".to_string())?;
+
+        if cash_currency != currency {
+            return Err("Cash account must use the same currency as the investment account".to_string());
+        }
+    }
+
+    let method = input.cost_basis_method.unwrap_or_else(|| "FIFO".to_string());
+    if method != "FIFO" && method != "AVERAGE" {
+        return Err("Cost basis method must be FIFO or AVERAGE".to_string());
+    }
+
+    let current_method: Option<String> = conn
+        .query_row(
+            "SELECT cost_basis_method FROM investment_accounts WHERE account_id = ?1",
+            params![input.account_id],
+            |row| row.get(0),
+        )
+        .ok();
+
+    if current_method.is_some_and(|m| m != method) {
+        let sales: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM investment_transactions WHERE account_id = ?1 AND type = 'SELL'",
+                params![input.account_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        // Posted gains were computed with the old method
+        if sales > 0 {
+            return Err("Cost basis method cannot change once the account has sales".to_string());
+        }
+    }
+
+    conn.execute(
+        r#"
+        INSERT INTO investment_accounts (account_id, cash_account_id, cost_basis_method, income_category_id)
+        VALUES (?1, ?2, ?3, ?4)
+        ON CONFLICT(account_id) DO UPDATE SET
+            cash_account_id = excluded.cash_account_id,
+            cost_basis_method = excluded.cost_basis_method,
+            income_category_id = excluded.income_category_id
+        "#,
+        params![input.account_id, cash_account_id, method, input.income_category_id],
+    )
+    .map_err(|e| format!("Failed to save investment account: {}", e))?;
+
+    fetch_investment_account(&conn, input.account_id)
+}
+
+#[tauri::command]
+pub fn get_investment_accounts(state: State<'_, AppState>) -> Result<Vec<InvestmentAccount>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            r#"
+            SELECT {}
+            FROM investment_accounts ia
+            JOIN accounts a ON ia.account_id = a.id
+            ORDER BY a.name
+            "#,
+            ACCOUNT_COLUMNS
+        ))
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let accounts = stmt
+        .query_map([], row_to_account)
+        .map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(accounts)
+}
+
+// ======================== TRANSACTIONS ========================
+
+/// Records a BUY, SELL, DIVIDEND or SPLIT and posts its cash side to the ledger.
+/// Purchases move cash from the cash account into the investment account at
+/// cost; sales move the proceeds back and post the realized gain or loss on
+/// the investment account, so its book balance stays at cost basis.
+#[tauri::command]
+pub fn create_investment_transaction(
+    state: State<'_, AppState>,
+    input: CreateInvestmentTransactionInput,
+) -> Result<InvestmentTransaction, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    create_investment_transaction_internal(&mut conn, input)
+}
+
+pub fn create_investment_transaction_internal(
+    conn: &mut rusqlite::Connection,
+    input: CreateInvestmentTransactionInput,
+) -> Result<InvestmentTransaction, String> {
+    let account = fetch_investment_account(conn, input.account_id)?;
+    let security = fetch_security(conn, input.security_id)?;
+    if security.currency != account.currency {
+        return Err(format!(
+            "{} is priced in {} but the account holds {}",
+            security.symbol, security.currency, account.currency
+        ));
+    }
+    parse_date(&input.date)?;
+
+    let last_sale: Option<String> = conn
+        .query_row(
+            r#"
+            SELECT MAX(date) FROM investment_transactions
+            WHERE account_id = ?1 AND security_id = ?2 AND type = 'SELL'
+            "#,
+            params![account.account_id, security.id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    if input.transaction_type != "DIVIDEND" && last_sale.as_ref().is_some_and(|d| input.date < *d) {
+        return Err(format!(
+            "Transactions for {} cannot be dated before its last sale on {}",
+            security.symbol,
+            last_sale.unwrap_or_default()
+        ));
+    }
+
+    let decimals = currency_decimals(&account.currency);
+    let quantity = input.quantity.unwrap_or(0.0);
+    let price = input.price.unwrap_or(0.0);
+    let fees = Money::from_major(input.fees.unwrap_or(0.0), decimals);
+
+    if !quantity.is_finite() || !price.is_finite() || price < 0.0 {
+        return Err("Quantity and price must be valid numbers".to_string());
+    }
+    if fees.is_negative() {
+        return Err("Fees cannot be negative".to_string());
+    }
+
+    let amount = match input.transaction_type.as_str() {
+        "BUY" | "SELL" => {
+            if quantity <= 0.0 {
+                return Err("Quantity must be greater than 0".to_string());
+            }
+            let gross = Money::from_major(quantity * price, decimals);
+            if input.transaction_type == "BUY" {
+                gross + fees
+            } else if fees > gross {
+                return Err("Fees cannot exceed the sale proceeds".to_string());
+            } else {
+                gross - fees
+            }
+        }
+        "DIVIDEND" => {
+            let amount = Money::from_major(input.amount.unwrap_or(0.0), decimals);
+            if !amount.is_positive() {
+                return Err("Dividend amount must be greater than 0".to_string());
+            }
+            amount
+        }
+        "SPLIT" => {
+            if !input.split_ratio.is_some_and(|r| r.is_finite() && r > 0.0) {
+                return Err("Split ratio must be greater than 0".to_string());
+            }
+            Money::zero(decimals)
+        }
+        _ => return Err("Transaction type must be BUY, SELL, DIVIDEND or SPLIT".to_string()),
+    };
+
+    // Replay with the new row last to validate quantities and price the sale
+    let mut events = fetch_events(conn, account.account_id, security.id, None)?;
+    events.push(PositionEvent {
+        id: i64::MAX,
+        kind: input.transaction_type.clone(),
+        date: input.date.clone(),
+        quantity,
+        amount,
+        split_ratio: input.split_ratio,
+    });
+    events.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id)));
+    let position = replay_position(&events, &account.cost_basis_method, decimals)?;
+    let realized_gain = position
+        .sell_gains
+        .iter()
+        .find(|(id, _)| *id == i64::MAX)
+        .map(|(_, gain)| *gain)
+        .unwrap_or(Money::zero(decimals));
+
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    tx.execute(
+        r#"
+        INSERT INTO investment_transactions (
+            account_id, security_id, type, date, quantity, price, fees, amount,
+            split_ratio, realized_gain, cash_account_id, memo
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+        "#,
+        params![
+            account.account_id,
+            security.id,
+            input.transaction_type,
+            input.date,
+            quantity,
+            price,
+            fees,
+            amount,
+            input.split_ratio,
+            realized_gain,
+            account.cash_account_id,
+            input.memo
+        ],
+    )
+    .map_err(|e| format!("Failed to create investment transaction: {}", e))?;
+    let investment_id = tx.last_insert_rowid();
+
+    let cash_account_id = account.cash_account_id;
+    let label = format!("{} {} {}", input.transaction_type, quantity, security.symbol);
+    let mut transaction_id = None;
+    let mut gain_transaction_id = None;
+
+    match input.transaction_type.as_str() {
+        "BUY" => {
+            if let Some(cash_id) = cash_account_id {
+                transaction_id = Some(post_cash(
+                    &tx, "TRANSFER", &input.date, amount, cash_id, Some(account.account_id), None, &label,
+                )?);
+            }
+        }
+        "SELL" => {
+            if let (Some(cash_id), true) = (cash_account_id, amount.is_positive()) {
+                transaction_id = Some(post_cash(
+                    &tx, "TRANSFER", &input.date, amount, account.account_id, Some(cash_id), None, &label,
+                )?);
+            }
+            let memo = format!("Realized gain on {}", security.symbol);
+            if realized_gain.is_positive() {
+                gain_transaction_id = Some(post_cash(
+                    &tx, "INCOME", &input.date, realized_gain, account.account_id, None,
+                    account.income_category_id, &memo,
+                )?);
+            } else if realized_gain.is_negative() {
+                gain_transaction_id = Some(post_cash(
+                    &tx, "EXPENSE", &input.date, -realized_gain, account.account_id, None, None, &memo,
+                )?);
+            }
+        }
+        "DIVIDEND" => {
+            transaction_id = Some(post_cash(
+                &tx,
+                "INCOME",
+                &input.date,
+                amount,
+                cash_account_id.unwrap_or(account.account_id),
+                None,
+                account.income_category_id,
+                &format!("Dividend from {}", security.symbol),
+            )?);
+        }
+        _ => {}
+    }
+
+    tx.execute(
+        "UPDATE investment_transactions SET transaction_id = ?1, gain_transaction_id = ?2 WHERE id = ?3",
+        params![transaction_id, gain_transaction_id, investment_id],
+    )
+    .map_err(|e| format!("Failed to link transactions: {}", e))?;
+
+    tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
+
+    fetch_investment_transaction(conn, investment_id)
+}
+
+#[tauri::command]
+pub fn get_investment_transactions(
+    state: State<'_, AppState>,
+    account_id: Option<i64>,
+) -> Result<Vec<InvestmentTransaction>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            r#"
+            SELECT {}
+            FROM investment_transactions it
+            JOIN securities s ON it.security_id = s.id
+            JOIN accounts a ON it.account_id = a.id
+            WHERE ?1 IS NULL OR it.account_id = ?1
+            ORDER BY it.date DESC, it.id DESC
+            "#,
+            TRANSACTION_COLUMNS
+        ))
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let transactions = stmt
+        .query_map(params![account_id], row_to_transaction)
+        .map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(transactions)
+}
+
+/// Deletes an investment transaction and the ledger transactions it posted.
+/// Anything a later sale's cost basis depends on has to go after that sale.
+#[tauri::command]
+pub fn delete_investment_transaction(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let existing = fetch_investment_transaction(&conn, id)?;
+
+    if existing.transaction_type != "DIVIDEND" {
+        let later_sales: i64 = conn
+            .query_row(
+                r#"
+                SELECT COUNT(*) FROM investment_transactions
+                WHERE account_id = ?1 AND security_id = ?2 AND type = 'SELL'
+                  AND (date > ?3 OR (date = ?3 AND id > ?4))
+                "#,
+                params![existing.account_id, existing.security_id, existing.date, id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        if later_sales > 0 {
+            return Err("Delete the later sales of this security first".to_string());
+        }
+    }
+
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    for transaction_id in [existing.transaction_id, existing.gain_transaction_id].into_iter().flatten() {
+        delete_transaction_internal(&tx, transaction_id)?;
+    }
+
+    tx.execute("DELETE FROM investment_transactions WHERE id = ?1", params![id])
+        .map_err(|e| format!("Failed to delete investment transaction: {}", e))?;
+
+    tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
+
+    Ok(())
+}
+
+// ======================== HOLDINGS & GAINS ========================
+
+/// Open positions valued at the latest price.
+#[tauri::command]
+pub fn get_holdings(state: State<'_, AppState>, account_id: Option<i64>) -> Result<Vec<Holding>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut holdings = Vec::new();
+
+    for (account, security) in positions(&conn, account_id)? {
+        let decimals = currency_decimals(&account.currency);
+        let events = fetch_events(&conn, account.account_id, security.id, None)?;
+        let position = replay_position(&events, &account.cost_basis_method, decimals)?;
+        if position.quantity <= QUANTITY_EPSILON {
+            continue;
+        }
+
+        let (price, price_date) = match price_at(&conn, security.id, None)? {
+            Some((price, date)) => (Some(price), Some(date)),
+            None => (None, None),
+        };
+        let market_value = price
+            .map(|p| Money::from_major(position.quantity * p, decimals))
+            .unwrap_or(position.cost_basis);
+        let unrealized_gain = market_value - position.cost_basis;
+
+        let (realized_gain, dividends): (i64, i64) = conn
+            .query_row(
+                r#"
+                SELECT COALESCE(SUM(CASE WHEN type = 'SELL' THEN realized_gain ELSE 0 END), 0),
+                       COALESCE(SUM(CASE WHEN type = 'DIVIDEND' THEN amount ELSE 0 END), 0)
+                FROM investment_transactions
+                WHERE account_id = ?1 AND security_id = ?2
+                "#,
+                params![account.account_id, security.id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        holdings.push(Holding {
+            account_id: account.account_id,
+            account_name: account.account_name.clone(),
+            security_id: security.id,
+            symbol: security.symbol.clone(),
+            name: security.name.clone(),
+            quantity: position.quantity,
+            cost_basis: position.cost_basis,
+            average_cost: position.cost_basis.to_major() / position.quantity,
+            price,
+            price_date,
+            market_value,
+            unrealized_gain,
+            unrealized_gain_percentage: (unrealized_gain.ratio(position.cost_basis) * 100.0 * 100.0).round() / 100.0,
+            realized_gain: Money::new(realized_gain, decimals),
+            dividends: Money::new(dividends, decimals),
+            lots: position.lots,
+        });
+    }
+
+    Ok(holdings)
+}
+
+/// Realized gains and dividends between the dates, unrealized gains at the end date.
+#[tauri::command]
+pub fn get_investment_gains(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+    account_id: Option<i64>,
+) -> Result<InvestmentGainReport, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    parse_date(&start_date)?;
+    parse_date(&end_date)?;
+
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let mut report = InvestmentGainReport {
+        start_date: start_date.clone(),
+        end_date: end_date.clone(),
+        currency: converter.target.clone(),
+        realized_gain: converter.zero(),
+        dividends: converter.zero(),
+        unrealized_gain: converter.zero(),
+        securities: Vec::new(),
+        missing_rates: Vec::new(),
+    };
+
+    for (account, security) in positions(&conn, account_id)? {
+        let decimals = currency_decimals(&account.currency);
+
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT date, type, realized_gain, amount
+                FROM investment_transactions
+                WHERE account_id = ?1 AND security_id = ?2 AND type IN ('SELL', 'DIVIDEND')
+                  AND date >= ?3 AND date <= ?4
+                "#,
+            )
+            .map_err(|e| format!("Database error: {}", e))?;
+        let rows: Vec<(String, String, i64, i64)> = stmt
+            .query_map(params![account.account_id, security.id, start_date, end_date], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .map_err(|e| format!("Database error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        let mut realized = Money::zero(decimals);
+        let mut dividends = Money::zero(decimals);
+        for (date, kind, gain, amount) in rows {
+            let (original, total) = if kind == "SELL" {
+                (Money::new(gain, decimals), &mut report.realized_gain)
+            } else {
+                (Money::new(amount, decimals), &mut report.dividends)
+            };
+            if kind == "SELL" {
+                realized += original;
+            } else {
+                dividends += original;
+            }
+            if let Some(converted) = converter.convert(original, &account.currency, &date) {
+                *total += converted;
+            }
+        }
+
+        let unrealized = position_gain_at(&conn, &account, security.id, Some(&end_date))?;
+        if let Some(converted) = converter.convert(unrealized, &account.currency, &end_date) {
+            report.unrealized_gain += converted;
+        }
+
+        if realized.is_zero() && dividends.is_zero() && unrealized.is_zero() {
+            continue;
+        }
+
+        report.securities.push(SecurityGain {
+            account_id: account.account_id,
+            security_id: security.id,
+            symbol: security.symbol.clone(),
+            currency: account.currency.clone(),
+            realized_gain: realized,
+            dividends,
+            unrealized_gain: unrealized,
+        });
+    }
+
+    report.missing_rates = converter.missing_rates();
+    Ok(report)
+}
+
+/// Market value minus cost basis of every holding on `as_of_date` (latest when
+/// None), per account currency. Net worth adds this to the book balances.
+pub fn unrealized_gains_at(
+    conn: &rusqlite::Connection,
+    as_of_date: Option<&str>,
+) -> Result<Vec<(String, Money)>, String> {
+    let mut totals: Vec<(String, Money)> = Vec::new();
+
+    for (account, security) in positions(conn, None)? {
+        let gain = position_gain_at(conn, &account, security.id, as_of_date)?;
+        match totals.iter_mut().find(|(currency, _)| *currency == account.currency) {
+            Some((_, total)) => *total += gain,
+            None => totals.push((account.currency.clone(), gain)),
+        }
+    }
+
+    Ok(totals)
+}
+
+/// Replays BUY/SELL/SPLIT events (sorted by date, then id) into open lots.
+/// FIFO sells consume the oldest lots first; AVERAGE pools the cost so every
+/// lot carries the same unit cost. Fails if a sale exceeds the quantity held.
+pub fn replay_position(events: &[PositionEvent], method: &str, decimals: u32) -> Result<Position, String> {
+    let mut lots: Vec<Lot> = Vec::new();
+    let mut sell_gains = Vec::new();
+
+    for event in events {
+        match event.kind.as_str() {
+            "BUY" => {
+                lots.push(Lot {
+                    buy_id: event.id,
+                    date: event.date.clone(),
+                    quantity: event.quantity,
+                    cost: event.amount,
+                });
+                if method == "AVERAGE" {
+                    pool_cost(&mut lots, decimals);
+                }
+            }
+            "SELL" => {
+                let held: f64 = lots.iter().map(|l| l.quantity).sum();
+                if event.quantity > held + QUANTITY_EPSILON {
+                    return Err(format!(
+                        "Cannot sell {} units on {}; only {} held",
+                        event.quantity, event.date, held
+                    ));
+                }
+
+                let mut removed = Money::zero(decimals);
+                let mut remaining = event.quantity;
+                for lot in lots.iter_mut() {
+                    if remaining <= QUANTITY_EPSILON {
+                        break;
+                    }
+                    let take = remaining.min(lot.quantity);
+                    let part = if take >= lot.quantity - QUANTITY_EPSILON {
+                        lot.cost
+                    } else {
+                        lot.cost.mul_f64(take / lot.quantity, decimals)
+                    };
+                    lot.quantity -= take;
+                    lot.cost -= part;
+                    removed += part;
+                    remaining -= take;
+                }
+                lots.retain(|l| l.quantity > QUANTITY_EPSILON);
+                if method == "AVERAGE" {
+                    pool_cost(&mut lots, decimals);
+                }
+
+                sell_gains.push((event.id, event.amount - removed));
+            }
+            "SPLIT" => {
+                let ratio = event.split_ratio.unwrap_or(1.0);
+                for lot in lots.iter_mut() {
+                    lot.quantity *= ratio;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Position {
+        quantity: lots.iter().map(|l| l.quantity).sum(),
+        cost_basis: lots.iter().map(|l| l.cost).sum::<Money>().rescale(decimals),
+        lots,
+        sell_gains,
+    })
+}
+
+/// Spreads the total cost over the lots by quantity, the last lot taking the remainder.
+fn pool_cost(lots: &mut [Lot], decimals: u32) {
+    let total_quantity: f64 = lots.iter().map(|l| l.quantity).sum();
+    let mut unallocated = lots.iter().map(|l| l.cost).sum::<Money>().rescale(decimals);
+    let total_cost = unallocated;
+    let count = lots.len();
+
+    for (i, lot) in lots.iter_mut().enumerate() {
+        lot.cost = if i + 1 == count {
+            unallocated
+        } else {
+            total_cost.mul_f64(lot.quantity / total_quantity, decimals)
+        };
+        unallocated -= lot.cost;
+    }
+}
+
+// ======================== HELPERS ========================
+
+fn position_gain_at(
+    conn: &rusqlite::Connection,
+    account: &InvestmentAccount,
+    security_id: i64,
+    as_of_date: Option<&str>,
+) -> Result<Money, String> {
+    let decimals = currency_decimals(&account.currency);
+    let events = fetch_events(conn, account.account_id, security_id, as_of_date)?;
+    let position = replay_position(&events, &account.cost_basis_method, decimals)?;
+
+    // Unpriced holdings stay at cost
+    match price_at(conn, security_id, as_of_date)? {
+        Some((price, _)) if position.quantity > QUANTITY_EPSILON => {
+            Ok(Money::from_major(position.quantity * price, decimals) - position.cost_basis)
+        }
+        _ => Ok(Money::zero(decimals)),
+    }
+}
+
+/// Every (account, security) pair with investment transactions
+fn positions(
+    conn: &rusqlite::Connection,
+    account_id: Option<i64>,
+) -> Result<Vec<(InvestmentAccount, Security)>, String> {
+    let pairs: Vec<(i64, i64)> = {
+        let mut stmt = conn
+            .prepare(
+                r#"
+                SELECT DISTINCT it.account_id, it.security_id
+                FROM investment_transactions it
+                JOIN accounts a ON it.account_id = a.id
+                JOIN securities s ON it.security_id = s.id
+                WHERE ?1 IS NULL OR it.account_id = ?1
+                ORDER BY a.name, s.symbol
+                "#,
+            )
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![account_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Database error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Database error: {}", e))?;
+        rows
+    };
+
+    pairs
+        .into_iter()
+        .map(|(account_id, security_id)| {
+            Ok((fetch_investment_account(conn, account_id)?, fetch_security(conn, security_id)?))
+        })
+        .collect()
+}
+
+fn fetch_events(
+    conn: &rusqlite::Connection,
+    account_id: i64,
+    security_id: i64,
+    as_of_date: Option<&str>,
+) -> Result<Vec<PositionEvent>, String> {
+    let decimals = crate::commands::currencies::account_decimals(conn, account_id)?;
+    let mut stmt = conn
+        .prepare(
+            r#"
+            SELECT id, type, date, quantity, amount, split_ratio
+            FROM investment_transactions
+            WHERE account_id = ?1 AND security_id = ?2 AND (?3 IS NULL OR date <= ?3)
+            ORDER BY date, id
+            "#,
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let events = stmt
+        .query_map(params![account_id, security_id, as_of_date], |row| {
+            Ok(PositionEvent {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                date: row.get(2)?,
+                quantity: row.get(3)?,
+                amount: Money::new(row.get(4)?, decimals),
+                split_ratio: row.get(5)?,
+            })
+        })
+        .map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(events)
+}
+
+/// Latest price on or before the date (or overall when None)
+fn price_at(
+    conn: &rusqlite::Connection,
+    security_id: i64,
+    as_of_date: Option<&str>,
+) -> Result<Option<(f64, String)>, String> {
+    let result = conn.query_row(
+        r#"
+        SELECT price, date FROM security_prices
+        WHERE security_id = ?1 AND (?2 IS NULL OR date <= ?2)
+        ORDER BY date DESC
+        LIMIT 1
+        "#,
+        params![security_id, as_of_date],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    );
+
+    match result {
+        Ok(price) => Ok(Some(price)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(format!("Database error: {}", e)),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn post_cash(
+    conn: &rusqlite::Connection,
+    transaction_type: &str,
+    date: &str,
+    amount: Money,
+    account_id: i64,
+    to_account_id: Option<i64>,
+    category_id: Option<i64>,
+    memo: &str,
+) -> Result<i64, String> {
+    // The cash account shares the investment account's currency, so a
+    // transfer delivers the amount it sends
+    insert_transaction(
+        conn,
+        CreateTransactionInput {
+            date: date.to_string(),
+            transaction_type: transaction_type.to_string(),
+            amount: amount.to_major(),
+            account_id,
+            to_account_id,
+            to_amount: to_account_id.map(|_| amount.to_major()),
+            category_id,
+            payee_id: None,
+            payee: None,
+            memo: Some(memo.to_string()),
+            tag_ids: None,
+            goal_allocations: None,
+            goal_withdrawals: None,
+            splits: None,
+        },
+    )
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("Invalid date: {}", e))
+}
+
+fn fetch_security(conn: &rusqlite::Connection, security_id: i64) -> Result<Security, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM securities s WHERE s.id = ?1", SECURITY_COLUMNS),
+        params![security_id],
+        row_to_security,
+    )
+    .map_err(|_| "Security not found".to_string())
+}
+
+fn fetch_investment_account(conn: &rusqlite::Connection, account_id: i64) -> Result<InvestmentAccount, String> {
+    conn.query_row(
+        &format!(
+            r#"
+            SELECT {}
+            FROM investment_accounts ia
+            JOIN accounts a ON ia.account_id = a.id
+            WHERE ia.account_id = ?1
+            "#,
+            ACCOUNT_COLUMNS
+        ),
+        params![account_id],
+        row_to_account,
+    )
+    .map_err(|_| "Account is not set up for investments".to_string())
+}
+
+fn fetch_investment_transaction(conn: &rusqlite::Connection, id: i64) -> Result<InvestmentTransaction, String> {
+    conn.query_row(
+        &format!(
+            r#"
+            SELECT {}
+            FROM investment_transactions it
+            JOIN securities s ON it.security_id = s.id
+            JOIN accounts a ON it.account_id = a.id
+            WHERE it.id = ?1
+            "#,
+            TRANSACTION_COLUMNS
+        ),
+        params![id],
+        row_to_transaction,
+    )
+    .map_err(|_| "Investment transaction not found".to_string())
+}
+
+fn row_to_security(row: &rusqlite::Row) -> rusqlite::Result<Security> {
+    Ok(Security {
+        id: row.get(0)?,
+        symbol: row.get(1)?,
+        name: row.get(2)?,
+        currency: row.get(3)?,
+        latest_price: row.get(4)?,
+        latest_price_date: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+fn row_to_price(row: &rusqlite::Row) -> rusqlite::Result<SecurityPrice> {
+    Ok(SecurityPrice {
+        id: row.get(0)?,
+        security_id: row.get(1)?,
+        date: row.get(2)?,
+        price: row.get(3)?,
+    })
+}
+
+fn row_to_account(row: &rusqlite::Row) -> rusqlite::Result<InvestmentAccount> {
+    Ok(InvestmentAccount {
+        account_id: row.get(0)?,
+        account_name: row.get(1)?,
+        currency: row.get(2)?,
+        cash_account_id: row.get(3)?,
+        cost_basis_method: row.get(4)?,
+        income_category_id: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+/// Expects the account currency in column 17.
+fn row_to_transaction(row: &rusqlite::Row) -> rusqlite::Result<InvestmentTransaction> {
+    let currency: String = row.get(17)?;
+    let decimals = currency_decimals(&currency);
+
+    Ok(InvestmentTransaction {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        security_id: row.get(2)?,
+        symbol: row.get(3)?,
+        transaction_type: row.get(4)?,
+        date: row.get(5)?,
+        quantity: row.get(6)?,
+        price: row.get(7)?,
+        fees: Money::new(row.get(8)?, decimals),
+        amount: Money::new(row.get(9)?, decimals),
+        split_ratio: row.get(10)?,
+        realized_gain: Money::new(row.get(11)?, decimals),
+        cash_account_id: row.get(12)?,
+        transaction_id: row.get(13)?,
+        gain_transaction_id: row.get(14)?,
+        memo: row.get(15)?,
+        created_at: row.get(16)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: i64, kind: &str, quantity: f64, amount: i64) -> PositionEvent {
+        PositionEvent {
+            id,
+            kind: kind.to_string(),
+            date: format!("2024-01-{:02}", id),
+            quantity,
+            amount: Money::new(amount, 2),
+            split_ratio: None,
+        }
+    }
+
+    #[test]
+    fn fifo_sells_oldest_lots_first() {
+        let events = vec![
+            event(1, "BUY", 10.0, 100000),
+            event(2, "BUY", 10.0, 200000),
+            event(3, "SELL", 15.0, 300000),
+        ];
+        let position = replay_position(&events, "FIFO", 2).unwrap();
+
+        // 10 @ 100 + 5 @ 200 = 2000 cost against 3000 proceeds
+        assert_eq!(position.sell_gains, vec![(3, Money::new(100000, 2))]);
+        assert_eq!(position.cost_basis, Money::new(100000, 2));
+        assert_eq!(position.lots.len(), 1);
+    }
+
+    #[test]
+    fn average_cost_pools_lots_and_splits_keep_cost() {
+        let mut events = vec![
+            event(1, "BUY", 10.0, 100000),
+            event(2, "BUY", 10.0, 200000),
+            event(3, "SELL", 15.0, 300000),
+        ];
+        let position = replay_position(&events, "AVERAGE", 2).unwrap();
+        assert_eq!(position.sell_gains, vec![(3, Money::new(75000, 2))]);
+        assert_eq!(position.cost_basis, Money::new(75000, 2));
+
+        events.push(PositionEvent { split_ratio: Some(2.0), ..event(4, "SPLIT", 0.0, 0) });
+        let position = replay_position(&events, "AVERAGE", 2).unwrap();
+        assert!((position.quantity - 10.0).abs() < QUANTITY_EPSILON);
+        assert_eq!(position.cost_basis, Money::new(75000, 2));
+
+        events.push(event(5, "SELL", 11.0, 100));
+        assert!(replay_position(&events, "AVERAGE", 2).is_err());
+    }
+}
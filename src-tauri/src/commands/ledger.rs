@@ -0,0 +1,334 @@
+// File: src-tauri/src/commands/ledger.rs
+use crate::commands::currencies::currency_decimals;
+use crate::commands::transactions::insert_journal_entries;
+use crate::models::ledger::{LedgerIssue, LedgerReport};
+use crate::models::money::Money;
+use crate::AppState;
+use rusqlite::params;
+use std::collections::{BTreeSet, HashMap};
+use tauri::State;
+
+/// Check that every transaction's journal lines match what its row implies.
+/// With `repair = true`, orphan lines are deleted and the lines of every
+/// repairable transaction are regenerated from the `transactions` row.
+#[tauri::command]
+pub fn verify_ledger(
+    state: State<'_, AppState>,
+    repair: Option<bool>,
+) -> Result<LedgerReport, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    verify_ledger_internal(&mut conn, repair.unwrap_or(false))
+}
+
+struct LedgerTransaction {
+    id: i64,
+    transaction_type: String,
+    amount: Money,
+    account_id: i64,
+    to_account_id: Option<i64>,
+    to_decimals: Option<u32>,
+    to_amount: Option<i64>,
+    cross_currency: bool,
+}
+
+struct JournalLine {
+    id: i64,
+    account_id: i64,
+    debit: Money,
+    credit: Money,
+}
+
+pub fn verify_ledger_internal(
+    conn: &mut rusqlite::Connection,
+    repair: bool,
+) -> Result<LedgerReport, String> {
+    let mut issues: Vec<LedgerIssue> = Vec::new();
+
+    // 1. Dangling foreign keys anywhere in the schema
+    let mut orphan_entry_ids: BTreeSet<i64> = BTreeSet::new();
+    let mut bad_account_entries: BTreeSet<i64> = BTreeSet::new();
+    {
+        let mut stmt = conn
+            .prepare("PRAGMA foreign_key_check")
+            .map_err(|e| format!("Query error: {}", e))?;
+        let violations: Vec<(String, Option<i64>, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?;
+
+        for (table, rowid, parent) in violations {
+            if table == "journal_entries" && parent == "transactions" {
+                if let Some(id) = rowid {
+                    orphan_entry_ids.insert(id);
+                }
+                continue;
+            }
+            if table == "journal_entries" && parent == "accounts" {
+                // Regenerating the owning transaction's lines fixes these
+                if let Some(id) = rowid {
+                    bad_account_entries.insert(id);
+                }
+                continue;
+            }
+            issues.push(LedgerIssue {
+                kind: "DANGLING_REFERENCE".to_string(),
+                transaction_id: if table == "transactions" { rowid } else { None },
+                journal_entry_id: None,
+                message: format!(
+                    "{} row {} references a missing row in {}",
+                    table,
+                    rowid.map(|id| id.to_string()).unwrap_or_else(|| "?".to_string()),
+                    parent
+                ),
+                repairable: false,
+            });
+        }
+    }
+
+    for id in &orphan_entry_ids {
+        issues.push(LedgerIssue {
+            kind: "ORPHAN_JOURNAL_ENTRY".to_string(),
+            transaction_id: None,
+            journal_entry_id: Some(*id),
+            message: format!("Journal entry {} belongs to a transaction that no longer exists", id),
+            repairable: true,
+        });
+    }
+
+    // 2. Load transactions whose accounts still exist
+    let transactions: Vec<LedgerTransaction> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.id, t.type, t.amount, t.account_id, t.to_account_id, a.currency, ta.currency, t.to_amount
+                 FROM transactions t
+                 INNER JOIN accounts a ON t.account_id = a.id
+                 LEFT JOIN accounts ta ON t.to_account_id = ta.id
+                 ORDER BY t.id",
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                let currency: String = row.get(5)?;
+                let to_currency: Option<String> = row.get(6)?;
+                Ok(LedgerTransaction {
+                    id: row.get(0)?,
+                    transaction_type: row.get(1)?,
+                    amount: Money::new(row.get(2)?, currency_decimals(&currency)),
+                    account_id: row.get(3)?,
+                    to_account_id: row.get(4)?,
+                    to_decimals: to_currency.as_deref().map(currency_decimals),
+                    to_amount: row.get(7)?,
+                    cross_currency: to_currency.is_some_and(|c| !c.eq_ignore_ascii_case(&currency)),
+                })
+            })
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?;
+        rows
+    };
+
+    let transactions_checked: i64 = conn
+        .query_row("SELECT COUNT(*) FROM transactions", [], |row| row.get(0))
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    // 3. Load journal lines grouped by transaction
+    let mut lines_by_transaction: HashMap<i64, Vec<JournalLine>> = HashMap::new();
+    let journal_entries_checked = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT je.id, je.transaction_id, je.account_id, je.debit, je.credit, a.currency
+                 FROM journal_entries je
+                 LEFT JOIN accounts a ON je.account_id = a.id
+                 ORDER BY je.id",
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                let currency: Option<String> = row.get(5)?;
+                let decimals = currency_decimals(&currency.unwrap_or_default());
+                Ok((
+                    row.get::<_, i64>(1)?,
+                    JournalLine {
+                        id: row.get(0)?,
+                        account_id: row.get(2)?,
+                        debit: Money::new(row.get(3)?, decimals),
+                        credit: Money::new(row.get(4)?, decimals),
+                    },
+                ))
+            })
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?;
+        let count = rows.len() as i64;
+        for (transaction_id, line) in rows {
+            lines_by_transaction.entry(transaction_id).or_default().push(line);
+        }
+        count
+    };
+
+    // 4. Compare each transaction against the lines its row implies
+    let mut to_regenerate: Vec<&LedgerTransaction> = Vec::new();
+
+    for txn in &transactions {
+        if txn.transaction_type == "TRANSFER" && (txn.to_account_id.is_none() || txn.to_decimals.is_none()) {
+            issues.push(LedgerIssue {
+                kind: "DANGLING_REFERENCE".to_string(),
+                transaction_id: Some(txn.id),
+                journal_entry_id: None,
+                message: format!("Transfer {} has no valid destination account", txn.id),
+                repairable: false,
+            });
+            continue;
+        }
+
+        let expected = expected_lines(txn);
+        let actual: Vec<(i64, i64, i64)> = {
+            let mut lines: Vec<(i64, i64, i64)> = lines_by_transaction
+                .get(&txn.id)
+                .map(|lines| {
+                    lines
+                        .iter()
+                        .map(|l| (l.account_id, l.debit.minor(), l.credit.minor()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            lines.sort_unstable();
+            lines
+        };
+
+        let issue = if actual.is_empty() {
+            Some((
+                "MISSING_JOURNAL_ENTRIES",
+                format!("Transaction {} has no journal entries", txn.id),
+            ))
+        } else if txn.transaction_type == "TRANSFER"
+            && !txn.cross_currency
+            && !is_balanced(&lines_by_transaction[&txn.id])
+        {
+            Some((
+                "UNBALANCED_TRANSFER",
+                format!("Transfer {} debits and credits do not match", txn.id),
+            ))
+        } else if actual != expected {
+            let bad_accounts = lines_by_transaction[&txn.id]
+                .iter()
+                .filter(|l| bad_account_entries.contains(&l.id))
+                .count();
+            let message = if bad_accounts > 0 {
+                format!("Transaction {} has journal entries posted to a missing account", txn.id)
+            } else {
+                format!(
+                    "Transaction {} {} of {} ({} journal entries, expected {})",
+                    txn.id,
+                    txn.transaction_type.to_lowercase(),
+                    txn.amount,
+                    actual.len(),
+                    expected.len()
+                )
+            };
+            Some(("JOURNAL_MISMATCH", message))
+        } else {
+            None
+        };
+
+        if let Some((kind, message)) = issue {
+            issues.push(LedgerIssue {
+                kind: kind.to_string(),
+                transaction_id: Some(txn.id),
+                journal_entry_id: None,
+                message,
+                repairable: true,
+            });
+            to_regenerate.push(txn);
+        }
+    }
+
+    let is_consistent = issues.is_empty();
+    let mut transactions_repaired = 0;
+    let mut journal_entries_removed = 0;
+
+    // 5. Opt-in repair
+    if repair && (!orphan_entry_ids.is_empty() || !to_regenerate.is_empty()) {
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Transaction error: {}", e))?;
+
+        for id in &orphan_entry_ids {
+            journal_entries_removed += tx
+                .execute("DELETE FROM journal_entries WHERE id = ?1", params![id])
+                .map_err(|e| format!("Failed to remove orphan journal entry: {}", e))?
+                as i64;
+        }
+
+        for txn in &to_regenerate {
+            journal_entries_removed += tx
+                .execute(
+                    "DELETE FROM journal_entries WHERE transaction_id = ?1",
+                    params![txn.id],
+                )
+                .map_err(|e| format!("Failed to clear journal entries: {}", e))?
+                as i64;
+
+            insert_journal_entries(
+                &tx,
+                txn.id,
+                &txn.transaction_type,
+                txn.account_id,
+                txn.to_account_id,
+                txn.amount,
+                transfer_to_amount(txn),
+            )?;
+            transactions_repaired += 1;
+        }
+
+        tx.commit()
+            .map_err(|e| format!("Failed to commit ledger repair: {}", e))?;
+    }
+
+    Ok(LedgerReport {
+        transactions_checked,
+        journal_entries_checked,
+        issues,
+        is_consistent,
+        repaired: repair,
+        transactions_repaired,
+        journal_entries_removed,
+    })
+}
+
+/// Destination-side amount of a transfer, in the destination account's currency.
+fn transfer_to_amount(txn: &LedgerTransaction) -> Money {
+    let to_decimals = txn.to_decimals.unwrap_or(txn.amount.decimals());
+    match txn.to_amount {
+        Some(minor) => Money::new(minor, to_decimals),
+        None => txn.amount.rescale(to_decimals),
+    }
+}
+
+/// Sorted (account_id, debit, credit) lines the transaction row implies.
+fn expected_lines(txn: &LedgerTransaction) -> Vec<(i64, i64, i64)> {
+    let amount = txn.amount.minor();
+    let mut lines = match txn.transaction_type.as_str() {
+        "INCOME" => vec![(txn.account_id, amount, 0)],
+        "EXPENSE" => vec![(txn.account_id, 0, amount)],
+        "TRANSFER" => match txn.to_account_id {
+            Some(to_account_id) => vec![
+                (txn.account_id, 0, amount),
+                (to_account_id, transfer_to_amount(txn).minor(), 0),
+            ],
+            None => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    lines.sort_unstable();
+    lines
+}
+
+fn is_balanced(lines: &[JournalLine]) -> bool {
+    let debit: Money = lines.iter().map(|l| l.debit).sum();
+    let credit: Money = lines.iter().map(|l| l.credit).sum();
+    debit == credit
+}
@@ -0,0 +1,798 @@
+// File: src-tauri/src/commands/loans.rs
+use crate::commands::currencies::currency_decimals;
+use crate::commands::transactions::insert_transaction;
+use crate::models::loan::{
+    CreateLoanInput, Loan, LoanDetails, LoanPayment, LoanPrepaymentInput, LoanPrepaymentPreview,
+    LoanScheduleRow,
+};
+use crate::models::money::Money;
+use crate::models::transactions::CreateTransactionInput;
+use crate::AppState;
+use chrono::{Datelike, Months, NaiveDate};
+use rusqlite::params;
+use tauri::State;
+
+/// Upper bound on generated schedules, so a payment that barely covers
+/// interest cannot loop for centuries.
+const MAX_LOAN_PAYMENTS: i32 = 1200;
+
+const LOAN_COLUMNS: &str = r#"
+    id, account_id, payment_account_id, interest_category_id, principal,
+    annual_rate, term_months, start_date, payment_day, emi, status,
+    created_at, updated_at,
+    (SELECT currency FROM accounts WHERE id = loans.account_id)
+"#;
+
+const PAYMENT_COLUMNS: &str = r#"
+    id, loan_id, transaction_id, interest_transaction_id, kind, payment_number,
+    payment_date, principal, interest, strategy, created_at
+"#;
+
+#[tauri::command]
+pub fn create_loan(state: State<'_, AppState>, input: CreateLoanInput) -> Result<Loan, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    create_loan_internal(&conn, input)
+}
+
+pub fn create_loan_internal(conn: &rusqlite::Connection, input: CreateLoanInput) -> Result<Loan, String> {
+    if input.term_months <= 0 {
+        return Err("Loan term must be at least one month".to_string());
+    }
+    if !(1..=28).contains(&input.payment_day) {
+        return Err("Payment day must be between 1 and 28".to_string());
+    }
+    if !input.annual_rate.is_finite() || input.annual_rate < 0.0 {
+        return Err("Interest rate cannot be negative".to_string());
+    }
+    let start = NaiveDate::parse_from_str(&input.start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+
+    let (group_type, currency): (String, String) = conn
+        .query_row(
+            r#"
+            SELECT g.type, a.currency
+            FROM accounts a
+            JOIN account_groups g ON a.group_id = g.id
+            WHERE a.id = ?1
+            "#,
+            params![input.account_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|_| "Loan account not found".to_string())?;
+
+    if group_type != "LIABILITY" {
+        return Err("A loan must be attached to a liability account".to_string());
+    }
+
+    let has_loan: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM loans WHERE account_id = ?1",
+            params![input.account_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+    if has_loan > 0 {
+        return Err("This account already has a loan attached".to_string());
+    }
+
+    check_payment_account(conn, input.payment_account_id, input.account_id, &currency)?;
+
+    if let Some(category_id) = input.interest_category_id {
+        let exists: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM categories WHERE id = ?1",
+                params![category_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Database error: {}", e))?;
+        if exists == 0 {
+            return Err("Interest category not found".to_string());
+        }
+    }
+
+    let principal = Money::from_major(input.principal, currency_decimals(&currency));
+    if !principal.is_positive() {
+        return Err("Principal must be greater than 0".to_string());
+    }
+
+    let monthly_rate = input.annual_rate / 1200.0;
+    let emi = annuity_payment(principal, monthly_rate, input.term_months);
+    // Fail early if the terms cannot produce a schedule
+    amortize(principal, monthly_rate, emi, start, input.payment_day, 1)?;
+
+    conn.execute(
+        r#"
+        INSERT INTO loans (
+            account_id, payment_account_id, interest_category_id, principal,
+            annual_rate, term_months, start_date, payment_day, emi
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+        "#,
+        params![
+            input.account_id,
+            input.payment_account_id,
+            input.interest_category_id,
+            principal,
+            input.annual_rate,
+            input.term_months,
+            input.start_date,
+            input.payment_day,
+            emi
+        ],
+    )
+    .map_err(|e| format!("Failed to create loan: {}", e))?;
+
+    fetch_loan(conn, conn.last_insert_rowid())
+}
+
+#[tauri::command]
+pub fn get_loans(state: State<'_, AppState>) -> Result<Vec<Loan>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM loans ORDER BY status ASC, start_date DESC",
+            LOAN_COLUMNS
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let loans = stmt
+        .query_map([], row_to_loan)
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    Ok(loans)
+}
+
+#[tauri::command]
+pub fn get_loan_details(state: State<'_, AppState>, loan_id: i64) -> Result<LoanDetails, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    get_loan_details_internal(&conn, loan_id)
+}
+
+pub fn get_loan_details_internal(conn: &rusqlite::Connection, loan_id: i64) -> Result<LoanDetails, String> {
+    let loan = fetch_loan(conn, loan_id)?;
+    let decimals = loan.principal.decimals();
+    let start = parse_date(&loan.start_date)?;
+    let monthly_rate = loan.annual_rate / 1200.0;
+
+    let account_name: String = conn
+        .query_row(
+            "SELECT name FROM accounts WHERE id = ?1",
+            params![loan.account_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let payments = fetch_payments(conn, &loan)?;
+    let principal_paid: Money = payments.iter().map(|p| p.principal).sum();
+    let interest_paid: Money = payments.iter().map(|p| p.interest).sum();
+    let payments_made = payments.iter().filter(|p| p.kind == "SCHEDULED").count() as i32;
+    let outstanding_principal = (loan.principal - principal_paid).max(Money::zero(decimals));
+
+    let schedule = if outstanding_principal.is_positive() {
+        amortize(outstanding_principal, monthly_rate, loan.emi, start, loan.payment_day, payments_made + 1)?
+    } else {
+        Vec::new()
+    };
+    let projected_interest: Money = schedule.iter().map(|r| r.interest).sum();
+
+    let original = amortize(
+        loan.principal,
+        monthly_rate,
+        annuity_payment(loan.principal, monthly_rate, loan.term_months),
+        start,
+        loan.payment_day,
+        1,
+    )?;
+    let original_total_interest: Money = original.iter().map(|r| r.interest).sum();
+    let original_payoff_date = original
+        .last()
+        .map(|r| r.due_date.clone())
+        .unwrap_or_else(|| loan.start_date.clone());
+
+    Ok(LoanDetails {
+        account_name,
+        outstanding_principal,
+        principal_paid,
+        interest_paid,
+        payments_made,
+        payments,
+        remaining_payments: schedule.len() as i32,
+        projected_payoff_date: schedule.last().map(|r| r.due_date.clone()),
+        projected_interest,
+        original_payoff_date,
+        original_total_interest,
+        interest_saved: (original_total_interest - interest_paid - projected_interest)
+            .max(Money::zero(decimals)),
+        schedule,
+        loan,
+    })
+}
+
+#[tauri::command]
+pub fn delete_loan(state: State<'_, AppState>, loan_id: i64) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    // Posted payments stay in the ledger; only the loan tracking is removed
+    let affected = conn
+        .execute("DELETE FROM loans WHERE id = ?1", params![loan_id])
+        .map_err(|e| format!("Failed to delete loan: {}", e))?;
+
+    if affected == 0 {
+        return Err("Loan not found".to_string());
+    }
+
+    Ok(())
+}
+
+/// Posts the next scheduled payment: principal as a transfer into the loan
+/// account and interest as an expense, both from the loan's payment account.
+#[tauri::command]
+pub fn process_loan_payment(
+    state: State<'_, AppState>,
+    loan_id: i64,
+    date: Option<String>,
+) -> Result<LoanPayment, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    process_loan_payment_internal(&mut conn, loan_id, date)
+}
+
+pub fn process_loan_payment_internal(
+    conn: &mut rusqlite::Connection,
+    loan_id: i64,
+    date: Option<String>,
+) -> Result<LoanPayment, String> {
+    let details = get_loan_details_internal(conn, loan_id)?;
+    let loan = &details.loan;
+
+    if loan.status != "ACTIVE" {
+        return Err("This loan is already closed".to_string());
+    }
+    let row = details
+        .schedule
+        .first()
+        .cloned()
+        .ok_or("This loan has no outstanding balance")?;
+
+    let date = date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    parse_date(&date)?;
+
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    let transaction_id = post_principal(
+        &tx,
+        loan,
+        loan.payment_account_id,
+        row.principal,
+        &date,
+        &format!("Loan payment {} - {}", row.payment_number, details.account_name),
+    )?;
+
+    let interest_transaction_id = if row.interest.is_positive() {
+        Some(insert_transaction(
+            &tx,
+            CreateTransactionInput {
+                date: date.clone(),
+                transaction_type: "EXPENSE".to_string(),
+                amount: row.interest.to_major(),
+                account_id: loan.payment_account_id,
+                to_account_id: None,
+                to_amount: None,
+                category_id: loan.interest_category_id,
+                payee_id: None,
+                payee: None,
+                memo: Some(format!("Loan interest {} - {}", row.payment_number, details.account_name)),
+                tag_ids: None,
+                goal_allocations: None,
+                goal_withdrawals: None,
+                splits: None,
+            },
+        )?)
+    } else {
+        None
+    };
+
+    tx.execute(
+        r#"
+        INSERT INTO loan_payments (
+            loan_id, transaction_id, interest_transaction_id, kind, payment_number,
+            payment_date, principal, interest
+        ) VALUES (?1, ?2, ?3, 'SCHEDULED', ?4, ?5, ?6, ?7)
+        "#,
+        params![
+            loan.id,
+            transaction_id,
+            interest_transaction_id,
+            row.payment_number,
+            date,
+            row.principal,
+            row.interest
+        ],
+    )
+    .map_err(|e| format!("Failed to record loan payment: {}", e))?;
+
+    let payment_id = tx.last_insert_rowid();
+    close_if_paid(&tx, loan, row.remaining_balance)?;
+
+    tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
+
+    fetch_payment(conn, payment_id, loan.principal.decimals())
+}
+
+#[tauri::command]
+pub fn preview_loan_prepayment(
+    state: State<'_, AppState>,
+    input: LoanPrepaymentInput,
+) -> Result<LoanPrepaymentPreview, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let details = get_loan_details_internal(&conn, input.loan_id)?;
+    let amount = Money::from_major(input.amount, details.loan.principal.decimals());
+
+    let (new_emi, schedule) = apply_prepayment(&details, amount, &input.strategy)?;
+    let projected_interest: Money = schedule.iter().map(|r| r.interest).sum();
+
+    Ok(LoanPrepaymentPreview {
+        strategy: input.strategy,
+        new_emi,
+        remaining_payments: schedule.len() as i32,
+        projected_payoff_date: schedule.last().map(|r| r.due_date.clone()),
+        projected_interest,
+        interest_saved: details.projected_interest - projected_interest,
+        payments_saved: details.remaining_payments - schedule.len() as i32,
+    })
+}
+
+/// Applies an extra principal payment. REDUCE_TERM keeps the EMI and pays the
+/// loan off sooner; REDUCE_EMI keeps the remaining term and lowers the EMI.
+#[tauri::command]
+pub fn make_loan_prepayment(
+    state: State<'_, AppState>,
+    input: LoanPrepaymentInput,
+) -> Result<LoanPayment, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    make_loan_prepayment_internal(&mut conn, input)
+}
+
+pub fn make_loan_prepayment_internal(
+    conn: &mut rusqlite::Connection,
+    input: LoanPrepaymentInput,
+) -> Result<LoanPayment, String> {
+    let details = get_loan_details_internal(conn, input.loan_id)?;
+    let loan = &details.loan;
+
+    if loan.status != "ACTIVE" {
+        return Err("This loan is already closed".to_string());
+    }
+
+    let amount = Money::from_major(input.amount, loan.principal.decimals());
+    let (new_emi, _) = apply_prepayment(&details, amount, &input.strategy)?;
+
+    let from_account_id = input.from_account_id.unwrap_or(loan.payment_account_id);
+
+    let date = input
+        .date
+        .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    parse_date(&date)?;
+
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    let transaction_id = post_principal(
+        &tx,
+        loan,
+        from_account_id,
+        amount,
+        &date,
+        &format!("Loan prepayment - {}", details.account_name),
+    )?;
+
+    tx.execute(
+        r#"
+        INSERT INTO loan_payments (
+            loan_id, transaction_id, kind, payment_date, principal, strategy
+        ) VALUES (?1, ?2, 'PREPAYMENT', ?3, ?4, ?5)
+        "#,
+        params![loan.id, transaction_id, date, amount, input.strategy],
+    )
+    .map_err(|e| format!("Failed to record prepayment: {}", e))?;
+
+    let payment_id = tx.last_insert_rowid();
+
+    tx.execute(
+        "UPDATE loans SET emi = ?1, updated_at = datetime('now') WHERE id = ?2",
+        params![new_emi, loan.id],
+    )
+    .map_err(|e| format!("Failed to update loan: {}", e))?;
+
+    close_if_paid(&tx, loan, details.outstanding_principal - amount)?;
+
+    tx.commit().map_err(|e| format!("Failed to commit: {}", e))?;
+
+    fetch_payment(conn, payment_id, loan.principal.decimals())
+}
+
+/// The EMI and remaining schedule after prepaying `amount`.
+fn apply_prepayment(
+    details: &LoanDetails,
+    amount: Money,
+    strategy: &str,
+) -> Result<(Money, Vec<LoanScheduleRow>), String> {
+    let loan = &details.loan;
+
+    if !amount.is_positive() {
+        return Err("Prepayment amount must be greater than 0".to_string());
+    }
+    if amount > details.outstanding_principal {
+        return Err(format!(
+            "Prepayment exceeds the outstanding principal of {}",
+            details.outstanding_principal
+        ));
+    }
+
+    let balance = details.outstanding_principal - amount;
+    if balance.is_zero() {
+        return Ok((loan.emi, Vec::new()));
+    }
+
+    let monthly_rate = loan.annual_rate / 1200.0;
+    let new_emi = match strategy {
+        "REDUCE_TERM" => loan.emi,
+        "REDUCE_EMI" => annuity_payment(balance, monthly_rate, details.remaining_payments.max(1)),
+        _ => return Err("Strategy must be REDUCE_TERM or REDUCE_EMI".to_string()),
+    };
+
+    let schedule = amortize(
+        balance,
+        monthly_rate,
+        new_emi,
+        parse_date(&loan.start_date)?,
+        loan.payment_day,
+        details.payments_made + 1,
+    )?;
+
+    Ok((new_emi, schedule))
+}
+
+/// Level monthly payment that clears `principal` in `months`. Rounded up to
+/// the next minor unit so the final payment never leaves a residue.
+pub fn annuity_payment(principal: Money, monthly_rate: f64, months: i32) -> Money {
+    let minor = principal.minor() as f64;
+    let payment = if monthly_rate > 0.0 {
+        minor * monthly_rate / (1.0 - (1.0 + monthly_rate).powi(-months))
+    } else {
+        minor / months as f64
+    };
+    Money::new((payment - 1e-6).ceil() as i64, principal.decimals())
+}
+
+/// Runs `balance` down at `emi` per month; the last payment is whatever is left.
+/// Payment numbers start at `first_number` and fall due on `payment_day` of
+/// each month after `start`.
+pub fn amortize(
+    balance: Money,
+    monthly_rate: f64,
+    emi: Money,
+    start: NaiveDate,
+    payment_day: u32,
+    first_number: i32,
+) -> Result<Vec<LoanScheduleRow>, String> {
+    let decimals = balance.decimals();
+    let mut balance = balance;
+    let mut number = first_number;
+    let mut schedule = Vec::new();
+
+    while balance.is_positive() {
+        if number - first_number >= MAX_LOAN_PAYMENTS {
+            return Err("The payment is too small to ever pay off this loan".to_string());
+        }
+
+        let interest = balance.mul_f64(monthly_rate, decimals);
+        let principal = (emi - interest).min(balance);
+        if !principal.is_positive() {
+            return Err("The payment does not cover the monthly interest".to_string());
+        }
+        balance -= principal;
+
+        schedule.push(LoanScheduleRow {
+            payment_number: number,
+            due_date: due_date(start, payment_day, number)?.format("%Y-%m-%d").to_string(),
+            payment: principal + interest,
+            principal,
+            interest,
+            remaining_balance: balance,
+        });
+        number += 1;
+    }
+
+    Ok(schedule)
+}
+
+fn due_date(start: NaiveDate, payment_day: u32, number: i32) -> Result<NaiveDate, String> {
+    start
+        .with_day(1)
+        .and_then(|d| d.checked_add_months(Months::new(number as u32)))
+        .and_then(|d| d.with_day(payment_day))
+        .ok_or_else(|| "Invalid loan due date".to_string())
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("Invalid date: {}", e))
+}
+
+fn check_payment_account(
+    conn: &rusqlite::Connection,
+    payment_account_id: i64,
+    loan_account_id: i64,
+    loan_currency: &str,
+) -> Result<(), String> {
+    if payment_account_id == loan_account_id {
+        return Err("Payments must come from a different account".to_string());
+    }
+
+    let currency: String = conn
+        .query_row(
+            "SELECT currency FROM accounts WHERE id = ?1",
+            params![payment_account_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| "Payment account not found".to_string())?;
+
+    if currency != loan_currency {
+        return Err("Payment account must use the same currency as the loan".to_string());
+    }
+
+    Ok(())
+}
+
+/// Transfer `amount` of principal into the loan account, journalled and
+/// audited like any other transaction.
+fn post_principal(
+    conn: &rusqlite::Connection,
+    loan: &Loan,
+    from_account_id: i64,
+    amount: Money,
+    date: &str,
+    memo: &str,
+) -> Result<i64, String> {
+    // `amount` is in the loan's currency; the payer may have changed currency
+    // since the loan was set up, and there is no amount sent to convert with
+    let loan_currency: String = conn
+        .query_row(
+            "SELECT currency FROM accounts WHERE id = ?1",
+            params![loan.account_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| "Loan account not found".to_string())?;
+    check_payment_account(conn, from_account_id, loan.account_id, &loan_currency)?;
+
+    insert_transaction(
+        conn,
+        CreateTransactionInput {
+            date: date.to_string(),
+            transaction_type: "TRANSFER".to_string(),
+            amount: amount.to_major(),
+            account_id: from_account_id,
+            to_account_id: Some(loan.account_id),
+            to_amount: None,
+            category_id: None,
+            payee_id: None,
+            payee: None,
+            memo: Some(memo.to_string()),
+            tag_ids: None,
+            goal_allocations: None,
+            goal_withdrawals: None,
+            splits: None,
+        },
+    )
+}
+
+fn close_if_paid(conn: &rusqlite::Connection, loan: &Loan, balance: Money) -> Result<(), String> {
+    if balance.is_positive() {
+        return Ok(());
+    }
+
+    conn.execute(
+        "UPDATE loans SET status = 'CLOSED', updated_at = datetime('now') WHERE id = ?1",
+        params![loan.id],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to close loan: {}", e))
+}
+
+fn fetch_loan(conn: &rusqlite::Connection, loan_id: i64) -> Result<Loan, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM loans WHERE id = ?1", LOAN_COLUMNS),
+        params![loan_id],
+        row_to_loan,
+    )
+    .map_err(|_| "Loan not found".to_string())
+}
+
+fn fetch_payments(conn: &rusqlite::Connection, loan: &Loan) -> Result<Vec<LoanPayment>, String> {
+    let decimals = loan.principal.decimals();
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM loan_payments WHERE loan_id = ?1 ORDER BY payment_date ASC, id ASC",
+            PAYMENT_COLUMNS
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let payments = stmt
+        .query_map(params![loan.id], |row| row_to_payment(row, decimals))
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    Ok(payments)
+}
+
+fn fetch_payment(conn: &rusqlite::Connection, payment_id: i64, decimals: u32) -> Result<LoanPayment, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM loan_payments WHERE id = ?1", PAYMENT_COLUMNS),
+        params![payment_id],
+        |row| row_to_payment(row, decimals),
+    )
+    .map_err(|e| format!("Database error: {}", e))
+}
+
+/// Expects the loan account's currency in column 13.
+fn row_to_loan(row: &rusqlite::Row) -> rusqlite::Result<Loan> {
+    let decimals = currency_decimals(&row.get::<_, String>(13)?);
+    Ok(Loan {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        payment_account_id: row.get(2)?,
+        interest_category_id: row.get(3)?,
+        principal: Money::new(row.get(4)?, decimals),
+        annual_rate: row.get(5)?,
+        term_months: row.get(6)?,
+        start_date: row.get(7)?,
+        payment_day: row.get(8)?,
+        emi: Money::new(row.get(9)?, decimals),
+        status: row.get(10)?,
+        created_at: row.get(11)?,
+        updated_at: row.get(12)?,
+    })
+}
+
+fn row_to_payment(row: &rusqlite::Row, decimals: u32) -> rusqlite::Result<LoanPayment> {
+    Ok(LoanPayment {
+        id: row.get(0)?,
+        loan_id: row.get(1)?,
+        transaction_id: row.get(2)?,
+        interest_transaction_id: row.get(3)?,
+        kind: row.get(4)?,
+        payment_number: row.get(5)?,
+        payment_date: row.get(6)?,
+        principal: Money::new(row.get(7)?, decimals),
+        interest: Money::new(row.get(8)?, decimals),
+        strategy: row.get(9)?,
+        created_at: row.get(10)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+    }
+
+    #[test]
+    fn annuity_schedule_pays_off_in_term() {
+        let principal = Money::new(10000000, 2);
+        let emi = annuity_payment(principal, 0.01, 24);
+        let schedule = amortize(principal, 0.01, emi, start(), 5, 1).unwrap();
+
+        assert_eq!(emi, Money::new(470735, 2));
+        assert_eq!(schedule.len(), 24);
+        assert_eq!(schedule[0].due_date, "2024-02-05");
+        assert_eq!(schedule[0].interest, Money::new(100000, 2));
+        assert!(schedule[23].payment <= emi);
+        assert!(schedule[23].remaining_balance.is_zero());
+    }
+
+    #[test]
+    fn reduce_term_shortens_schedule() {
+        let principal = Money::new(10000000, 2);
+        let emi = annuity_payment(principal, 0.01, 24);
+        let full = amortize(principal, 0.01, emi, start(), 5, 1).unwrap();
+        let prepaid = amortize(principal - Money::new(3000000, 2), 0.01, emi, start(), 5, 1).unwrap();
+
+        let interest = |rows: &[LoanScheduleRow]| rows.iter().map(|r| r.interest).sum::<Money>();
+        assert!(prepaid.len() < full.len());
+        assert!(interest(&prepaid) < interest(&full));
+    }
+
+    #[test]
+    fn rejects_payment_below_interest() {
+        let principal = Money::new(10000000, 2);
+        assert!(amortize(principal, 0.01, Money::new(50000, 2), start(), 5, 1).is_err());
+    }
+
+    #[test]
+    fn payments_are_journalled_and_audited() {
+        let dir = std::env::temp_dir().join(format!("mm-loan-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = crate::db::init_database_unencrypted(&dir.join("money_manager.db")).unwrap();
+        let mut conn = pool.lock().unwrap();
+
+        let id = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, i64>(0)).unwrap();
+        let account = |group_id: i64, name: &str| {
+            conn.execute(
+                "INSERT INTO accounts (group_id, name, initial_balance) VALUES (?1, ?2, 0)",
+                params![group_id, name],
+            )
+            .unwrap();
+            conn.last_insert_rowid()
+        };
+        let liability_group = id("SELECT id FROM account_groups WHERE type = 'LIABILITY' LIMIT 1");
+        let asset_group = id("SELECT id FROM account_groups WHERE type = 'ASSET' LIMIT 1");
+        let interest_category = id("SELECT id FROM categories WHERE type = 'EXPENSE' LIMIT 1");
+        let (loan_account, checking) = (account(liability_group, "Car Loan"), account(asset_group, "Loan Checking"));
+
+        let loan = create_loan_internal(
+            &conn,
+            CreateLoanInput {
+                account_id: loan_account,
+                payment_account_id: checking,
+                interest_category_id: Some(interest_category),
+                principal: 100000.0,
+                annual_rate: 12.0,
+                term_months: 24,
+                start_date: "2030-01-15".into(),
+                payment_day: 5,
+            },
+        )
+        .unwrap();
+
+        let payment = process_loan_payment_internal(&mut conn, loan.id, Some("2030-02-05".into())).unwrap();
+        let interest_id = payment.interest_transaction_id.unwrap();
+
+        for (transaction_id, amount) in [(payment.transaction_id, payment.principal), (interest_id, payment.interest)] {
+            let credited: i64 = conn
+                .query_row(
+                    "SELECT SUM(credit) FROM journal_entries WHERE transaction_id = ?1 AND account_id = ?2",
+                    params![transaction_id, checking],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(credited, amount.minor());
+
+            let logged: i64 = conn
+                .query_row(
+                    "SELECT COUNT(*) FROM audit_log
+                     WHERE entity_type = 'TRANSACTION' AND entity_id = ?1 AND action = 'CREATE'",
+                    params![transaction_id],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(logged, 1);
+        }
+
+        // A payer that moved to another currency has no amount sent to post
+        conn.execute("UPDATE accounts SET currency = 'USD' WHERE id = ?1", params![checking]).unwrap();
+        assert!(process_loan_payment_internal(&mut conn, loan.id, Some("2030-03-05".into())).is_err());
+        let payments: i64 = conn
+            .query_row("SELECT COUNT(*) FROM transactions WHERE account_id = ?1", params![checking], |row| row.get(0))
+            .unwrap();
+        assert_eq!(payments, 2);
+
+        drop(conn);
+        pool.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,6 +1,7 @@
 // File: src-tauri/src/commands/mod.rs
 pub mod accounts;
 pub mod analytics;
+pub mod audit;
 pub mod bills;
 pub mod budgets;
 pub mod categories;
@@ -10,12 +11,23 @@ pub mod export;
 pub mod goals;
 pub mod import;
 pub mod installments;
+pub mod investments;
+pub mod ledger;
+pub mod loans;
 pub mod networth;
+pub mod ofx;
+pub mod payees;
 pub mod photos;
+pub mod qif;
+pub mod rate_import;
+pub mod reconciliation;
 pub mod recurring;
+pub mod saved_filters;
 pub mod scheduled_backup;
+pub mod search;
 pub mod security;
 pub mod settings;
+pub mod suggestions;
 pub mod tags;
 pub mod templates;
 pub mod transactions;
@@ -1,26 +1,52 @@
 // File: src-tauri/src/commands/networth.rs
-use crate::models::networth::{NetWorthSnapshot, NetWorthSummary};
+use crate::commands::currencies::{
+    currency_decimals, get_primary_currency_internal, primary_decimals, RateConverter,
+};
+use crate::commands::investments::unrealized_gains_at;
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
+use crate::models::networth::{CurrencyNetWorth, NetWorthSnapshot, NetWorthSummary};
 use crate::AppState;
 use chrono::{Datelike, Local, NaiveDate};
 use rusqlite::params;
+use serde::{Deserialize, Serialize};
 use tauri::State;
 
 // ── Helpers ──────────────────────────────────────────────────────────
 
-fn calc_net_worth_at(
+/// Net worth converted to the primary currency at the rates effective on the
+/// valuation date, with the per-currency figures it was built from.
+pub struct Valuation {
+    pub assets: Money,
+    pub liabilities: Money,
+    pub by_currency: Vec<CurrencyNetWorth>,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Per-currency figures as stored in `net_worth_snapshots.currency_breakdown`
+#[derive(Serialize, Deserialize)]
+struct StoredBreakdown {
+    currency: String,
+    assets: i64,
+    liabilities: i64,
+    rate: Option<f64>,
+}
+
+pub fn calc_net_worth_at(
     conn: &rusqlite::Connection,
     as_of_date: Option<&str>,
-) -> Result<(f64, f64), String> {
+) -> Result<Valuation, String> {
     let mut stmt = if let Some(_date) = as_of_date {
         conn.prepare(
             r#"
             SELECT a.initial_balance, ag.type as account_type,
-                   CAST(COALESCE(
+                   COALESCE(
                        (SELECT SUM(je2.debit) - SUM(je2.credit)
                         FROM journal_entries je2
                         JOIN transactions t2 ON je2.transaction_id = t2.id
                         WHERE je2.account_id = a.id AND t2.date <= ?1),
-                   0) AS REAL) as journal_balance
+                   0) as journal_balance,
+                   a.currency
             FROM accounts a
             JOIN account_groups ag ON a.group_id = ag.id
             GROUP BY a.id
@@ -30,7 +56,8 @@ fn calc_net_worth_at(
         conn.prepare(
             r#"
             SELECT a.initial_balance, ag.type as account_type,
-                   CAST(COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0) AS REAL) as journal_balance
+                   COALESCE(SUM(je.debit), 0) - COALESCE(SUM(je.credit), 0) as journal_balance,
+                   a.currency
             FROM accounts a
             JOIN account_groups ag ON a.group_id = ag.id
             LEFT JOIN journal_entries je ON je.account_id = a.id
@@ -39,46 +66,120 @@ fn calc_net_worth_at(
         ).map_err(|e| format!("Prepare error: {}", e))?
     };
 
+    let read_row = |row: &rusqlite::Row| {
+        let currency: String = row.get(3)?;
+        let balance = row.get::<_, i64>(0)? + row.get::<_, i64>(2)?;
+        Ok((Money::new(balance, currency_decimals(&currency)), row.get::<_, String>(1)?, currency))
+    };
+
     let rows = if let Some(date) = as_of_date {
-        stmt.query_map(params![date], |row| {
-            Ok((
-                row.get::<_, f64>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, f64>(2)?,
-            ))
-        })
-        .map_err(|e| format!("Execute error: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Read error: {}", e))?
+        stmt.query_map(params![date], read_row)
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?
     } else {
-        stmt.query_map([], |row| {
-            Ok((
-                row.get::<_, f64>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, f64>(2)?,
-            ))
-        })
-        .map_err(|e| format!("Execute error: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Read error: {}", e))?
+        stmt.query_map([], read_row)
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?
     };
 
-    let mut assets = 0.0_f64;
-    let mut liabilities = 0.0_f64;
+    let mut by_currency: Vec<CurrencyNetWorth> = Vec::new();
+    let mut entry = |currency: &str| -> usize {
+        match by_currency.iter().position(|c| c.currency == currency) {
+            Some(index) => index,
+            None => {
+                let zero = Money::zero(currency_decimals(currency));
+                by_currency.push(CurrencyNetWorth {
+                    currency: currency.to_string(),
+                    assets: zero,
+                    liabilities: zero,
+                    net_worth: zero,
+                    rate: None,
+                });
+                by_currency.len() - 1
+            }
+        }
+    };
 
-    for (initial, acc_type, journal) in rows {
-        let balance = initial + journal;
+    let mut indices = Vec::new();
+    for (balance, acc_type, currency) in rows {
+        let index = entry(&currency);
+        indices.push((index, balance, acc_type));
+    }
+    // Investment accounts carry holdings at cost; revalue them at market
+    let gains = unrealized_gains_at(conn, as_of_date)?;
+    let gain_indices: Vec<(usize, Money)> = gains
+        .into_iter()
+        .map(|(currency, gain)| (entry(&currency), gain))
+        .collect();
+
+    for (index, balance, acc_type) in indices {
+        let totals = &mut by_currency[index];
         match acc_type.as_str() {
-            "ASSET" => assets += balance,
-            "LIABILITY" => liabilities += (-balance).max(0.0),
+            "ASSET" => totals.assets += balance,
+            "LIABILITY" => totals.liabilities += (-balance).max(Money::zero(balance.decimals())),
             _ => {}
         }
     }
+    for (index, gain) in gain_indices {
+        by_currency[index].assets += gain;
+    }
+
+    let date = as_of_date
+        .map(str::to_string)
+        .unwrap_or_else(|| Local::now().format("%Y-%m-%d").to_string());
+    let mut converter = RateConverter::to_primary(conn)?;
+    let mut assets = converter.zero();
+    let mut liabilities = converter.zero();
+
+    for totals in by_currency.iter_mut() {
+        totals.net_worth = totals.assets - totals.liabilities;
+        totals.rate = converter.rate(&totals.currency, &date);
+        if let Some(rate) = totals.rate {
+            assets += totals.assets.mul_f64(rate, converter.decimals);
+            liabilities += totals.liabilities.mul_f64(rate, converter.decimals);
+        }
+    }
 
-    Ok((
-        (assets * 100.0).round() / 100.0,
-        (liabilities * 100.0).round() / 100.0,
-    ))
+    Ok(Valuation {
+        assets,
+        liabilities,
+        by_currency,
+        missing_rates: converter.missing_rates(),
+    })
+}
+
+fn breakdown_to_json(by_currency: &[CurrencyNetWorth]) -> Result<String, String> {
+    let stored: Vec<StoredBreakdown> = by_currency
+        .iter()
+        .map(|c| StoredBreakdown {
+            currency: c.currency.clone(),
+            assets: c.assets.minor(),
+            liabilities: c.liabilities.minor(),
+            rate: c.rate,
+        })
+        .collect();
+
+    serde_json::to_string(&stored).map_err(|e| format!("Failed to encode breakdown: {}", e))
+}
+
+fn breakdown_from_json(json: Option<String>) -> Vec<CurrencyNetWorth> {
+    json.and_then(|j| serde_json::from_str::<Vec<StoredBreakdown>>(&j).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| {
+            let decimals = currency_decimals(&c.currency);
+            let (assets, liabilities) = (Money::new(c.assets, decimals), Money::new(c.liabilities, decimals));
+            CurrencyNetWorth {
+                currency: c.currency,
+                assets,
+                liabilities,
+                net_worth: assets - liabilities,
+                rate: c.rate,
+            }
+        })
+        .collect()
 }
 
 fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
@@ -98,8 +199,8 @@ pub fn get_current_net_worth(state: State<'_, AppState>) -> Result<NetWorthSumma
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    let (assets, liabilities) = calc_net_worth_at(&conn, None)?;
-    let net_worth = assets - liabilities;
+    let current = calc_net_worth_at(&conn, None)?;
+    let net_worth = current.assets - current.liabilities;
 
     // Previous month-end for comparison
     let today = Local::now().date_naive();
@@ -108,22 +209,21 @@ pub fn get_current_net_worth(state: State<'_, AppState>) -> Result<NetWorthSumma
     let prev_month_end = first_of_month.pred_opt().ok_or("Invalid date")?;
     let prev_date_str = prev_month_end.format("%Y-%m-%d").to_string();
 
-    let (prev_assets, prev_liabilities) = calc_net_worth_at(&conn, Some(&prev_date_str))?;
-    let prev_net_worth = prev_assets - prev_liabilities;
+    let previous = calc_net_worth_at(&conn, Some(&prev_date_str))?;
+    let prev_net_worth = previous.assets - previous.liabilities;
 
-    let change_amount = ((net_worth - prev_net_worth) * 100.0).round() / 100.0;
-    let change_percentage = if prev_net_worth.abs() > 0.01 {
-        ((change_amount / prev_net_worth.abs()) * 100.0 * 100.0).round() / 100.0
-    } else {
-        0.0
-    };
+    let change_amount = net_worth - prev_net_worth;
+    let change_percentage = (change_amount.ratio(prev_net_worth.abs()) * 100.0 * 100.0).round() / 100.0;
 
     Ok(NetWorthSummary {
-        assets,
-        liabilities,
+        assets: current.assets,
+        liabilities: current.liabilities,
         net_worth,
         change_amount,
         change_percentage,
+        currency: get_primary_currency_internal(&conn)?,
+        by_currency: current.by_currency,
+        missing_rates: current.missing_rates,
     })
 }
 
@@ -136,10 +236,12 @@ pub fn get_net_worth_snapshots(
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let limit = months.unwrap_or(12);
+    let decimals = primary_decimals(&conn);
+    let primary = get_primary_currency_internal(&conn)?;
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, snapshot_date, total_assets, total_liabilities, net_worth
+            "SELECT id, snapshot_date, total_assets, total_liabilities, net_worth, currency_breakdown
              FROM net_worth_snapshots
              ORDER BY snapshot_date DESC
              LIMIT ?1",
@@ -148,12 +250,28 @@ pub fn get_net_worth_snapshots(
 
     let mut snapshots = stmt
         .query_map(params![limit], |row| {
+            let snapshot_date: String = row.get(1)?;
+            let by_currency = breakdown_from_json(row.get(5)?);
+            let missing_rates = by_currency
+                .iter()
+                .filter(|c| c.rate.is_none())
+                .map(|c| MissingRate {
+                    currency: c.currency.clone(),
+                    to_currency: primary.clone(),
+                    first_date: snapshot_date.clone(),
+                    last_date: snapshot_date.clone(),
+                    occurrences: 1,
+                })
+                .collect();
+
             Ok(NetWorthSnapshot {
                 id: row.get(0)?,
-                snapshot_date: row.get(1)?,
-                total_assets: row.get(2)?,
-                total_liabilities: row.get(3)?,
-                net_worth: row.get(4)?,
+                snapshot_date,
+                total_assets: Money::new(row.get(2)?, decimals),
+                total_liabilities: Money::new(row.get(3)?, decimals),
+                net_worth: Money::new(row.get(4)?, decimals),
+                by_currency,
+                missing_rates,
             })
         })
         .map_err(|e| format!("Failed to fetch snapshots: {}", e))?
@@ -179,19 +297,20 @@ pub fn generate_net_worth_snapshot(conn: &rusqlite::Connection) -> Result<(), St
         )
         .unwrap_or(0) > 0;
 
-    let (assets, liabilities) = calc_net_worth_at(conn, None)?;
-    let net_worth = assets - liabilities;
+    let valuation = calc_net_worth_at(conn, None)?;
+    let net_worth = valuation.assets - valuation.liabilities;
+    let breakdown = breakdown_to_json(&valuation.by_currency)?;
 
     if exists {
         conn.execute(
-            "UPDATE net_worth_snapshots SET total_assets = ?1, total_liabilities = ?2, net_worth = ?3 WHERE snapshot_date = ?4",
-            params![assets, liabilities, net_worth, date_str],
+            "UPDATE net_worth_snapshots SET total_assets = ?1, total_liabilities = ?2, net_worth = ?3, currency_breakdown = ?4 WHERE snapshot_date = ?5",
+            params![valuation.assets, valuation.liabilities, net_worth, breakdown, date_str],
         )
         .map_err(|e| format!("Failed to update snapshot: {}", e))?;
     } else {
         conn.execute(
-            "INSERT INTO net_worth_snapshots (snapshot_date, total_assets, total_liabilities, net_worth) VALUES (?1, ?2, ?3, ?4)",
-            params![date_str, assets, liabilities, net_worth],
+            "INSERT INTO net_worth_snapshots (snapshot_date, total_assets, total_liabilities, net_worth, currency_breakdown) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![date_str, valuation.assets, valuation.liabilities, net_worth, breakdown],
         )
         .map_err(|e| format!("Failed to insert snapshot: {}", e))?;
     }
@@ -199,19 +318,9 @@ pub fn generate_net_worth_snapshot(conn: &rusqlite::Connection) -> Result<(), St
     Ok(())
 }
 
-pub fn backfill_net_worth_snapshots(conn: &rusqlite::Connection) -> Result<(), String> {
-    let count: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM net_worth_snapshots",
-            [],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
-
-    if count > 0 {
-        return Ok(()); // Already populated
-    }
-
+/// Valuations at each month end from the first transaction up to last month;
+/// the current month is handled by generate_net_worth_snapshot
+fn month_end_valuations(conn: &rusqlite::Connection) -> Result<Vec<(String, Valuation)>, String> {
     let min_date_str: Option<String> = conn
         .query_row(
             "SELECT MIN(date) FROM transactions",
@@ -224,28 +333,23 @@ pub fn backfill_net_worth_snapshots(conn: &rusqlite::Connection) -> Result<(), S
     let start = match min_date_str {
         Some(d) => NaiveDate::parse_from_str(&d, "%Y-%m-%d")
             .map_err(|_| "Failed to parse earliest date".to_string())?,
-        None => return Ok(()), // No transactions
+        None => return Ok(Vec::new()), // No transactions
     };
 
     let today = Local::now().date_naive();
     let mut year = start.year();
     let mut month = start.month();
+    let mut valuations = Vec::new();
 
     loop {
         let end_of_month = last_day_of_month(year, month);
         if end_of_month > today {
-            break; // Current month handled by generate_net_worth_snapshot
+            break;
         }
 
         let date_str = end_of_month.format("%Y-%m-%d").to_string();
-        let (assets, liabilities) = calc_net_worth_at(conn, Some(&date_str))?;
-        let net_worth = assets - liabilities;
-
-        conn.execute(
-            "INSERT OR IGNORE INTO net_worth_snapshots (snapshot_date, total_assets, total_liabilities, net_worth) VALUES (?1, ?2, ?3, ?4)",
-            params![date_str, assets, liabilities, net_worth],
-        )
-        .map_err(|e| format!("Failed to backfill snapshot: {}", e))?;
+        let valuation = calc_net_worth_at(conn, Some(&date_str))?;
+        valuations.push((date_str, valuation));
 
         if month == 12 {
             year += 1;
@@ -255,5 +359,53 @@ pub fn backfill_net_worth_snapshots(conn: &rusqlite::Connection) -> Result<(), S
         }
     }
 
+    Ok(valuations)
+}
+
+fn insert_month_end_snapshots(
+    conn: &rusqlite::Connection,
+    valuations: &[(String, Valuation)],
+) -> Result<(), String> {
+    for (date_str, valuation) in valuations {
+        let net_worth = valuation.assets - valuation.liabilities;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO net_worth_snapshots (snapshot_date, total_assets, total_liabilities, net_worth, currency_breakdown) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                date_str,
+                valuation.assets,
+                valuation.liabilities,
+                net_worth,
+                breakdown_to_json(&valuation.by_currency)?
+            ],
+        )
+        .map_err(|e| format!("Failed to backfill snapshot: {}", e))?;
+    }
+
     Ok(())
 }
+
+/// Recomputes every snapshot, e.g. after exchange rates for past dates were entered.
+/// The valuations are worked out on a reader in the background; the writer is
+/// only held while the snapshots are swapped.
+#[tauri::command]
+pub async fn rebuild_net_worth_snapshots(state: State<'_, AppState>) -> Result<(), String> {
+    crate::run_blocking(&state, |pool| {
+        let valuations = month_end_valuations(&*pool.read()?)?;
+
+        let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        tx.execute("DELETE FROM net_worth_snapshots", [])
+            .map_err(|e| format!("Failed to clear snapshots: {}", e))?;
+
+        insert_month_end_snapshots(&tx, &valuations)?;
+        generate_net_worth_snapshot(&tx)?;
+
+        tx.commit()
+            .map_err(|e| format!("Failed to commit snapshots: {}", e))
+    })
+    .await
+}
@@ -0,0 +1,499 @@
+// File: src-tauri/src/commands/ofx.rs
+use crate::commands::advanced::{apply_categorization_rules, load_categorization_rules, RuleSubject};
+use crate::commands::audit::log_change;
+use crate::commands::currencies::{account_decimals, currency_decimals};
+use crate::commands::import::{
+    combine_payee_memo, load_accounts, load_categories, record_import_history, strip_bom,
+};
+use crate::commands::transactions::insert_journal_entries;
+use crate::models::import::{
+    ImportResult, OfxImportOptions, OfxPreview, OfxStatementPreview, OfxTransactionPreview,
+};
+use crate::models::money::Money;
+use crate::AppState;
+use rusqlite::params;
+use std::collections::HashSet;
+use tauri::State;
+
+// ======================== PREVIEW ========================
+
+#[tauri::command]
+pub fn parse_ofx_preview(
+    state: State<'_, AppState>,
+    file_path: String,
+) -> Result<OfxPreview, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let content = std::fs::read(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let text = strip_bom(&content);
+    let (version, statements) = parse_ofx(&text)?;
+
+    let accounts = load_accounts(&conn)?;
+    let mut previews: Vec<OfxStatementPreview> = Vec::new();
+
+    for statement in statements {
+        let account_key = statement.account_key();
+        let matched_account_id = linked_account(&conn, &account_key)?
+            .or_else(|| match_account_by_number(&statement.account_number, &accounts));
+        let matched_account_name = matched_account_id.and_then(|id| {
+            accounts.iter().find(|(a, _)| *a == id).map(|(_, name)| name.clone())
+        });
+
+        let decimals = match matched_account_id {
+            Some(id) => account_decimals(&conn, id)?,
+            None => currency_decimals(&statement.currency),
+        };
+        let existing = match matched_account_id {
+            Some(id) => load_external_ids(&conn, id)?,
+            None => HashSet::new(),
+        };
+
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut duplicate_count: i64 = 0;
+        let transactions: Vec<OfxTransactionPreview> = statement
+            .transactions
+            .iter()
+            .map(|t| {
+                let is_duplicate = existing.contains(&t.fitid) || !seen.insert(t.fitid.clone());
+                if is_duplicate {
+                    duplicate_count += 1;
+                }
+                let amount = parse_ofx_amount(&t.amount, decimals).unwrap_or(Money::zero(decimals));
+                OfxTransactionPreview {
+                    fitid: t.fitid.clone(),
+                    date: t.date.clone().unwrap_or_default(),
+                    amount: amount.abs(),
+                    transaction_type: ofx_transaction_type(amount).to_string(),
+                    payee: t.payee.clone(),
+                    memo: t.memo.clone(),
+                    is_duplicate,
+                }
+            })
+            .collect();
+
+        previews.push(OfxStatementPreview {
+            account_key,
+            account_number: statement.account_number,
+            account_type: statement.account_type,
+            currency: statement.currency,
+            start_date: statement.start_date,
+            end_date: statement.end_date,
+            total_rows: transactions.len() as i64,
+            duplicate_count,
+            matched_account_id,
+            matched_account_name,
+            transactions,
+        });
+    }
+
+    Ok(OfxPreview {
+        version,
+        statements: previews,
+    })
+}
+
+// ======================== EXECUTE IMPORT ========================
+
+#[tauri::command]
+pub async fn execute_ofx_import(
+    state: State<'_, AppState>,
+    file_path: String,
+    options: OfxImportOptions,
+) -> Result<ImportResult, String> {
+    crate::run_blocking(&state, move |pool| {
+        let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+        execute_ofx_import_internal(&mut conn, &file_path, options)
+    })
+    .await
+}
+
+pub fn execute_ofx_import_internal(
+    conn: &mut rusqlite::Connection,
+    file_path: &str,
+    options: OfxImportOptions,
+) -> Result<ImportResult, String> {
+
+    let content = std::fs::read(file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let text = strip_bom(&content);
+    let (_, statements) = parse_ofx(&text)?;
+
+    let batch_id = uuid::Uuid::new_v4().to_string();
+
+    let categories = load_categories(conn)?;
+    let rules = load_categorization_rules(conn)?;
+
+    let mut imported: i64 = 0;
+    let mut skipped: i64 = 0;
+    let mut errors: i64 = 0;
+    let mut total_rows: i64 = 0;
+
+    let tx = conn.transaction().map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+    for statement in &statements {
+        let account_key = statement.account_key();
+        total_rows += statement.transactions.len() as i64;
+
+        let account_id = match options.account_mapping.get(&account_key).copied() {
+            Some(id) => Some(id),
+            None => linked_account(&tx, &account_key)?,
+        };
+        let Some(account_id) = account_id else {
+            errors += statement.transactions.len() as i64;
+            continue;
+        };
+
+        let decimals = match account_decimals(&tx, account_id) {
+            Ok(d) => d,
+            Err(_) => {
+                errors += statement.transactions.len() as i64;
+                continue;
+            }
+        };
+
+        tx.execute(
+            "INSERT OR REPLACE INTO ofx_account_links (account_key, account_id) VALUES (?1, ?2)",
+            params![account_key, account_id],
+        ).map_err(|e| format!("Failed to link OFX account: {}", e))?;
+
+        let mut existing = load_external_ids(&tx, account_id)?;
+
+        for t in &statement.transactions {
+            let Some(date) = t.date.clone() else {
+                errors += 1;
+                continue;
+            };
+            if t.fitid.is_empty() {
+                errors += 1;
+                continue;
+            }
+            if existing.contains(&t.fitid) {
+                skipped += 1;
+                continue;
+            }
+
+            let signed = match parse_ofx_amount(&t.amount, decimals) {
+                Some(a) if !a.is_zero() => a,
+                _ => {
+                    errors += 1;
+                    continue;
+                }
+            };
+            let txn_type = ofx_transaction_type(signed);
+            let amount = signed.abs();
+
+            let subject = RuleSubject {
+                memo: &t.payee,
+                amount: amount.to_major(),
+                account_id: Some(account_id),
+                transaction_type: txn_type,
+            };
+            let category_id = apply_categorization_rules(&subject, &rules, &categories)
+                .map(|(id, _)| id);
+
+            let memo = combine_payee_memo(&t.payee, &t.memo);
+
+            let result = tx.execute(
+                "INSERT INTO transactions (date, type, amount, account_id, category_id, memo, import_batch_id, external_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![date, txn_type, amount, account_id, category_id, memo, batch_id, t.fitid],
+            );
+
+            let txn_id = match result {
+                Ok(_) => tx.last_insert_rowid(),
+                Err(e) => {
+                    eprintln!("Failed to import OFX transaction {}: {}", t.fitid, e);
+                    errors += 1;
+                    continue;
+                }
+            };
+
+            insert_journal_entries(&tx, txn_id, txn_type, account_id, None, amount, amount)?;
+            log_change(&tx, "TRANSACTION", txn_id, "CREATE", None)?;
+
+            existing.insert(t.fitid.clone());
+            imported += 1;
+        }
+    }
+
+    record_import_history(&tx, &batch_id, file_path, total_rows, imported, skipped, errors)?;
+
+    tx.commit().map_err(|e| format!("Failed to commit import: {}", e))?;
+
+    Ok(ImportResult {
+        batch_id,
+        imported,
+        skipped,
+        errors,
+    })
+}
+
+// ======================== INTERNAL HELPERS ========================
+
+fn linked_account(conn: &rusqlite::Connection, account_key: &str) -> Result<Option<i64>, String> {
+    let mut stmt = conn
+        .prepare("SELECT account_id FROM ofx_account_links WHERE account_key = ?1")
+        .map_err(|e| format!("Database error: {}", e))?;
+    let id: Option<i64> = stmt.query_row(params![account_key], |row| row.get(0)).ok();
+    Ok(id)
+}
+
+/// Accounts are often named after the last digits of the bank account number.
+fn match_account_by_number(account_number: &str, accounts: &[(i64, String)]) -> Option<i64> {
+    let digits: String = account_number.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() < 4 {
+        return None;
+    }
+    let last_four = &digits[digits.len() - 4..];
+    accounts
+        .iter()
+        .find(|(_, name)| name.contains(last_four))
+        .map(|(id, _)| *id)
+}
+
+fn load_external_ids(conn: &rusqlite::Connection, account_id: i64) -> Result<HashSet<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT external_id FROM transactions WHERE account_id = ?1 AND external_id IS NOT NULL")
+        .map_err(|e| format!("Database error: {}", e))?;
+    let ids: HashSet<String> = stmt
+        .query_map(params![account_id], |row| row.get(0))
+        .map_err(|e| format!("Database error: {}", e))?
+        .filter_map(Result::ok)
+        .collect();
+    Ok(ids)
+}
+
+fn ofx_transaction_type(signed_amount: Money) -> &'static str {
+    if signed_amount.is_negative() {
+        "EXPENSE"
+    } else {
+        "INCOME"
+    }
+}
+
+/// TRNAMT is signed; some banks write a decimal comma.
+fn parse_ofx_amount(raw: &str, decimals: u32) -> Option<Money> {
+    let trimmed = raw.trim();
+    let cleaned = if trimmed.contains('.') {
+        trimmed.replace(',', "")
+    } else {
+        trimmed.replace(',', ".")
+    };
+    Money::parse(&cleaned, decimals).ok()
+}
+
+/// OFX dates are `YYYYMMDD[HHMMSS[.XXX]][[offset:TZ]]`; only the day matters here.
+fn parse_ofx_date(raw: &str) -> Option<String> {
+    let day = raw.trim().get(..8)?;
+    chrono::NaiveDate::parse_from_str(day, "%Y%m%d")
+        .ok()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+// ======================== OFX PARSER ========================
+
+#[derive(Debug, Default)]
+struct OfxTransaction {
+    fitid: String,
+    date: Option<String>,
+    amount: String,
+    payee: String,
+    memo: String,
+}
+
+#[derive(Debug, Default)]
+struct OfxStatement {
+    bank_id: Option<String>,
+    account_number: String,
+    account_type: String,
+    currency: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    transactions: Vec<OfxTransaction>,
+}
+
+impl OfxStatement {
+    fn account_key(&self) -> String {
+        match &self.bank_id {
+            Some(bank_id) => format!("{}:{}", bank_id, self.account_number),
+            None => self.account_number.clone(),
+        }
+    }
+}
+
+/// Parses both OFX 1.x (SGML, leaf elements without closing tags) and
+/// OFX 2.x (XML). Aggregates are tracked on a stack; an element followed
+/// directly by text is a leaf, whether or not it is closed.
+fn parse_ofx(text: &str) -> Result<(String, Vec<OfxStatement>), String> {
+    let start = text
+        .to_ascii_uppercase()
+        .find("<OFX>")
+        .ok_or_else(|| "Not an OFX file: missing <OFX> element".to_string())?;
+    let version = if text[..start].contains("<?xml") || text[..start].contains("<?OFX") {
+        "2.x"
+    } else {
+        "1.x"
+    };
+
+    let mut stack: Vec<String> = Vec::new();
+    let mut statements: Vec<OfxStatement> = Vec::new();
+    let mut statement: Option<OfxStatement> = None;
+    let mut transaction: Option<OfxTransaction> = None;
+
+    let mut rest = &text[start..];
+    while let Some(lt) = rest.find('<') {
+        let after = &rest[lt + 1..];
+        let Some(gt) = after.find('>') else { break };
+        let tag = after[..gt].trim();
+        rest = &after[gt + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_uppercase();
+            // Closing a leaf (XML) leaves the stack alone; closing an aggregate
+            // also closes anything an SGML file left open inside it
+            if let Some(pos) = stack.iter().rposition(|t| *t == name) {
+                for closed in stack.drain(pos..).rev() {
+                    match closed.as_str() {
+                        "STMTTRN" => {
+                            if let (Some(s), Some(t)) = (statement.as_mut(), transaction.take()) {
+                                s.transactions.push(t);
+                            }
+                        }
+                        "STMTRS" | "CCSTMTRS" => {
+                            if let Some(s) = statement.take() {
+                                statements.push(s);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            continue;
+        }
+
+        let name = tag.trim_end_matches('/').trim().to_ascii_uppercase();
+        let value_end = rest.find('<').unwrap_or(rest.len());
+        let value = decode_entities(rest[..value_end].trim());
+
+        if value.is_empty() {
+            match name.as_str() {
+                "STMTRS" => statement = Some(OfxStatement::default()),
+                "CCSTMTRS" => {
+                    statement = Some(OfxStatement {
+                        account_type: "CREDITCARD".to_string(),
+                        ..OfxStatement::default()
+                    })
+                }
+                "STMTTRN" => transaction = Some(OfxTransaction::default()),
+                _ => {}
+            }
+            if !tag.ends_with('/') {
+                stack.push(name);
+            }
+            continue;
+        }
+
+        let in_stack = |aggregate: &str| stack.iter().any(|t| t == aggregate);
+
+        if let Some(t) = transaction.as_mut().filter(|_| in_stack("STMTTRN")) {
+            match name.as_str() {
+                "FITID" => t.fitid = value,
+                "DTPOSTED" => t.date = parse_ofx_date(&value),
+                "TRNAMT" => t.amount = value,
+                "NAME" if in_stack("PAYEE") || t.payee.is_empty() => t.payee = value,
+                "MEMO" => t.memo = value,
+                _ => {}
+            }
+        } else if let Some(s) = statement.as_mut() {
+            match name.as_str() {
+                "BANKID" if in_stack("BANKACCTFROM") => s.bank_id = Some(value),
+                "ACCTID" if in_stack("BANKACCTFROM") || in_stack("CCACCTFROM") => {
+                    s.account_number = value
+                }
+                "ACCTTYPE" if in_stack("BANKACCTFROM") => s.account_type = value,
+                "CURDEF" => s.currency = value,
+                "DTSTART" => s.start_date = parse_ofx_date(&value),
+                "DTEND" => s.end_date = parse_ofx_date(&value),
+                _ => {}
+            }
+        }
+    }
+
+    // SGML files sometimes end without closing the statement aggregate
+    if let Some(mut s) = statement.take() {
+        if let Some(t) = transaction.take() {
+            s.transactions.push(t);
+        }
+        statements.push(s);
+    }
+
+    if statements.is_empty() {
+        return Err("No bank or credit card statements found in OFX file".to_string());
+    }
+
+    Ok((version.to_string(), statements))
+}
+
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SGML: &str = "OFXHEADER:100\nDATA:OFXSGML\nVERSION:102\n\n<OFX>\n<BANKMSGSRSV1><STMTTRNRS><STMTRS>\n<CURDEF>USD\n<BANKACCTFROM><BANKID>121000248<ACCTID>0001234567<ACCTTYPE>CHECKING</BANKACCTFROM>\n<BANKTRANLIST><DTSTART>20240101<DTEND>20240131\n<STMTTRN><TRNTYPE>DEBIT<DTPOSTED>20240105120000.000[-5:EST]<TRNAMT>-42.10<FITID>A1<NAME>Coffee &amp; Co<MEMO>POS</STMTTRN>\n<STMTTRN><TRNTYPE>CREDIT<DTPOSTED>20240115<TRNAMT>1500,00<FITID>A2<NAME>Payroll</STMTTRN>\n</BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>";
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<?OFX OFXHEADER="200" VERSION="220"?>
+<OFX><CREDITCARDMSGSRSV1><CCSTMTTRNRS><CCSTMTRS>
+<CURDEF>EUR</CURDEF>
+<CCACCTFROM><ACCTID>4111111111111111</ACCTID></CCACCTFROM>
+<BANKTRANLIST>
+<STMTTRN><TRNTYPE>DEBIT</TRNTYPE><DTPOSTED>20240203</DTPOSTED><TRNAMT>-9.99</TRNAMT><FITID>X9</FITID><PAYEE><NAME>Streaming</NAME></PAYEE><MEMO></MEMO></STMTTRN>
+</BANKTRANLIST></CCSTMTRS></CCSTMTTRNRS></CREDITCARDMSGSRSV1></OFX>"#;
+
+    #[test]
+    fn parses_sgml_statement() {
+        let (version, statements) = parse_ofx(SGML).unwrap();
+        assert_eq!(version, "1.x");
+        assert_eq!(statements.len(), 1);
+        let s = &statements[0];
+        assert_eq!(s.account_key(), "121000248:0001234567");
+        assert_eq!(s.account_type, "CHECKING");
+        assert_eq!(s.currency, "USD");
+        assert_eq!(s.start_date.as_deref(), Some("2024-01-01"));
+        assert_eq!(s.transactions.len(), 2);
+        assert_eq!(s.transactions[0].fitid, "A1");
+        assert_eq!(s.transactions[0].date.as_deref(), Some("2024-01-05"));
+        assert_eq!(s.transactions[0].payee, "Coffee & Co");
+        assert_eq!(s.transactions[0].memo, "POS");
+        assert_eq!(parse_ofx_amount(&s.transactions[0].amount, 2), Some(Money::new(-4210, 2)));
+        assert_eq!(parse_ofx_amount(&s.transactions[1].amount, 2), Some(Money::new(150000, 2)));
+    }
+
+    #[test]
+    fn parses_xml_credit_card_statement() {
+        let (version, statements) = parse_ofx(XML).unwrap();
+        assert_eq!(version, "2.x");
+        let s = &statements[0];
+        assert_eq!(s.account_key(), "4111111111111111");
+        assert_eq!(s.account_type, "CREDITCARD");
+        assert_eq!(s.transactions.len(), 1);
+        assert_eq!(s.transactions[0].payee, "Streaming");
+        assert_eq!(s.transactions[0].memo, "");
+        assert_eq!(s.transactions[0].date.as_deref(), Some("2024-02-03"));
+    }
+}
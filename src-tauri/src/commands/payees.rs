@@ -0,0 +1,495 @@
+// File: src-tauri/src/commands/payees.rs
+// Payees with aliases and default category/tags, resolution of free-text
+// payee names, merging duplicates, and spending per payee.
+use crate::commands::currencies::{currency_decimals, record_missing_rate, RateConverter};
+use crate::commands::import::find_best_match;
+use crate::models::money::Money;
+use crate::models::payee::*;
+use crate::AppState;
+use rusqlite::{params, OptionalExtension};
+use tauri::State;
+
+/// Lowest `find_best_match` score accepted, i.e. one name contains the other
+const MIN_MATCH_SCORE: f64 = 0.7;
+/// Shorter names and aliases only resolve on an exact match
+const MIN_FUZZY_LENGTH: usize = 3;
+
+/// ((payee id, name), type, date, currency, minor amount, transaction count)
+type PayeeDayRow = ((i64, String), String, String, String, i64, i64);
+
+fn load_payees(conn: &rusqlite::Connection, payee_id: Option<i64>) -> Result<Vec<Payee>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id, p.name, p.default_category_id, c.name,
+                    (SELECT COUNT(*) FROM transactions t WHERE t.payee_id = p.id),
+                    p.created_at, p.updated_at
+             FROM payees p
+             LEFT JOIN categories c ON p.default_category_id = c.id
+             WHERE ?1 IS NULL OR p.id = ?1
+             ORDER BY p.name COLLATE NOCASE",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+    let mut payees = stmt
+        .query_map(params![payee_id], |row| {
+            Ok(Payee {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                default_category_id: row.get(2)?,
+                default_category_name: row.get(3)?,
+                aliases: Vec::new(),
+                tag_ids: Vec::new(),
+                transaction_count: row.get(4)?,
+                created_at: row.get(5)?,
+                updated_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    let aliases = conn
+        .prepare("SELECT payee_id, alias FROM payee_aliases ORDER BY alias COLLATE NOCASE")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| format!("Failed to load aliases: {}", e))?;
+    let tags = conn
+        .prepare("SELECT payee_id, tag_id FROM payee_tags ORDER BY tag_id")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| format!("Failed to load payee tags: {}", e))?;
+
+    for payee in &mut payees {
+        payee.aliases = aliases
+            .iter()
+            .filter(|(id, _)| *id == payee.id)
+            .map(|(_, alias)| alias.clone())
+            .collect();
+        payee.tag_ids = tags
+            .iter()
+            .filter(|(id, _)| *id == payee.id)
+            .map(|(_, tag_id)| *tag_id)
+            .collect();
+    }
+
+    Ok(payees)
+}
+
+fn get_payee(conn: &rusqlite::Connection, payee_id: i64) -> Result<Payee, String> {
+    load_payees(conn, Some(payee_id))?
+        .pop()
+        .ok_or_else(|| "Payee not found".to_string())
+}
+
+/// Names and aliases of every payee, longest first so the most specific
+/// name wins when several are contained in the text
+fn payee_names(conn: &rusqlite::Connection) -> Result<Vec<(i64, String)>, String> {
+    let mut names = conn
+        .prepare(
+            "SELECT id, name FROM payees
+             UNION ALL
+             SELECT payee_id, alias FROM payee_aliases",
+        )
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<(i64, String)>, _>>()
+        })
+        .map_err(|e| format!("Failed to load payees: {}", e))?;
+    names.sort_by_key(|(_, name)| std::cmp::Reverse(name.chars().count()));
+    Ok(names)
+}
+
+/// Resolve free text to a payee by exact name or alias, then by the import
+/// fuzzy matcher. `create` is for text that names the payee outright (a payee
+/// field rather than a memo): a fuzzy hit is remembered as a new alias so the
+/// next lookup is exact, and text matching nothing becomes a new payee.
+pub(crate) fn resolve_payee(
+    conn: &rusqlite::Connection,
+    text: &str,
+    create: bool,
+) -> Result<Option<i64>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    let names = payee_names(conn)?;
+    if let Some((id, _)) = names.iter().find(|(_, name)| name.eq_ignore_ascii_case(text)) {
+        return Ok(Some(*id));
+    }
+
+    if text.chars().count() >= MIN_FUZZY_LENGTH {
+        let candidates: Vec<(i64, String)> = names
+            .into_iter()
+            .filter(|(_, name)| name.chars().count() >= MIN_FUZZY_LENGTH)
+            .collect();
+        if let Some((id, _, score)) = find_best_match(text, &candidates) {
+            if score >= MIN_MATCH_SCORE {
+                if create {
+                    conn.execute(
+                        "INSERT OR IGNORE INTO payee_aliases (payee_id, alias) VALUES (?1, ?2)",
+                        params![id, text],
+                    )
+                    .map_err(|e| format!("Failed to save payee alias: {}", e))?;
+                }
+                return Ok(Some(id));
+            }
+        }
+    }
+
+    if !create {
+        return Ok(None);
+    }
+    conn.execute("INSERT INTO payees (name) VALUES (?1)", params![text])
+        .map_err(|e| format!("Failed to create payee: {}", e))?;
+    Ok(Some(conn.last_insert_rowid()))
+}
+
+/// Default category and tags of a payee
+pub(crate) fn payee_defaults(
+    conn: &rusqlite::Connection,
+    payee_id: i64,
+) -> Result<(Option<i64>, Vec<i64>), String> {
+    let category_id: Option<i64> = conn
+        .query_row(
+            "SELECT default_category_id FROM payees WHERE id = ?1",
+            params![payee_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or("Payee does not exist")?;
+    let tag_ids = conn
+        .prepare("SELECT tag_id FROM payee_tags WHERE payee_id = ?1 ORDER BY tag_id")
+        .and_then(|mut stmt| {
+            stmt.query_map(params![payee_id], |row| row.get(0))?
+                .collect::<Result<Vec<i64>, _>>()
+        })
+        .map_err(|e| format!("Failed to load payee tags: {}", e))?;
+    Ok((category_id, tag_ids))
+}
+
+fn normalize_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Payee name cannot be empty".to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// Replace a payee's aliases and tags. Aliases repeating the payee's own name
+/// are dropped; one naming a different payee is an error.
+fn save_payee_details(
+    conn: &rusqlite::Connection,
+    payee_id: i64,
+    name: &str,
+    aliases: &[String],
+    tag_ids: &[i64],
+) -> Result<(), String> {
+    let taken: Option<i64> = conn
+        .query_row(
+            "SELECT payee_id FROM payee_aliases WHERE alias = ?1 AND payee_id != ?2",
+            params![name, payee_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Database error: {}", e))?;
+    if taken.is_some() {
+        return Err(format!("'{}' is already an alias of another payee", name));
+    }
+
+    conn.execute("DELETE FROM payee_aliases WHERE payee_id = ?1", params![payee_id])
+        .map_err(|e| format!("Failed to clear aliases: {}", e))?;
+    for alias in aliases.iter().map(|a| a.trim()) {
+        if alias.is_empty() || alias.eq_ignore_ascii_case(name) {
+            continue;
+        }
+        let is_payee: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM payees WHERE name = ?1 AND id != ?2",
+                params![alias, payee_id],
+                |row| row.get::<_, i64>(0),
+            )
+            .map_err(|e| format!("Database error: {}", e))?
+            > 0;
+        if is_payee {
+            return Err(format!("'{}' is already the name of another payee", alias));
+        }
+        let owner: Option<i64> = conn
+            .query_row(
+                "SELECT payee_id FROM payee_aliases WHERE alias = ?1",
+                params![alias],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Database error: {}", e))?;
+        match owner {
+            Some(owner) if owner != payee_id => {
+                return Err(format!("'{}' is already an alias of another payee", alias));
+            }
+            Some(_) => {}
+            None => {
+                conn.execute(
+                    "INSERT INTO payee_aliases (payee_id, alias) VALUES (?1, ?2)",
+                    params![payee_id, alias],
+                )
+                .map_err(|e| format!("Failed to save alias: {}", e))?;
+            }
+        }
+    }
+
+    conn.execute("DELETE FROM payee_tags WHERE payee_id = ?1", params![payee_id])
+        .map_err(|e| format!("Failed to clear payee tags: {}", e))?;
+    for tag_id in tag_ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO payee_tags (payee_id, tag_id) VALUES (?1, ?2)",
+            params![payee_id, tag_id],
+        )
+        .map_err(|e| format!("Failed to associate tag: {}", e))?;
+    }
+
+    Ok(())
+}
+
+fn unique_name_error(e: rusqlite::Error, name: &str, action: &str) -> String {
+    if e.to_string().contains("UNIQUE") {
+        format!("Payee '{}' already exists", name)
+    } else {
+        format!("Failed to {} payee: {}", action, e)
+    }
+}
+
+#[tauri::command]
+pub fn get_payees(state: State<'_, AppState>) -> Result<Vec<Payee>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    load_payees(&conn, None)
+}
+
+#[tauri::command]
+pub fn create_payee(state: State<'_, AppState>, input: CreatePayeeInput) -> Result<Payee, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let name = normalize_name(&input.name)?;
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    tx.execute(
+        "INSERT INTO payees (name, default_category_id) VALUES (?1, ?2)",
+        params![name, input.default_category_id],
+    )
+    .map_err(|e| unique_name_error(e, &name, "create"))?;
+    let payee_id = tx.last_insert_rowid();
+    save_payee_details(
+        &tx,
+        payee_id,
+        &name,
+        input.aliases.as_deref().unwrap_or(&[]),
+        input.tag_ids.as_deref().unwrap_or(&[]),
+    )?;
+
+    tx.commit().map_err(|e| format!("Commit error: {}", e))?;
+
+    get_payee(&conn, payee_id)
+}
+
+#[tauri::command]
+pub fn update_payee(state: State<'_, AppState>, input: UpdatePayeeInput) -> Result<Payee, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let name = normalize_name(&input.name)?;
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    let updated = tx
+        .execute(
+            "UPDATE payees SET name = ?1, default_category_id = ?2, updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?3",
+            params![name, input.default_category_id, input.id],
+        )
+        .map_err(|e| unique_name_error(e, &name, "update"))?;
+    if updated == 0 {
+        return Err("Payee not found".to_string());
+    }
+    save_payee_details(&tx, input.id, &name, &input.aliases, &input.tag_ids)?;
+
+    tx.commit().map_err(|e| format!("Commit error: {}", e))?;
+
+    get_payee(&conn, input.id)
+}
+
+/// Transactions of a deleted payee keep their memo and lose the payee link
+#[tauri::command]
+pub fn delete_payee(state: State<'_, AppState>, payee_id: i64) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let deleted = conn
+        .execute("DELETE FROM payees WHERE id = ?1", params![payee_id])
+        .map_err(|e| format!("Failed to delete payee: {}", e))?;
+    if deleted == 0 {
+        return Err("Payee not found".to_string());
+    }
+    Ok(())
+}
+
+/// Fold duplicate payees into one. The sources' transactions, aliases and
+/// tags move to the target, each source name becomes an alias, and the
+/// target inherits a default category if it has none.
+#[tauri::command]
+pub fn merge_payees(state: State<'_, AppState>, input: MergePayeesInput) -> Result<Payee, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    merge_payees_internal(&mut conn, &input)
+}
+
+pub fn merge_payees_internal(
+    conn: &mut rusqlite::Connection,
+    input: &MergePayeesInput,
+) -> Result<Payee, String> {
+    let target = get_payee(conn, input.target_id)?;
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+
+    for &source_id in input.source_ids.iter().filter(|id| **id != target.id) {
+        let (name, category_id): (String, Option<i64>) = tx
+            .query_row(
+                "SELECT name, default_category_id FROM payees WHERE id = ?1",
+                params![source_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Database error: {}", e))?
+            .ok_or_else(|| format!("Payee {} not found", source_id))?;
+
+        tx.execute(
+            "UPDATE transactions SET payee_id = ?1 WHERE payee_id = ?2",
+            params![target.id, source_id],
+        )
+        .map_err(|e| format!("Failed to move transactions: {}", e))?;
+        tx.execute(
+            "UPDATE payee_aliases SET payee_id = ?1 WHERE payee_id = ?2",
+            params![target.id, source_id],
+        )
+        .map_err(|e| format!("Failed to move aliases: {}", e))?;
+        tx.execute(
+            "INSERT OR IGNORE INTO payee_tags (payee_id, tag_id)
+             SELECT ?1, tag_id FROM payee_tags WHERE payee_id = ?2",
+            params![target.id, source_id],
+        )
+        .map_err(|e| format!("Failed to move payee tags: {}", e))?;
+        tx.execute(
+            "UPDATE payees SET default_category_id = COALESCE(default_category_id, ?1),
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?2",
+            params![category_id, target.id],
+        )
+        .map_err(|e| format!("Failed to update payee: {}", e))?;
+        tx.execute("DELETE FROM payees WHERE id = ?1", params![source_id])
+            .map_err(|e| format!("Failed to delete payee: {}", e))?;
+
+        if !name.eq_ignore_ascii_case(&target.name) {
+            tx.execute(
+                "INSERT OR IGNORE INTO payee_aliases (payee_id, alias) VALUES (?1, ?2)",
+                params![target.id, name],
+            )
+            .map_err(|e| format!("Failed to save alias: {}", e))?;
+        }
+    }
+
+    tx.commit().map_err(|e| format!("Commit error: {}", e))?;
+
+    get_payee(conn, target.id)
+}
+
+#[tauri::command]
+pub fn get_spending_by_payee(
+    state: State<'_, AppState>,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<PayeeSpending>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    get_spending_by_payee_internal(&conn, &start_date, &end_date)
+}
+
+pub fn get_spending_by_payee_internal(
+    conn: &rusqlite::Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<PayeeSpending>, String> {
+    // Grouped by day and currency so each amount converts at its own date's rate
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id, p.name, t.type, t.date, a.currency,
+                    COALESCE(SUM(t.amount), 0), COUNT(t.id)
+             FROM payees p
+             INNER JOIN transactions t ON t.payee_id = p.id
+             INNER JOIN accounts a ON t.account_id = a.id
+             WHERE t.date >= ?1 AND t.date <= ?2
+             GROUP BY p.id, t.type, t.date, a.currency
+             ORDER BY t.date",
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let rows: Vec<PayeeDayRow> = stmt
+        .query_map(params![start_date, end_date], |row| {
+            Ok((
+                (row.get(0)?, row.get(1)?),
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+            ))
+        })
+        .map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let mut converter = RateConverter::to_primary(conn)?;
+    let mut spending: Vec<PayeeSpending> = Vec::new();
+
+    for ((payee_id, payee_name), transaction_type, date, currency, amount, count) in rows {
+        let index = match spending.iter().position(|s| s.payee_id == payee_id) {
+            Some(index) => index,
+            None => {
+                spending.push(PayeeSpending {
+                    payee_id,
+                    payee_name,
+                    total_income: converter.zero(),
+                    total_expense: converter.zero(),
+                    transaction_count: 0,
+                    first_date: date.clone(),
+                    last_date: date.clone(),
+                    currency: converter.target.clone(),
+                    missing_rates: Vec::new(),
+                });
+                spending.len() - 1
+            }
+        };
+        let entry = &mut spending[index];
+        entry.transaction_count += count;
+        entry.last_date = date.clone();
+
+        let amount = Money::new(amount, currency_decimals(&currency));
+        let total = match transaction_type.as_str() {
+            "INCOME" => &mut entry.total_income,
+            "EXPENSE" => &mut entry.total_expense,
+            _ => continue,
+        };
+        match converter.convert(amount, &currency, &date) {
+            Some(converted) => *total += converted,
+            None => record_missing_rate(&mut entry.missing_rates, &currency, &converter.target, &date),
+        }
+    }
+
+    spending.sort_by_key(|s| std::cmp::Reverse(s.total_expense));
+
+    Ok(spending)
+}
@@ -0,0 +1,336 @@
+// File: src-tauri/src/commands/rate_import.rs
+// Bulk import of exchange rate history from CSV files and ECB reference rate XML
+use crate::commands::currencies::refresh_fx_postings;
+use crate::commands::import::{detect_delimiter, parse_date, strip_bom};
+use crate::models::currency::{ExchangeRateImportOptions, ExchangeRateImportResult};
+use crate::AppState;
+use regex::Regex;
+use rusqlite::{params, OptionalExtension};
+use tauri::State;
+
+/// One quote from a rate file: 1 `from` buys `rate` units of `to` on `date`
+#[derive(Debug, Clone, PartialEq)]
+struct RateRow {
+    date: String,
+    from: String,
+    to: String,
+    rate: f64,
+}
+
+#[tauri::command]
+pub fn import_exchange_rates(
+    state: State<'_, AppState>,
+    file_path: String,
+    options: ExchangeRateImportOptions,
+) -> Result<ExchangeRateImportResult, String> {
+    let content = std::fs::read(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    import_exchange_rates_internal(&mut conn, &strip_bom(&content), options)
+}
+
+pub fn import_exchange_rates_internal(
+    conn: &mut rusqlite::Connection,
+    text: &str,
+    options: ExchangeRateImportOptions,
+) -> Result<ExchangeRateImportResult, String> {
+    let base = currency_code(options.base_currency.as_deref().unwrap_or("EUR"))
+        .ok_or("Base currency must be a 3-letter code")?;
+    let format = match options.format {
+        Some(format) => format.trim().to_uppercase(),
+        None if text.trim_start().starts_with('<') => "ECB_XML".to_string(),
+        None => "CSV".to_string(),
+    };
+
+    let (mut rows, errors) = match format.as_str() {
+        "ECB_XML" => parse_ecb_xml(text, &base),
+        "CSV" => parse_rate_csv(text, &base)?,
+        other => return Err(format!("Unsupported exchange rate file format: {}", other)),
+    };
+
+    if let Some(currencies) = &options.currencies {
+        let wanted: Vec<String> = currencies.iter().filter_map(|c| currency_code(c)).collect();
+        let keep = |code: &str| code == base || wanted.iter().any(|w| w == code);
+        rows.retain(|r| keep(&r.from) && keep(&r.to));
+    }
+
+    if rows.is_empty() && errors.is_empty() {
+        return Err("No exchange rates found in file".to_string());
+    }
+
+    let overwrite = options.overwrite.unwrap_or(false);
+    let mut result = ExchangeRateImportResult {
+        format,
+        imported: 0,
+        updated: 0,
+        skipped: 0,
+        errors,
+        first_date: None,
+        last_date: None,
+    };
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    for row in &rows {
+        let existing: Option<f64> = tx
+            .query_row(
+                "SELECT rate FROM exchange_rates
+                 WHERE from_currency = ?1 AND to_currency = ?2 AND effective_date = ?3",
+                params![row.from, row.to, row.date],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        match existing {
+            None => {
+                tx.execute(
+                    "INSERT INTO exchange_rates (from_currency, to_currency, rate, effective_date)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![row.from, row.to, row.rate, row.date],
+                )
+                .map_err(|e| format!("Failed to import rate: {}", e))?;
+                result.imported += 1;
+            }
+            Some(rate) if overwrite && rate != row.rate => {
+                tx.execute(
+                    "UPDATE exchange_rates SET rate = ?1, updated_at = datetime('now')
+                     WHERE from_currency = ?2 AND to_currency = ?3 AND effective_date = ?4",
+                    params![row.rate, row.from, row.to, row.date],
+                )
+                .map_err(|e| format!("Failed to update rate: {}", e))?;
+                result.updated += 1;
+            }
+            Some(_) => {
+                result.skipped += 1;
+                continue;
+            }
+        }
+
+        if result.first_date.as_ref().map_or(true, |d| row.date < *d) {
+            result.first_date = Some(row.date.clone());
+        }
+        if result.last_date.as_ref().map_or(true, |d| row.date > *d) {
+            result.last_date = Some(row.date.clone());
+        }
+    }
+
+    if let Some(first_date) = &result.first_date {
+        refresh_fx_postings(&tx, Some(first_date), None)?;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit import: {}", e))?;
+
+    Ok(result)
+}
+
+/// Parse an ECB reference rate file (daily, 90-day or full history). Each
+/// `<Cube time>` opens a day whose `<Cube currency rate>` entries quote one
+/// unit of `base` (EUR for ECB files).
+fn parse_ecb_xml(text: &str, base: &str) -> (Vec<RateRow>, Vec<String>) {
+    let cube = Regex::new(r"<Cube\s+([^>]*?)/?>").unwrap();
+    let attribute = Regex::new(r#"(\w+)\s*=\s*["']([^"']*)["']"#).unwrap();
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut date: Option<String> = None;
+
+    for tag in cube.captures_iter(text) {
+        let mut time = None;
+        let mut currency = None;
+        let mut rate = None;
+        for attr in attribute.captures_iter(&tag[1]) {
+            match &attr[1] {
+                "time" => time = Some(attr[2].to_string()),
+                "currency" => currency = Some(attr[2].to_string()),
+                "rate" => rate = Some(attr[2].to_string()),
+                _ => {}
+            }
+        }
+
+        if let Some(time) = time {
+            date = parse_date(&time, "YYYY-MM-DD");
+            if date.is_none() {
+                errors.push(format!("Invalid date '{}'", time));
+            }
+            continue;
+        }
+
+        let (Some(currency), Some(rate)) = (currency, rate) else {
+            continue;
+        };
+        let Some(date) = &date else {
+            errors.push(format!("Rate for {} has no date", currency));
+            continue;
+        };
+        match quote(date, base, &currency, &rate) {
+            Ok(Some(row)) => rows.push(row),
+            Ok(None) => {}
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (rows, errors)
+}
+
+/// Parse a rate CSV. Either one quote per row (date, from, to, rate columns,
+/// with `from` defaulting to the base currency), or one column per currency
+/// quoting the base as in the ECB's eurofxref-hist.csv.
+fn parse_rate_csv(text: &str, base: &str) -> Result<(Vec<RateRow>, Vec<String>), String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(detect_delimiter(text))
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Failed to read headers: {}", e))?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+
+    let date_col = column(&["date", "effective_date", "time"])
+        .ok_or("Rate file needs a date column")?;
+    let from_col = column(&["from", "from_currency", "base"]);
+    let to_col = column(&["to", "to_currency", "currency", "quote"]);
+    let rate_col = column(&["rate", "exchange_rate"]);
+
+    // Wide layout: every other column named by a currency code
+    let quote_cols: Vec<(usize, String)> = match (to_col, rate_col) {
+        (Some(_), Some(_)) => Vec::new(),
+        _ => headers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != date_col)
+            .filter_map(|(i, h)| currency_code(h).map(|code| (i, code)))
+            .collect(),
+    };
+    if (to_col.is_none() || rate_col.is_none()) && quote_cols.is_empty() {
+        return Err("Rate file needs 'to' and 'rate' columns, or one column per currency".to_string());
+    }
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, record) in reader.records().enumerate() {
+        let line = index + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(format!("Row {}: {}", line, e));
+                continue;
+            }
+        };
+        let field = |i: usize| record.get(i).unwrap_or("").trim();
+
+        let raw_date = field(date_col);
+        if raw_date.is_empty() {
+            continue;
+        }
+        let Some(date) = parse_date(raw_date, "YYYY-MM-DD") else {
+            errors.push(format!("Row {}: invalid date '{}'", line, raw_date));
+            continue;
+        };
+
+        let quotes: Vec<(String, String, &str)> = match (to_col, rate_col) {
+            (Some(to), Some(rate)) => {
+                let from = from_col.map(field).unwrap_or(base);
+                vec![(from.to_string(), field(to).to_string(), field(rate))]
+            }
+            _ => quote_cols
+                .iter()
+                .map(|(i, code)| (base.to_string(), code.clone(), field(*i)))
+                .collect(),
+        };
+
+        for (from, to, rate) in quotes {
+            match quote(&date, &from, &to, rate) {
+                Ok(Some(row)) => rows.push(row),
+                Ok(None) => {}
+                Err(e) => errors.push(format!("Row {}: {}", line, e)),
+            }
+        }
+    }
+
+    Ok((rows, errors))
+}
+
+/// Validate one quote. Blank and N/A rates (currencies not quoted that day) are skipped.
+fn quote(date: &str, from: &str, to: &str, rate: &str) -> Result<Option<RateRow>, String> {
+    if rate.is_empty() || rate.eq_ignore_ascii_case("N/A") {
+        return Ok(None);
+    }
+    let from = currency_code(from).ok_or_else(|| format!("invalid currency '{}'", from))?;
+    let to = currency_code(to).ok_or_else(|| format!("invalid currency '{}'", to))?;
+    if from == to {
+        return Err(format!("{} is quoted against itself", from));
+    }
+    match rate.parse::<f64>() {
+        Ok(rate) if rate > 0.0 && rate.is_finite() => Ok(Some(RateRow {
+            date: date.to_string(),
+            from,
+            to,
+            rate,
+        })),
+        _ => Err(format!("invalid {}/{} rate '{}'", from, to, rate)),
+    }
+}
+
+fn currency_code(raw: &str) -> Option<String> {
+    let code = raw.trim().to_uppercase();
+    (code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic())).then_some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ECB: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+	<gesmes:subject>Reference rates</gesmes:subject>
+	<Cube>
+		<Cube time='2024-03-01'>
+			<Cube currency='USD' rate='1.0830'/>
+			<Cube currency='JPY' rate='162.41'/>
+		</Cube>
+		<Cube time="2024-02-29">
+			<Cube currency="USD" rate="1.0813"/>
+		</Cube>
+	</Cube>
+</gesmes:Envelope>"#;
+
+    #[test]
+    fn parses_ecb_history() {
+        let (rows, errors) = parse_ecb_xml(ECB, "EUR");
+        assert!(errors.is_empty());
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            RateRow { date: "2024-03-01".into(), from: "EUR".into(), to: "USD".into(), rate: 1.083 }
+        );
+        assert_eq!(rows[2].date, "2024-02-29");
+    }
+
+    #[test]
+    fn parses_long_and_wide_csv() {
+        let (rows, errors) =
+            parse_rate_csv("date,from,to,rate\n2024-03-01,usd,lkr,305.5\n2024-03-02,USD,USD,1\n", "EUR").unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].from, "USD");
+        assert_eq!(errors.len(), 1);
+
+        let (rows, errors) =
+            parse_rate_csv("Date,USD,JPY,\n2024-03-01,1.0830,N/A,\n2024-02-29,1.0813,161.2,\n", "EUR").unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|r| r.from == "EUR"));
+    }
+}
@@ -0,0 +1,549 @@
+// File: src-tauri/src/commands/reconciliation.rs
+use crate::commands::currencies::{account_decimals, currency_decimals};
+use crate::models::money::Money;
+use crate::models::reconciliation::{
+    Reconciliation, ReconciliationSummary, ReconciliationTransaction, StartReconciliationInput,
+};
+use crate::AppState;
+use rusqlite::params;
+use tauri::State;
+
+const RECONCILIATION_SELECT: &str =
+    "SELECT r.id, r.account_id, r.statement_date, r.statement_balance, r.status,
+            r.cleared_balance, r.created_at, r.completed_at, a.currency
+     FROM reconciliations r
+     INNER JOIN accounts a ON r.account_id = a.id";
+
+fn row_to_reconciliation(row: &rusqlite::Row) -> rusqlite::Result<Reconciliation> {
+    let decimals = currency_decimals(&row.get::<_, String>(8)?);
+    Ok(Reconciliation {
+        id: row.get(0)?,
+        account_id: row.get(1)?,
+        statement_date: row.get(2)?,
+        statement_balance: Money::new(row.get(3)?, decimals),
+        status: row.get(4)?,
+        cleared_balance: row.get::<_, Option<i64>>(5)?.map(|m| Money::new(m, decimals)),
+        created_at: row.get(6)?,
+        completed_at: row.get(7)?,
+    })
+}
+
+/// Status of a transaction on its own account (`t.account_id`), for listings.
+/// Status is kept per account side in `transaction_account_status`.
+pub const TRANSACTION_STATUS_SQL: &str =
+    "COALESCE((SELECT tas.status FROM transaction_account_status tas
+               WHERE tas.transaction_id = t.id AND tas.account_id = t.account_id), 'UNCLEARED')";
+
+/// Reconciled sides are locked; changes to them must be refused until
+/// `unlock_transaction` moves them back to CLEARED. Checks the side in
+/// `account_id`, or every side when None.
+pub fn ensure_unlocked(
+    conn: &rusqlite::Connection,
+    transaction_id: i64,
+    account_id: Option<i64>,
+) -> Result<(), String> {
+    let locked: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM transaction_account_status
+             WHERE transaction_id = ?1 AND status = 'RECONCILED' AND (?2 IS NULL OR account_id = ?2)",
+            params![transaction_id, account_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    if locked > 0 {
+        return Err("Transaction is reconciled and locked. Unlock it before making changes.".to_string());
+    }
+    Ok(())
+}
+
+fn get_reconciliation_internal(conn: &rusqlite::Connection, id: i64) -> Result<Reconciliation, String> {
+    conn.query_row(
+        &format!("{} WHERE r.id = ?1", RECONCILIATION_SELECT),
+        params![id],
+        row_to_reconciliation,
+    )
+    .map_err(|_| "Reconciliation not found".to_string())
+}
+
+/// Initial balance plus journal movements whose side of the account is in
+/// one of the given statuses (CLEARED or RECONCILED)
+fn balance_with_status(
+    conn: &rusqlite::Connection,
+    account_id: i64,
+    statuses: &[&str],
+) -> Result<Money, String> {
+    let decimals = account_decimals(conn, account_id)?;
+    let status_list: Vec<String> = statuses.iter().map(|s| format!("'{}'", s)).collect();
+
+    let (initial, movement): (i64, i64) = conn
+        .query_row(
+            &format!(
+                "SELECT a.initial_balance,
+                        COALESCE((SELECT SUM(je.debit) - SUM(je.credit)
+                                  FROM journal_entries je
+                                  INNER JOIN transaction_account_status tas
+                                      ON tas.transaction_id = je.transaction_id AND tas.account_id = je.account_id
+                                  WHERE je.account_id = a.id AND tas.status IN ({})), 0)
+                 FROM accounts a WHERE a.id = ?1",
+                status_list.join(", ")
+            ),
+            params![account_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    Ok(Money::new(initial, decimals) + Money::new(movement, decimals))
+}
+
+// ======================== TRANSACTION STATUS ========================
+
+/// Mark transactions UNCLEARED or CLEARED in one account. The other side of
+/// a transfer keeps its own status. RECONCILED is only set by
+/// `finish_reconciliation`.
+#[tauri::command]
+pub fn set_transaction_status(
+    state: State<'_, AppState>,
+    account_id: i64,
+    transaction_ids: Vec<i64>,
+    status: String,
+) -> Result<i64, String> {
+    if status != "UNCLEARED" && status != "CLEARED" {
+        return Err("Status must be UNCLEARED or CLEARED".to_string());
+    }
+
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    set_transaction_status_internal(&mut conn, account_id, &transaction_ids, &status)
+}
+
+fn set_transaction_status_internal(
+    conn: &mut rusqlite::Connection,
+    account_id: i64,
+    transaction_ids: &[i64],
+    status: &str,
+) -> Result<i64, String> {
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    let mut updated: i64 = 0;
+    for id in transaction_ids {
+        ensure_unlocked(&tx, *id, Some(account_id))?;
+
+        let posts: bool = tx
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM journal_entries WHERE transaction_id = ?1 AND account_id = ?2)",
+                params![id, account_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Database error: {}", e))?;
+        if !posts {
+            continue;
+        }
+
+        if status == "CLEARED" {
+            tx.execute(
+                "INSERT INTO transaction_account_status (transaction_id, account_id, status)
+                 VALUES (?1, ?2, 'CLEARED')
+                 ON CONFLICT(transaction_id, account_id) DO UPDATE SET status = excluded.status",
+                params![id, account_id],
+            )
+        } else {
+            tx.execute(
+                "DELETE FROM transaction_account_status WHERE transaction_id = ?1 AND account_id = ?2",
+                params![id, account_id],
+            )
+        }
+        .map_err(|e| format!("Failed to update status: {}", e))?;
+        updated += 1;
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+
+    Ok(updated)
+}
+
+/// Move a reconciled transaction back to CLEARED in one account
+#[tauri::command]
+pub fn unlock_transaction(
+    state: State<'_, AppState>,
+    transaction_id: i64,
+    account_id: i64,
+) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let changed = conn
+        .execute(
+            "UPDATE transaction_account_status SET status = 'CLEARED', reconciliation_id = NULL
+             WHERE transaction_id = ?1 AND account_id = ?2 AND status = 'RECONCILED'",
+            params![transaction_id, account_id],
+        )
+        .map_err(|e| format!("Failed to unlock transaction: {}", e))?;
+
+    if changed == 0 {
+        return Err("Transaction is not reconciled".to_string());
+    }
+    Ok(())
+}
+
+// ======================== SESSIONS ========================
+
+#[tauri::command]
+pub fn start_reconciliation(
+    state: State<'_, AppState>,
+    input: StartReconciliationInput,
+) -> Result<Reconciliation, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let decimals = account_decimals(&conn, input.account_id)?;
+
+    let open: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM reconciliations WHERE account_id = ?1 AND status = 'IN_PROGRESS'",
+            params![input.account_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Database error: {}", e))?;
+    if open > 0 {
+        return Err("This account already has a reconciliation in progress".to_string());
+    }
+
+    conn.execute(
+        "INSERT INTO reconciliations (account_id, statement_date, statement_balance) VALUES (?1, ?2, ?3)",
+        params![
+            input.account_id,
+            input.statement_date,
+            Money::from_major(input.statement_balance, decimals)
+        ],
+    )
+    .map_err(|e| format!("Failed to start reconciliation: {}", e))?;
+
+    get_reconciliation_internal(&conn, conn.last_insert_rowid())
+}
+
+#[tauri::command]
+pub fn get_reconciliations(
+    state: State<'_, AppState>,
+    account_id: i64,
+) -> Result<Vec<Reconciliation>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE r.account_id = ?1 ORDER BY r.statement_date DESC, r.id DESC",
+            RECONCILIATION_SELECT
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![account_id], row_to_reconciliation)
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    Ok(rows)
+}
+
+/// Cleared balance, difference from the statement, and the transactions still
+/// to be ticked off (anything unreconciled up to the statement date, plus
+/// later transactions that were already marked cleared).
+#[tauri::command]
+pub fn get_reconciliation_summary(
+    state: State<'_, AppState>,
+    reconciliation_id: i64,
+) -> Result<ReconciliationSummary, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    get_reconciliation_summary_internal(&conn, reconciliation_id)
+}
+
+fn get_reconciliation_summary_internal(
+    conn: &rusqlite::Connection,
+    reconciliation_id: i64,
+) -> Result<ReconciliationSummary, String> {
+    let reconciliation = get_reconciliation_internal(conn, reconciliation_id)?;
+    let account_id = reconciliation.account_id;
+    let decimals = reconciliation.statement_balance.decimals();
+
+    let account_name: String = conn
+        .query_row("SELECT name FROM accounts WHERE id = ?1", params![account_id], |row| row.get(0))
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let opening_balance = balance_with_status(conn, account_id, &["RECONCILED"])?;
+    let cleared_balance = balance_with_status(conn, account_id, &["CLEARED", "RECONCILED"])?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT t.id, t.date, t.type, t.memo, COALESCE(tas.status, 'UNCLEARED'),
+                    SUM(je.debit) - SUM(je.credit)
+             FROM journal_entries je
+             INNER JOIN transactions t ON je.transaction_id = t.id
+             LEFT JOIN transaction_account_status tas
+                 ON tas.transaction_id = je.transaction_id AND tas.account_id = je.account_id
+             WHERE je.account_id = ?1
+               AND tas.status IS NOT 'RECONCILED'
+               AND (t.date <= ?2 OR tas.status = 'CLEARED')
+             GROUP BY t.id
+             ORDER BY t.date, t.id",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let transactions: Vec<ReconciliationTransaction> = stmt
+        .query_map(params![account_id, reconciliation.statement_date], |row| {
+            Ok(ReconciliationTransaction {
+                transaction_id: row.get(0)?,
+                date: row.get(1)?,
+                transaction_type: row.get(2)?,
+                memo: row.get(3)?,
+                status: row.get(4)?,
+                amount: Money::new(row.get(5)?, decimals),
+            })
+        })
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    let cleared_count = transactions.iter().filter(|t| t.status == "CLEARED").count() as i64;
+    let uncleared_count = transactions.len() as i64 - cleared_count;
+
+    Ok(ReconciliationSummary {
+        difference: reconciliation.statement_balance - cleared_balance,
+        reconciliation,
+        account_name,
+        opening_balance,
+        cleared_balance,
+        cleared_count,
+        uncleared_count,
+        transactions,
+    })
+}
+
+/// Lock every cleared transaction of the account as RECONCILED, on this
+/// account's side only. Only allowed once the cleared balance matches the
+/// statement balance exactly.
+#[tauri::command]
+pub fn finish_reconciliation(
+    state: State<'_, AppState>,
+    reconciliation_id: i64,
+) -> Result<Reconciliation, String> {
+    let pool = crate::get_db(&state)?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    finish_reconciliation_internal(&mut conn, reconciliation_id)
+}
+
+fn finish_reconciliation_internal(
+    conn: &mut rusqlite::Connection,
+    reconciliation_id: i64,
+) -> Result<Reconciliation, String> {
+    let summary = get_reconciliation_summary_internal(conn, reconciliation_id)?;
+    if summary.reconciliation.status != "IN_PROGRESS" {
+        return Err("Reconciliation is already completed".to_string());
+    }
+    if !summary.difference.is_zero() {
+        return Err(format!(
+            "Cleared balance ({}) does not match the statement balance ({}); difference {}",
+            summary.cleared_balance, summary.reconciliation.statement_balance, summary.difference
+        ));
+    }
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    tx.execute(
+        "UPDATE transaction_account_status SET status = 'RECONCILED', reconciliation_id = ?1
+         WHERE account_id = ?2 AND status = 'CLEARED'",
+        params![reconciliation_id, summary.reconciliation.account_id],
+    )
+    .map_err(|e| format!("Failed to lock reconciled transactions: {}", e))?;
+
+    tx.execute(
+        "UPDATE reconciliations
+         SET status = 'COMPLETED', cleared_balance = ?1, completed_at = datetime('now')
+         WHERE id = ?2",
+        params![summary.cleared_balance, reconciliation_id],
+    )
+    .map_err(|e| format!("Failed to complete reconciliation: {}", e))?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+
+    get_reconciliation_internal(conn, reconciliation_id)
+}
+
+#[tauri::command]
+pub fn cancel_reconciliation(
+    state: State<'_, AppState>,
+    reconciliation_id: i64,
+) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let deleted = conn
+        .execute(
+            "DELETE FROM reconciliations WHERE id = ?1 AND status = 'IN_PROGRESS'",
+            params![reconciliation_id],
+        )
+        .map_err(|e| format!("Failed to cancel reconciliation: {}", e))?;
+
+    if deleted == 0 {
+        return Err("No reconciliation in progress with that id".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::transactions::{
+        create_transaction_internal, delete_transaction_internal, update_transaction_internal,
+    };
+    use crate::models::transactions::{CreateTransactionInput, UpdateTransactionInput};
+
+    #[test]
+    fn reconciling_one_side_of_a_transfer_leaves_the_other_open() {
+        let dir = std::env::temp_dir().join(format!("mm-recon-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = crate::db::init_database_unencrypted(&dir.join("money_manager.db")).unwrap();
+        let mut conn = pool.lock().unwrap();
+
+        let account = |name: &str| {
+            conn.execute(
+                "INSERT INTO accounts (group_id, name, initial_balance) VALUES (1, ?1, 0)",
+                params![name],
+            )
+            .unwrap();
+            conn.last_insert_rowid()
+        };
+        let (checking, savings) = (account("Recon Checking"), account("Recon Savings"));
+
+        let transfer = create_transaction_internal(
+            &mut conn,
+            CreateTransactionInput {
+                date: "2030-01-10".into(),
+                transaction_type: "TRANSFER".into(),
+                amount: 50.0,
+                account_id: checking,
+                to_account_id: Some(savings),
+                to_amount: None,
+                category_id: None,
+                payee_id: None,
+                payee: None,
+                memo: None,
+                tag_ids: None,
+                goal_allocations: None,
+                goal_withdrawals: None,
+                splits: None,
+            },
+        )
+        .unwrap();
+
+        let start = |conn: &rusqlite::Connection, account_id: i64, statement_balance: i64| {
+            conn.execute(
+                "INSERT INTO reconciliations (account_id, statement_date, statement_balance)
+                 VALUES (?1, '2030-01-31', ?2)",
+                params![account_id, statement_balance],
+            )
+            .unwrap();
+            conn.last_insert_rowid()
+        };
+        let checking_session = start(&conn, checking, -5000);
+
+        assert_eq!(set_transaction_status_internal(&mut conn, checking, &[transfer], "CLEARED").unwrap(), 1);
+        finish_reconciliation_internal(&mut conn, checking_session).unwrap();
+
+        assert!(ensure_unlocked(&conn, transfer, Some(checking)).is_err());
+        assert!(ensure_unlocked(&conn, transfer, Some(savings)).is_ok());
+        assert!(delete_transaction_internal(&conn, transfer).is_err());
+
+        // The savings side is still outstanding and can be reconciled on its own
+        let savings_session = start(&conn, savings, 5000);
+        let summary = get_reconciliation_summary_internal(&conn, savings_session).unwrap();
+        assert!(summary.opening_balance.is_zero());
+        assert_eq!(summary.transactions.len(), 1);
+        assert_eq!(summary.transactions[0].status, "UNCLEARED");
+
+        assert_eq!(set_transaction_status_internal(&mut conn, savings, &[transfer], "CLEARED").unwrap(), 1);
+        finish_reconciliation_internal(&mut conn, savings_session).unwrap();
+        assert!(ensure_unlocked(&conn, transfer, Some(savings)).is_err());
+
+        drop(conn);
+        pool.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_reconciled_destination_locks_the_transfer_date() {
+        let dir = std::env::temp_dir().join(format!("mm-recon-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = crate::db::init_database_unencrypted(&dir.join("money_manager.db")).unwrap();
+        let mut conn = pool.lock().unwrap();
+
+        let account = |name: &str| {
+            conn.execute(
+                "INSERT INTO accounts (group_id, name, initial_balance) VALUES (1, ?1, 0)",
+                params![name],
+            )
+            .unwrap();
+            conn.last_insert_rowid()
+        };
+        let (checking, savings) = (account("Lock Checking"), account("Lock Savings"));
+
+        let transfer = create_transaction_internal(
+            &mut conn,
+            CreateTransactionInput {
+                date: "2030-02-10".into(),
+                transaction_type: "TRANSFER".into(),
+                amount: 25.0,
+                account_id: checking,
+                to_account_id: Some(savings),
+                to_amount: None,
+                category_id: None,
+                payee_id: None,
+                payee: None,
+                memo: None,
+                tag_ids: None,
+                goal_allocations: None,
+                goal_withdrawals: None,
+                splits: None,
+            },
+        )
+        .unwrap();
+
+        conn.execute(
+            "INSERT INTO reconciliations (account_id, statement_date, statement_balance)
+             VALUES (?1, '2030-02-28', 2500)",
+            params![savings],
+        )
+        .unwrap();
+        let session = conn.last_insert_rowid();
+        assert_eq!(set_transaction_status_internal(&mut conn, savings, &[transfer], "CLEARED").unwrap(), 1);
+        finish_reconciliation_internal(&mut conn, session).unwrap();
+
+        let edit = |date: Option<&str>, memo: Option<&str>| UpdateTransactionInput {
+            id: transfer,
+            date: date.map(String::from),
+            amount: None,
+            to_amount: None,
+            category_id: None,
+            payee_id: None,
+            payee: None,
+            memo: memo.map(String::from),
+            tag_ids: None,
+            splits: None,
+        };
+        // Moving the date would move the reconciled savings side too
+        assert!(update_transaction_internal(&mut conn, edit(Some("2030-03-01"), None)).is_err());
+        assert!(update_transaction_internal(&mut conn, edit(None, Some("rent"))).is_ok());
+
+        drop(conn);
+        pool.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,6 +3,9 @@ use crate::models::recurring::{
     CreateRecurringTransactionInput, RecurringExecutionLog, RecurringTransaction,
     RecurringTransactionWithDetails, UpcomingExecution, UpdateRecurringTransactionInput,
 };
+use crate::commands::audit::{log_change, snapshot};
+use crate::commands::currencies::{account_decimals, currency_decimals};
+use crate::models::money::Money;
 use crate::models::transactions::CreateTransactionInput;
 use crate::AppState;
 use chrono::{Datelike, Duration, NaiveDate};
@@ -21,7 +24,8 @@ pub fn get_recurring_transactions(
             "SELECT id, name, description, transaction_type, amount, account_id, to_account_id, 
                     category_id, frequency, interval_days, start_date, end_date, next_execution_date, 
                     is_active, last_executed_date, execution_count, created_at,
-                    amount_mode, resume_date, active_months, auto_approve
+                    amount_mode, resume_date, active_months, auto_approve,
+                    (SELECT currency FROM accounts WHERE id = recurring_transactions.account_id)
              FROM recurring_transactions
              ORDER BY next_execution_date ASC",
         )
@@ -34,7 +38,7 @@ pub fn get_recurring_transactions(
                 name: row.get(1)?,
                 description: row.get(2)?,
                 transaction_type: row.get(3)?,
-                amount: row.get(4)?,
+                amount: Money::new(row.get(4)?, currency_decimals(&row.get::<_, String>(21)?)),
                 account_id: row.get(5)?,
                 to_account_id: row.get(6)?,
                 category_id: row.get(7)?,
@@ -77,7 +81,8 @@ pub fn get_recurring_transactions_with_details(
                 rt.amount_mode, rt.resume_date, rt.active_months, rt.auto_approve,
                 a.name as account_name,
                 ta.name as to_account_name,
-                c.name as category_name
+                c.name as category_name,
+                a.currency
              FROM recurring_transactions rt
              INNER JOIN accounts a ON rt.account_id = a.id
              LEFT JOIN accounts ta ON rt.to_account_id = ta.id
@@ -94,7 +99,7 @@ pub fn get_recurring_transactions_with_details(
                     name: row.get(1)?,
                     description: row.get(2)?,
                     transaction_type: row.get(3)?,
-                    amount: row.get(4)?,
+                    amount: Money::new(row.get(4)?, currency_decimals(&row.get::<_, String>(24)?)),
                     account_id: row.get(5)?,
                     to_account_id: row.get(6)?,
                     category_id: row.get(7)?,
@@ -131,7 +136,13 @@ pub fn create_recurring_transaction(
 ) -> Result<i64, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    create_recurring_transaction_internal(&conn, input)
+}
 
+pub fn create_recurring_transaction_internal(
+    conn: &rusqlite::Connection,
+    input: CreateRecurringTransactionInput,
+) -> Result<i64, String> {
     // Validate transaction type
     if input.transaction_type != "INCOME"
         && input.transaction_type != "EXPENSE"
@@ -156,7 +167,10 @@ pub fn create_recurring_transaction(
         1
     };
 
-    if input.amount <= 0.0 {
+    // Validate account
+    let amount = Money::from_major(input.amount, account_decimals(conn, input.account_id)?);
+
+    if !amount.is_positive() {
         // VARIABLE mode allows 0 as placeholder amount
         let amount_mode = input.amount_mode.as_deref().unwrap_or("FIXED");
         if amount_mode != "VARIABLE" {
@@ -170,18 +184,6 @@ pub fn create_recurring_transaction(
         return Err("amount_mode must be FIXED or VARIABLE".to_string());
     }
 
-    // Validate account
-    let acct_exists: bool = conn
-        .query_row(
-            "SELECT COUNT(*) FROM accounts WHERE id = ?1",
-            params![input.account_id],
-            |row| row.get::<_, i64>(0),
-        )
-        .unwrap_or(0) > 0;
-    if !acct_exists {
-        return Err("Account does not exist".to_string());
-    }
-
     if input.transaction_type == "TRANSFER" {
         let to_account_id = input.to_account_id.ok_or("Transfer requires to_account_id")?;
         if to_account_id == input.account_id {
@@ -242,7 +244,7 @@ pub fn create_recurring_transaction(
             input.name,
             input.description,
             input.transaction_type,
-            input.amount,
+            amount,
             input.account_id,
             input.to_account_id,
             input.category_id,
@@ -258,7 +260,10 @@ pub fn create_recurring_transaction(
     )
     .map_err(|e| format!("Failed to create recurring transaction: {}", e))?;
 
-    Ok(conn.last_insert_rowid())
+    let recurring_id = conn.last_insert_rowid();
+    log_change(conn, "RECURRING", recurring_id, "CREATE", None)?;
+
+    Ok(recurring_id)
 }
 
 #[tauri::command]
@@ -268,18 +273,20 @@ pub fn update_recurring_transaction(
 ) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    update_recurring_transaction_internal(&conn, input)
+}
 
-    let exists: bool = conn
+pub fn update_recurring_transaction_internal(
+    conn: &rusqlite::Connection,
+    input: UpdateRecurringTransactionInput,
+) -> Result<(), String> {
+    let account_id: i64 = conn
         .query_row(
-            "SELECT COUNT(*) FROM recurring_transactions WHERE id = ?1",
+            "SELECT account_id FROM recurring_transactions WHERE id = ?1",
             params![input.id],
-            |row| row.get::<_, i64>(0),
+            |row| row.get(0),
         )
-        .unwrap_or(0) > 0;
-
-    if !exists {
-        return Err("Recurring transaction not found".to_string());
-    }
+        .map_err(|_| "Recurring transaction not found".to_string())?;
 
     let mut updates = Vec::new();
 
@@ -290,10 +297,11 @@ pub fn update_recurring_transaction(
         updates.push(format!("description = '{}'", description.replace('\'', "''")));
     }
     if let Some(amount) = input.amount {
-        if amount <= 0.0 {
+        let amount = Money::from_major(amount, account_decimals(conn, account_id)?);
+        if !amount.is_positive() {
             return Err("Amount must be greater than zero".to_string());
         }
-        updates.push(format!("amount = {}", amount));
+        updates.push(format!("amount = {}", amount.minor()));
     }
     if let Some(frequency) = &input.frequency {
         if !["DAILY", "WEEKLY", "MONTHLY", "YEARLY", "CUSTOM"].contains(&frequency.as_str()) {
@@ -348,10 +356,12 @@ pub fn update_recurring_transaction(
         input.id
     );
 
+    let before = snapshot(conn, "RECURRING", input.id)?;
+
     conn.execute(&query, [])
         .map_err(|e| format!("Failed to update recurring transaction: {}", e))?;
 
-    Ok(())
+    log_change(conn, "RECURRING", input.id, "UPDATE", before)
 }
 
 #[tauri::command]
@@ -361,6 +371,14 @@ pub fn delete_recurring_transaction(
 ) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    delete_recurring_transaction_internal(&conn, recurring_id)
+}
+
+pub fn delete_recurring_transaction_internal(
+    conn: &rusqlite::Connection,
+    recurring_id: i64,
+) -> Result<(), String> {
+    let before = snapshot(conn, "RECURRING", recurring_id)?;
 
     let rows = conn
         .execute(
@@ -372,7 +390,7 @@ pub fn delete_recurring_transaction(
     if rows == 0 {
         return Err("Recurring transaction not found".to_string());
     }
-    Ok(())
+    log_change(conn, "RECURRING", recurring_id, "DELETE", before)
 }
 
 #[tauri::command]
@@ -470,15 +488,17 @@ pub fn execute_recurring_transaction(
     let (
         transaction_type, amount, account_id, to_account_id, category_id,
         frequency, interval_days, next_execution_date, end_date, is_active, amount_mode
-    ): (String, f64, i64, Option<i64>, Option<i64>, String, i64, String, Option<String>, i64, String) = conn
+    ): (String, Money, i64, Option<i64>, Option<i64>, String, i64, String, Option<String>, i64, String) = conn
         .query_row(
             "SELECT transaction_type, amount, account_id, to_account_id, category_id,
-                    frequency, interval_days, next_execution_date, end_date, is_active, amount_mode
+                    frequency, interval_days, next_execution_date, end_date, is_active, amount_mode,
+                    (SELECT currency FROM accounts WHERE id = recurring_transactions.account_id)
              FROM recurring_transactions
              WHERE id = ?1",
             params![recurring_id],
             |row| Ok((
-                row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?,
+                row.get(0)?, Money::new(row.get(1)?, currency_decimals(&row.get::<_, String>(11)?)),
+                row.get(2)?, row.get(3)?, row.get(4)?,
                 row.get(5)?, row.get(6)?, row.get(7)?, row.get(8)?, row.get(9)?, row.get(10)?
             )),
         )
@@ -498,14 +518,18 @@ pub fn execute_recurring_transaction(
     let transaction_input = CreateTransactionInput {
         date: today_str.clone(),
         transaction_type,
-        amount,
+        amount: amount.to_major(),
         account_id,
         to_account_id,
+        to_amount: None,
         category_id,
+        payee_id: None,
+        payee: None,
         memo: Some("Auto-generated from recurring transaction".to_string()),
         tag_ids: None,
         goal_allocations: None,
         goal_withdrawals: None,
+        splits: None,
     };
 
     // Need to release lock before calling create_transaction
@@ -571,7 +595,8 @@ pub fn get_upcoming_executions(
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, name, transaction_type, amount, next_execution_date
+            "SELECT id, name, transaction_type, amount, next_execution_date,
+                    (SELECT currency FROM accounts WHERE id = recurring_transactions.account_id)
              FROM recurring_transactions
              WHERE is_active = 1 
                AND next_execution_date >= ?1 
@@ -595,7 +620,7 @@ pub fn get_upcoming_executions(
                     recurring_id: row.get(0)?,
                     name: row.get(1)?,
                     transaction_type: row.get(2)?,
-                    amount: row.get(3)?,
+                    amount: Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(5)?)),
                     next_execution_date: next_date_str,
                     days_until_execution: days_until,
                 })
@@ -613,15 +638,24 @@ pub fn process_recurring_transactions(
     state: State<'_, AppState>,
 ) -> Result<Vec<i64>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
     let today = chrono::Local::now().naive_local().date();
+    process_recurring_transactions_internal(&mut conn, today)
+}
+
+/// Create every recurring transaction due on or before `today` and advance its schedule
+pub fn process_recurring_transactions_internal(
+    conn: &mut rusqlite::Connection,
+    today: NaiveDate,
+) -> Result<Vec<i64>, String> {
     let today_str = today.format("%Y-%m-%d").to_string();
 
     let mut stmt = conn
         .prepare(
             "SELECT id, transaction_type, amount, account_id, to_account_id, category_id, 
-                    frequency, interval_days, next_execution_date, end_date
+                    frequency, interval_days, next_execution_date, end_date,
+                    (SELECT currency FROM accounts WHERE id = recurring_transactions.account_id)
              FROM recurring_transactions
              WHERE is_active = 1 AND next_execution_date <= ?1",
         )
@@ -632,7 +666,7 @@ pub fn process_recurring_transactions(
             Ok((
                 row.get::<_, i64>(0)?,
                 row.get::<_, String>(1)?,
-                row.get::<_, f64>(2)?,
+                Money::new(row.get(2)?, currency_decimals(&row.get::<_, String>(10)?)),
                 row.get::<_, i64>(3)?,
                 row.get::<_, Option<i64>>(4)?,
                 row.get::<_, Option<i64>>(5)?,
@@ -647,8 +681,6 @@ pub fn process_recurring_transactions(
         .map_err(|e| format!("Read error: {}", e))?;
 
     drop(stmt);
-    // Drop lock before processing
-    drop(conn);
 
     let mut created_transaction_ids = Vec::new();
 
@@ -659,17 +691,21 @@ pub fn process_recurring_transactions(
         let transaction_input = CreateTransactionInput {
             date: today_str.clone(),
             transaction_type,
-            amount,
+            amount: amount.to_major(),
             account_id,
             to_account_id,
+            to_amount: None,
             category_id,
+            payee_id: None,
+            payee: None,
             memo: Some("Auto-generated from recurring transaction".to_string()),
             tag_ids: None,
             goal_allocations: None,
             goal_withdrawals: None,
+            splits: None,
         };
 
-        match crate::commands::transactions::create_transaction(state.clone(), transaction_input) {
+        match crate::commands::transactions::create_transaction_internal(conn, transaction_input) {
             Ok(txn_id) => {
                 created_transaction_ids.push(txn_id);
 
@@ -684,18 +720,15 @@ pub fn process_recurring_transactions(
                     }
                 }
 
-                // Reacquire lock just to update this transaction
-                let update_conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
-
                 if should_deactivate {
-                    let _ = update_conn.execute(
+                    let _ = conn.execute(
                         "UPDATE recurring_transactions 
                          SET last_executed_date = ?1, execution_count = execution_count + 1, is_active = 0
                          WHERE id = ?2",
                         params![today_str, recurring_id],
                     );
                 } else {
-                    let _ = update_conn.execute(
+                    let _ = conn.execute(
                         "UPDATE recurring_transactions 
                          SET next_execution_date = ?1, last_executed_date = ?2, execution_count = execution_count + 1
                          WHERE id = ?3",
@@ -768,7 +801,10 @@ pub fn get_execution_history(
 
     let mut stmt = conn
         .prepare(
-            "SELECT id, recurring_id, execution_date, status, amount, transaction_id, notes, created_at
+            "SELECT id, recurring_id, execution_date, status, amount, transaction_id, notes, created_at,
+                    (SELECT a.currency FROM recurring_transactions rt
+                     JOIN accounts a ON rt.account_id = a.id
+                     WHERE rt.id = recurring_execution_log.recurring_id)
              FROM recurring_execution_log
              WHERE recurring_id = ?1
              ORDER BY execution_date DESC
@@ -778,12 +814,14 @@ pub fn get_execution_history(
 
     let logs = stmt
         .query_map(params![recurring_id], |row| {
+            let currency: Option<String> = row.get(8)?;
+            let decimals = currency_decimals(&currency.unwrap_or_default());
             Ok(RecurringExecutionLog {
                 id: row.get(0)?,
                 recurring_id: row.get(1)?,
                 execution_date: row.get(2)?,
                 status: row.get(3)?,
-                amount: row.get(4)?,
+                amount: row.get::<_, Option<i64>>(4)?.map(|minor| Money::new(minor, decimals)),
                 transaction_id: row.get(5)?,
                 notes: row.get(6)?,
                 created_at: row.get(7)?,
@@ -802,10 +840,6 @@ pub fn confirm_variable_amount(
     recurring_id: i64,
     amount: f64,
 ) -> Result<i64, String> {
-    if amount <= 0.0 {
-        return Err("Amount must be greater than zero".to_string());
-    }
-
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
@@ -833,20 +867,29 @@ pub fn confirm_variable_amount(
         return Err("This command is only for VARIABLE recurring transactions".to_string());
     }
 
+    let amount = Money::from_major(amount, account_decimals(&conn, account_id)?);
+    if !amount.is_positive() {
+        return Err("Amount must be greater than zero".to_string());
+    }
+
     let today = chrono::Local::now().naive_local().date();
     let today_str = today.format("%Y-%m-%d").to_string();
 
     let transaction_input = CreateTransactionInput {
         date: today_str.clone(),
         transaction_type,
-        amount,
+        amount: amount.to_major(),
         account_id,
         to_account_id,
+        to_amount: None,
         category_id,
-        memo: Some(format!("Variable recurring — confirmed amount: {:.2}", amount)),
+        payee_id: None,
+        payee: None,
+        memo: Some(format!("Variable recurring — confirmed amount: {}", amount)),
         tag_ids: None,
         goal_allocations: None,
         goal_withdrawals: None,
+        splits: None,
     };
 
     drop(conn);
@@ -917,7 +960,7 @@ pub fn pause_with_resume(
 }
 
 /// Called on app startup or bill refresh. Reactivates paused items whose resume_date has arrived.
-pub fn check_and_resume(pool: &std::sync::Mutex<rusqlite::Connection>) -> Result<(), String> {
+pub fn check_and_resume(pool: &crate::db::DbPool) -> Result<(), String> {
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
     let today_str = chrono::Local::now().naive_local().date().format("%Y-%m-%d").to_string();
 
@@ -933,18 +976,19 @@ pub fn check_and_resume(pool: &std::sync::Mutex<rusqlite::Connection>) -> Result
 
 /// Auto-execute all due recurring transactions that have auto_approve=1 and amount_mode='FIXED'.
 /// Called before loading the bills widget, so auto-approved items never sit in the pending list.
-pub fn process_auto_approvals(pool: &std::sync::Mutex<rusqlite::Connection>) -> Result<Vec<i64>, String> {
+pub fn process_auto_approvals(pool: &crate::db::DbPool) -> Result<Vec<i64>, String> {
     let today = chrono::Local::now().naive_local().date();
     let today_str = today.format("%Y-%m-%d").to_string();
     let current_month = today.month();
     let mut executed_ids: Vec<i64> = Vec::new();
 
     // Collect all auto-approvable items
-    let items: Vec<(i64, String, f64, i64, Option<i64>, Option<i64>, String, i64, Option<String>, Option<String>)> = {
+    let items: Vec<(i64, String, Money, i64, Option<i64>, Option<i64>, String, i64, Option<String>, Option<String>)> = {
         let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
         let mut stmt = conn.prepare(
             "SELECT id, transaction_type, amount, account_id, to_account_id, category_id,
-                    frequency, interval_days, end_date, active_months
+                    frequency, interval_days, end_date, active_months,
+                    (SELECT currency FROM accounts WHERE id = recurring_transactions.account_id)
              FROM recurring_transactions
              WHERE is_active = 1
                AND auto_approve = 1
@@ -956,7 +1000,7 @@ pub fn process_auto_approvals(pool: &std::sync::Mutex<rusqlite::Connection>) ->
             Ok((
                 row.get::<_, i64>(0)?,
                 row.get::<_, String>(1)?,
-                row.get::<_, f64>(2)?,
+                Money::new(row.get(2)?, currency_decimals(&row.get::<_, String>(10)?)),
                 row.get::<_, i64>(3)?,
                 row.get::<_, Option<i64>>(4)?,
                 row.get::<_, Option<i64>>(5)?,
@@ -996,65 +1040,26 @@ pub fn process_auto_approvals(pool: &std::sync::Mutex<rusqlite::Connection>) ->
         let transaction_input = crate::models::transactions::CreateTransactionInput {
             date: today_str.clone(),
             transaction_type: txn_type,
-            amount,
+            amount: amount.to_major(),
             account_id,
             to_account_id,
+            to_amount: None,
             category_id,
+            payee_id: None,
+            payee: None,
             memo: Some("Auto-executed from recurring transaction".to_string()),
             tag_ids: None,
             goal_allocations: None,
             goal_withdrawals: None,
+            splits: None,
         };
 
-        // Insert transaction directly using pool
+        // Goes through the regular path so the entry is journalled and categorized
         let txn_id = {
-            let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+            let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-            // Insert the transaction
-            conn.execute(
-                "INSERT INTO transactions (date, transaction_type, amount, account_id, to_account_id, category_id, memo)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                params![
-                    transaction_input.date,
-                    transaction_input.transaction_type,
-                    transaction_input.amount,
-                    transaction_input.account_id,
-                    transaction_input.to_account_id,
-                    transaction_input.category_id,
-                    transaction_input.memo
-                ],
-            ).map_err(|e| format!("Failed to auto-execute transaction: {}", e))?;
-
-            let txn_id = conn.last_insert_rowid();
-
-            // Update account balances
-            match transaction_input.transaction_type.as_str() {
-                "INCOME" => {
-                    conn.execute(
-                        "UPDATE accounts SET balance = balance + ?1 WHERE id = ?2",
-                        params![transaction_input.amount, transaction_input.account_id],
-                    ).ok();
-                }
-                "EXPENSE" => {
-                    conn.execute(
-                        "UPDATE accounts SET balance = balance - ?1 WHERE id = ?2",
-                        params![transaction_input.amount, transaction_input.account_id],
-                    ).ok();
-                }
-                "TRANSFER" => {
-                    conn.execute(
-                        "UPDATE accounts SET balance = balance - ?1 WHERE id = ?2",
-                        params![transaction_input.amount, transaction_input.account_id],
-                    ).ok();
-                    if let Some(to_id) = transaction_input.to_account_id {
-                        conn.execute(
-                            "UPDATE accounts SET balance = balance + ?1 WHERE id = ?2",
-                            params![transaction_input.amount, to_id],
-                        ).ok();
-                    }
-                }
-                _ => {}
-            }
+            let txn_id = crate::commands::transactions::create_transaction_internal(&mut conn, transaction_input)
+                .map_err(|e| format!("Failed to auto-execute transaction: {}", e))?;
 
             // Update recurring transaction
             let next_str: String = conn.query_row(
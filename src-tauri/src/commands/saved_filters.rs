@@ -0,0 +1,419 @@
+// File: src-tauri/src/commands/saved_filters.rs
+// Saved transaction filters ("views"). A saved filter is a TransactionFilter
+// stored under a name; its date range expression is resolved when it is used,
+// so "last 3 months" always means the three months up to today. Views can be
+// passed to get_transactions_filtered, exports, tag/category reports and
+// budgets, and summarised for dashboard widgets.
+use crate::commands::currencies::RateConverter;
+use crate::commands::transactions::filter_scope_sql;
+use crate::models::money::Money;
+use crate::models::saved_filter::*;
+use crate::models::transactions::TransactionFilter;
+use crate::AppState;
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use rusqlite::{params, OptionalExtension};
+use tauri::State;
+
+const DATE_RANGE_HELP: &str =
+    "Use e.g. 'this month', 'last month', 'last 3 months', 'year to date' or 'this financial year'";
+
+fn row_to_saved_filter(row: &rusqlite::Row) -> rusqlite::Result<(SavedFilter, String)> {
+    Ok((
+        SavedFilter {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            filter: TransactionFilter::default(),
+            created_at: row.get(3)?,
+            updated_at: row.get(4)?,
+        },
+        row.get(2)?,
+    ))
+}
+
+fn parse_filter(saved: (SavedFilter, String)) -> Result<SavedFilter, String> {
+    let (mut saved, json) = saved;
+    saved.filter = serde_json::from_str(&json)
+        .map_err(|e| format!("Saved filter '{}' is invalid: {}", saved.name, e))?;
+    Ok(saved)
+}
+
+fn get_saved_filter(conn: &rusqlite::Connection, id: i64) -> Result<SavedFilter, String> {
+    let saved = conn
+        .query_row(
+            "SELECT id, name, filter, created_at, updated_at FROM saved_filters WHERE id = ?1",
+            params![id],
+            row_to_saved_filter,
+        )
+        .optional()
+        .map_err(|e| format!("Query error: {}", e))?
+        .ok_or_else(|| "Saved filter not found".to_string())?;
+    parse_filter(saved)
+}
+
+/// First month of the financial year from settings, January if unset
+fn financial_year_start_month(conn: &rusqlite::Connection) -> u32 {
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = 'financial_year_start_month'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|value| value.trim().parse::<u32>().ok())
+    .filter(|month| (1..=12).contains(month))
+    .unwrap_or(1)
+}
+
+/// First day of the `months`-long period containing `date`, with periods
+/// counted from `first_month` (January for quarters and years)
+fn period_containing(date: NaiveDate, months: u32, first_month: u32) -> NaiveDate {
+    let months_in = (date.month() + 12 - first_month) % months;
+    NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+        .and_then(|d| d.checked_sub_months(Months::new(months_in)))
+        .unwrap_or(date)
+}
+
+/// Inclusive dates for a relative range such as "this month", "last 3 months"
+/// or "last financial year", as seen on `today`.
+pub(crate) fn resolve_date_range(
+    expression: &str,
+    today: NaiveDate,
+    financial_year_start: u32,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let words: Vec<String> = expression.split_whitespace().map(|w| w.to_lowercase()).collect();
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let invalid = || format!("Unknown date range '{}'. {}", expression.trim(), DATE_RANGE_HELP);
+    let day_before = |d: NaiveDate| d.pred_opt().unwrap_or(d);
+
+    // (start, length in months) of the calendar periods, current and previous
+    let calendar = |months: u32, first_month: u32, previous: bool| {
+        let mut start = period_containing(today, months, first_month);
+        if previous {
+            start = start.checked_sub_months(Months::new(months)).unwrap_or(start);
+        }
+        let end = start.checked_add_months(Months::new(months)).map(day_before).unwrap_or(start);
+        (start, end)
+    };
+
+    let range = match words.as_slice() {
+        ["today"] => (today, today),
+        ["yesterday"] => (day_before(today), day_before(today)),
+        [which @ ("this" | "last"), unit] => {
+            let previous = *which == "last";
+            match *unit {
+                "week" => {
+                    let monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+                    let start = if previous { monday - Days::new(7) } else { monday };
+                    (start, start + Days::new(6))
+                }
+                "month" => calendar(1, 1, previous),
+                "quarter" => calendar(3, 1, previous),
+                "year" => calendar(12, 1, previous),
+                "fy" => calendar(12, financial_year_start, previous),
+                _ => return Err(invalid()),
+            }
+        }
+        [which @ ("this" | "last"), "financial" | "fiscal", "year"] => {
+            calendar(12, financial_year_start, *which == "last")
+        }
+        ["year", "to", "date"] | ["ytd"] => (calendar(12, 1, false).0, today),
+        ["month", "to", "date"] | ["mtd"] => (calendar(1, 1, false).0, today),
+        // N whole units ending today
+        ["last", count, unit] => {
+            let count: u32 = count.parse().ok().filter(|n| *n > 0).ok_or_else(invalid)?;
+            let after = match unit.trim_end_matches('s') {
+                "day" => today.checked_sub_days(Days::new(count as u64)),
+                "week" => today.checked_sub_days(Days::new(7 * count as u64)),
+                "month" => today.checked_sub_months(Months::new(count)),
+                "year" => today.checked_sub_months(Months::new(12 * count)),
+                _ => return Err(invalid()),
+            };
+            let start = after.and_then(|d| d.succ_opt()).ok_or_else(invalid)?;
+            (start, today)
+        }
+        _ => return Err(invalid()),
+    };
+
+    Ok(range)
+}
+
+/// The filter with the fields it leaves unset taken from its saved filter and
+/// its date range turned into dates. Dates given directly win over a range,
+/// and any dates or range on the filter replace the saved filter's; search
+/// text from both must match.
+pub(crate) fn resolve_filter(
+    conn: &rusqlite::Connection,
+    mut filter: TransactionFilter,
+) -> Result<TransactionFilter, String> {
+    if let Some(id) = filter.saved_filter_id.take() {
+        let saved = get_saved_filter(conn, id)?.filter;
+        if filter.start_date.is_none() && filter.end_date.is_none() && filter.date_range.is_none() {
+            filter.start_date = saved.start_date;
+            filter.end_date = saved.end_date;
+            filter.date_range = saved.date_range;
+        }
+        filter.transaction_type = filter.transaction_type.or(saved.transaction_type);
+        filter.account_id = filter.account_id.or(saved.account_id);
+        if filter.category_id.is_none() {
+            filter.category_id = saved.category_id;
+            filter.include_subcategories = saved.include_subcategories;
+        }
+        filter.payee_id = filter.payee_id.or(saved.payee_id);
+        filter.tag_ids = filter.tag_ids.filter(|ids| !ids.is_empty()).or(saved.tag_ids);
+        let search: Vec<String> = [saved.search_query, filter.search_query]
+            .into_iter()
+            .flatten()
+            .filter(|query| !query.trim().is_empty())
+            .collect();
+        filter.search_query = (!search.is_empty()).then(|| search.join(" "));
+    }
+
+    if filter.start_date.is_none() && filter.end_date.is_none() {
+        if let Some(expression) = filter.date_range.as_deref().filter(|e| !e.trim().is_empty()) {
+            let today = Local::now().date_naive();
+            let (start, end) = resolve_date_range(expression, today, financial_year_start_month(conn))?;
+            filter.start_date = Some(start.format("%Y-%m-%d").to_string());
+            filter.end_date = Some(end.format("%Y-%m-%d").to_string());
+        }
+    }
+
+    Ok(filter)
+}
+
+/// SQL condition limiting `t.id` to a saved filter's transactions, empty
+/// without one. Without `with_dates` the filter's own dates are dropped, for
+/// callers such as budgets that bring their own windows.
+pub(crate) fn saved_filter_condition(
+    conn: &rusqlite::Connection,
+    saved_filter_id: Option<i64>,
+    with_dates: bool,
+) -> Result<String, String> {
+    let Some(id) = saved_filter_id else {
+        return Ok(String::new());
+    };
+    let mut filter = resolve_filter(
+        conn,
+        TransactionFilter {
+            saved_filter_id: Some(id),
+            ..Default::default()
+        },
+    )?;
+    if !with_dates {
+        filter.start_date = None;
+        filter.end_date = None;
+    }
+    Ok(format!(" AND t.id IN ({})", filter_scope_sql(&filter)))
+}
+
+fn validate_saved_filter(conn: &rusqlite::Connection, name: &str, filter: &TransactionFilter) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Saved filter name is required".to_string());
+    }
+    if filter.saved_filter_id.is_some() {
+        return Err("A saved filter cannot be based on another saved filter".to_string());
+    }
+    if let Some(expression) = filter.date_range.as_deref().filter(|e| !e.trim().is_empty()) {
+        resolve_date_range(expression, Local::now().date_naive(), financial_year_start_month(conn))?;
+    }
+    for date in [&filter.start_date, &filter.end_date].into_iter().flatten() {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| "Invalid date format. Use YYYY-MM-DD".to_string())?;
+    }
+    Ok(())
+}
+
+fn filter_json(filter: &TransactionFilter) -> Result<String, String> {
+    serde_json::to_string(filter).map_err(|e| format!("Failed to save filter: {}", e))
+}
+
+#[tauri::command]
+pub fn get_saved_filters(state: State<'_, AppState>) -> Result<Vec<SavedFilter>, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, filter, created_at, updated_at FROM saved_filters
+             ORDER BY name COLLATE NOCASE",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+    let rows = stmt
+        .query_map([], row_to_saved_filter)
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    rows.into_iter().map(parse_filter).collect()
+}
+
+#[tauri::command]
+pub fn create_saved_filter(
+    state: State<'_, AppState>,
+    input: CreateSavedFilterInput,
+) -> Result<SavedFilter, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    validate_saved_filter(&conn, &input.name, &input.filter)?;
+    conn.execute(
+        "INSERT INTO saved_filters (name, filter) VALUES (?1, ?2)",
+        params![input.name.trim(), filter_json(&input.filter)?],
+    )
+    .map_err(|e| {
+        if e.to_string().contains("UNIQUE") {
+            format!("A saved filter named '{}' already exists", input.name.trim())
+        } else {
+            format!("Failed to create saved filter: {}", e)
+        }
+    })?;
+
+    get_saved_filter(&conn, conn.last_insert_rowid())
+}
+
+#[tauri::command]
+pub fn update_saved_filter(
+    state: State<'_, AppState>,
+    input: UpdateSavedFilterInput,
+) -> Result<SavedFilter, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    validate_saved_filter(&conn, &input.name, &input.filter)?;
+    let rows_affected = conn
+        .execute(
+            "UPDATE saved_filters SET name = ?1, filter = ?2, updated_at = CURRENT_TIMESTAMP WHERE id = ?3",
+            params![input.name.trim(), filter_json(&input.filter)?, input.id],
+        )
+        .map_err(|e| {
+            if e.to_string().contains("UNIQUE") {
+                format!("A saved filter named '{}' already exists", input.name.trim())
+            } else {
+                format!("Failed to update saved filter: {}", e)
+            }
+        })?;
+    if rows_affected == 0 {
+        return Err("Saved filter not found".to_string());
+    }
+
+    get_saved_filter(&conn, input.id)
+}
+
+/// Budgets and export templates using the filter go back to their own scope
+#[tauri::command]
+pub fn delete_saved_filter(state: State<'_, AppState>, id: i64) -> Result<(), String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    let rows_affected = conn
+        .execute("DELETE FROM saved_filters WHERE id = ?1", params![id])
+        .map_err(|e| format!("Failed to delete saved filter: {}", e))?;
+    if rows_affected == 0 {
+        return Err("Saved filter not found".to_string());
+    }
+    Ok(())
+}
+
+/// Income, expense and count of a saved filter's transactions in the primary currency
+#[tauri::command]
+pub fn get_saved_filter_summary(
+    state: State<'_, AppState>,
+    saved_filter_id: i64,
+) -> Result<SavedFilterSummary, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    get_saved_filter_summary_internal(&conn, saved_filter_id)
+}
+
+pub fn get_saved_filter_summary_internal(
+    conn: &rusqlite::Connection,
+    saved_filter_id: i64,
+) -> Result<SavedFilterSummary, String> {
+    let name = get_saved_filter(conn, saved_filter_id)?.name;
+    let filter = resolve_filter(
+        conn,
+        TransactionFilter {
+            saved_filter_id: Some(saved_filter_id),
+            ..Default::default()
+        },
+    )?;
+
+    // Grouped by day and currency so each amount converts at its own date's rate
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT t.type, t.date, a.currency, COALESCE(SUM(t.amount), 0), COUNT(*)
+             FROM transactions t
+             INNER JOIN accounts a ON t.account_id = a.id
+             WHERE t.id IN ({})
+             GROUP BY t.type, t.date, a.currency",
+            filter_scope_sql(&filter)
+        ))
+        .map_err(|e| format!("Query error: {}", e))?;
+    let rows: Vec<(String, String, String, i64, i64)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    let transaction_count = rows.iter().map(|row| row.4).sum();
+    let mut converter = RateConverter::to_primary(conn)?;
+    let totals = converter.sum_by(
+        rows.into_iter()
+            .map(|(kind, date, currency, amount, _)| (kind, date, currency, amount))
+            .collect(),
+    );
+    let total = |kind: &str| -> Money {
+        totals
+            .iter()
+            .find(|t| t.key == kind)
+            .map(|t| t.total)
+            .unwrap_or(converter.zero())
+    };
+    let (total_income, total_expense) = (total("INCOME"), total("EXPENSE"));
+
+    Ok(SavedFilterSummary {
+        saved_filter_id,
+        name,
+        start_date: filter.start_date,
+        end_date: filter.end_date,
+        total_income,
+        total_expense,
+        net: total_income - total_expense,
+        transaction_count,
+        currency: converter.target.clone(),
+        missing_rates: converter.missing_rates(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(expression: &str, financial_year_start: u32) -> (String, String) {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+        let (start, end) = resolve_date_range(expression, today, financial_year_start).unwrap();
+        (start.to_string(), end.to_string())
+    }
+
+    #[test]
+    fn resolves_relative_date_ranges() {
+        let expected = |start: &str, end: &str| (start.to_string(), end.to_string());
+        assert_eq!(range("this month", 1), expected("2026-02-01", "2026-02-28"));
+        assert_eq!(range("Last  Month", 1), expected("2026-01-01", "2026-01-31"));
+        assert_eq!(range("last 3 months", 1), expected("2025-11-19", "2026-02-18"));
+        assert_eq!(range("last 7 days", 1), expected("2026-02-12", "2026-02-18"));
+        assert_eq!(range("this week", 1), expected("2026-02-16", "2026-02-22"));
+        assert_eq!(range("last quarter", 1), expected("2025-10-01", "2025-12-31"));
+        assert_eq!(range("ytd", 1), expected("2026-01-01", "2026-02-18"));
+        // An April financial year: February is in the year that started last April
+        assert_eq!(range("this financial year", 4), expected("2025-04-01", "2026-03-31"));
+        assert_eq!(range("last fy", 4), expected("2024-04-01", "2025-03-31"));
+        assert_eq!(range("this financial year", 1), expected("2026-01-01", "2026-12-31"));
+    }
+
+    #[test]
+    fn rejects_unknown_date_ranges() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 18).unwrap();
+        for expression in ["", "next month", "last 0 days", "last three months", "this decade"] {
+            assert!(resolve_date_range(expression, today, 1).is_err(), "{}", expression);
+        }
+    }
+}
@@ -1,4 +1,10 @@
 // File: src-tauri/src/commands/scheduled_backup.rs
+use crate::commands::currencies::{currency_decimals, primary_decimals};
+use crate::commands::goals::GOAL_CURRENCY_SQL;
+use crate::commands::reconciliation::TRANSACTION_STATUS_SQL;
+use crate::commands::transactions::received_amount;
+use crate::db::DbPool;
+use crate::models::money::Money;
 use crate::AppState;
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
@@ -128,60 +134,71 @@ pub fn get_backup_status(
 }
 
 #[tauri::command]
-pub fn run_auto_backup_now(
+pub async fn run_auto_backup_now(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
 ) -> Result<BackupResult, String> {
-    let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
-    let settings = get_backup_settings_internal(&conn)?;
-
-    if settings.auto_backup_path.is_empty() {
-        return Err("No backup path configured. Please select a backup folder first.".to_string());
-    }
-
     let app_data_dir = app_handle
         .path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
-    let result = perform_backup(
-        &conn,
-        &settings.auto_backup_path,
-        settings.auto_backup_include_photos,
-        &app_data_dir,
-    )?;
-
-    set_setting(&conn, "auto_backup_last_run", &chrono::Utc::now().to_rfc3339())?;
+    crate::run_blocking(&state, move |pool| {
+        let settings = get_backup_settings_internal(&*pool.read()?)?;
 
-    apply_retention(&settings.auto_backup_path, settings.auto_backup_retention)?;
+        if settings.auto_backup_path.is_empty() {
+            return Err("No backup path configured. Please select a backup folder first.".to_string());
+        }
 
-    Ok(result)
+        run_backup(&pool, &settings, &app_data_dir)
+    })
+    .await
 }
 
 #[tauri::command]
-pub fn check_and_run_auto_backup(
+pub async fn check_and_run_auto_backup(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
 ) -> Result<Option<String>, String> {
-    let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
-    check_and_run_auto_backup_internal(&conn, &app_handle)
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+    crate::run_blocking(&state, move |pool| {
+        check_and_run_auto_backup_internal(&pool, &app_data_dir)
+    })
+    .await
 }
 
 #[tauri::command]
-pub fn restore_from_zip_backup(
+pub async fn restore_from_zip_backup(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
     zip_path: String,
 ) -> Result<ZipRestoreResult, String> {
-    let pool = crate::get_db(&state)?;
-    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
+    crate::run_blocking(&state, move |pool| {
+        let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+        restore_from_zip_backup_internal(&mut conn, &zip_path, &app_data_dir)
+    })
+    .await
+}
+
+/// Internal version without Tauri handles — photos are extracted into `app_data_dir/photos`
+pub fn restore_from_zip_backup_internal(
+    conn: &mut rusqlite::Connection,
+    zip_path: &str,
+    app_data_dir: &Path,
+) -> Result<ZipRestoreResult, String> {
     println!("=== restore_from_zip_backup called ===");
     println!("Zip path: {}", zip_path);
 
-    let file = fs::File::open(&zip_path)
+    let file = fs::File::open(zip_path)
         .map_err(|e| format!("Failed to open zip file: {}", e))?;
     let mut archive = zip::ZipArchive::new(file)
         .map_err(|e| format!("Failed to read zip archive: {}", e))?;
@@ -206,18 +223,14 @@ pub fn restore_from_zip_backup(
     }
 
     let restore_result = crate::commands::settings::restore_from_backup_internal(
-        &mut conn, &backup_json
+        conn, &backup_json
     )?;
 
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
     let photos_dir = app_data_dir.join("photos");
 
     let mut photos_restored: i64 = 0;
 
-    let file2 = fs::File::open(&zip_path)
+    let file2 = fs::File::open(zip_path)
         .map_err(|e| format!("Failed to re-open zip file: {}", e))?;
     let mut archive2 = zip::ZipArchive::new(file2)
         .map_err(|e| format!("Failed to re-read zip archive: {}", e))?;
@@ -315,10 +328,10 @@ pub fn restore_from_zip_backup(
 }
 
 pub fn check_and_run_auto_backup_internal(
-    conn: &rusqlite::Connection,
-    app_handle: &tauri::AppHandle,
+    pool: &DbPool,
+    app_data_dir: &Path,
 ) -> Result<Option<String>, String> {
-    let settings = get_backup_settings_internal(conn)?;
+    let settings = get_backup_settings_internal(&*pool.read()?)?;
 
     if !settings.auto_backup_enabled {
         return Ok(None);
@@ -332,21 +345,7 @@ pub fn check_and_run_auto_backup_internal(
         return Ok(None);
     }
 
-    let app_data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
-
-    let result = perform_backup(
-        conn,
-        &settings.auto_backup_path,
-        settings.auto_backup_include_photos,
-        &app_data_dir,
-    )?;
-
-    set_setting(conn, "auto_backup_last_run", &chrono::Utc::now().to_rfc3339())?;
-
-    apply_retention(&settings.auto_backup_path, settings.auto_backup_retention)?;
+    let result = run_backup(pool, &settings, app_data_dir)?;
 
     Ok(Some(format!(
         "Backup completed: {} ({} bytes)",
@@ -356,6 +355,37 @@ pub fn check_and_run_auto_backup_internal(
 
 // ======================== INTERNAL HELPERS ========================
 
+/// Back up to the configured folder from a reader, so other commands keep
+/// running meanwhile. Only recording the run needs the writer.
+fn run_backup(
+    pool: &DbPool,
+    settings: &BackupSettings,
+    app_data_dir: &Path,
+) -> Result<BackupResult, String> {
+    let result = {
+        let mut reader = pool.read()?;
+        // One read transaction keeps every table at the same point in time
+        let snapshot = reader
+            .transaction()
+            .map_err(|e| format!("Failed to start backup: {}", e))?;
+        perform_backup(
+            &snapshot,
+            &settings.auto_backup_path,
+            settings.auto_backup_include_photos,
+            app_data_dir,
+        )?
+    };
+
+    {
+        let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+        set_setting(&conn, "auto_backup_last_run", &chrono::Utc::now().to_rfc3339())?;
+    }
+
+    apply_retention(&settings.auto_backup_path, settings.auto_backup_retention)?;
+
+    Ok(result)
+}
+
 fn get_backup_settings_internal(conn: &rusqlite::Connection) -> Result<BackupSettings, String> {
     let mut stmt = conn.prepare(
         "SELECT key, value FROM app_settings WHERE key LIKE 'auto_backup_%'"
@@ -402,7 +432,7 @@ fn set_setting(conn: &rusqlite::Connection, key: &str, value: &str) -> Result<()
     Ok(())
 }
 
-fn perform_backup(
+pub fn perform_backup(
     conn: &rusqlite::Connection,
     backup_path: &str,
     include_photos: bool,
@@ -462,12 +492,14 @@ fn generate_backup_json(conn: &rusqlite::Connection) -> Result<String, String> {
     let mut stmt = conn.prepare(
         "SELECT id, group_id, name, initial_balance, currency, created_at FROM accounts ORDER BY name",
     ).unwrap();
+    // Amounts are written in major units so backups stay readable by older versions
     let accounts: Vec<serde_json::Value> = stmt.query_map([], |row| {
+        let currency: String = row.get(4)?;
         Ok(serde_json::json!({
             "id": row.get::<_, i64>(0)?,
             "group_id": row.get::<_, i64>(1)?,
             "name": row.get::<_, String>(2)?,
-            "initial_balance": row.get::<_, f64>(3)?,
+            "initial_balance": Money::new(row.get(3)?, currency_decimals(&currency)),
             "currency": row.get::<_, String>(4)?,
             "created_at": row.get::<_, String>(5)?
         }))
@@ -483,31 +515,44 @@ fn generate_backup_json(conn: &rusqlite::Connection) -> Result<String, String> {
         }))
     }).unwrap().filter_map(Result::ok).collect();
 
-    let mut stmt = conn.prepare(
+    let mut stmt = conn.prepare(&format!(
         "SELECT t.id, t.date, t.type, t.amount, t.account_id, t.to_account_id,
                 t.category_id, t.memo, t.photo_path, t.created_at,
                 a.name as account_name,
                 ta.name as to_account_name,
-                c.name as category_name
+                c.name as category_name,
+                a.currency,
+                {},
+                t.to_amount,
+                ta.currency,
+                CASE WHEN t.to_account_id IS NOT NULL THEN
+                    COALESCE((SELECT tas.status FROM transaction_account_status tas
+                              WHERE tas.transaction_id = t.id AND tas.account_id = t.to_account_id), 'UNCLEARED')
+                END
          FROM transactions t
          INNER JOIN accounts a ON t.account_id = a.id
          LEFT JOIN accounts ta ON t.to_account_id = ta.id
          LEFT JOIN categories c ON t.category_id = c.id
          ORDER BY t.date DESC, t.created_at DESC",
-    ).unwrap();
+        TRANSACTION_STATUS_SQL
+    )).unwrap();
     let transactions: Vec<serde_json::Value> = stmt.query_map([], |row| {
+        let amount = Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(13)?));
         Ok(serde_json::json!({
             "transaction": {
                 "id": row.get::<_, i64>(0)?,
                 "date": row.get::<_, String>(1)?,
                 "transaction_type": row.get::<_, String>(2)?,
-                "amount": row.get::<_, f64>(3)?,
+                "amount": amount,
                 "account_id": row.get::<_, i64>(4)?,
                 "to_account_id": row.get::<_, Option<i64>>(5)?,
+                "to_amount": received_amount(amount, row.get(15)?, row.get(16)?),
                 "category_id": row.get::<_, Option<i64>>(6)?,
                 "memo": row.get::<_, Option<String>>(7)?,
                 "photo_path": row.get::<_, Option<String>>(8)?,
-                "created_at": row.get::<_, String>(9)?
+                "created_at": row.get::<_, String>(9)?,
+                "status": row.get::<_, String>(14)?,
+                "to_status": row.get::<_, Option<String>>(17)?
             },
             "account_name": row.get::<_, String>(10)?,
             "to_account_name": row.get::<_, Option<String>>(11)?,
@@ -515,14 +560,18 @@ fn generate_backup_json(conn: &rusqlite::Connection) -> Result<String, String> {
         }))
     }).unwrap().filter_map(Result::ok).collect();
 
-    let mut stmt = conn.prepare("SELECT id, category_id, amount, period, start_date FROM budgets ORDER BY id").unwrap();
+    let budget_decimals = primary_decimals(conn);
+    let mut stmt = conn.prepare("SELECT id, category_id, amount, period, start_date, rollover_enabled, rollover_cap, end_date FROM budgets ORDER BY id").unwrap();
     let budgets: Vec<serde_json::Value> = stmt.query_map([], |row| {
         Ok(serde_json::json!({
             "id": row.get::<_, i64>(0)?,
             "category_id": row.get::<_, i64>(1)?,
-            "amount": row.get::<_, f64>(2)?,
+            "amount": Money::new(row.get(2)?, budget_decimals),
             "period": row.get::<_, String>(3)?,
-            "start_date": row.get::<_, String>(4)?
+            "start_date": row.get::<_, String>(4)?,
+            "rollover_enabled": row.get::<_, bool>(5)?,
+            "rollover_cap": row.get::<_, Option<i64>>(6)?.map(|cap| Money::new(cap, budget_decimals)),
+            "end_date": row.get::<_, Option<String>>(7)?
         }))
     }).unwrap().filter_map(Result::ok).collect();
 
@@ -554,12 +603,31 @@ fn generate_backup_json(conn: &rusqlite::Connection) -> Result<String, String> {
         }))
     }).unwrap().filter_map(Result::ok).collect();
 
-    let mut stmt = conn.prepare("SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at FROM savings_goals ORDER BY id").unwrap();
+    let mut stmt = conn.prepare(
+        "SELECT s.transaction_id, s.category_id, s.amount, s.memo, a.currency
+         FROM transaction_splits s
+         JOIN transactions t ON s.transaction_id = t.id
+         JOIN accounts a ON t.account_id = a.id
+         ORDER BY s.id",
+    ).unwrap();
+    let transaction_splits: Vec<serde_json::Value> = stmt.query_map([], |row| {
+        Ok(serde_json::json!({
+            "transaction_id": row.get::<_, i64>(0)?,
+            "category_id": row.get::<_, i64>(1)?,
+            "amount": Money::new(row.get(2)?, currency_decimals(&row.get::<_, String>(4)?)),
+            "memo": row.get::<_, Option<String>>(3)?
+        }))
+    }).unwrap().filter_map(Result::ok).collect();
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at, {} FROM savings_goals ORDER BY id",
+        GOAL_CURRENCY_SQL
+    )).unwrap();
     let savings_goals: Vec<serde_json::Value> = stmt.query_map([], |row| {
         Ok(serde_json::json!({
             "id": row.get::<_, i64>(0)?,
             "name": row.get::<_, String>(1)?,
-            "target_amount": row.get::<_, f64>(2)?,
+            "target_amount": Money::new(row.get(2)?, currency_decimals(&row.get::<_, String>(10)?)),
             "target_date": row.get::<_, Option<String>>(3)?,
             "linked_account_id": row.get::<_, Option<i64>>(4)?,
             "color": row.get::<_, String>(5)?,
@@ -570,12 +638,18 @@ fn generate_backup_json(conn: &rusqlite::Connection) -> Result<String, String> {
         }))
     }).unwrap().filter_map(Result::ok).collect();
 
-    let mut stmt = conn.prepare("SELECT id, goal_id, amount, contribution_date, note, created_at FROM goal_contributions ORDER BY id").unwrap();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT gc.id, gc.goal_id, gc.amount, gc.contribution_date, gc.note, gc.created_at, {}
+         FROM goal_contributions gc
+         JOIN savings_goals ON gc.goal_id = savings_goals.id
+         ORDER BY gc.id",
+        GOAL_CURRENCY_SQL
+    )).unwrap();
     let goal_contributions: Vec<serde_json::Value> = stmt.query_map([], |row| {
         Ok(serde_json::json!({
             "id": row.get::<_, i64>(0)?,
             "goal_id": row.get::<_, i64>(1)?,
-            "amount": row.get::<_, f64>(2)?,
+            "amount": Money::new(row.get(2)?, currency_decimals(&row.get::<_, String>(6)?)),
             "contribution_date": row.get::<_, String>(3)?,
             "note": row.get::<_, Option<String>>(4)?,
             "created_at": row.get::<_, String>(5)?
@@ -593,6 +667,7 @@ fn generate_backup_json(conn: &rusqlite::Connection) -> Result<String, String> {
             "transaction_photos": transaction_photos,
             "tags": tags,
             "transaction_tags": transaction_tags,
+            "transaction_splits": transaction_splits,
             "savings_goals": savings_goals,
             "goal_contributions": goal_contributions
         }
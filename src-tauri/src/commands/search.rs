@@ -0,0 +1,336 @@
+// File: src-tauri/src/commands/search.rs
+// Transaction search query language. Free words are looked up in the
+// transaction_search FTS5 index; `key:value` terms become SQL conditions on
+// the transaction filter query, e.g.
+//   amount>5000 tag:travel account:"HNB Savings" -category:Rent before:2026-01-01
+use crate::commands::currencies::{common_decimals, common_scale_sql, major_text_sql};
+use crate::commands::reconciliation::TRANSACTION_STATUS_SQL;
+use chrono::NaiveDate;
+use regex::Regex;
+
+/// Marks around matched words in search snippets
+pub const HIGHLIGHT_START: &str = "<mark>";
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn parse(op: &str) -> Self {
+        match op {
+            "<" => Comparison::Less,
+            "<=" => Comparison::LessOrEqual,
+            ">=" => Comparison::GreaterOrEqual,
+            ">" => Comparison::Greater,
+            _ => Comparison::Equal,
+        }
+    }
+
+    fn sql(self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Equal => "=",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Greater => ">",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    /// Word prefix, or an exact phrase when quoted, in any indexed column
+    Text { text: String, phrase: bool },
+    Memo(String),
+    /// Major units of the transaction's own currency
+    Amount(Comparison, f64),
+    Date(Comparison, String),
+    Tag(String),
+    Account(String),
+    Category(String),
+    Payee(String),
+    Type(String),
+    Status(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Clause {
+    negated: bool,
+    term: Term,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    clauses: Vec<Clause>,
+}
+
+/// One whitespace-separated token; quotes group words and are removed
+struct RawToken {
+    negated: bool,
+    text: String,
+    /// Bytes of `text` before the first quote
+    unquoted_len: usize,
+}
+
+fn raw_tokens(input: &str) -> Vec<RawToken> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else { break };
+
+        let negated = first == '-';
+        if negated {
+            chars.next();
+        }
+
+        let mut text = String::new();
+        let mut unquoted_len = None;
+        let mut in_quotes = false;
+        while let Some(&c) = chars.peek() {
+            if c == '"' {
+                in_quotes = !in_quotes;
+                unquoted_len.get_or_insert(text.len());
+            } else if c.is_whitespace() && !in_quotes {
+                break;
+            } else {
+                text.push(c);
+            }
+            chars.next();
+        }
+
+        if !text.is_empty() {
+            tokens.push(RawToken {
+                negated,
+                unquoted_len: unquoted_len.unwrap_or(text.len()),
+                text,
+            });
+        }
+    }
+
+    tokens
+}
+
+fn parse_date(value: &str) -> Option<String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+/// The term a `key<op>value` token stands for, if the key is known and the value valid
+fn keyed_term(key: &str, op: &str, value: &str) -> Option<Term> {
+    let comparison = Comparison::parse(op);
+    let is_match = matches!(op, ":" | "=");
+    match key {
+        "amount" | "amt" => value
+            .replace(',', "")
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(|v| Term::Amount(comparison, v)),
+        "date" | "on" => parse_date(value).map(|d| Term::Date(comparison, d)),
+        "before" if is_match => parse_date(value).map(|d| Term::Date(Comparison::Less, d)),
+        "after" if is_match => parse_date(value).map(|d| Term::Date(Comparison::Greater, d)),
+        "tag" if is_match => Some(Term::Tag(value.to_string())),
+        "account" | "acct" if is_match => Some(Term::Account(value.to_string())),
+        "category" | "cat" if is_match => Some(Term::Category(value.to_string())),
+        "payee" if is_match => Some(Term::Payee(value.to_string())),
+        "memo" if is_match => Some(Term::Memo(value.to_string())),
+        "type" if is_match => {
+            let value = value.to_uppercase();
+            matches!(value.as_str(), "INCOME" | "EXPENSE" | "TRANSFER").then_some(Term::Type(value))
+        }
+        "status" if is_match => {
+            let value = value.to_uppercase();
+            matches!(value.as_str(), "UNCLEARED" | "CLEARED" | "RECONCILED").then_some(Term::Status(value))
+        }
+        _ => None,
+    }
+}
+
+impl SearchQuery {
+    /// Parse a search string. Anything that is not a recognised `key:value`
+    /// term is searched for as text, so the parse never fails.
+    pub fn parse(input: &str) -> Self {
+        let key = Regex::new(r"^([A-Za-z]+)(>=|<=|:|>|<|=)").unwrap();
+
+        let clauses = raw_tokens(input)
+            .into_iter()
+            .map(|token| {
+                let keyed = key.captures(&token.text[..token.unquoted_len]).and_then(|caps| {
+                    let value = token.text[caps[0].len()..].trim();
+                    if value.is_empty() {
+                        return None;
+                    }
+                    keyed_term(&caps[1].to_lowercase(), &caps[2], value)
+                });
+                let term = keyed.unwrap_or(Term::Text {
+                    phrase: token.unquoted_len == 0,
+                    text: token.text,
+                });
+                Clause {
+                    negated: token.negated,
+                    term,
+                }
+            })
+            .collect();
+
+        SearchQuery { clauses }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// FTS5 MATCH expression for the words that must appear, if any
+    pub fn match_expression(&self) -> Option<String> {
+        let parts: Vec<String> = self
+            .clauses
+            .iter()
+            .filter(|c| !c.negated && !is_bare_number(&c.term))
+            .filter_map(|c| fts_term(&c.term))
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" AND "))
+    }
+
+    /// SQL conditions for everything else, over `t` (transactions) joined to
+    /// its account as `a`. Values are escaped for inlining.
+    pub fn sql_conditions(&self) -> Vec<String> {
+        self.clauses
+            .iter()
+            .filter_map(|clause| {
+                let condition = match &clause.term {
+                    // A bare number may also be part of the amount, as the old search allowed
+                    Term::Text { text, .. } if is_bare_number(&clause.term) => format!(
+                        "({} OR {} LIKE '%{}%')",
+                        fts_condition(&clause.term)?,
+                        major_text_sql("t.amount", "a.currency"),
+                        escape(&text.replace(',', ""))
+                    ),
+                    // Other words that must appear are matched through the index join
+                    Term::Text { .. } | Term::Memo(_) if !clause.negated => return None,
+                    Term::Text { .. } | Term::Memo(_) => fts_condition(&clause.term)?,
+                    Term::Amount(comparison, value) => {
+                        let scale = 10_f64.powi(common_decimals() as i32);
+                        format!(
+                            "{} {} {}",
+                            common_scale_sql("t.amount", "a.currency"),
+                            comparison.sql(),
+                            (value * scale).round() as i64
+                        )
+                    }
+                    Term::Date(comparison, date) => format!("t.date {} '{}'", comparison.sql(), date),
+                    Term::Tag(name) => format!(
+                        "t.id IN (SELECT tt.transaction_id FROM transaction_tags tt
+                         INNER JOIN tags tg ON tt.tag_id = tg.id WHERE tg.name = '{}' COLLATE NOCASE)",
+                        escape(name)
+                    ),
+                    Term::Account(name) => format!(
+                        "(t.account_id IN (SELECT id FROM accounts WHERE name = '{0}' COLLATE NOCASE)
+                         OR t.to_account_id IN (SELECT id FROM accounts WHERE name = '{0}' COLLATE NOCASE))",
+                        escape(name)
+                    ),
+                    // A parent category also finds its subcategories
+                    Term::Category(name) => format!(
+                        "t.id IN (SELECT id FROM transaction_category_lines WHERE category_id IN (
+                            SELECT c.id FROM categories c LEFT JOIN categories pc ON c.parent_id = pc.id
+                            WHERE c.name = '{0}' COLLATE NOCASE OR pc.name = '{0}' COLLATE NOCASE))",
+                        escape(name)
+                    ),
+                    Term::Payee(name) => format!(
+                        "t.payee_id IN (SELECT id FROM payees WHERE name = '{0}' COLLATE NOCASE
+                         UNION SELECT payee_id FROM payee_aliases WHERE alias = '{0}' COLLATE NOCASE)",
+                        escape(name)
+                    ),
+                    Term::Type(value) => format!("t.type = '{}'", value),
+                    Term::Status(value) => format!("{} = '{}'", TRANSACTION_STATUS_SQL, value),
+                };
+                Some(if clause.negated {
+                    format!("NOT ({})", condition)
+                } else {
+                    condition
+                })
+            })
+            .collect()
+    }
+}
+
+fn is_bare_number(term: &Term) -> bool {
+    matches!(term, Term::Text { text, phrase: false } if text.replace(',', "").parse::<f64>().is_ok())
+}
+
+fn fts_condition(term: &Term) -> Option<String> {
+    Some(format!(
+        "t.id IN (SELECT rowid FROM transaction_search WHERE transaction_search MATCH '{}')",
+        escape(&fts_term(term)?)
+    ))
+}
+
+/// FTS5 syntax for a text term: quoted so operators are taken literally, with
+/// a prefix wildcard unless it was an exact phrase
+fn fts_term(term: &Term) -> Option<String> {
+    let (column, text, phrase) = match term {
+        Term::Text { text, phrase } => (None, text, *phrase),
+        Term::Memo(text) => (Some("memo"), text, false),
+        _ => return None,
+    };
+    // Punctuation alone leaves no word to look for
+    if !text.chars().any(char::is_alphanumeric) {
+        return None;
+    }
+    let quoted = format!("\"{}\"{}", text.replace('"', "\"\""), if phrase { "" } else { "*" });
+    Some(match column {
+        Some(column) => format!("{} : {}", column, quoted),
+        None => quoted,
+    })
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_keys_quotes_and_negation() {
+        let query = SearchQuery::parse(
+            r#"amount>5000 tag:travel account:"HNB Savings" -category:Rent before:2026-01-01 keells "super market""#,
+        );
+        let terms: Vec<(bool, Term)> = query.clauses.into_iter().map(|c| (c.negated, c.term)).collect();
+        assert_eq!(
+            terms,
+            vec![
+                (false, Term::Amount(Comparison::Greater, 5000.0)),
+                (false, Term::Tag("travel".into())),
+                (false, Term::Account("HNB Savings".into())),
+                (true, Term::Category("Rent".into())),
+                (false, Term::Date(Comparison::Less, "2026-01-01".into())),
+                (false, Term::Text { text: "keells".into(), phrase: false }),
+                (false, Term::Text { text: "super market".into(), phrase: true }),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_or_invalid_keys_are_text() {
+        let query = SearchQuery::parse("http://x amount>lots before:soon -uber");
+        assert_eq!(
+            query.match_expression().as_deref(),
+            Some(r#""http://x"* AND "amount>lots"* AND "before:soon"*"#)
+        );
+        let conditions = query.sql_conditions();
+        assert_eq!(conditions.len(), 1);
+        assert!(conditions[0].starts_with("NOT (t.id IN"));
+        assert!(conditions[0].contains(r#"MATCH '"uber"*'"#));
+        assert!(SearchQuery::parse("  - ").is_empty());
+    }
+}
@@ -71,11 +71,9 @@ pub fn set_master_password(
     let key = db::encryption::derive_key(&password, &salt)?;
     let verify_hash = db::encryption::create_verify_hash(&password)?;
 
-    // Drop the current active database connection so Windows doesn't block the file rename
-    {
-        let mut db_guard = state.db.lock().map_err(|_| "Lock poisoned".to_string())?;
-        *db_guard = None;
-    }
+    // Close the active database (checkpointing the WAL into the main file)
+    // so the export sees every write and Windows doesn't block the rename
+    close_active_database(&state)?;
 
     // Encrypt the database
     let encrypted_path = state.db_path.with_extension("db.encrypted");
@@ -167,21 +165,23 @@ pub fn change_master_password(
     }
 
     // Derive old and new keys
-    let _old_key = db::encryption::derive_key(&current_password, &config.salt)?;
+    let old_key = db::encryption::derive_key(&current_password, &config.salt)?;
     let new_salt = db::encryption::generate_salt();
     let new_key = db::encryption::derive_key(&new_password, &new_salt)?;
     let new_verify_hash = db::encryption::create_verify_hash(&new_password)?;
 
-    // Re-key the database using PRAGMA rekey
+    // Re-key the closed file, then reopen it so the writer and pooled
+    // readers all connect with the new key
+    close_active_database(&state)?;
+    let rekeyed = db::rekey_database(&state.db_path, &old_key, &new_key);
+    let reopen_key = if rekeyed.is_ok() { &new_key } else { &old_key };
+    let pool = db::init_database_encrypted(&state.db_path, reopen_key)
+        .map_err(|e| format!("Failed to reopen database: {}", e))?;
     {
-        let db_guard = state.db.lock().map_err(|_| "Lock poisoned".to_string())?;
-        let pool = db_guard
-            .as_ref()
-            .ok_or_else(|| "Database is locked".to_string())?;
-        let conn = pool.lock().map_err(|_| "Connection lock poisoned".to_string())?;
-        conn.execute_batch(&format!("PRAGMA rekey = \"x'{}'\";", new_key))
-            .map_err(|e| format!("Failed to re-key database: {}", e))?;
+        let mut db_guard = state.db.lock().map_err(|_| "Lock poisoned".to_string())?;
+        *db_guard = Some(pool);
     }
+    rekeyed.map_err(|e| format!("Failed to re-key database: {}", e))?;
 
     // Update config
     let new_config = db::encryption::EncryptionConfig {
@@ -214,16 +214,28 @@ pub fn remove_encryption(
         return Err("Password is incorrect".to_string());
     }
 
-    // Decrypt: use PRAGMA rekey with empty key
+    // PRAGMA rekey can't turn an encrypted file back into a plain one, so
+    // export the closed database into a plain copy and swap it in
+    let key = db::encryption::derive_key(&password, &config.salt)?;
+    close_active_database(&state)?;
+    let decrypted_path = state.db_path.with_extension("db.decrypted");
+    let decrypted = db::decrypt_database(&state.db_path, &decrypted_path, &key)
+        .map_err(|e| format!("Failed to remove encryption: {}", e))
+        .and_then(|_| {
+            std::fs::rename(&decrypted_path, &state.db_path)
+                .map_err(|e| format!("Failed to replace database: {}", e))
+        });
+
+    let pool = match decrypted {
+        Ok(()) => db::init_database_unencrypted(&state.db_path),
+        Err(_) => db::init_database_encrypted(&state.db_path, &key),
+    }
+    .map_err(|e| format!("Failed to reopen database: {}", e))?;
     {
-        let db_guard = state.db.lock().map_err(|_| "Lock poisoned".to_string())?;
-        let pool = db_guard
-            .as_ref()
-            .ok_or_else(|| "Database is locked".to_string())?;
-        let conn = pool.lock().map_err(|_| "Connection lock poisoned".to_string())?;
-        conn.execute_batch("PRAGMA rekey = '';")
-            .map_err(|e| format!("Failed to remove encryption: {}", e))?;
+        let mut db_guard = state.db.lock().map_err(|_| "Lock poisoned".to_string())?;
+        *db_guard = Some(pool);
     }
+    decrypted?;
 
     // Remove encryption config
     let config_path = db::encryption::config_path(&state.app_data_dir);
@@ -236,6 +248,20 @@ pub fn remove_encryption(
     Ok(())
 }
 
+/// Take the pool out of state and close it, leaving a single checkpointed
+/// file that can be re-keyed or swapped. On failure the pool is put back.
+fn close_active_database(state: &AppState) -> Result<(), String> {
+    let mut db_guard = state.db.lock().map_err(|_| "Lock poisoned".to_string())?;
+    let Some(pool) = db_guard.take() else {
+        return Ok(());
+    };
+    if let Err(e) = pool.close() {
+        *db_guard = Some(pool);
+        return Err(e);
+    }
+    Ok(())
+}
+
 // ======================== PIN COMMANDS ========================
 
 #[tauri::command]
@@ -1,4 +1,7 @@
 // File: src-tauri/src/commands/settings.rs
+use crate::commands::audit::mark_restore_boundary;
+use crate::commands::currencies::{currency_decimals, primary_decimals};
+use crate::models::money::Money;
 use crate::AppState;
 use rusqlite::params;
 use serde::Serialize;
@@ -54,13 +57,15 @@ pub fn set_setting(
 /// This will DELETE all existing data and replace it with the backup data.
 /// The backup format matches what `export_full_backup` produces.
 #[tauri::command]
-pub fn restore_from_backup(
+pub async fn restore_from_backup(
     state: State<'_, AppState>,
     backup_json: String,
 ) -> Result<RestoreResult, String> {
-    let pool = crate::get_db(&state)?;
-    let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
-    restore_from_backup_internal(&mut conn, &backup_json)
+    crate::run_blocking(&state, move |pool| {
+        let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+        restore_from_backup_internal(&mut conn, &backup_json)
+    })
+    .await
 }
 
 /// Internal version without State wrapper — callable from scheduled_backup.rs
@@ -120,6 +125,12 @@ pub fn restore_from_backup_internal(
         .map(|v| v.as_slice())
         .unwrap_or(&[]);
 
+    let transaction_splits = data
+        .get("transaction_splits")
+        .and_then(|v| v.as_array())
+        .map(|v| v.as_slice())
+        .unwrap_or(&[]);
+
     let savings_goals = data
         .get("savings_goals")
         .and_then(|v| v.as_array())
@@ -146,9 +157,21 @@ pub fn restore_from_backup_internal(
         "goal_contributions",
         "savings_goals",
         "transaction_tags",
+        "transaction_splits",
+        "categorization_rule_tags",
+        "payee_tags",
         "tags",
         "journal_entries",
+        "fx_postings",
         "installment_payments",
+        "loan_payments",
+        "loans",
+        "investment_transactions",
+        "investment_accounts",
+        "security_prices",
+        "securities",
+        "credit_card_rewards",
+        "credit_card_reward_rules",
         "credit_card_statements",
         "credit_card_settings",
         "installment_plans",
@@ -157,6 +180,8 @@ pub fn restore_from_backup_internal(
         "exchange_rates",
         "budgets",
         "transactions",
+        "payee_aliases",
+        "payees",
         "categories",
         "accounts",
         // Note: account_groups is kept — it's seeded data
@@ -181,6 +206,8 @@ pub fn restore_from_backup_internal(
     // 7. Restore accounts & build old_id → new_id map
     // ============================================================
     let mut account_id_map: std::collections::HashMap<i64, i64> = std::collections::HashMap::new();
+    // Backups carry major units; each amount is converted using the currency it is held in
+    let mut account_decimals_map: std::collections::HashMap<i64, u32> = std::collections::HashMap::new();
     let mut accounts_restored: i64 = 0;
 
     for account in accounts {
@@ -205,6 +232,8 @@ pub fn restore_from_backup_internal(
             .get("created_at")
             .and_then(|v| v.as_str())
             .unwrap_or("");
+        let decimals = currency_decimals(currency);
+        let initial_balance = Money::from_major(initial_balance, decimals);
 
         tx.execute(
             "INSERT INTO accounts (group_id, name, initial_balance, currency, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
@@ -214,6 +243,7 @@ pub fn restore_from_backup_internal(
 
         let new_id = tx.last_insert_rowid();
         account_id_map.insert(old_id, new_id);
+        account_decimals_map.insert(new_id, decimals);
         accounts_restored += 1;
     }
 
@@ -311,6 +341,8 @@ pub fn restore_from_backup_internal(
     // 8b. Restore savings goals & build old_id → new_id map
     // ============================================================
     let mut goal_id_map: std::collections::HashMap<i64, i64> = std::collections::HashMap::new();
+    let mut goal_decimals_map: std::collections::HashMap<i64, u32> = std::collections::HashMap::new();
+    let primary_dec = primary_decimals(&tx);
     let mut savings_goals_restored: i64 = 0;
 
     for goal in savings_goals {
@@ -326,6 +358,10 @@ pub fn restore_from_backup_internal(
         let updated_at = goal.get("updated_at").and_then(|v| v.as_str()).unwrap_or("");
 
         let new_linked_account_id = old_linked_account_id.and_then(|id| account_id_map.get(&id).copied());
+        let decimals = new_linked_account_id
+            .and_then(|id| account_decimals_map.get(&id).copied())
+            .unwrap_or(primary_dec);
+        let target_amount = Money::from_major(target_amount, decimals);
 
         tx.execute(
             "INSERT INTO savings_goals (name, target_amount, target_date, linked_account_id, color, icon, status, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
@@ -337,7 +373,9 @@ pub fn restore_from_backup_internal(
         )
         .map_err(|e| format!("Failed to restore savings goal '{}': {}", name, e))?;
 
-        goal_id_map.insert(old_id, tx.last_insert_rowid());
+        let new_goal_id = tx.last_insert_rowid();
+        goal_id_map.insert(old_id, new_goal_id);
+        goal_decimals_map.insert(new_goal_id, decimals);
         savings_goals_restored += 1;
     }
 
@@ -345,6 +383,7 @@ pub fn restore_from_backup_internal(
     // 9. Restore transactions and journal entries
     // ============================================================
     let mut txn_id_map: std::collections::HashMap<i64, i64> = std::collections::HashMap::new();
+    let mut txn_decimals_map: std::collections::HashMap<i64, u32> = std::collections::HashMap::new();
     let mut transactions_restored: i64 = 0;
 
     for txn in transactions {
@@ -371,6 +410,9 @@ pub fn restore_from_backup_internal(
         let old_category_id = txn_data.get("category_id").and_then(|v| v.as_i64());
         let memo = txn_data.get("memo").and_then(|v| v.as_str());
         let photo_path = txn_data.get("photo_path").and_then(|v| v.as_str());
+        let status = txn_data.get("status").and_then(|v| v.as_str()).unwrap_or("UNCLEARED");
+        // Older backups have one status for the whole transaction
+        let to_status = txn_data.get("to_status").and_then(|v| v.as_str()).unwrap_or(status);
 
         let new_account_id = account_id_map.get(&old_account_id).copied().ok_or_else(|| {
             format!(
@@ -382,11 +424,23 @@ pub fn restore_from_backup_internal(
         let new_to_account_id = old_to_account_id.map(|id| account_id_map.get(&id).copied().unwrap_or(id));
         let new_category_id = old_category_id.map(|id| category_id_map.get(&id).copied().unwrap_or(id));
 
+        let decimals = account_decimals_map.get(&new_account_id).copied().unwrap_or(2);
+        let amount = Money::from_major(amount, decimals);
+
+        // Older backups have no amount received; transfers then keep the amount sent
+        let to_amount = new_to_account_id.map(|to_acc_id| {
+            let to_decimals = account_decimals_map.get(&to_acc_id).copied().unwrap_or(decimals);
+            match txn_data.get("to_amount").and_then(|v| v.as_f64()) {
+                Some(received) => Money::from_major(received, to_decimals),
+                None => amount.rescale(to_decimals),
+            }
+        });
+
         tx.execute(
-            r#"INSERT INTO transactions (date, type, amount, account_id, to_account_id, category_id, memo, photo_path)
-               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+            r#"INSERT INTO transactions (date, type, amount, account_id, to_account_id, to_amount, category_id, memo, photo_path)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"#,
             params![
-                date, txn_type, amount, new_account_id, new_to_account_id, new_category_id, memo, photo_path
+                date, txn_type, amount, new_account_id, new_to_account_id, to_amount, new_category_id, memo, photo_path
             ]
         )
         .map_err(|e| format!("Failed to restore transaction (date: {}): {}", date, e))?;
@@ -395,6 +449,7 @@ pub fn restore_from_backup_internal(
         if old_id > 0 {
             txn_id_map.insert(old_id, new_txn_id);
         }
+        txn_decimals_map.insert(new_txn_id, decimals);
 
         match txn_type {
             "INCOME" => {
@@ -410,7 +465,7 @@ pub fn restore_from_backup_internal(
                 ).map_err(|e| format!("Failed to create journal entry: {}", e))?;
             }
             "TRANSFER" => {
-                if let Some(to_acc_id) = new_to_account_id {
+                if let (Some(to_acc_id), Some(to_amount)) = (new_to_account_id, to_amount) {
                     tx.execute(
                         "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) VALUES (?1, ?2, 0, ?3)",
                         params![new_txn_id, new_account_id, amount]
@@ -418,7 +473,7 @@ pub fn restore_from_backup_internal(
 
                     tx.execute(
                         "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) VALUES (?1, ?2, ?3, 0)",
-                        params![new_txn_id, to_acc_id, amount]
+                        params![new_txn_id, to_acc_id, to_amount]
                     ).map_err(|e| format!("Failed to create journal entry: {}", e))?;
                 }
             }
@@ -427,6 +482,19 @@ pub fn restore_from_backup_internal(
             }
         }
 
+        let sides = [(Some(new_account_id), status), (new_to_account_id, to_status)];
+        for (side_account_id, side_status) in sides {
+            if let (Some(side_account_id), "CLEARED" | "RECONCILED") = (side_account_id, side_status) {
+                tx.execute(
+                    "INSERT OR IGNORE INTO transaction_account_status (transaction_id, account_id, status)
+                     SELECT transaction_id, account_id, ?3 FROM journal_entries
+                     WHERE transaction_id = ?1 AND account_id = ?2",
+                    params![new_txn_id, side_account_id, side_status],
+                )
+                .map_err(|e| format!("Failed to restore transaction status: {}", e))?;
+            }
+        }
+
         transactions_restored += 1;
     }
 
@@ -447,6 +515,26 @@ pub fn restore_from_backup_internal(
         }
     }
 
+    // ============================================================
+    // 9b-2. Restore transaction splits
+    // ============================================================
+    for split_entry in transaction_splits {
+        let old_txn_id = split_entry.get("transaction_id").and_then(|v| v.as_i64()).unwrap_or(0);
+        let old_category_id = split_entry.get("category_id").and_then(|v| v.as_i64()).unwrap_or(0);
+        let amount = split_entry.get("amount").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let memo = split_entry.get("memo").and_then(|v| v.as_str());
+
+        if let (Some(&new_txn_id), Some(&new_category_id)) = (txn_id_map.get(&old_txn_id), category_id_map.get(&old_category_id)) {
+            let decimals = txn_decimals_map.get(&new_txn_id).copied().unwrap_or(2);
+            let amount = Money::from_major(amount, decimals);
+            tx.execute(
+                "INSERT INTO transaction_splits (transaction_id, category_id, amount, memo) VALUES (?1, ?2, ?3, ?4)",
+                params![new_txn_id, new_category_id, amount, memo]
+            )
+            .map_err(|e| format!("Failed to restore transaction split: {}", e))?;
+        }
+    }
+
     // ============================================================
     // 9c. Restore goal contributions
     // ============================================================
@@ -459,6 +547,8 @@ pub fn restore_from_backup_internal(
         let created_at = contrib_entry.get("created_at").and_then(|v| v.as_str()).unwrap_or("");
 
         if let Some(&new_goal_id) = goal_id_map.get(&old_goal_id) {
+            let decimals = goal_decimals_map.get(&new_goal_id).copied().unwrap_or(primary_dec);
+            let amount = Money::from_major(amount, decimals);
             let _ = tx.execute(
                 "INSERT INTO goal_contributions (goal_id, amount, contribution_date, note, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
                 params![
@@ -499,17 +589,31 @@ pub fn restore_from_backup_internal(
             .get(&old_category_id)
             .copied()
             .unwrap_or(old_category_id);
+        let amount = Money::from_major(amount, primary_dec);
+        let rollover_enabled = budget
+            .get("rollover_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let rollover_cap = budget
+            .get("rollover_cap")
+            .and_then(|v| v.as_f64())
+            .map(|cap| Money::from_major(cap, primary_dec));
+        let end_date = budget.get("end_date").and_then(|v| v.as_str());
 
         tx.execute(
-            "INSERT INTO budgets (category_id, amount, period, start_date) VALUES (?1, ?2, ?3, ?4)",
-            params![new_category_id, amount, period, start_date]
+            "INSERT INTO budgets (category_id, amount, period, start_date, end_date, rollover_enabled, rollover_cap)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![new_category_id, amount, period, start_date, end_date, rollover_enabled, rollover_cap]
         )
         .map_err(|e| format!("Failed to restore budget: {}", e))?;
 
         budgets_restored += 1;
     }
 
-    // 11. Commit the transaction
+    // 11. Ids were reassigned, so earlier audit entries can no longer be reverted
+    mark_restore_boundary(&tx)?;
+
+    // 12. Commit the transaction
     tx.commit()
         .map_err(|e| format!("Failed to commit restore: {}", e))?;
 
@@ -551,9 +655,21 @@ pub fn clear_all_data(state: State<'_, AppState>) -> Result<ClearResult, String>
         "goal_contributions",
         "savings_goals",
         "transaction_tags",
+        "transaction_splits",
+        "categorization_rule_tags",
+        "payee_tags",
         "tags",
         "journal_entries",
+        "fx_postings",
         "installment_payments",
+        "loan_payments",
+        "loans",
+        "investment_transactions",
+        "investment_accounts",
+        "security_prices",
+        "securities",
+        "credit_card_rewards",
+        "credit_card_reward_rules",
         "credit_card_statements",
         "credit_card_settings",
         "installment_plans",
@@ -562,6 +678,8 @@ pub fn clear_all_data(state: State<'_, AppState>) -> Result<ClearResult, String>
         "exchange_rates",
         "budgets",
         "transactions",
+        "payee_aliases",
+        "payees",
         "categories",
         "accounts",
         // Note: account_groups preserved (seed data)
@@ -583,6 +701,9 @@ pub fn clear_all_data(state: State<'_, AppState>) -> Result<ClearResult, String>
     tx.execute("PRAGMA foreign_keys = ON", [])
         .map_err(|e| format!("Failed to re-enable foreign keys: {}", e))?;
 
+    // Ids will be reused, so earlier audit entries can no longer be reverted
+    mark_restore_boundary(&tx)?;
+
     tx.commit()
         .map_err(|e| format!("Failed to commit clear: {}", e))?;
 
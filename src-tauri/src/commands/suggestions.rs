@@ -0,0 +1,312 @@
+// File: src-tauri/src/commands/suggestions.rs
+// Offline category and tag suggestions learned from past transactions with a
+// naive Bayes model over memo tokens, amount size, account and weekday.
+use crate::commands::currencies::currency_decimals;
+use crate::models::money::Money;
+use crate::models::suggestion::{
+    CategorySuggestion, SuggestionInput, TagSuggestion, TransactionSuggestions,
+};
+use crate::AppState;
+use chrono::{Datelike, NaiveDate};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use tauri::State;
+
+/// Most recent transactions the model learns from
+const TRAINING_LIMIT: i64 = 5000;
+/// Tags below this confidence are not suggested
+const MIN_TAG_CONFIDENCE: f64 = 0.3;
+const DEFAULT_LIMIT: usize = 3;
+
+/// Multinomial naive Bayes with Laplace smoothing. Features never seen in
+/// training carry no evidence and are ignored.
+struct Classifier<L> {
+    labels: HashMap<L, LabelStats>,
+    vocabulary: HashSet<String>,
+    documents: u32,
+}
+
+#[derive(Default)]
+struct LabelStats {
+    documents: u32,
+    feature_total: u32,
+    features: HashMap<String, u32>,
+}
+
+impl<L: Eq + Hash + Copy> Classifier<L> {
+    fn new() -> Self {
+        Classifier {
+            labels: HashMap::new(),
+            vocabulary: HashSet::new(),
+            documents: 0,
+        }
+    }
+
+    fn train(&mut self, label: L, features: &[String]) {
+        let stats = self.labels.entry(label).or_default();
+        stats.documents += 1;
+        for feature in features {
+            *stats.features.entry(feature.clone()).or_insert(0) += 1;
+            stats.feature_total += 1;
+            self.vocabulary.insert(feature.clone());
+        }
+        self.documents += 1;
+    }
+
+    /// Posterior probability of every label, most likely first
+    fn classify(&self, features: &[String]) -> Vec<(L, f64)> {
+        if self.documents == 0 {
+            return Vec::new();
+        }
+        let known: Vec<&String> = features.iter().filter(|f| self.vocabulary.contains(*f)).collect();
+        let vocabulary = self.vocabulary.len() as f64;
+
+        let scores: Vec<(L, f64)> = self
+            .labels
+            .iter()
+            .map(|(label, stats)| {
+                let prior = (stats.documents as f64 / self.documents as f64).ln();
+                let denominator = stats.feature_total as f64 + vocabulary;
+                let likelihood: f64 = known
+                    .iter()
+                    .map(|f| {
+                        let count = stats.features.get(*f).copied().unwrap_or(0) as f64;
+                        ((count + 1.0) / denominator).ln()
+                    })
+                    .sum();
+                (*label, prior + likelihood)
+            })
+            .collect();
+
+        // Normalize log scores into probabilities without overflowing
+        let max = scores.iter().map(|(_, s)| *s).fold(f64::NEG_INFINITY, f64::max);
+        let total: f64 = scores.iter().map(|(_, s)| (s - max).exp()).sum();
+        let mut posteriors: Vec<(L, f64)> = scores
+            .into_iter()
+            .map(|(label, s)| (label, (s - max).exp() / total))
+            .collect();
+        posteriors.sort_by(|a, b| b.1.total_cmp(&a.1));
+        posteriors
+    }
+}
+
+/// Lowercase words of two or more characters containing a letter; reference
+/// numbers and card digits change between otherwise identical memos.
+fn memo_tokens(memo: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = memo
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= 2 && t.chars().any(|c| c.is_alphabetic()))
+        .map(|t| t.to_lowercase())
+        .collect();
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+fn features(
+    transaction_type: &str,
+    memo: Option<&str>,
+    amount: Option<f64>,
+    account_id: Option<i64>,
+    date: Option<&str>,
+) -> Vec<String> {
+    let mut features: Vec<String> = memo
+        .map(memo_tokens)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| format!("word:{}", t))
+        .collect();
+    features.push(format!("type:{}", transaction_type));
+    // Half orders of magnitude, so 40 and 60 fall together but 40 and 400 do not
+    if let Some(amount) = amount.filter(|a| *a > 0.0) {
+        features.push(format!("amount:{}", (amount.log10() * 2.0).floor() as i64));
+    }
+    if let Some(account_id) = account_id {
+        features.push(format!("account:{}", account_id));
+    }
+    if let Some(date) = date.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()) {
+        features.push(format!("weekday:{}", date.weekday().num_days_from_monday()));
+    }
+    features
+}
+
+/// Category and tag models trained on the transaction history
+pub struct Suggester {
+    /// One category model per transaction type, since categories are typed
+    categories: HashMap<String, Classifier<i64>>,
+    /// One present/absent model per tag
+    tags: Vec<(i64, Classifier<bool>)>,
+    category_names: HashMap<i64, String>,
+    tag_names: HashMap<i64, String>,
+    training_size: usize,
+}
+
+impl Suggester {
+    pub fn train(conn: &rusqlite::Connection) -> Result<Self, String> {
+        let category_names: HashMap<i64, String> = conn
+            .prepare("SELECT id, name FROM categories")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<Result<_, _>>()
+            })
+            .map_err(|e| format!("Failed to load categories: {}", e))?;
+        let tag_names: HashMap<i64, String> = conn
+            .prepare("SELECT id, name FROM tags")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<Result<_, _>>()
+            })
+            .map_err(|e| format!("Failed to load tags: {}", e))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.type, t.category_id, t.memo, t.amount, a.currency, t.account_id, t.date,
+                        (SELECT GROUP_CONCAT(tt.tag_id) FROM transaction_tags tt WHERE tt.transaction_id = t.id)
+                 FROM transactions t
+                 INNER JOIN accounts a ON t.account_id = a.id
+                 ORDER BY t.date DESC, t.id DESC
+                 LIMIT ?1",
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+        let history = stmt
+            .query_map([TRAINING_LIMIT], |row| {
+                let txn_type: String = row.get(0)?;
+                let amount = Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(4)?));
+                let features = features(
+                    &txn_type,
+                    row.get::<_, Option<String>>(2)?.as_deref(),
+                    Some(amount.to_major()),
+                    row.get(5)?,
+                    row.get::<_, Option<String>>(6)?.as_deref(),
+                );
+                let tag_ids: Vec<i64> = row
+                    .get::<_, Option<String>>(7)?
+                    .unwrap_or_default()
+                    .split(',')
+                    .filter_map(|id| id.parse().ok())
+                    .collect();
+                Ok((txn_type, row.get::<_, Option<i64>>(1)?, features, tag_ids))
+            })
+            .map_err(|e| format!("Execute error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Read error: {}", e))?;
+
+        let mut categories: HashMap<String, Classifier<i64>> = HashMap::new();
+        let mut tags: Vec<(i64, Classifier<bool>)> = tag_names
+            .keys()
+            .map(|id| (*id, Classifier::new()))
+            .collect();
+
+        for (txn_type, category_id, features, tag_ids) in &history {
+            if let Some(category_id) = category_id.filter(|id| category_names.contains_key(id)) {
+                categories
+                    .entry(txn_type.clone())
+                    .or_insert_with(Classifier::new)
+                    .train(category_id, features);
+            }
+            for (tag_id, model) in tags.iter_mut() {
+                model.train(tag_ids.contains(tag_id), features);
+            }
+        }
+        // A tag nobody has used yet can't be learned
+        tags.retain(|(_, model)| model.labels.contains_key(&true));
+
+        Ok(Suggester {
+            categories,
+            tags,
+            category_names,
+            tag_names,
+            training_size: history.len(),
+        })
+    }
+
+    pub fn suggest(&self, input: &SuggestionInput) -> TransactionSuggestions {
+        let features = features(
+            &input.transaction_type,
+            input.memo.as_deref(),
+            input.amount,
+            input.account_id,
+            input.date.as_deref(),
+        );
+        let limit = input.limit.unwrap_or(DEFAULT_LIMIT);
+        let model = self.categories.get(&input.transaction_type);
+        let memo_recognized = model.is_some_and(|model| {
+            features
+                .iter()
+                .any(|f| f.starts_with("word:") && model.vocabulary.contains(f))
+        });
+
+        let categories = model
+            .map(|model| model.classify(&features))
+            .unwrap_or_default()
+            .into_iter()
+            .take(limit)
+            .map(|(category_id, confidence)| CategorySuggestion {
+                category_id,
+                category_name: self.category_names[&category_id].clone(),
+                confidence,
+            })
+            .collect();
+
+        let mut tags: Vec<TagSuggestion> = self
+            .tags
+            .iter()
+            .filter_map(|(tag_id, model)| {
+                let confidence = model
+                    .classify(&features)
+                    .into_iter()
+                    .find(|(present, _)| *present)
+                    .map(|(_, p)| p)?;
+                (confidence >= MIN_TAG_CONFIDENCE).then(|| TagSuggestion {
+                    tag_id: *tag_id,
+                    tag_name: self.tag_names[tag_id].clone(),
+                    confidence,
+                })
+            })
+            .collect();
+        tags.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        tags.truncate(limit);
+
+        TransactionSuggestions {
+            categories,
+            tags,
+            memo_recognized,
+            training_size: self.training_size,
+        }
+    }
+}
+
+#[tauri::command]
+pub fn suggest_transaction_details(
+    state: State<'_, AppState>,
+    input: SuggestionInput,
+) -> Result<TransactionSuggestions, String> {
+    let pool = crate::get_db(&state)?;
+    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+
+    Ok(Suggester::train(&conn)?.suggest(&input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_drop_reference_numbers() {
+        assert_eq!(memo_tokens("UBER *TRIP 4829 uber"), vec!["trip", "uber"]);
+        assert_eq!(memo_tokens("POS 12/03 A1"), vec!["a1", "pos"]);
+    }
+
+    #[test]
+    fn classifier_prefers_matching_history() {
+        let mut model = Classifier::new();
+        for _ in 0..3 {
+            model.train(1, &features("EXPENSE", Some("Keells Super"), Some(45.0), Some(1), None));
+            model.train(2, &features("EXPENSE", Some("Shell fuel"), Some(60.0), Some(1), None));
+        }
+        let ranked = model.classify(&features("EXPENSE", Some("keells"), Some(50.0), Some(1), None));
+        assert_eq!(ranked[0].0, 1);
+        assert!(ranked[0].1 > 0.75);
+        assert!((ranked.iter().map(|(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+}
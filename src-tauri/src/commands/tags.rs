@@ -1,9 +1,15 @@
 // File: src-tauri/src/commands/tags.rs
+use crate::commands::currencies::{currency_decimals, record_missing_rate, RateConverter};
+use crate::commands::saved_filters::saved_filter_condition;
+use crate::models::money::Money;
 use crate::models::tag::*;
 use crate::AppState;
 use rusqlite::params;
 use tauri::State;
 
+/// ((tag id, name, color), type, date, currency, minor amount, transaction count)
+type TagDayRow = ((i64, String, String), String, String, String, i64, i64);
+
 #[tauri::command]
 pub fn create_tag(
     state: State<'_, AppState>,
@@ -152,36 +158,75 @@ pub fn get_spending_by_tag(
     state: State<'_, AppState>,
     start_date: String,
     end_date: String,
+    saved_filter_id: Option<i64>, // Only the saved filter's transactions
 ) -> Result<Vec<TagSpending>, String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    let mut stmt = conn.prepare(
-        "SELECT
-            tg.id as tag_id,
-            tg.name as tag_name,
-            tg.color as tag_color,
-            CAST(COALESCE(SUM(CASE WHEN t.type = 'INCOME' THEN t.amount ELSE 0 END), 0) AS REAL) as total_income,
-            CAST(COALESCE(SUM(CASE WHEN t.type = 'EXPENSE' THEN t.amount ELSE 0 END), 0) AS REAL) as total_expense,
-            COUNT(t.id) as transaction_count
+    // Grouped by day and currency so each amount converts at its own date's rate
+    let mut stmt = conn.prepare(&format!(
+        "SELECT tg.id, tg.name, tg.color, t.type, t.date, a.currency,
+                COALESCE(SUM(t.amount), 0), COUNT(t.id)
          FROM tags tg
          INNER JOIN transaction_tags tt ON tg.id = tt.tag_id
          INNER JOIN transactions t ON tt.transaction_id = t.id
-         WHERE t.date >= ?1 AND t.date <= ?2
-         GROUP BY tg.id
-         ORDER BY total_expense DESC",
-    ).map_err(|e| format!("Database error: {}", e))?;
-
-    let spending: Vec<TagSpending> = stmt.query_map(params![start_date, end_date], |row| {
-        Ok(TagSpending {
-            tag_id: row.get(0)?,
-            tag_name: row.get(1)?,
-            tag_color: row.get(2)?,
-            total_income: row.get(3)?,
-            total_expense: row.get(4)?,
-            transaction_count: row.get(5)?,
+         INNER JOIN accounts a ON t.account_id = a.id
+         WHERE t.date >= ?1 AND t.date <= ?2{}
+         GROUP BY tg.id, t.type, t.date, a.currency",
+        saved_filter_condition(&conn, saved_filter_id, true)?
+    )).map_err(|e| format!("Database error: {}", e))?;
+
+    let rows: Vec<TagDayRow> = stmt
+        .query_map(params![start_date, end_date], |row| {
+            Ok((
+                (row.get(0)?, row.get(1)?, row.get(2)?),
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+                row.get(7)?,
+            ))
         })
-    }).unwrap().filter_map(Result::ok).collect();
+        .map_err(|e| format!("Database error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let mut spending: Vec<TagSpending> = Vec::new();
+
+    for ((tag_id, tag_name, tag_color), transaction_type, date, currency, amount, count) in rows {
+        let index = match spending.iter().position(|s| s.tag_id == tag_id) {
+            Some(index) => index,
+            None => {
+                spending.push(TagSpending {
+                    tag_id,
+                    tag_name,
+                    tag_color,
+                    total_income: converter.zero(),
+                    total_expense: converter.zero(),
+                    transaction_count: 0,
+                    currency: converter.target.clone(),
+                    missing_rates: Vec::new(),
+                });
+                spending.len() - 1
+            }
+        };
+        let entry = &mut spending[index];
+        entry.transaction_count += count;
+
+        let amount = Money::new(amount, currency_decimals(&currency));
+        let total = match transaction_type.as_str() {
+            "INCOME" => &mut entry.total_income,
+            "EXPENSE" => &mut entry.total_expense,
+            _ => continue,
+        };
+        match converter.convert(amount, &currency, &date) {
+            Some(converted) => *total += converted,
+            None => record_missing_rate(&mut entry.missing_rates, &currency, &converter.target, &date),
+        }
+    }
+
+    spending.sort_by_key(|s| std::cmp::Reverse(s.total_expense));
 
     Ok(spending)
 }
@@ -2,6 +2,9 @@
 use crate::models::template::{
     CreateTemplateInput, TransactionTemplate, TransactionTemplateWithDetails, UpdateTemplateInput,
 };
+use crate::commands::advanced::{evaluate_categorization_rules, load_categorization_rules, RuleSubject};
+use crate::commands::currencies::{account_decimals, currency_decimals, primary_decimals};
+use crate::models::money::Money;
 use crate::AppState;
 use rusqlite::{params, OptionalExtension};
 use tauri::State;
@@ -19,7 +22,8 @@ pub fn get_templates(
             t.category_id, t.memo, t.use_count, t.last_used_at, t.created_at, t.updated_at,
             a.name as account_name,
             ta.name as to_account_name,
-            c.name as category_name
+            c.name as category_name,
+            COALESCE(a.currency, (SELECT value FROM app_settings WHERE key = 'primary_currency'), 'LKR')
          FROM transaction_templates t
          LEFT JOIN accounts a ON t.account_id = a.id
          LEFT JOIN accounts ta ON t.to_account_id = ta.id
@@ -33,7 +37,7 @@ pub fn get_templates(
                 id: row.get(0)?,
                 name: row.get(1)?,
                 transaction_type: row.get(2)?,
-                amount: row.get(3)?,
+                amount: Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(15)?)),
                 account_id: row.get(4)?,
                 to_account_id: row.get(5)?,
                 category_id: row.get(6)?,
@@ -46,6 +50,7 @@ pub fn get_templates(
             account_name: row.get(12)?,
             to_account_name: row.get(13)?,
             category_name: row.get(14)?,
+            tag_ids: Vec::new(),
         })
     }).unwrap().filter_map(Result::ok).collect();
 
@@ -73,23 +78,16 @@ pub fn create_template(
         return Err("Template name is required.".to_string());
     }
 
+    // Validate account exists if provided
+    let decimals = template_decimals(&conn, input.account_id)
+        .map_err(|_| "Account not found.".to_string())?;
+
     // Validate amount
-    if input.amount < 0.0 {
+    let amount = Money::from_major(input.amount, decimals);
+    if amount.is_negative() {
         return Err("Amount cannot be negative.".to_string());
     }
 
-    // Validate account exists if provided
-    if let Some(account_id) = input.account_id {
-        let exists: bool = conn.query_row(
-            "SELECT COUNT(id) FROM accounts WHERE id = ?1",
-            params![account_id],
-            |row| row.get::<_, i64>(0),
-        ).unwrap_or(0) > 0;
-        if !exists {
-            return Err("Account not found.".to_string());
-        }
-    }
-
     // Validate to_account exists if provided
     if let Some(to_account_id) = input.to_account_id {
         let exists: bool = conn.query_row(
@@ -133,7 +131,7 @@ pub fn create_template(
         params![
             input.name.trim(),
             input.transaction_type,
-            input.amount,
+            amount,
             input.account_id,
             input.to_account_id,
             input.category_id,
@@ -154,7 +152,7 @@ pub fn create_template(
             id: row.get(0)?,
             name: row.get(1)?,
             transaction_type: row.get(2)?,
-            amount: row.get(3)?,
+            amount: Money::new(row.get(3)?, decimals),
             account_id: row.get(4)?,
             to_account_id: row.get(5)?,
             category_id: row.get(6)?,
@@ -175,15 +173,12 @@ pub fn update_template(
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
 
-    let exists: bool = conn.query_row(
-        "SELECT COUNT(id) FROM transaction_templates WHERE id = ?1",
+    let current_account_id: Option<i64> = conn.query_row(
+        "SELECT account_id FROM transaction_templates WHERE id = ?1",
         params![input.id],
-        |row| row.get::<_, i64>(0),
-    ).unwrap_or(0) > 0;
-
-    if !exists {
-        return Err("Template not found.".to_string());
-    }
+        |row| row.get(0),
+    ).optional().map_err(|e| format!("Database error: {}", e))?
+    .ok_or_else(|| "Template not found.".to_string())?;
 
     let mut set_clauses: Vec<String> = Vec::new();
 
@@ -195,10 +190,13 @@ pub fn update_template(
     }
 
     if let Some(amount) = input.amount {
-        if amount < 0.0 {
+        let decimals = template_decimals(&conn, input.account_id.or(current_account_id))
+            .map_err(|_| "Account not found.".to_string())?;
+        let amount = Money::from_major(amount, decimals);
+        if amount.is_negative() {
             return Err("Amount cannot be negative.".to_string());
         }
-        set_clauses.push(format!("amount = {}", amount));
+        set_clauses.push(format!("amount = {}", amount.minor()));
     }
 
     if let Some(account_id) = input.account_id {
@@ -297,7 +295,8 @@ pub fn use_template(
             t.category_id, t.memo, t.use_count, t.last_used_at, t.created_at, t.updated_at,
             a.name as account_name,
             ta.name as to_account_name,
-            c.name as category_name
+            c.name as category_name,
+            COALESCE(a.currency, (SELECT value FROM app_settings WHERE key = 'primary_currency'), 'LKR')
          FROM transaction_templates t
          LEFT JOIN accounts a ON t.account_id = a.id
          LEFT JOIN accounts ta ON t.to_account_id = ta.id
@@ -305,13 +304,13 @@ pub fn use_template(
          WHERE t.id = ?1",
     ).map_err(|e| format!("Database error: {}", e))?;
 
-    let details = stmt.query_row(params![template_id], |row| {
+    let mut details = stmt.query_row(params![template_id], |row| {
         Ok(TransactionTemplateWithDetails {
             template: TransactionTemplate {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 transaction_type: row.get(2)?,
-                amount: row.get(3)?,
+                amount: Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(15)?)),
                 account_id: row.get(4)?,
                 to_account_id: row.get(5)?,
                 category_id: row.get(6)?,
@@ -324,8 +323,51 @@ pub fn use_template(
             account_name: row.get(12)?,
             to_account_name: row.get(13)?,
             category_name: row.get(14)?,
+            tag_ids: Vec::new(),
         })
     }).optional().map_err(|e| format!("Database error: {}", e))?.ok_or_else(|| "Template not found.".to_string())?;
 
+    if details.template.category_id.is_none() {
+        suggest_from_rules(&conn, &mut details)?;
+    }
+
     Ok(details)
 }
+
+/// Fill an uncategorized template's category, tags and memo from the categorization rules
+fn suggest_from_rules(conn: &rusqlite::Connection, details: &mut TransactionTemplateWithDetails) -> Result<(), String> {
+    let rules = load_categorization_rules(conn)?;
+    let template = &details.template;
+    let subject = RuleSubject {
+        memo: template.memo.as_deref().unwrap_or(""),
+        amount: template.amount.to_major(),
+        account_id: template.account_id,
+        transaction_type: &template.transaction_type,
+    };
+    let Some(outcome) = evaluate_categorization_rules(&rules, &subject) else {
+        return Ok(());
+    };
+
+    if let Some(category_id) = outcome.category_id.filter(|_| details.template.transaction_type != "TRANSFER") {
+        details.category_name = conn
+            .query_row("SELECT name FROM categories WHERE id = ?1", params![category_id], |row| row.get(0))
+            .optional()
+            .map_err(|e| format!("Database error: {}", e))?;
+        if details.category_name.is_some() {
+            details.template.category_id = Some(category_id);
+        }
+    }
+    if outcome.memo.is_some() {
+        details.template.memo = outcome.memo;
+    }
+    details.tag_ids = outcome.tag_ids;
+    Ok(())
+}
+
+/// Templates are stored in their account's currency, or the primary currency when unlinked.
+fn template_decimals(conn: &rusqlite::Connection, account_id: Option<i64>) -> Result<u32, String> {
+    match account_id {
+        Some(id) => account_decimals(conn, id),
+        None => Ok(primary_decimals(conn)),
+    }
+}
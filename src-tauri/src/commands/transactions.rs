@@ -1,96 +1,307 @@
 // File: src-tauri/src/commands/transactions.rs
+use crate::commands::advanced::{evaluate_categorization_rules, load_categorization_rules, RuleSubject};
+use crate::commands::audit::{log_change, snapshot};
+use crate::commands::currencies::{
+    account_currency, add_currency_amount, currency_decimals, record_missing_rate,
+    refresh_fx_postings, RateConverter,
+};
+use crate::commands::goals::goal_decimals;
+use crate::commands::payees::{payee_defaults, resolve_payee};
+use crate::commands::reconciliation::{ensure_unlocked, TRANSACTION_STATUS_SQL};
+use crate::commands::saved_filters::resolve_filter;
+use crate::commands::search::{SearchQuery, HIGHLIGHT_END, HIGHLIGHT_START};
+use crate::models::currency::{CurrencyAmount, MissingRate};
+use crate::models::money::Money;
 use crate::models::tag::TagInfo;
 use crate::models::transactions::{
     CategorySpending, CreateTransactionInput, DailySummary, IncomeExpenseSummary, MonthlyTrend,
-    Transaction, TransactionFilter, TransactionWithDetails, UpdateTransactionInput,
+    Transaction, TransactionFilter, TransactionPage, TransactionSplit, TransactionSplitInput,
+    TransactionWithDetails, UpdateTransactionInput,
 };
 use crate::AppState;
 use rusqlite::params;
+use rusqlite::types::Value as SqlValue;
+use std::collections::HashMap;
 use tauri::State;
 
-/// Load tags for a batch of transaction IDs. Returns a Vec of (transaction_id, TagInfo) pairs.
+/// Ids bound per `IN (...)` list, well under SQLite's bound-variable limit
+const ID_CHUNK_SIZE: usize = 500;
+
+/// Load tags for a batch of transaction IDs, grouped by transaction id.
+/// Ids are queried in chunks so large ledgers stay under the variable limit.
 fn load_tags_for_transactions(
     conn: &rusqlite::Connection,
     transaction_ids: &[i64],
-) -> Result<Vec<(i64, TagInfo)>, String> {
-    if transaction_ids.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    let placeholders: Vec<String> = transaction_ids.iter().map(|_| "?".to_string()).collect();
-    let query_str = format!(
-        "SELECT tt.transaction_id, tg.id, tg.name, tg.color
-         FROM transaction_tags tt
-         INNER JOIN tags tg ON tt.tag_id = tg.id
-         WHERE tt.transaction_id IN ({})
-         ORDER BY tg.name ASC",
-        placeholders.join(", ")
-    );
+) -> Result<HashMap<i64, Vec<TagInfo>>, String> {
+    let mut tags: HashMap<i64, Vec<TagInfo>> = HashMap::new();
+
+    for chunk in transaction_ids.chunks(ID_CHUNK_SIZE) {
+        let placeholders: Vec<String> = chunk.iter().map(|_| "?".to_string()).collect();
+        let query_str = format!(
+            "SELECT tt.transaction_id, tg.id, tg.name, tg.color
+             FROM transaction_tags tt
+             INNER JOIN tags tg ON tt.tag_id = tg.id
+             WHERE tt.transaction_id IN ({})
+             ORDER BY tg.name ASC",
+            placeholders.join(", ")
+        );
 
-    let mut stmt = conn
-        .prepare(&query_str)
-        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+        let mut stmt = conn
+            .prepare(&query_str)
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(chunk.iter()), |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    TagInfo {
+                        id: row.get(1)?,
+                        name: row.get(2)?,
+                        color: row.get(3)?,
+                    },
+                ))
+            })
+            .map_err(|e| format!("Failed to load tags: {}", e))?;
 
-    let params_iter = rusqlite::params_from_iter(transaction_ids.iter());
+        for row in rows {
+            let (transaction_id, tag) = row.map_err(|e| format!("Failed to read tags: {}", e))?;
+            tags.entry(transaction_id).or_default().push(tag);
+        }
+    }
 
-    let rows = stmt
-        .query_map(params_iter, |row| {
-            Ok((
-                row.get::<_, i64>(0)?,
-                TagInfo {
-                    id: row.get(1)?,
-                    name: row.get(2)?,
-                    color: row.get(3)?,
-                },
-            ))
-        })
-        .map_err(|e| format!("Failed to load tags: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Failed to read tags: {}", e))?;
+    Ok(tags)
+}
+
+/// Load split lines for a batch of transaction IDs, grouped by transaction id
+/// in line order. Ids are queried in chunks like the tags.
+pub fn load_splits_for_transactions(
+    conn: &rusqlite::Connection,
+    transaction_ids: &[i64],
+) -> Result<HashMap<i64, Vec<TransactionSplit>>, String> {
+    let mut splits: HashMap<i64, Vec<TransactionSplit>> = HashMap::new();
+
+    for chunk in transaction_ids.chunks(ID_CHUNK_SIZE) {
+        let placeholders: Vec<String> = chunk.iter().map(|_| "?".to_string()).collect();
+        let query_str = format!(
+            "SELECT s.id, s.transaction_id, s.category_id, c.name, s.amount, s.memo, a.currency
+             FROM transaction_splits s
+             INNER JOIN transactions t ON s.transaction_id = t.id
+             INNER JOIN accounts a ON t.account_id = a.id
+             LEFT JOIN categories c ON s.category_id = c.id
+             WHERE s.transaction_id IN ({})
+             ORDER BY s.id ASC",
+            placeholders.join(", ")
+        );
+
+        let mut stmt = conn
+            .prepare(&query_str)
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(chunk.iter()), |row| {
+                Ok(TransactionSplit {
+                    id: row.get(0)?,
+                    transaction_id: row.get(1)?,
+                    category_id: row.get(2)?,
+                    category_name: row.get(3)?,
+                    amount: Money::new(row.get(4)?, currency_decimals(&row.get::<_, String>(6)?)),
+                    memo: row.get(5)?,
+                })
+            })
+            .map_err(|e| format!("Failed to load splits: {}", e))?;
+
+        for split in rows {
+            let split = split.map_err(|e| format!("Failed to read splits: {}", e))?;
+            splits.entry(split.transaction_id).or_default().push(split);
+        }
+    }
 
-    Ok(rows)
+    Ok(splits)
 }
 
-/// Insert tag associations for a transaction
-fn insert_transaction_tags(
+/// Validate split lines against the transaction they belong to.
+/// Lines must be positive, reference existing categories and sum to the transaction amount.
+fn validate_splits(
+    conn: &rusqlite::Connection,
+    transaction_type: &str,
+    amount: Money,
+    splits: &[TransactionSplitInput],
+) -> Result<(), String> {
+    if splits.is_empty() {
+        return Ok(());
+    }
+
+    if transaction_type == "TRANSFER" {
+        return Err("Transfers cannot be split across categories".to_string());
+    }
+
+    for split in splits {
+        if !Money::from_major(split.amount, amount.decimals()).is_positive() {
+            return Err("Each split amount must be greater than zero".to_string());
+        }
+        let category_type: Option<String> = conn
+            .query_row(
+                "SELECT type FROM categories WHERE id = ?1",
+                params![split.category_id],
+                |row| row.get(0),
+            )
+            .ok();
+        match category_type {
+            None => return Err(format!("Split category {} does not exist", split.category_id)),
+            Some(t) if t != transaction_type => {
+                return Err(format!(
+                    "Split category {} is a {} category, not {}",
+                    split.category_id, t, transaction_type
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    let total: Money = splits
+        .iter()
+        .map(|s| Money::from_major(s.amount, amount.decimals()))
+        .sum();
+    if total != amount {
+        return Err(format!(
+            "Split amounts ({}) must add up to the transaction amount ({})",
+            total, amount
+        ));
+    }
+
+    Ok(())
+}
+
+/// Insert split lines for a transaction
+fn insert_transaction_splits(
     conn: &rusqlite::Connection,
     transaction_id: i64,
-    tag_ids: &[i64],
+    decimals: u32,
+    splits: &[TransactionSplitInput],
 ) -> Result<(), String> {
-    for tag_id in tag_ids {
+    for split in splits {
         conn.execute(
-            "INSERT OR IGNORE INTO transaction_tags (transaction_id, tag_id) VALUES (?1, ?2)",
-            params![transaction_id, tag_id],
+            "INSERT INTO transaction_splits (transaction_id, category_id, amount, memo) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                transaction_id,
+                split.category_id,
+                Money::from_major(split.amount, decimals),
+                split.memo
+            ],
         )
-        .map_err(|e| format!("Failed to associate tag: {}", e))?;
+        .map_err(|e| format!("Failed to create split: {}", e))?;
     }
     Ok(())
 }
 
-#[tauri::command]
-pub fn get_transactions(state: State<'_, AppState>) -> Result<Vec<Transaction>, String> {
+/// Write the double-entry lines for a transaction: INCOME debits the account,
+/// EXPENSE credits it, and a TRANSFER credits the source and debits the destination.
+/// `to_amount` is the transfer amount in the destination account's currency.
+pub fn insert_journal_entries(
+    conn: &rusqlite::Connection,
+    transaction_id: i64,
+    transaction_type: &str,
+    account_id: i64,
+    to_account_id: Option<i64>,
+    amount: Money,
+    to_amount: Money,
+) -> Result<(), String> {
+    let insert = |account_id: i64, debit: Money, credit: Money| {
+        conn.execute(
+            "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) 
+             VALUES (?1, ?2, ?3, ?4)",
+            params![transaction_id, account_id, debit, credit],
+        )
+        .map(|_| ())
+        .map_err(|e| format!("Failed to create journal entry: {}", e))
+    };
+
+    match transaction_type {
+        "INCOME" => insert(account_id, amount, Money::zero(amount.decimals())),
+        "EXPENSE" => insert(account_id, Money::zero(amount.decimals()), amount),
+        "TRANSFER" => {
+            let to_account_id = to_account_id.ok_or("Transfer requires to_account_id")?;
+            insert(account_id, Money::zero(amount.decimals()), amount)?;
+            insert(to_account_id, to_amount, Money::zero(to_amount.decimals()))
+        }
+        _ => Err("Invalid transaction type".to_string()),
+    }
+}
+
+/// Amount a transfer delivered, in the destination currency: the recorded
+/// `to_amount`, or the amount sent when none was recorded. None when there
+/// is no destination account.
+pub fn received_amount(amount: Money, to_amount: Option<i64>, to_currency: Option<String>) -> Option<Money> {
+    let to_decimals = currency_decimals(&to_currency?);
+    Some(match to_amount {
+        Some(minor) => Money::new(minor, to_decimals),
+        None => amount.rescale(to_decimals),
+    })
+}
+
+/// Destination amount for a new or corrected transfer. Transfers between
+/// currencies must state the amount received; within one currency it
+/// defaults to, and must equal, the amount sent.
+fn transfer_to_amount(
+    amount: Money,
+    currency: &str,
+    to_currency: &str,
+    to_amount: Option<f64>,
+) -> Result<Money, String> {
+    let to_decimals = currency_decimals(to_currency);
+    let same_currency = currency.eq_ignore_ascii_case(to_currency);
+    match to_amount.map(|v| Money::from_major(v, to_decimals)) {
+        None if same_currency => Ok(amount.rescale(to_decimals)),
+        None => Err(format!(
+            "Transfers from {} to {} require the amount received (to_amount)",
+            currency, to_currency
+        )),
+        Some(received) if !received.is_positive() => {
+            Err("Amount received must be greater than zero".to_string())
+        }
+        Some(received) if same_currency && received != amount.rescale(to_decimals) => Err(
+            "Amount received must equal the amount sent for transfers in one currency".to_string(),
+        ),
+        Some(received) => Ok(received),
+    }
+}
+
+#[tauri::command(async)]
+pub fn get_transactions(
+    state: State<'_, AppState>,
+    page: Option<TransactionPage>,
+) -> Result<Vec<Transaction>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
+    let (page_condition, limit, page_values) = page_sql(page.as_ref())?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, date, type, amount, account_id, to_account_id, category_id, memo, photo_path, created_at 
-         FROM transactions 
-         ORDER BY date DESC, created_at DESC"
-    ).map_err(|e| format!("Query error: {}", e))?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT t.id, t.date, t.type, t.amount, t.account_id, t.to_account_id, t.category_id, t.memo, t.photo_path, t.created_at, a.currency, {}, t.to_amount, ta.currency, t.payee_id
+         FROM transactions t
+         INNER JOIN accounts a ON t.account_id = a.id
+         LEFT JOIN accounts ta ON t.to_account_id = ta.id
+         WHERE 1=1{}
+         ORDER BY t.date DESC, t.id DESC{}",
+        TRANSACTION_STATUS_SQL, page_condition, limit
+    )).map_err(|e| format!("Query error: {}", e))?;
 
     let transactions = stmt
-        .query_map([], |row| {
+        .query_map(rusqlite::params_from_iter(page_values), |row| {
+            let amount = Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(10)?));
             Ok(Transaction {
                 id: row.get(0)?,
                 date: row.get(1)?,
                 transaction_type: row.get(2)?,
-                amount: row.get(3)?,
+                amount,
                 account_id: row.get(4)?,
                 to_account_id: row.get(5)?,
+                to_amount: received_amount(amount, row.get(12)?, row.get(13)?),
                 category_id: row.get(6)?,
+                payee_id: row.get(14)?,
                 memo: row.get(7)?,
                 photo_path: row.get(8)?,
                 created_at: row.get(9)?,
+                status: row.get(11)?,
             })
         })
         .map_err(|e| format!("Execution error: {}", e))?
@@ -100,48 +311,66 @@ pub fn get_transactions(state: State<'_, AppState>) -> Result<Vec<Transaction>,
     Ok(transactions)
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_transactions_with_details(
     state: State<'_, AppState>,
+    page: Option<TransactionPage>,
 ) -> Result<Vec<TransactionWithDetails>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
+    let (page_condition, limit, page_values) = page_sql(page.as_ref())?;
 
-    let mut stmt = conn.prepare(
+    let mut stmt = conn.prepare(&format!(
         "SELECT 
             t.id, t.date, t.type, t.amount, t.account_id, t.to_account_id, 
             t.category_id, t.memo, t.photo_path, t.created_at,
             a.name as account_name,
             ta.name as to_account_name,
             c.name as category_name,
-            (SELECT COUNT(*) FROM transaction_photos tp WHERE tp.transaction_id = t.id) as photo_count
+            (SELECT COUNT(*) FROM transaction_photos tp WHERE tp.transaction_id = t.id) as photo_count,
+            a.currency,
+            {},
+            t.to_amount,
+            ta.currency,
+            t.payee_id,
+            p.name as payee_name
          FROM transactions t
          INNER JOIN accounts a ON t.account_id = a.id
          LEFT JOIN accounts ta ON t.to_account_id = ta.id
          LEFT JOIN categories c ON t.category_id = c.id
-         ORDER BY t.date DESC, t.created_at DESC"
-    ).map_err(|e| format!("Query error: {}", e))?;
+         LEFT JOIN payees p ON t.payee_id = p.id
+         WHERE 1=1{}
+         ORDER BY t.date DESC, t.id DESC{}",
+        TRANSACTION_STATUS_SQL, page_condition, limit
+    )).map_err(|e| format!("Query error: {}", e))?;
 
     let results: Vec<TransactionWithDetails> = stmt
-        .query_map([], |row| {
+        .query_map(rusqlite::params_from_iter(page_values), |row| {
+            let amount = Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(14)?));
             Ok(TransactionWithDetails {
                 transaction: Transaction {
                     id: row.get(0)?,
                     date: row.get(1)?,
                     transaction_type: row.get(2)?,
-                    amount: row.get(3)?,
+                    amount,
                     account_id: row.get(4)?,
                     to_account_id: row.get(5)?,
+                    to_amount: received_amount(amount, row.get(16)?, row.get(17)?),
                     category_id: row.get(6)?,
+                    payee_id: row.get(18)?,
                     memo: row.get(7)?,
                     photo_path: row.get(8)?,
                     created_at: row.get(9)?,
+                    status: row.get(15)?,
                 },
                 account_name: row.get(10)?,
                 to_account_name: row.get(11)?,
                 category_name: row.get(12)?,
+                payee_name: row.get(19)?,
+                snippet: None,
                 photo_count: row.get(13)?,
                 tags: Vec::new(),
+                splits: Vec::new(),
             })
         })
         .map_err(|e| format!("Execution error: {}", e))?
@@ -151,19 +380,17 @@ pub fn get_transactions_with_details(
     attach_tags(&conn, results)
 }
 
-// Helper to attach tags to a list of TransactionWithDetails
+// Helper to attach tags and split lines to a list of TransactionWithDetails
 fn attach_tags(
     conn: &rusqlite::Connection,
     mut results: Vec<TransactionWithDetails>,
 ) -> Result<Vec<TransactionWithDetails>, String> {
     let ids: Vec<i64> = results.iter().map(|r| r.transaction.id).collect();
-    let tag_pairs = load_tags_for_transactions(conn, &ids)?;
+    let mut tags = load_tags_for_transactions(conn, &ids)?;
+    let mut splits = load_splits_for_transactions(conn, &ids)?;
     for twd in &mut results {
-        twd.tags = tag_pairs
-            .iter()
-            .filter(|(tid, _)| *tid == twd.transaction.id)
-            .map(|(_, tag)| tag.clone())
-            .collect();
+        twd.tags = tags.remove(&twd.transaction.id).unwrap_or_default();
+        twd.splits = splits.remove(&twd.transaction.id).unwrap_or_default();
     }
     Ok(results)
 }
@@ -175,7 +402,92 @@ pub fn create_transaction(
 ) -> Result<i64, String> {
     let pool = crate::get_db(&state)?;
     let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    create_transaction_internal(&mut conn, input)
+}
+
+/// Internal version without State wrapper — shared by recurring processing and the CLI.
+/// The payee is resolved from its name, or failing that recognized in the memo, and
+/// adds its default tags. When no category is given the payee's default category
+/// applies, then categorization rules fill in the category, tags and memo.
+pub fn create_transaction_internal(
+    conn: &mut rusqlite::Connection,
+    mut input: CreateTransactionInput,
+) -> Result<i64, String> {
+    if input.payee_id.is_none() {
+        input.payee_id = match (&input.payee, &input.memo) {
+            (Some(payee), _) => resolve_payee(conn, payee, true)?,
+            (None, Some(memo)) => resolve_payee(conn, memo, false)?,
+            (None, None) => None,
+        };
+    }
+    if let Some(payee_id) = input.payee_id {
+        let (category_id, payee_tag_ids) = payee_defaults(conn, payee_id)?;
+        if input.category_id.is_none()
+            && input.splits.as_ref().map_or(true, |s| s.is_empty())
+            && input.transaction_type != "TRANSFER"
+        {
+            input.category_id = category_id;
+        }
+        let tag_ids = input.tag_ids.get_or_insert_with(Vec::new);
+        for tag_id in payee_tag_ids {
+            if !tag_ids.contains(&tag_id) {
+                tag_ids.push(tag_id);
+            }
+        }
+    }
+
+    if input.category_id.is_none() && input.splits.as_ref().map_or(true, |s| s.is_empty()) {
+        let rules = load_categorization_rules(conn)?;
+        let subject = RuleSubject {
+            memo: input.memo.as_deref().unwrap_or(""),
+            amount: input.amount,
+            account_id: Some(input.account_id),
+            transaction_type: &input.transaction_type,
+        };
+        if let Some(outcome) = evaluate_categorization_rules(&rules, &subject) {
+            if input.transaction_type != "TRANSFER" {
+                input.category_id = outcome.category_id;
+            }
+            if outcome.memo.is_some() {
+                input.memo = outcome.memo;
+            }
+            let tag_ids = input.tag_ids.get_or_insert_with(Vec::new);
+            for tag_id in outcome.tag_ids {
+                if !tag_ids.contains(&tag_id) {
+                    tag_ids.push(tag_id);
+                }
+            }
+        }
+    }
+
+    record_transaction(conn, input)
+}
+
+/// Insert a transaction exactly as given, without running categorization
+/// rules — used when the audit log recreates a deleted transaction.
+pub fn record_transaction(
+    conn: &mut rusqlite::Connection,
+    input: CreateTransactionInput,
+) -> Result<i64, String> {
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Transaction error: {}", e))?;
+
+    let transaction_id = insert_transaction(&tx, input)?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok(transaction_id)
+}
 
+/// Validate and write a transaction with its journal entries and audit entry
+/// on a connection that is already inside a transaction, so callers posting
+/// several records can commit them together.
+pub fn insert_transaction(
+    conn: &rusqlite::Connection,
+    input: CreateTransactionInput,
+) -> Result<i64, String> {
     // Validate transaction type
     if input.transaction_type != "INCOME"
         && input.transaction_type != "EXPENSE"
@@ -184,39 +496,29 @@ pub fn create_transaction(
         return Err("Invalid transaction type".to_string());
     }
 
-    if input.amount <= 0.0 {
-        return Err("Amount must be greater than zero".to_string());
-    }
+    // Validate account exists and convert the amount to its minor units
+    let currency = account_currency(conn, input.account_id)?;
+    let decimals = currency_decimals(&currency);
+    let amount = Money::from_major(input.amount, decimals);
 
-    // Validate account exists
-    let acct_exists: bool = conn
-        .query_row(
-            "SELECT COUNT(*) FROM accounts WHERE id = ?1",
-            params![input.account_id],
-            |row| row.get::<_, i64>(0),
-        )
-        .unwrap_or(0) > 0;
-
-    if !acct_exists {
-        return Err("Account does not exist".to_string());
+    if !amount.is_positive() {
+        return Err("Amount must be greater than zero".to_string());
     }
 
-    // Validate transfer requirements
+    // Validate transfer requirements; the destination side is journalled in its own currency
+    let mut to_amount = None;
+    let mut cross_currency = false;
     if input.transaction_type == "TRANSFER" {
         let to_account_id = input.to_account_id.ok_or("Transfer requires to_account_id")?;
         if to_account_id == input.account_id {
             return Err("Cannot transfer to the same account".to_string());
         }
-        let to_exists: bool = conn
-            .query_row(
-                "SELECT COUNT(*) FROM accounts WHERE id = ?1",
-                params![to_account_id],
-                |row| row.get::<_, i64>(0),
-            )
-            .unwrap_or(0) > 0;
-        if !to_exists {
-            return Err("Destination account does not exist".to_string());
-        }
+        let to_currency = account_currency(conn, to_account_id)
+            .map_err(|_| "Destination account does not exist".to_string())?;
+        to_amount = Some(transfer_to_amount(amount, &currency, &to_currency, input.to_amount)?);
+        cross_currency = !currency.eq_ignore_ascii_case(&to_currency);
+    } else if input.to_amount.is_some() {
+        return Err("Only transfers have an amount received".to_string());
     }
 
     if let Some(category_id) = input.category_id {
@@ -232,20 +534,43 @@ pub fn create_transaction(
         }
     }
 
+    if let Some(payee_id) = input.payee_id {
+        let payee_exists: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM payees WHERE id = ?1",
+                params![payee_id],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap_or(0) > 0;
+        if !payee_exists {
+            return Err("Payee does not exist".to_string());
+        }
+    }
+
+    // Validate split lines
+    let splits: &[TransactionSplitInput] = input.splits.as_deref().unwrap_or(&[]);
+    validate_splits(conn, &input.transaction_type, amount, splits)?;
+
+    // Split transactions carry their categories on the lines, not the header
+    let header_category_id = if splits.is_empty() { input.category_id } else { None };
+
     // Validate goal allocations (INCOME)
     if let Some(ref allocations) = input.goal_allocations {
         if input.transaction_type != "INCOME" {
             return Err("Goal allocations are only allowed for INCOME transactions".to_string());
         }
-        let total_alloc: f64 = allocations.iter().map(|a| a.amount).sum();
-        if total_alloc > input.amount {
+        let total_alloc: Money = allocations
+            .iter()
+            .map(|a| Money::from_major(a.amount, decimals))
+            .sum();
+        if total_alloc > amount {
             return Err(format!(
-                "Total goal allocations ({:.2}) exceed transaction amount ({:.2})",
-                total_alloc, input.amount
+                "Total goal allocations ({}) exceed transaction amount ({})",
+                total_alloc, amount
             ));
         }
         for alloc in allocations {
-            if alloc.amount <= 0.0 {
+            if !Money::from_major(alloc.amount, decimals).is_positive() {
                 return Err("Each goal allocation amount must be positive".to_string());
             }
         }
@@ -263,71 +588,48 @@ pub fn create_transaction(
         }
     }
 
-    // Start transaction
-    let tx = conn
-        .transaction()
-        .map_err(|e| format!("Transaction error: {}", e))?;
-
     // Insert transaction record
-    tx.execute(
-        "INSERT INTO transactions (date, type, amount, account_id, to_account_id, category_id, memo) 
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    conn.execute(
+        "INSERT INTO transactions (date, type, amount, account_id, to_account_id, to_amount, category_id, payee_id, memo) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
         params![
             input.date,
             input.transaction_type,
-            input.amount,
+            amount,
             input.account_id,
             input.to_account_id,
-            input.category_id,
+            to_amount,
+            header_category_id,
+            input.payee_id,
             input.memo
         ],
     )
     .map_err(|e| format!("Failed to create transaction: {}", e))?;
 
-    let transaction_id = tx.last_insert_rowid();
+    let transaction_id = conn.last_insert_rowid();
 
-    // Create journal entries
-    match input.transaction_type.as_str() {
-        "INCOME" => {
-            tx.execute(
-                "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) 
-                 VALUES (?1, ?2, ?3, 0)",
-                params![transaction_id, input.account_id, input.amount],
-            )
-            .map_err(|e| format!("Failed to create journal entry: {}", e))?;
-        }
-        "EXPENSE" => {
-            tx.execute(
-                "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) 
-                 VALUES (?1, ?2, 0, ?3)",
-                params![transaction_id, input.account_id, input.amount],
-            )
-            .map_err(|e| format!("Failed to create journal entry: {}", e))?;
-        }
-        "TRANSFER" => {
-            let to_account_id = input.to_account_id.unwrap();
-            tx.execute(
-                "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) 
-                 VALUES (?1, ?2, 0, ?3)",
-                params![transaction_id, input.account_id, input.amount],
-            )
-            .map_err(|e| format!("Failed to create journal entry: {}", e))?;
+    insert_transaction_splits(conn, transaction_id, decimals, splits)?;
 
-            tx.execute(
-                "INSERT INTO journal_entries (transaction_id, account_id, debit, credit) 
-                 VALUES (?1, ?2, ?3, 0)",
-                params![transaction_id, to_account_id, input.amount],
-            )
-            .map_err(|e| format!("Failed to create journal entry: {}", e))?;
-        }
-        _ => return Err("Invalid transaction type".to_string()),
+    // Create journal entries
+    insert_journal_entries(
+        conn,
+        transaction_id,
+        &input.transaction_type,
+        input.account_id,
+        input.to_account_id,
+        amount,
+        to_amount.unwrap_or(amount),
+    )?;
+
+    if cross_currency {
+        refresh_fx_postings(conn, None, Some(transaction_id))?;
     }
 
     // Insert tags
     if let Some(tag_ids) = &input.tag_ids {
         if !tag_ids.is_empty() {
             for tag_id in tag_ids {
-                tx.execute(
+                conn.execute(
                     "INSERT OR IGNORE INTO transaction_tags (transaction_id, tag_id) VALUES (?1, ?2)",
                     params![transaction_id, tag_id],
                 )
@@ -340,7 +642,7 @@ pub fn create_transaction(
     if let Some(allocations) = &input.goal_allocations {
         for alloc in allocations {
             // Validate goal exists, is ACTIVE, and is linked to this account
-            let goal_check: Result<(String, Option<i64>), _> = tx.query_row(
+            let goal_check: Result<(String, Option<i64>), _> = conn.query_row(
                 "SELECT status, linked_account_id FROM savings_goals WHERE id = ?1",
                 params![alloc.goal_id],
                 |row| Ok((row.get(0)?, row.get(1)?)),
@@ -361,12 +663,12 @@ pub fn create_transaction(
                 Err(_) => return Err(format!("Goal {} not found", alloc.goal_id)),
             }
 
-            tx.execute(
+            conn.execute(
                 "INSERT INTO goal_contributions (goal_id, amount, contribution_date, note, transaction_id, contribution_type)
                  VALUES (?1, ?2, ?3, ?4, ?5, 'TRANSACTION')",
                 params![
                     alloc.goal_id,
-                    alloc.amount,
+                    Money::from_major(alloc.amount, decimals),
                     input.date,
                     format!("Auto-allocated from income transaction"),
                     transaction_id
@@ -380,28 +682,34 @@ pub fn create_transaction(
     if let Some(withdrawals) = &input.goal_withdrawals {
         for w in withdrawals {
             // Validate goal exists and has sufficient allocated balance
-            let goal_allocated: f64 = tx.query_row(
-                "SELECT COALESCE(SUM(gc.amount), 0.0)
-                 FROM goal_contributions gc
-                 WHERE gc.goal_id = ?1",
-                params![w.goal_id],
-                |row| row.get(0),
-            ).unwrap_or(0.0);
+            let goal_dec = goal_decimals(conn, w.goal_id)?;
+            let withdrawal = Money::from_major(w.amount, goal_dec);
+            let goal_allocated = Money::new(
+                conn.query_row(
+                    "SELECT COALESCE(SUM(gc.amount), 0)
+                     FROM goal_contributions gc
+                     WHERE gc.goal_id = ?1",
+                    params![w.goal_id],
+                    |row| row.get(0),
+                )
+                .unwrap_or(0),
+                goal_dec,
+            );
 
-            if w.amount > goal_allocated {
+            if withdrawal > goal_allocated {
                 return Err(format!(
-                    "Withdrawal amount ({:.2}) exceeds goal's allocated balance ({:.2})",
-                    w.amount, goal_allocated
+                    "Withdrawal amount ({}) exceeds goal's allocated balance ({})",
+                    withdrawal, goal_allocated
                 ));
             }
 
             // Withdrawals are stored as negative amounts
-            tx.execute(
+            conn.execute(
                 "INSERT INTO goal_contributions (goal_id, amount, contribution_date, note, transaction_id, contribution_type)
                  VALUES (?1, ?2, ?3, ?4, ?5, 'WITHDRAWAL')",
                 params![
                     w.goal_id,
-                    -w.amount,
+                    -withdrawal,
                     input.date,
                     format!("Reduced due to expense transaction"),
                     transaction_id
@@ -411,8 +719,7 @@ pub fn create_transaction(
         }
     }
 
-    tx.commit()
-        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+    log_change(conn, "TRANSACTION", transaction_id, "CREATE", None)?;
 
     Ok(transaction_id)
 }
@@ -424,17 +731,80 @@ pub fn update_transaction(
 ) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let mut conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    update_transaction_internal(&mut conn, input)
+}
+
+/// (type, account, amount, currency, destination account, destination currency)
+type StoredTransaction = (String, i64, Money, String, Option<i64>, Option<String>);
+
+pub fn update_transaction_internal(
+    conn: &mut rusqlite::Connection,
+    input: UpdateTransactionInput,
+) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+    apply_transaction_update(&tx, input)?;
+    tx.commit().map_err(|e| format!("Failed to commit update: {}", e))?;
 
-    let exists: bool = conn
+    Ok(())
+}
+
+/// Validate and apply an edit on a connection that is already inside a
+/// transaction, logging it to the audit trail.
+pub fn apply_transaction_update(
+    conn: &rusqlite::Connection,
+    input: UpdateTransactionInput,
+) -> Result<(), String> {
+    let existing: Option<StoredTransaction> = conn
         .query_row(
-            "SELECT COUNT(*) FROM transactions WHERE id = ?1",
+            "SELECT t.type, t.account_id, t.amount, a.currency, t.to_account_id, ta.currency
+             FROM transactions t
+             INNER JOIN accounts a ON t.account_id = a.id
+             LEFT JOIN accounts ta ON t.to_account_id = ta.id
+             WHERE t.id = ?1",
             params![input.id],
-            |row| row.get::<_, i64>(0),
+            |row| {
+                let currency: String = row.get(3)?;
+                let amount = Money::new(row.get(2)?, currency_decimals(&currency));
+                Ok((row.get(0)?, row.get(1)?, amount, currency, row.get(4)?, row.get(5)?))
+            },
         )
-        .unwrap_or(0) > 0;
+        .ok();
+
+    let (txn_type, account_id, amount, currency, to_account_id, to_currency) = match existing {
+        Some(found) => found,
+        None => return Err("Transaction not found".to_string()),
+    };
+
+    // Each side is locked on its own: the date and amount move both sides,
+    // the amount received only the destination, every other field the source
+    let edits_both_sides = input.date.is_some() || input.amount.is_some();
+    let edits_source = edits_both_sides
+        || input.category_id.is_some()
+        || input.payee_id.is_some()
+        || input.payee.is_some()
+        || input.memo.is_some()
+        || input.tag_ids.is_some()
+        || input.splits.is_some();
+    if edits_source {
+        ensure_unlocked(conn, input.id, Some(account_id))?;
+    }
 
-    if !exists {
-        return Err("Transaction not found".to_string());
+    // Correcting the amount received re-journals the destination side
+    let to_amount = match (input.to_amount, to_account_id, &to_currency) {
+        (None, _, _) => None,
+        (Some(received), Some(to_account_id), Some(to_currency)) if txn_type == "TRANSFER" => Some((
+            to_account_id,
+            transfer_to_amount(amount, &currency, to_currency, Some(received))?,
+        )),
+        _ => return Err("Only transfers have an amount received".to_string()),
+    };
+    let edits_destination = edits_both_sides || to_amount.is_some();
+    if let (true, Some(to_account_id)) = (edits_destination, to_account_id) {
+        ensure_unlocked(conn, input.id, Some(to_account_id))?;
+    }
+
+    if let Some(splits) = &input.splits {
+        validate_splits(conn, &txn_type, amount, splits)?;
     }
 
     let mut updates = Vec::new();
@@ -443,19 +813,52 @@ pub fn update_transaction(
         updates.push(format!("date = '{}'", date));
     }
 
-    if let Some(category_id) = input.category_id {
-        updates.push(format!("category_id = {}", category_id));
+    // Removing the split lines puts the transaction back on one category:
+    // the one given, or else the category of its first line
+    let category_id = match &input.splits {
+        Some(splits) if splits.is_empty() && input.category_id.is_none() && txn_type != "TRANSFER" => conn
+            .query_row(
+                "SELECT COALESCE(t.category_id,
+                        (SELECT s.category_id FROM transaction_splits s
+                         WHERE s.transaction_id = t.id ORDER BY s.id LIMIT 1))
+                 FROM transactions t WHERE t.id = ?1",
+                params![input.id],
+                |row| row.get::<_, Option<i64>>(0),
+            )
+            .map_err(|e| format!("Database error: {}", e))?,
+        _ => input.category_id,
+    };
+
+    match &input.splits {
+        Some(splits) if !splits.is_empty() => updates.push("category_id = NULL".to_string()),
+        _ => {
+            if let Some(category_id) = category_id {
+                updates.push(format!("category_id = {}", category_id));
+            }
+        }
     }
 
     if let Some(memo) = &input.memo {
         updates.push(format!("memo = '{}'", memo.replace('\'', "''")));
     }
 
-    if updates.is_empty() && input.tag_ids.is_none() {
+    let payee_id = match (input.payee_id, &input.payee) {
+        (Some(payee_id), _) => Some(Some(payee_id)),
+        (None, Some(payee)) if payee.trim().is_empty() => Some(None),
+        (None, Some(payee)) => Some(resolve_payee(conn, payee, true)?),
+        (None, None) => None,
+    };
+    match payee_id {
+        Some(Some(payee_id)) => updates.push(format!("payee_id = {}", payee_id)),
+        Some(None) => updates.push("payee_id = NULL".to_string()),
+        None => {}
+    }
+
+    if updates.is_empty() && input.tag_ids.is_none() && input.splits.is_none() && to_amount.is_none() {
         return Err("No fields to update".to_string());
     }
 
-    let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+    let before = snapshot(conn, "TRANSACTION", input.id)?;
 
     if !updates.is_empty() {
         let query = format!(
@@ -463,17 +866,34 @@ pub fn update_transaction(
             updates.join(", "),
             input.id
         );
-        tx.execute(&query, [])
+        conn.execute(&query, [])
             .map_err(|e| format!("Failed to update transaction: {}", e))?;
     }
 
+    if let Some((to_account_id, to_amount)) = to_amount {
+        conn.execute(
+            "UPDATE transactions SET to_amount = ?1 WHERE id = ?2",
+            params![to_amount, input.id],
+        )
+        .map_err(|e| format!("Failed to update transaction: {}", e))?;
+        conn.execute(
+            "UPDATE journal_entries SET debit = ?1 WHERE transaction_id = ?2 AND account_id = ?3",
+            params![to_amount, input.id, to_account_id],
+        )
+        .map_err(|e| format!("Failed to update journal entry: {}", e))?;
+    }
+
+    if txn_type == "TRANSFER" {
+        refresh_fx_postings(conn, None, Some(input.id))?;
+    }
+
     // Replace tags if provided
     if let Some(tag_ids) = &input.tag_ids {
-        tx.execute("DELETE FROM transaction_tags WHERE transaction_id = ?1", params![input.id])
+        conn.execute("DELETE FROM transaction_tags WHERE transaction_id = ?1", params![input.id])
             .map_err(|e| format!("Failed to clear old tags: {}", e))?;
         if !tag_ids.is_empty() {
             for tag_id in tag_ids {
-                tx.execute(
+                conn.execute(
                     "INSERT OR IGNORE INTO transaction_tags (transaction_id, tag_id) VALUES (?1, ?2)",
                     params![input.id, tag_id],
                 )
@@ -482,7 +902,14 @@ pub fn update_transaction(
         }
     }
 
-    tx.commit().map_err(|e| format!("Failed to commit update: {}", e))?;
+    // Replace split lines if provided
+    if let Some(splits) = &input.splits {
+        conn.execute("DELETE FROM transaction_splits WHERE transaction_id = ?1", params![input.id])
+            .map_err(|e| format!("Failed to clear old splits: {}", e))?;
+        insert_transaction_splits(conn, input.id, amount.decimals(), splits)?;
+    }
+
+    log_change(conn, "TRANSACTION", input.id, "UPDATE", before)?;
 
     Ok(())
 }
@@ -491,6 +918,16 @@ pub fn update_transaction(
 pub fn delete_transaction(state: State<'_, AppState>, transaction_id: i64) -> Result<(), String> {
     let pool = crate::get_db(&state)?;
     let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    delete_transaction_internal(&conn, transaction_id)
+}
+
+pub fn delete_transaction_internal(
+    conn: &rusqlite::Connection,
+    transaction_id: i64,
+) -> Result<(), String> {
+    ensure_unlocked(conn, transaction_id, None)?;
+
+    let before = snapshot(conn, "TRANSACTION", transaction_id)?;
 
     conn.execute(
         "DELETE FROM transactions WHERE id = ?1",
@@ -498,139 +935,355 @@ pub fn delete_transaction(state: State<'_, AppState>, transaction_id: i64) -> Re
     )
     .map_err(|e| format!("Failed to delete transaction: {}", e))?;
 
+    if before.is_some() {
+        log_change(conn, "TRANSACTION", transaction_id, "DELETE", before)?;
+    }
+
     Ok(())
 }
 
 // ==================== PHASE 2: FILTERING & ANALYTICS ====================
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_transactions_filtered(
     state: State<'_, AppState>,
     filter: TransactionFilter,
+    page: Option<TransactionPage>,
 ) -> Result<Vec<TransactionWithDetails>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
+    get_transactions_filtered_internal(&conn, filter, page)
+}
 
-    let mut query = String::from(
+pub fn get_transactions_filtered_internal(
+    conn: &rusqlite::Connection,
+    filter: TransactionFilter,
+    page: Option<TransactionPage>,
+) -> Result<Vec<TransactionWithDetails>, String> {
+    // Ranked hits have no stable position to resume from, so paged searches keep date order
+    let ranked = page.is_none();
+    query_filtered_transactions(conn, filter, page, ranked)
+}
+
+/// Filtered transactions, best search matches first when `ranked` and the
+/// filter has words to find, otherwise newest first
+fn query_filtered_transactions(
+    conn: &rusqlite::Connection,
+    filter: TransactionFilter,
+    page: Option<TransactionPage>,
+    ranked: bool,
+) -> Result<Vec<TransactionWithDetails>, String> {
+    let filter = resolve_filter(conn, filter)?;
+    let (page_condition, limit, page_values) = page_sql(page.as_ref())?;
+    let search = filter
+        .search_query
+        .as_deref()
+        .map(SearchQuery::parse)
+        .filter(|search| !search.is_empty());
+    // Words to find join the full-text index, which ranks the hits and marks them in a snippet
+    let match_expression = search.as_ref().and_then(|search| search.match_expression());
+    let (snippet_column, search_join) = match &match_expression {
+        Some(expression) => (
+            "fts.snippet",
+            format!(
+                "INNER JOIN (
+                    SELECT rowid AS id, bm25(transaction_search) AS rank,
+                        snippet(transaction_search, -1, '{}', '{}', '…', 12) AS snippet
+                    FROM transaction_search WHERE transaction_search MATCH '{}'
+                 ) fts ON fts.id = t.id",
+                HIGHLIGHT_START,
+                HIGHLIGHT_END,
+                expression.replace('\'', "''")
+            ),
+        ),
+        None => ("NULL", String::new()),
+    };
+
+    let mut query = format!(
         "SELECT 
             t.id, t.date, t.type, t.amount, t.account_id, t.to_account_id, 
             t.category_id, t.memo, t.photo_path, t.created_at,
             a.name as account_name,
             ta.name as to_account_name,
             c.name as category_name,
-            (SELECT COUNT(*) FROM transaction_photos tp WHERE tp.transaction_id = t.id) as photo_count
+            (SELECT COUNT(*) FROM transaction_photos tp WHERE tp.transaction_id = t.id) as photo_count,
+            a.currency,
+            {},
+            t.to_amount,
+            ta.currency,
+            t.payee_id,
+            p.name as payee_name,
+            {}
          FROM transactions t
          INNER JOIN accounts a ON t.account_id = a.id
          LEFT JOIN accounts ta ON t.to_account_id = ta.id
          LEFT JOIN categories c ON t.category_id = c.id
+         LEFT JOIN payees p ON t.payee_id = p.id
+         {}
          WHERE 1=1",
+        TRANSACTION_STATUS_SQL, snippet_column, search_join
     );
 
+    for condition in filter_conditions(&filter, search.as_ref()) {
+        query.push_str(&format!(" AND {}", condition));
+    }
+
+    query.push_str(&page_condition);
+
+    if match_expression.is_some() && ranked {
+        query.push_str(" ORDER BY fts.rank, t.date DESC, t.id DESC");
+    } else {
+        query.push_str(" ORDER BY t.date DESC, t.id DESC");
+    }
+    query.push_str(&limit);
+
+    let mut stmt = conn
+        .prepare(&query)
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let results: Vec<TransactionWithDetails> = stmt
+        .query_map(rusqlite::params_from_iter(page_values), |row| {
+            let amount = Money::new(row.get(3)?, currency_decimals(&row.get::<_, String>(14)?));
+            Ok(TransactionWithDetails {
+                transaction: Transaction {
+                    id: row.get(0)?,
+                    date: row.get(1)?,
+                    transaction_type: row.get(2)?,
+                    amount,
+                    account_id: row.get(4)?,
+                    to_account_id: row.get(5)?,
+                    to_amount: received_amount(amount, row.get(16)?, row.get(17)?),
+                    category_id: row.get(6)?,
+                    payee_id: row.get(18)?,
+                    memo: row.get(7)?,
+                    photo_path: row.get(8)?,
+                    created_at: row.get(9)?,
+                    status: row.get(15)?,
+                },
+                account_name: row.get(10)?,
+                to_account_name: row.get(11)?,
+                category_name: row.get(12)?,
+                payee_name: row.get(19)?,
+                snippet: row.get(20)?,
+                photo_count: row.get(13)?,
+                tags: Vec::new(),
+                splits: Vec::new(),
+            })
+        })
+        .map_err(|e| format!("Execute error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Read error: {}", e))?;
+
+    attach_tags(conn, results)
+}
+
+/// Keyset condition, LIMIT clause and the cursor values the condition binds,
+/// selecting one page of transactions ordered by date then id, newest first.
+/// All are empty when no page is given.
+pub(crate) fn page_sql(page: Option<&TransactionPage>) -> Result<(String, String, Vec<SqlValue>), String> {
+    let Some(page) = page else {
+        return Ok((String::new(), String::new(), Vec::new()));
+    };
+
+    let (condition, values) = match &page.after {
+        Some(cursor) => (
+            " AND (t.date < ?1 OR (t.date = ?1 AND t.id < ?2))".to_string(),
+            vec![SqlValue::Text(cursor.date.clone()), SqlValue::Integer(cursor.id)],
+        ),
+        None => (String::new(), Vec::new()),
+    };
+
+    let limit = match page.limit {
+        Some(limit) if limit <= 0 => return Err("Page limit must be greater than zero".to_string()),
+        Some(limit) => format!(" LIMIT {}", limit),
+        None => String::new(),
+    };
+
+    Ok((condition, limit, values))
+}
+
+/// SQL conditions for a resolved filter over `t` (transactions) joined to its
+/// account as `a`, apart from the words `search` looks up in the index
+fn filter_conditions(filter: &TransactionFilter, search: Option<&SearchQuery>) -> Vec<String> {
+    let mut conditions = Vec::new();
+
     if let Some(start_date) = &filter.start_date {
-        query.push_str(&format!(" AND t.date >= '{}'", start_date));
+        conditions.push(format!("t.date >= '{}'", start_date));
     }
     if let Some(end_date) = &filter.end_date {
-        query.push_str(&format!(" AND t.date <= '{}'", end_date));
+        conditions.push(format!("t.date <= '{}'", end_date));
     }
 
     if let Some(txn_type) = &filter.transaction_type {
-        query.push_str(&format!(" AND t.type = '{}'", txn_type));
+        conditions.push(format!("t.type = '{}'", txn_type));
     }
 
     if let Some(account_id) = filter.account_id {
-        query.push_str(&format!(
-            " AND (t.account_id = {} OR t.to_account_id = {})",
+        conditions.push(format!(
+            "(t.account_id = {} OR t.to_account_id = {})",
             account_id, account_id
         ));
     }
 
     if let Some(category_id) = filter.category_id {
-        if filter.include_subcategories.unwrap_or(false) {
-            query.push_str(&format!(
-                " AND t.category_id IN (
-                    SELECT id FROM categories WHERE id = {} OR parent_id = {}
-                )",
+        // Match the header category or any split line
+        let category_set = if filter.include_subcategories.unwrap_or(false) {
+            format!(
+                "SELECT id FROM categories WHERE id = {} OR parent_id = {}",
                 category_id, category_id
-            ));
+            )
         } else {
-            query.push_str(&format!(" AND t.category_id = {}", category_id));
-        }
+            category_id.to_string()
+        };
+        conditions.push(format!(
+            "t.id IN (SELECT id FROM transaction_category_lines WHERE category_id IN ({}))",
+            category_set
+        ));
     }
 
-    if let Some(search) = &filter.search_query {
-        let escaped_search = search.replace('\'', "''");
-        query.push_str(&format!(
-            " AND (t.memo LIKE '%{}%' OR CAST(t.amount AS TEXT) LIKE '%{}%')",
-            escaped_search, escaped_search
-        ));
+    if let Some(payee_id) = filter.payee_id {
+        conditions.push(format!("t.payee_id = {}", payee_id));
+    }
+
+    if let Some(search) = search {
+        conditions.extend(search.sql_conditions());
     }
 
     if let Some(tag_ids) = &filter.tag_ids {
         if !tag_ids.is_empty() {
             let id_list: Vec<String> = tag_ids.iter().map(|id| id.to_string()).collect();
-            query.push_str(&format!(
-                " AND t.id IN (SELECT transaction_id FROM transaction_tags WHERE tag_id IN ({}))",
+            conditions.push(format!(
+                "t.id IN (SELECT transaction_id FROM transaction_tags WHERE tag_id IN ({}))",
                 id_list.join(", ")
             ));
         }
     }
 
-    query.push_str(" ORDER BY t.date DESC, t.created_at DESC");
+    conditions
+}
+
+/// Query selecting the ids of the transactions a resolved filter matches,
+/// for reports to use as `t.id IN (...)`
+pub(crate) fn filter_scope_sql(filter: &TransactionFilter) -> String {
+    let search = filter
+        .search_query
+        .as_deref()
+        .map(SearchQuery::parse)
+        .filter(|search| !search.is_empty());
+    let mut conditions = filter_conditions(filter, search.as_ref());
+    if let Some(expression) = search.as_ref().and_then(|search| search.match_expression()) {
+        conditions.push(format!(
+            "t.id IN (SELECT rowid FROM transaction_search WHERE transaction_search MATCH '{}')",
+            expression.replace('\'', "''")
+        ));
+    }
+
+    let mut query = String::from(
+        "SELECT t.id FROM transactions t INNER JOIN accounts a ON t.account_id = a.id WHERE 1=1",
+    );
+    for condition in conditions {
+        query.push_str(&format!(" AND {}", condition));
+    }
+    query
+}
 
+/// (category id, name, date, currency, minor amount, transaction count)
+type CategoryDayRow = (Option<i64>, Option<String>, String, String, i64, i64);
+
+/// INCOME and EXPENSE totals for one reporting period, converted to the
+/// primary currency at each day's rate with the original amounts alongside.
+struct FlowTotals {
+    income: Money,
+    expense: Money,
+    transaction_count: i64,
+    income_by_currency: Vec<CurrencyAmount>,
+    expense_by_currency: Vec<CurrencyAmount>,
+    missing_rates: Vec<MissingRate>,
+}
+
+/// Totals for transactions between the dates, grouped by `period(date)` in date order.
+fn flow_totals(
+    conn: &rusqlite::Connection,
+    converter: &mut RateConverter,
+    start_date: &str,
+    end_date: &str,
+    period: impl Fn(&str) -> String,
+) -> Result<Vec<(String, FlowTotals)>, String> {
     let mut stmt = conn
-        .prepare(&query)
+        .prepare(
+            "SELECT t.date, a.currency, t.type, COALESCE(SUM(t.amount), 0), COUNT(*)
+             FROM transactions t
+             INNER JOIN accounts a ON t.account_id = a.id
+             WHERE t.date >= ?1 AND t.date <= ?2
+             GROUP BY t.date, a.currency, t.type
+             ORDER BY t.date ASC",
+        )
         .map_err(|e| format!("Query error: {}", e))?;
 
-    let results: Vec<TransactionWithDetails> = stmt
-        .query_map([], |row| {
-            Ok(TransactionWithDetails {
-                transaction: Transaction {
-                    id: row.get(0)?,
-                    date: row.get(1)?,
-                    transaction_type: row.get(2)?,
-                    amount: row.get(3)?,
-                    account_id: row.get(4)?,
-                    to_account_id: row.get(5)?,
-                    category_id: row.get(6)?,
-                    memo: row.get(7)?,
-                    photo_path: row.get(8)?,
-                    created_at: row.get(9)?,
-                },
-                account_name: row.get(10)?,
-                to_account_name: row.get(11)?,
-                category_name: row.get(12)?,
-                photo_count: row.get(13)?,
-                tags: Vec::new(),
-            })
+    let rows: Vec<(String, String, String, i64, i64)> = stmt
+        .query_map(params![start_date, end_date], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
         })
         .map_err(|e| format!("Execute error: {}", e))?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| format!("Read error: {}", e))?;
 
-    attach_tags(&conn, results)
+    let mut periods: Vec<(String, FlowTotals)> = Vec::new();
+
+    for (date, currency, transaction_type, amount, count) in rows {
+        let key = period(&date);
+        if periods.last().map_or(true, |(k, _)| *k != key) {
+            periods.push((
+                key,
+                FlowTotals {
+                    income: converter.zero(),
+                    expense: converter.zero(),
+                    transaction_count: 0,
+                    income_by_currency: Vec::new(),
+                    expense_by_currency: Vec::new(),
+                    missing_rates: Vec::new(),
+                },
+            ));
+        }
+        let totals = &mut periods.last_mut().unwrap().1;
+        totals.transaction_count += count;
+
+        let amount = Money::new(amount, currency_decimals(&currency));
+        let (total, by_currency) = match transaction_type.as_str() {
+            "INCOME" => (&mut totals.income, &mut totals.income_by_currency),
+            "EXPENSE" => (&mut totals.expense, &mut totals.expense_by_currency),
+            _ => continue,
+        };
+
+        add_currency_amount(by_currency, &currency, amount);
+        match converter.convert(amount, &currency, &date) {
+            Some(converted) => *total += converted,
+            None => record_missing_rate(&mut totals.missing_rates, &currency, &converter.target, &date),
+        }
+    }
+
+    Ok(periods)
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_income_expense_summary(
     state: State<'_, AppState>,
     start_date: String,
     end_date: String,
 ) -> Result<IncomeExpenseSummary, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
-    let (total_income, total_expense, transaction_count): (f64, f64, i64) = conn
-        .query_row(
-            "SELECT 
-                CAST(COALESCE(SUM(CASE WHEN type = 'INCOME' THEN amount ELSE 0 END), 0) AS REAL) as total_income,
-                CAST(COALESCE(SUM(CASE WHEN type = 'EXPENSE' THEN amount ELSE 0 END), 0) AS REAL) as total_expense,
-                COUNT(*) as transaction_count
-             FROM transactions
-             WHERE date >= ?1 AND date <= ?2",
-            params![start_date, end_date],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
-        )
-        .map_err(|e| format!("Failed to calculate summary: {}", e))?;
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let totals = flow_totals(&conn, &mut converter, &start_date, &end_date, |_| String::new())?
+        .pop()
+        .map(|(_, totals)| totals);
+
+    let (total_income, total_expense, transaction_count, income_by_currency, expense_by_currency, missing_rates) =
+        match totals {
+            Some(t) => (t.income, t.expense, t.transaction_count, t.income_by_currency, t.expense_by_currency, t.missing_rates),
+            None => (converter.zero(), converter.zero(), 0, Vec::new(), Vec::new(), Vec::new()),
+        };
 
     Ok(IncomeExpenseSummary {
         total_income,
@@ -639,10 +1292,14 @@ pub fn get_income_expense_summary(
         transaction_count,
         start_date,
         end_date,
+        currency: converter.target.clone(),
+        income_by_currency,
+        expense_by_currency,
+        missing_rates,
     })
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_category_spending(
     state: State<'_, AppState>,
     start_date: String,
@@ -650,185 +1307,165 @@ pub fn get_category_spending(
     transaction_type: String,
 ) -> Result<Vec<CategorySpending>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
+    // Grouped by day and currency so each line converts at its own date's rate
     let mut stmt = conn
         .prepare(
             "SELECT 
                 COALESCE(c.parent_id, c.id) as category_id,
                 COALESCE(pc.name, c.name) as category_name,
-                CAST(SUM(t.amount) AS REAL) as total_amount,
-                COUNT(*) as transaction_count
-             FROM transactions t
+                t.date,
+                a.currency,
+                SUM(t.amount) as total_amount,
+                COUNT(DISTINCT t.id) as transaction_count
+             FROM transaction_category_lines t
+             INNER JOIN accounts a ON t.account_id = a.id
              INNER JOIN categories c ON t.category_id = c.id
              LEFT JOIN categories pc ON c.parent_id = pc.id
              WHERE t.date >= ?1 AND t.date <= ?2 AND t.type = ?3
-             GROUP BY COALESCE(c.parent_id, c.id)
-             ORDER BY total_amount DESC",
+             GROUP BY COALESCE(c.parent_id, c.id), t.date, a.currency",
         )
         .map_err(|e| format!("Query error: {}", e))?;
 
-    let rows: Vec<(Option<i64>, Option<String>, f64, i64)> = stmt
+    let rows: Vec<CategoryDayRow> = stmt
         .query_map(params![start_date, end_date, transaction_type], |row| {
             Ok((
                 row.get(0)?,
                 row.get(1)?,
                 row.get(2)?,
                 row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
             ))
         })
         .map_err(|e| format!("Execute error: {}", e))?
         .collect::<Result<Vec<_>, _>>()
         .map_err(|e| format!("Read error: {}", e))?;
 
-    let total: f64 = rows.iter().map(|r| r.2).sum();
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let mut spending: Vec<CategorySpending> = Vec::new();
+
+    for (cat_id, cat_name, date, currency, amount, count) in rows {
+        let cat_id = cat_id.unwrap_or(0);
+        let index = match spending.iter().position(|s| s.category_id == cat_id) {
+            Some(index) => index,
+            None => {
+                spending.push(CategorySpending {
+                    category_id: cat_id,
+                    category_name: cat_name.unwrap_or_default(),
+                    total_amount: converter.zero(),
+                    transaction_count: 0,
+                    percentage: 0.0,
+                    currency: converter.target.clone(),
+                    by_currency: Vec::new(),
+                    missing_rates: Vec::new(),
+                });
+                spending.len() - 1
+            }
+        };
+        let entry = &mut spending[index];
+
+        let amount = Money::new(amount, currency_decimals(&currency));
+        entry.transaction_count += count;
+        add_currency_amount(&mut entry.by_currency, &currency, amount);
+        match converter.convert(amount, &currency, &date) {
+            Some(converted) => entry.total_amount += converted,
+            None => record_missing_rate(&mut entry.missing_rates, &currency, &converter.target, &date),
+        }
+    }
 
-    Ok(rows
-        .into_iter()
-        .map(|(cat_id, cat_name, amount, count)| CategorySpending {
-            category_id: cat_id.unwrap_or(0),
-            category_name: cat_name.unwrap_or_default(),
-            total_amount: amount,
-            transaction_count: count,
-            percentage: if total > 0.0 {
-                (amount / total) * 100.0
-            } else {
-                0.0
-            },
-        })
-        .collect())
+    let total: Money = spending.iter().map(|s| s.total_amount).sum();
+    for entry in spending.iter_mut() {
+        entry.percentage = entry.total_amount.ratio(total) * 100.0;
+    }
+    spending.sort_by_key(|s| std::cmp::Reverse(s.total_amount));
+
+    Ok(spending)
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_daily_summary(
     state: State<'_, AppState>,
     start_date: String,
     end_date: String,
 ) -> Result<Vec<DailySummary>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
-    let mut stmt = conn
-        .prepare(
-            "SELECT 
-                date,
-                CAST(COALESCE(SUM(CASE WHEN type = 'INCOME' THEN amount ELSE 0 END), 0) AS REAL) as total_income,
-                CAST(COALESCE(SUM(CASE WHEN type = 'EXPENSE' THEN amount ELSE 0 END), 0) AS REAL) as total_expense,
-                COUNT(*) as transaction_count
-             FROM transactions
-             WHERE date >= ?1 AND date <= ?2
-             GROUP BY date
-             ORDER BY date DESC",
-        )
-        .map_err(|e| format!("Query error: {}", e))?;
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let days = flow_totals(&conn, &mut converter, &start_date, &end_date, |date| date.to_string())?;
 
-    let summaries = stmt
-        .query_map(params![start_date, end_date], |row| {
-            let income: f64 = row.get(1)?;
-            let expense: f64 = row.get(2)?;
-            Ok(DailySummary {
-                date: row.get(0)?,
-                total_income: income,
-                total_expense: expense,
-                net: income - expense,
-                transaction_count: row.get(3)?,
-            })
+    Ok(days
+        .into_iter()
+        .rev()
+        .map(|(date, t)| DailySummary {
+            date,
+            total_income: t.income,
+            total_expense: t.expense,
+            net: t.income - t.expense,
+            transaction_count: t.transaction_count,
+            currency: converter.target.clone(),
+            income_by_currency: t.income_by_currency,
+            expense_by_currency: t.expense_by_currency,
+            missing_rates: t.missing_rates,
         })
-        .map_err(|e| format!("Execute error: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Read error: {}", e))?;
-
-    Ok(summaries)
+        .collect())
 }
 
-#[tauri::command]
+/// Quick search with the same query language as the filter, best matches first
+#[tauri::command(async)]
 pub fn search_transactions(
     state: State<'_, AppState>,
     query: String,
 ) -> Result<Vec<TransactionWithDetails>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
-
-    let escaped_query = query.replace('\'', "''");
-
-    let mut stmt = conn
-        .prepare(
-            "SELECT 
-                t.id, t.date, t.type, t.amount, t.account_id, t.to_account_id, 
-                t.category_id, t.memo, t.photo_path, t.created_at,
-                a.name as account_name,
-                ta.name as to_account_name,
-                c.name as category_name,
-                (SELECT COUNT(*) FROM transaction_photos tp WHERE tp.transaction_id = t.id) as photo_count
-             FROM transactions t
-             INNER JOIN accounts a ON t.account_id = a.id
-             LEFT JOIN accounts ta ON t.to_account_id = ta.id
-             LEFT JOIN categories c ON t.category_id = c.id
-             WHERE t.memo LIKE ?1 OR CAST(t.amount AS TEXT) LIKE ?2
-             ORDER BY t.date DESC, t.created_at DESC
-             LIMIT 100",
-        )
-        .map_err(|e| format!("Query error: {}", e))?;
-
-    let search_term = format!("%{}%", escaped_query);
-    let results: Vec<TransactionWithDetails> = stmt
-        .query_map(params![search_term, search_term], |row| {
-            Ok(TransactionWithDetails {
-                transaction: Transaction {
-                    id: row.get(0)?,
-                    date: row.get(1)?,
-                    transaction_type: row.get(2)?,
-                    amount: row.get(3)?,
-                    account_id: row.get(4)?,
-                    to_account_id: row.get(5)?,
-                    category_id: row.get(6)?,
-                    memo: row.get(7)?,
-                    photo_path: row.get(8)?,
-                    created_at: row.get(9)?,
-                },
-                account_name: row.get(10)?,
-                to_account_name: row.get(11)?,
-                category_name: row.get(12)?,
-                photo_count: row.get(13)?,
-                tags: Vec::new(),
-            })
-        })
-        .map_err(|e| format!("Execute error: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Read error: {}", e))?;
-
-    attach_tags(&conn, results)
+    let conn = pool.read()?;
+
+    query_filtered_transactions(
+        &conn,
+        TransactionFilter {
+            start_date: None,
+            end_date: None,
+            date_range: None,
+            saved_filter_id: None,
+            transaction_type: None,
+            account_id: None,
+            category_id: None,
+            payee_id: None,
+            search_query: Some(query),
+            include_subcategories: None,
+            tag_ids: None,
+        },
+        Some(TransactionPage {
+            after: None,
+            limit: Some(100),
+        }),
+        true,
+    )
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn get_monthly_trends(
     state: State<'_, AppState>,
     months: i32,
 ) -> Result<Vec<MonthlyTrend>, String> {
     let pool = crate::get_db(&state)?;
-    let conn = pool.lock().map_err(|_| "DB lock error".to_string())?;
+    let conn = pool.read()?;
 
-    let mut stmt = conn
-        .prepare(
-            "SELECT 
-                strftime('%Y-%m', date) as month,
-                CAST(COALESCE(SUM(CASE WHEN type = 'INCOME' THEN amount ELSE 0 END), 0) AS REAL) as income,
-                CAST(COALESCE(SUM(CASE WHEN type = 'EXPENSE' THEN amount ELSE 0 END), 0) AS REAL) as expense,
-                COUNT(*) as transaction_count
-             FROM transactions
-             WHERE date >= date('now', ?1 || ' months')
-             GROUP BY strftime('%Y-%m', date)
-             ORDER BY month ASC",
-        )
+    let limit_str = format!("-{}", months);
+    let start_date: String = conn
+        .query_row("SELECT date('now', ?1 || ' months')", params![limit_str], |row| row.get(0))
         .map_err(|e| format!("Query error: {}", e))?;
 
-    let limit_str = format!("-{}", months);
-    let trends = stmt
-        .query_map(params![limit_str], |row| {
-            let month: String = row.get(0)?;
-            let income: f64 = row.get(1)?;
-            let expense: f64 = row.get(2)?;
+    let mut converter = RateConverter::to_primary(&conn)?;
+    let periods = flow_totals(&conn, &mut converter, &start_date, "9999-12-31", |date| {
+        date.get(..7).unwrap_or(date).to_string()
+    })?;
 
+    let trends = periods
+        .into_iter()
+        .map(|(month, t)| {
             // Parse month for display name
             let parts: Vec<&str> = month.split('-').collect();
             let month_name = if parts.len() == 2 {
@@ -848,18 +1485,208 @@ pub fn get_monthly_trends(
                 month.clone()
             };
 
-            Ok(MonthlyTrend {
+            MonthlyTrend {
                 month,
                 month_name,
-                income,
-                expense,
-                net: income - expense,
-                transaction_count: row.get(3)?,
-            })
+                income: t.income,
+                expense: t.expense,
+                net: t.income - t.expense,
+                transaction_count: t.transaction_count,
+                currency: converter.target.clone(),
+                income_by_currency: t.income_by_currency,
+                expense_by_currency: t.expense_by_currency,
+                missing_rates: t.missing_rates,
+            }
         })
-        .map_err(|e| format!("Execute error: {}", e))?
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Read error: {}", e))?;
+        .collect();
 
     Ok(trends)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removing_splits_keeps_a_category() {
+        let dir = std::env::temp_dir().join(format!("mm-splits-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = crate::db::init_database_unencrypted(&dir.join("money_manager.db")).unwrap();
+        let mut conn = pool.lock().unwrap();
+
+        let categories: Vec<i64> = conn
+            .prepare("SELECT id FROM categories WHERE type = 'EXPENSE' ORDER BY id LIMIT 3")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let line = |category_id: i64, amount: f64| TransactionSplitInput {
+            category_id,
+            amount,
+            memo: None,
+        };
+        let split_expense = |conn: &mut rusqlite::Connection| {
+            create_transaction_internal(
+                conn,
+                CreateTransactionInput {
+                    date: "2030-03-01".into(),
+                    transaction_type: "EXPENSE".into(),
+                    amount: 30.0,
+                    account_id: 1,
+                    to_account_id: None,
+                    to_amount: None,
+                    category_id: None,
+                    payee_id: None,
+                    payee: None,
+                    memo: None,
+                    tag_ids: None,
+                    goal_allocations: None,
+                    goal_withdrawals: None,
+                    splits: Some(vec![line(categories[0], 20.0), line(categories[1], 10.0)]),
+                },
+            )
+            .unwrap()
+        };
+        let clear_splits = |conn: &mut rusqlite::Connection, id: i64, category_id: Option<i64>| {
+            update_transaction_internal(
+                conn,
+                UpdateTransactionInput {
+                    id,
+                    date: None,
+                    amount: None,
+                    to_amount: None,
+                    category_id,
+                    payee_id: None,
+                    payee: None,
+                    memo: None,
+                    tag_ids: None,
+                    splits: Some(Vec::new()),
+                },
+            )
+            .unwrap();
+            conn.query_row("SELECT category_id FROM transactions WHERE id = ?1", params![id], |row| {
+                row.get::<_, Option<i64>>(0)
+            })
+            .unwrap()
+        };
+
+        let first = split_expense(&mut conn);
+        assert_eq!(clear_splits(&mut conn, first, None), Some(categories[0]));
+
+        let second = split_expense(&mut conn);
+        assert_eq!(clear_splits(&mut conn, second, Some(categories[2])), Some(categories[2]));
+
+        drop(conn);
+        pool.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn split_categories_must_match_the_transaction_type() {
+        let dir = std::env::temp_dir().join(format!("mm-splits-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = crate::db::init_database_unencrypted(&dir.join("money_manager.db")).unwrap();
+        let mut conn = pool.lock().unwrap();
+
+        let category = |kind: &str| {
+            conn.query_row("SELECT id FROM categories WHERE type = ?1 LIMIT 1", params![kind], |row| {
+                row.get::<_, i64>(0)
+            })
+            .unwrap()
+        };
+        let (expense, income) = (category("EXPENSE"), category("INCOME"));
+        let line = |category_id: i64, amount: f64| TransactionSplitInput {
+            category_id,
+            amount,
+            memo: None,
+        };
+
+        let err = create_transaction_internal(
+            &mut conn,
+            CreateTransactionInput {
+                date: "2030-03-01".into(),
+                transaction_type: "EXPENSE".into(),
+                amount: 30.0,
+                account_id: 1,
+                to_account_id: None,
+                to_amount: None,
+                category_id: None,
+                payee_id: None,
+                payee: None,
+                memo: None,
+                tag_ids: None,
+                goal_allocations: None,
+                goal_withdrawals: None,
+                splits: Some(vec![line(expense, 20.0), line(income, 10.0)]),
+            },
+        )
+        .unwrap_err();
+        assert!(err.contains("INCOME"), "{}", err);
+
+        drop(conn);
+        pool.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn pages_resume_after_the_cursor() {
+        use crate::models::transactions::TransactionCursor;
+
+        let dir = std::env::temp_dir().join(format!("mm-pages-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pool = crate::db::init_database_unencrypted(&dir.join("money_manager.db")).unwrap();
+        let conn = pool.lock().unwrap();
+
+        let filter = || TransactionFilter {
+            account_id: Some(1),
+            ..Default::default()
+        };
+        let all = get_transactions_filtered_internal(&conn, filter(), None).unwrap();
+        assert!(all.len() > 2);
+
+        let first = get_transactions_filtered_internal(
+            &conn,
+            filter(),
+            Some(TransactionPage {
+                after: None,
+                limit: Some(2),
+            }),
+        )
+        .unwrap();
+        let last = &first[1].transaction;
+        let rest = get_transactions_filtered_internal(
+            &conn,
+            filter(),
+            Some(TransactionPage {
+                after: Some(TransactionCursor {
+                    date: last.date.clone(),
+                    id: last.id,
+                }),
+                limit: None,
+            }),
+        )
+        .unwrap();
+        let ids: Vec<i64> = first.iter().chain(&rest).map(|t| t.transaction.id).collect();
+        assert_eq!(ids, all.iter().map(|t| t.transaction.id).collect::<Vec<_>>());
+
+        // The cursor is bound, so a quoted date is compared as plain text and every earlier row stays
+        let odd = get_transactions_filtered_internal(
+            &conn,
+            filter(),
+            Some(TransactionPage {
+                after: Some(TransactionCursor {
+                    date: "2030' OR '1'='1".into(),
+                    id: 0,
+                }),
+                limit: None,
+            }),
+        )
+        .unwrap();
+        assert_eq!(odd.len(), all.len());
+
+        drop(conn);
+        pool.close().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
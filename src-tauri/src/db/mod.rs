@@ -3,49 +3,233 @@
 pub mod encryption;
 use anyhow::Result;
 use rusqlite::Connection;
-use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+use std::time::{Duration, Instant};
 
-/// Thread-safe database connection wrapper.
-/// All command handlers lock this mutex to run queries.
-pub type DbPool = Arc<Mutex<Connection>>;
+/// Read-only connections kept alongside the writer
+const READER_CONNECTIONS: u32 = 4;
 
-/// Open a SQLCipher-encrypted database connection and run migrations.
-/// `key` is the hex-encoded 256-bit key derived from the master password.
-pub fn init_database_encrypted(db_path: &Path, key: &str) -> Result<DbPool> {
-    let conn = Connection::open(db_path)?;
+/// How long `close()` waits for commands still holding a reader
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(30);
 
-    // Apply the SQLCipher encryption key
-    conn.execute_batch(&format!("PRAGMA key = \"x'{}'\";", key))?;
+type ReaderPool = r2d2::Pool<ReaderConnectionManager>;
 
-    // Enable WAL mode and foreign keys
-    conn.execute_batch(
-        "PRAGMA journal_mode = WAL;
-         PRAGMA foreign_keys = ON;",
-    )?;
+/// Connection pool for the database file. Under WAL, readers never wait for
+/// the writer, so read-only commands take a pooled connection from `read()`
+/// while anything that changes data goes through the single writer in `lock()`.
+/// Cloning is cheap and shares the same connections.
+#[derive(Clone)]
+pub struct DbPool {
+    writer: Arc<Mutex<Option<Connection>>>,
+    readers: Arc<RwLock<Option<ReaderPool>>>,
+    /// Shared with the reader manager so returned connections get closed
+    closing: Arc<AtomicBool>,
+}
 
-    // Run migrations
-    run_migrations(&conn)?;
+/// A pooled read-only connection, returned to the pool when dropped
+pub type DbReader = r2d2::PooledConnection<ReaderConnectionManager>;
+
+/// The writer connection, held exclusively until the guard is dropped
+pub struct DbWriter<'a>(MutexGuard<'a, Option<Connection>>);
+
+impl Deref for DbWriter<'_> {
+    type Target = Connection;
 
-    Ok(Arc::new(Mutex::new(conn)))
+    fn deref(&self) -> &Connection {
+        self.0.as_ref().expect("writer guards only exist for an open pool")
+    }
+}
+
+impl DerefMut for DbWriter<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.0.as_mut().expect("writer guards only exist for an open pool")
+    }
 }
 
-/// Open an UNENCRYPTED database connection and run migrations.
+impl DbPool {
+    /// The writer connection. Writes serialize on this lock.
+    pub fn lock(&self) -> Result<DbWriter<'_>, String> {
+        let guard = self.writer.lock().map_err(|_| "DB lock error".to_string())?;
+        if guard.is_none() {
+            return Err("Database is closed".to_string());
+        }
+        Ok(DbWriter(guard))
+    }
+
+    /// A read-only connection. It sees the last committed state, even while
+    /// the writer is in the middle of a transaction.
+    pub fn read(&self) -> Result<DbReader, String> {
+        // Checking out under the lock lets `close()` know no checkout is in flight
+        let readers = self.readers.read().map_err(|_| "DB lock error".to_string())?;
+        readers
+            .as_ref()
+            .ok_or_else(|| "Database is closed".to_string())?
+            .get()
+            .map_err(|e| format!("DB pool error: {}", e))
+    }
+
+    /// Close every connection so the file can be re-keyed, copied or renamed.
+    /// Waits for the writer and any readers still in use, then checkpoints the
+    /// WAL into the main file and switches back to a rollback journal, so no
+    /// `-wal`/`-shm` files are left beside it. All clones fail afterwards.
+    pub fn close(&self) -> Result<(), String> {
+        let mut writer = self.writer.lock().map_err(|_| "DB lock error".to_string())?;
+        let Some(conn) = writer.as_ref() else {
+            return Ok(());
+        };
+
+        let mut readers = self.readers.write().map_err(|_| "DB lock error".to_string())?;
+        if let Some(pool) = readers.as_ref() {
+            let deadline = Instant::now() + CLOSE_TIMEOUT;
+            while pool.state().idle_connections < pool.state().connections {
+                if Instant::now() > deadline {
+                    return Err("Timed out waiting for database reads to finish".to_string());
+                }
+                std::thread::sleep(Duration::from_millis(20));
+            }
+
+            // Check every idle reader out and back in; once `closing` is set the
+            // pool treats them as broken and closes them on the way back
+            self.closing.store(true, Ordering::SeqCst);
+            let idle: Vec<DbReader> = std::iter::from_fn(|| pool.try_get()).collect();
+            drop(idle);
+        }
+
+        let checkpointed = conn
+            .execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+            .and_then(|_| conn.query_row("PRAGMA journal_mode = DELETE;", [], |row| row.get(0)))
+            .map_err(|e| format!("Failed to checkpoint database: {}", e))
+            .and_then(|mode: String| {
+                mode.eq_ignore_ascii_case("delete")
+                    .then_some(())
+                    .ok_or_else(|| "Database is still in use; try again".to_string())
+            });
+        if let Err(e) = checkpointed {
+            // Still open: let the readers reconnect on demand
+            self.closing.store(false, Ordering::SeqCst);
+            return Err(e);
+        }
+        *readers = None;
+
+        if let Some(conn) = writer.take() {
+            conn.close()
+                .map_err(|(_, e)| format!("Failed to close database: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Opens reader connections with the same key as the writer
+pub struct ReaderConnectionManager {
+    db_path: PathBuf,
+    key: Option<String>,
+    closing: Arc<AtomicBool>,
+}
+
+impl r2d2::ManageConnection for ReaderConnectionManager {
+    type Connection = Connection;
+    type Error = rusqlite::Error;
+
+    fn connect(&self) -> Result<Connection, rusqlite::Error> {
+        let conn = open_connection(&self.db_path, self.key.as_deref())?;
+        conn.execute_batch("PRAGMA query_only = ON;")?;
+        Ok(conn)
+    }
+
+    fn is_valid(&self, conn: &mut Connection) -> Result<(), rusqlite::Error> {
+        conn.execute_batch("SELECT 1;")
+    }
+
+    fn has_broken(&self, _conn: &mut Connection) -> bool {
+        self.closing.load(Ordering::SeqCst)
+    }
+}
+
+/// Open a SQLCipher-encrypted database and run migrations.
+/// `key` is the hex-encoded 256-bit key derived from the master password.
+pub fn init_database_encrypted(db_path: &Path, key: &str) -> Result<DbPool> {
+    open_pool(db_path, Some(key))
+}
+
+/// Open an UNENCRYPTED database and run migrations.
 /// Used for first launch before the user sets a master password,
 /// and for the migration wizard source database.
 pub fn init_database_unencrypted(db_path: &Path) -> Result<DbPool> {
+    open_pool(db_path, None)
+}
+
+fn open_pool(db_path: &Path, key: Option<&str>) -> Result<DbPool> {
+    let writer = open_connection(db_path, key)?;
+
+    // WAL is recorded in the file, so the readers pick it up too. `close()`
+    // switches back to a rollback journal; reopening turns WAL on again.
+    writer.execute_batch("PRAGMA journal_mode = WAL;")?;
+
+    // Run migrations
+    run_migrations(&writer)?;
+
+    // Readers connect on first use
+    let closing = Arc::new(AtomicBool::new(false));
+    let readers = r2d2::Pool::builder()
+        .max_size(READER_CONNECTIONS)
+        .min_idle(Some(0))
+        .build(ReaderConnectionManager {
+            db_path: db_path.to_path_buf(),
+            key: key.map(str::to_string),
+            closing: closing.clone(),
+        })?;
+
+    Ok(DbPool {
+        writer: Arc::new(Mutex::new(Some(writer))),
+        readers: Arc::new(RwLock::new(Some(readers))),
+        closing,
+    })
+}
+
+/// Open one connection, applying the SQLCipher key first when there is one
+fn open_connection(db_path: &Path, key: Option<&str>) -> rusqlite::Result<Connection> {
     let conn = Connection::open(db_path)?;
 
-    // Enable WAL mode and foreign keys
+    if let Some(key) = key {
+        conn.execute_batch(&format!("PRAGMA key = \"x'{}'\";", key))?;
+    }
+
+    // Wait out the writer's brief checkpoint locks instead of failing
     conn.execute_batch(
-        "PRAGMA journal_mode = WAL;
-         PRAGMA foreign_keys = ON;",
+        "PRAGMA foreign_keys = ON;
+         PRAGMA busy_timeout = 5000;",
     )?;
 
-    // Run migrations
-    run_migrations(&conn)?;
+    Ok(conn)
+}
+
+/// Change the key of a closed SQLCipher database in place.
+pub fn rekey_database(db_path: &Path, key: &str, new_key: &str) -> Result<()> {
+    let conn = open_connection(db_path, Some(key))?;
+    conn.execute_batch(&format!("PRAGMA rekey = \"x'{}'\";", new_key))?;
+    conn.close().map_err(|(_, e)| e)?;
+    Ok(())
+}
+
+/// Decrypt a closed SQLCipher database into a new plain file, the reverse of
+/// `encrypt_database`. The caller should then swap the files.
+pub fn decrypt_database(encrypted_path: &Path, decrypted_path: &Path, key: &str) -> Result<()> {
+    if decrypted_path.exists() {
+        std::fs::remove_file(decrypted_path)?;
+    }
 
-    Ok(Arc::new(Mutex::new(conn)))
+    let conn = open_connection(encrypted_path, Some(key))?;
+    conn.execute_batch(&format!(
+        "ATTACH DATABASE '{}' AS plaintext KEY '';
+         SELECT sqlcipher_export('plaintext');
+         DETACH DATABASE plaintext;",
+        decrypted_path.to_string_lossy().replace('\'', "''")
+    ))?;
+    conn.close().map_err(|(_, e)| e)?;
+    Ok(())
 }
 
 /// Encrypt an existing unencrypted database into a new SQLCipher-encrypted file.
@@ -170,8 +354,90 @@ fn run_migrations(conn: &Connection) -> Result<()> {
             "20240217000001_goal_allocations",
             include_str!("../../migrations/20240217000001_goal_allocations.sql"),
         ),
+        (
+            "20240218000001_transaction_splits",
+            include_str!("../../migrations/20240218000001_transaction_splits.sql"),
+        ),
+        (
+            "20240219000001_money_minor_units",
+            include_str!("../../migrations/20240219000001_money_minor_units.sql"),
+        ),
+        (
+            "20240220000001_ofx_import",
+            include_str!("../../migrations/20240220000001_ofx_import.sql"),
+        ),
+        (
+            "20240221000001_reconciliation",
+            include_str!("../../migrations/20240221000001_reconciliation.sql"),
+        ),
+        (
+            "20240222000001_audit_log",
+            include_str!("../../migrations/20240222000001_audit_log.sql"),
+        ),
+        (
+            "20240223000001_budget_rollover",
+            include_str!("../../migrations/20240223000001_budget_rollover.sql"),
+        ),
+        (
+            "20240224000001_budget_periods",
+            include_str!("../../migrations/20240224000001_budget_periods.sql"),
+        ),
+        (
+            "20240225000001_installment_interest",
+            include_str!("../../migrations/20240225000001_installment_interest.sql"),
+        ),
+        (
+            "20240226000001_loans",
+            include_str!("../../migrations/20240226000001_loans.sql"),
+        ),
+        (
+            "20240227000001_credit_card_interest",
+            include_str!("../../migrations/20240227000001_credit_card_interest.sql"),
+        ),
+        (
+            "20240228000001_credit_card_rewards",
+            include_str!("../../migrations/20240228000001_credit_card_rewards.sql"),
+        ),
+        (
+            "20240229000001_investments",
+            include_str!("../../migrations/20240229000001_investments.sql"),
+        ),
+        (
+            "20240301000001_net_worth_currency_breakdown",
+            include_str!("../../migrations/20240301000001_net_worth_currency_breakdown.sql"),
+        ),
+        (
+            "20240302000001_cross_currency_transfers",
+            include_str!("../../migrations/20240302000001_cross_currency_transfers.sql"),
+        ),
+        (
+            "20240303000001_categorization_rule_conditions",
+            include_str!("../../migrations/20240303000001_categorization_rule_conditions.sql"),
+        ),
+        (
+            "20240304000001_payees",
+            include_str!("../../migrations/20240304000001_payees.sql"),
+        ),
+        (
+            "20240305000001_transaction_search",
+            include_str!("../../migrations/20240305000001_transaction_search.sql"),
+        ),
+        (
+            "20240306000001_saved_filters",
+            include_str!("../../migrations/20240306000001_saved_filters.sql"),
+        ),
+        (
+            "20240307000001_transaction_lookup_index",
+            include_str!("../../migrations/20240307000001_transaction_lookup_index.sql"),
+        ),
+        (
+            "20240308000001_reconciliation_account_status",
+            include_str!("../../migrations/20240308000001_reconciliation_account_status.sql"),
+        ),
     ];
 
+    create_currency_factor_table(conn)?;
+
     for (name, sql) in &migrations {
         // Check if already applied
         let applied: bool = conn
@@ -198,5 +464,102 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         }
     }
 
+    conn.execute_batch("DROP TABLE IF EXISTS temp._currency_factor;")?;
+
+    Ok(())
+}
+
+/// Temp table of major-to-minor unit factors for every supported currency,
+/// for migrations that convert stored amounts. Built from the same list as
+/// `currency_decimals` so the SQL never hardcodes decimal places.
+fn create_currency_factor_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TEMP TABLE IF NOT EXISTS _currency_factor (
+            code TEXT PRIMARY KEY,
+            factor INTEGER NOT NULL
+        );",
+    )?;
+
+    for (code, _, _, decimals) in crate::commands::currencies::SUPPORTED_CURRENCIES {
+        conn.execute(
+            "INSERT OR REPLACE INTO temp._currency_factor (code, factor) VALUES (?1, ?2)",
+            rusqlite::params![code, 10_i64.pow(*decimals)],
+        )?;
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wal_path(db_path: &Path) -> PathBuf {
+        PathBuf::from(format!("{}-wal", db_path.display()))
+    }
+
+    #[test]
+    fn encrypts_a_wal_database_with_uncheckpointed_writes() {
+        let dir = std::env::temp_dir().join(format!("mm-wal-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("money_manager.db");
+        let key = "11".repeat(32);
+        let new_key = "22".repeat(32);
+
+        let pool = init_database_unencrypted(&db_path).unwrap();
+        {
+            let conn = pool.lock().unwrap();
+            conn.execute_batch(
+                "PRAGMA wal_autocheckpoint = 0;
+                 INSERT INTO app_settings (key, value) VALUES ('wal_probe', 'kept');",
+            )
+            .unwrap();
+        }
+        assert!(std::fs::metadata(wal_path(&db_path)).unwrap().len() > 0);
+
+        // A reader still checked out holds up the close until it is returned
+        let reader = pool.read().unwrap();
+        let closer = {
+            let pool = pool.clone();
+            std::thread::spawn(move || pool.close())
+        };
+        std::thread::sleep(Duration::from_millis(100));
+        drop(reader);
+        closer.join().unwrap().unwrap();
+        assert!(!wal_path(&db_path).exists());
+        assert!(pool.lock().is_err());
+        assert!(pool.read().is_err());
+
+        let encrypted_path = db_path.with_extension("db.encrypted");
+        encrypt_database(&db_path, &encrypted_path, &key).unwrap();
+        std::fs::rename(&encrypted_path, &db_path).unwrap();
+
+        let read_probe = |pool: &DbPool| -> String {
+            pool.read()
+                .unwrap()
+                .query_row("SELECT value FROM app_settings WHERE key = 'wal_probe'", [], |row| {
+                    row.get(0)
+                })
+                .unwrap()
+        };
+
+        let pool = init_database_encrypted(&db_path, &key).unwrap();
+        assert_eq!(read_probe(&pool), "kept");
+        pool.close().unwrap();
+
+        rekey_database(&db_path, &key, &new_key).unwrap();
+        assert!(init_database_encrypted(&db_path, &key).is_err());
+        let pool = init_database_encrypted(&db_path, &new_key).unwrap();
+        assert_eq!(read_probe(&pool), "kept");
+        pool.close().unwrap();
+
+        let decrypted_path = db_path.with_extension("db.decrypted");
+        decrypt_database(&db_path, &decrypted_path, &new_key).unwrap();
+        std::fs::rename(&decrypted_path, &db_path).unwrap();
+        let pool = init_database_unencrypted(&db_path).unwrap();
+        assert_eq!(read_probe(&pool), "kept");
+        pool.close().unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
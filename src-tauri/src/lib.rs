@@ -1,7 +1,7 @@
 // File: src-tauri/src/lib.rs
-mod commands;
-mod db;
-mod models;
+pub mod commands;
+pub mod db;
+pub mod models;
 
 use db::DbPool;
 use std::path::PathBuf;
@@ -91,6 +91,7 @@ pub fn run() {
             commands::categories::update_category,
             commands::categories::delete_category,
             commands::categories::get_recent_categories,
+            commands::suggestions::suggest_transaction_details,
             // Transaction commands
             commands::transactions::get_transactions,
             commands::transactions::get_transactions_with_details,
@@ -103,6 +104,19 @@ pub fn run() {
             commands::transactions::get_daily_summary,
             commands::transactions::search_transactions,
             commands::transactions::get_monthly_trends,
+            // Ledger commands
+            commands::ledger::verify_ledger,
+            // Reconciliation commands
+            commands::reconciliation::set_transaction_status,
+            commands::reconciliation::unlock_transaction,
+            commands::reconciliation::start_reconciliation,
+            commands::reconciliation::get_reconciliations,
+            commands::reconciliation::get_reconciliation_summary,
+            commands::reconciliation::finish_reconciliation,
+            commands::reconciliation::cancel_reconciliation,
+            // Audit log commands
+            commands::audit::get_audit_history,
+            commands::audit::revert_audit_entry,
             // Recurring commands
             commands::recurring::create_recurring_transaction,
             commands::recurring::get_recurring_transactions,
@@ -134,6 +148,28 @@ pub fn run() {
             commands::installments::cancel_installment_plan,
             commands::installments::delete_installment_plan,
             commands::installments::get_upcoming_installments,
+            // Loan commands
+            commands::loans::create_loan,
+            commands::loans::get_loans,
+            commands::loans::get_loan_details,
+            commands::loans::delete_loan,
+            commands::loans::process_loan_payment,
+            commands::loans::preview_loan_prepayment,
+            commands::loans::make_loan_prepayment,
+            // Investment commands
+            commands::investments::create_security,
+            commands::investments::get_securities,
+            commands::investments::delete_security,
+            commands::investments::set_security_price,
+            commands::investments::get_security_prices,
+            commands::investments::delete_security_price,
+            commands::investments::set_investment_account,
+            commands::investments::get_investment_accounts,
+            commands::investments::create_investment_transaction,
+            commands::investments::get_investment_transactions,
+            commands::investments::delete_investment_transaction,
+            commands::investments::get_holdings,
+            commands::investments::get_investment_gains,
             // Template commands
             commands::templates::get_templates,
             commands::templates::create_template,
@@ -142,8 +178,10 @@ pub fn run() {
             commands::templates::use_template,
             // Export commands
             commands::export::export_transactions_csv,
+            commands::export::export_transactions_qif,
             commands::export::export_transactions_json,
             commands::export::export_transactions_excel,
+            commands::export::export_transactions_to_file,
             commands::export::export_full_backup,
             // Credit Card commands
             commands::credit_cards::create_credit_card_settings,
@@ -160,6 +198,13 @@ pub fn run() {
             commands::credit_cards::settle_credit_card,
             commands::credit_cards::get_credit_card_summaries,
             commands::credit_cards::process_auto_settlements,
+            commands::credit_cards::process_credit_card_charges,
+            commands::credit_cards::project_minimum_payments,
+            commands::credit_cards::get_reward_rules,
+            commands::credit_cards::create_reward_rule,
+            commands::credit_cards::delete_reward_rule,
+            commands::credit_cards::redeem_credit_card_rewards,
+            commands::credit_cards::get_credit_card_reward_summaries,
             // Analytics commands
             commands::analytics::get_net_worth_history,
             commands::analytics::get_account_balance_history,
@@ -170,6 +215,7 @@ pub fn run() {
             // Net Worth commands
             commands::networth::get_current_net_worth,
             commands::networth::get_net_worth_snapshots,
+            commands::networth::rebuild_net_worth_snapshots,
             // Currency commands
             commands::currencies::get_supported_currencies,
             commands::currencies::get_primary_currency,
@@ -181,6 +227,10 @@ pub fn run() {
             commands::currencies::convert_amount,
             commands::currencies::get_exchange_rate_summaries,
             commands::currencies::convert_balances_to_primary,
+            commands::currencies::get_fx_gains,
+            commands::currencies::get_rate_base_currency,
+            commands::currencies::set_rate_base_currency,
+            commands::rate_import::import_exchange_rates,
             // Settings commands
             commands::settings::get_setting,
             commands::settings::set_setting,
@@ -220,6 +270,19 @@ pub fn run() {
             commands::tags::update_tag,
             commands::tags::delete_tag,
             commands::tags::get_spending_by_tag,
+            // Payee commands
+            commands::payees::get_payees,
+            commands::payees::create_payee,
+            commands::payees::update_payee,
+            commands::payees::delete_payee,
+            commands::payees::merge_payees,
+            commands::payees::get_spending_by_payee,
+            // Saved filter commands
+            commands::saved_filters::get_saved_filters,
+            commands::saved_filters::create_saved_filter,
+            commands::saved_filters::update_saved_filter,
+            commands::saved_filters::delete_saved_filter,
+            commands::saved_filters::get_saved_filter_summary,
             // Security commands
             commands::security::set_pin,
             commands::security::verify_pin,
@@ -241,11 +304,16 @@ pub fn run() {
             commands::import::execute_import,
             commands::import::undo_import,
             commands::import::get_import_history,
+            commands::ofx::parse_ofx_preview,
+            commands::ofx::execute_ofx_import,
+            commands::qif::parse_qif_preview,
+            commands::qif::execute_qif_import,
             // Advanced commands
             commands::advanced::get_categorization_rules,
             commands::advanced::create_categorization_rule,
             commands::advanced::update_categorization_rule,
             commands::advanced::delete_categorization_rule,
+            commands::advanced::rerun_categorization_rules,
             commands::advanced::get_export_templates,
             commands::advanced::create_export_template,
             commands::advanced::update_export_template,
@@ -264,3 +332,16 @@ pub fn get_db(state: &AppState) -> Result<DbPool, String> {
         .cloned()
         .ok_or_else(|| "Database is locked. Please enter your master password.".to_string())
 }
+
+/// Run long database work (backups, imports, rebuilds) on a blocking thread so
+/// the command thread stays free. Pooled readers keep serving other commands.
+pub async fn run_blocking<T, F>(state: &AppState, work: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(DbPool) -> Result<T, String> + Send + 'static,
+{
+    let pool = get_db(state)?;
+    tauri::async_runtime::spawn_blocking(move || work(pool))
+        .await
+        .map_err(|e| format!("Background task failed: {}", e))?
+}
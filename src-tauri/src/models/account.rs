@@ -1,4 +1,5 @@
 // File: src-tauri/src/models/account.rs
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -9,12 +10,12 @@ pub struct AccountGroup {
     pub account_type: String, // ASSET or LIABILITY
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct Account {
     pub id: i64,
     pub group_id: i64,
     pub name: String,
-    pub initial_balance: f64,
+    pub initial_balance: Money,
     pub currency: String,
     pub created_at: String,
 }
@@ -39,5 +40,5 @@ pub struct UpdateAccountInput {
 pub struct AccountWithBalance {
     #[serde(flatten)]
     pub account: Account,
-    pub current_balance: f64,
+    pub current_balance: Money,
 }
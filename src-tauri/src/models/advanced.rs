@@ -1,3 +1,4 @@
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -5,8 +6,14 @@ pub struct CategorizationRule {
     pub id: String,
     pub match_pattern: String,
     pub match_type: String, // 'exact', 'contains', 'starts_with', 'regex'
-    pub category_id: String,
+    pub category_id: Option<String>,
     pub priority: i32,
+    pub min_amount: Option<f64>,
+    pub max_amount: Option<f64>,
+    pub account_id: Option<i64>,
+    pub transaction_type: Option<String>,
+    pub memo_rewrite: Option<String>,
+    pub tag_ids: Vec<i64>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -16,8 +23,48 @@ pub struct CreateCategorizationRuleInput {
     pub id: String,
     pub match_pattern: String,
     pub match_type: String,
-    pub category_id: String,
+    pub category_id: Option<String>,
     pub priority: i32,
+    pub min_amount: Option<f64>,
+    pub max_amount: Option<f64>,
+    pub account_id: Option<i64>,
+    pub transaction_type: Option<String>,
+    pub memo_rewrite: Option<String>,
+    pub tag_ids: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApplyRulesInput {
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub account_id: Option<i64>,
+    /// Also re-categorize transactions that already have a category
+    pub include_categorized: Option<bool>,
+    pub dry_run: bool,
+}
+
+/// What re-running the rules would change (or changed) on one transaction
+#[derive(Debug, Serialize, Clone)]
+pub struct RuleChange {
+    pub transaction_id: i64,
+    pub date: String,
+    pub amount: Money,
+    pub memo: Option<String>,
+    pub rule_ids: Vec<String>,
+    pub old_category_id: Option<i64>,
+    pub new_category_id: Option<i64>,
+    pub new_category_name: Option<String>,
+    pub new_memo: Option<String>,
+    pub added_tag_ids: Vec<i64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RuleApplicationResult {
+    pub dry_run: bool,
+    pub scanned: i64,
+    /// Transactions skipped because they belong to a reconciliation
+    pub locked: i64,
+    pub changes: Vec<RuleChange>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -26,6 +73,7 @@ pub struct ExportTemplate {
     pub name: String,
     pub columns: String, // JSON array of column names
     pub filters: Option<String>, // JSON object representing filters
+    pub saved_filter_id: Option<i64>,
     pub format: String, // 'csv' or 'json'
     pub created_at: String,
     pub updated_at: String,
@@ -37,5 +85,6 @@ pub struct CreateExportTemplateInput {
     pub name: String,
     pub columns: String,
     pub filters: Option<String>,
+    pub saved_filter_id: Option<i64>,
     pub format: String,
 }
@@ -1,14 +1,17 @@
 // File: src-tauri/src/models/analytics.rs
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
+use crate::models::networth::CurrencyNetWorth;
 use serde::{Deserialize, Serialize};
 
 /// Net worth snapshot at a point in time
 #[derive(Debug, Clone, Serialize)]
 pub struct NetWorthSnapshot {
     pub date: String,           // e.g., "2025-12" (month-end)
-    pub total_assets: f64,      // Sum of all ASSET account balances
-    pub total_liabilities: f64, // Sum of all LIABILITY account balances (positive = owed)
-    pub net_worth: f64,         // assets - liabilities
-    pub growth: f64,            // Change from previous snapshot
+    pub total_assets: Money,      // Sum of all ASSET account balances
+    pub total_liabilities: Money, // Sum of all LIABILITY account balances (positive = owed)
+    pub net_worth: Money,         // assets - liabilities
+    pub growth: Money,            // Change from previous snapshot
     pub growth_percentage: f64,
 }
 
@@ -16,7 +19,7 @@ pub struct NetWorthSnapshot {
 #[derive(Debug, Clone, Serialize)]
 pub struct AccountBalancePoint {
     pub date: String,
-    pub balance: f64,
+    pub balance: Money,
 }
 
 /// Full account performance report
@@ -25,15 +28,15 @@ pub struct AccountPerformance {
     pub account_id: i64,
     pub account_name: String,
     pub account_type: String, // ASSET or LIABILITY
-    pub current_balance: f64,
-    pub opening_balance: f64, // Balance at start of period
-    pub closing_balance: f64, // Balance at end of period
-    pub highest_balance: f64,
-    pub lowest_balance: f64,
-    pub average_daily_balance: f64,
-    pub total_inflow: f64,  // Debits (money in)
-    pub total_outflow: f64, // Credits (money out)
-    pub net_change: f64,
+    pub current_balance: Money,
+    pub opening_balance: Money, // Balance at start of period
+    pub closing_balance: Money, // Balance at end of period
+    pub highest_balance: Money,
+    pub lowest_balance: Money,
+    pub average_daily_balance: Money,
+    pub total_inflow: Money,  // Debits (money in)
+    pub total_outflow: Money, // Credits (money out)
+    pub net_change: Money,
     pub transaction_count: i64,
     pub balance_history: Vec<AccountBalancePoint>,
 }
@@ -43,9 +46,9 @@ pub struct AccountPerformance {
 pub struct TopCategory {
     pub category_id: i64,
     pub category_name: String,
-    pub current_amount: f64,
-    pub previous_amount: f64,
-    pub change: f64,
+    pub current_amount: Money,
+    pub previous_amount: Money,
+    pub change: Money,
     pub change_percentage: f64,
     pub transaction_count: i64,
     pub percentage_of_total: f64,
@@ -56,7 +59,7 @@ pub struct TopCategory {
 pub struct SubcategoryBreakdown {
     pub parent_category_id: i64,
     pub parent_category_name: String,
-    pub total_amount: f64,
+    pub total_amount: Money,
     pub subcategories: Vec<SubcategoryItem>,
 }
 
@@ -64,7 +67,7 @@ pub struct SubcategoryBreakdown {
 pub struct SubcategoryItem {
     pub category_id: i64,
     pub category_name: String,
-    pub amount: f64,
+    pub amount: Money,
     pub percentage: f64,
     pub transaction_count: i64,
 }
@@ -74,12 +77,12 @@ pub struct SubcategoryItem {
 pub struct YearComparison {
     pub month: String, // e.g., "January"
     pub month_num: i32,
-    pub current_year_income: f64,
-    pub current_year_expense: f64,
-    pub previous_year_income: f64,
-    pub previous_year_expense: f64,
-    pub income_change: f64,
-    pub expense_change: f64,
+    pub current_year_income: Money,
+    pub current_year_expense: Money,
+    pub previous_year_income: Money,
+    pub previous_year_expense: Money,
+    pub income_change: Money,
+    pub expense_change: Money,
 }
 
 /// Input for analytics queries
@@ -92,42 +95,47 @@ pub struct AnalyticsPeriod {
 /// Dashboard analytics summary
 #[derive(Debug, Clone, Serialize)]
 pub struct AnalyticsDashboard {
-    pub net_worth: f64,
-    pub net_worth_change: f64,
-    pub total_income_this_month: f64,
-    pub total_expense_this_month: f64,
+    pub net_worth: Money,
+    pub net_worth_change: Money,
+    pub total_income_this_month: Money,
+    pub total_expense_this_month: Money,
     pub savings_rate: f64, // (income - expense) / income * 100
     pub top_expense_category: Option<String>,
-    pub top_expense_amount: f64,
-    pub daily_average_expense: f64,
+    pub top_expense_amount: Money,
+    pub daily_average_expense: Money,
     pub days_in_period: i64,
 }
 
 #[derive(Debug, Serialize)]
 pub struct NetWorthHistory {
     pub month: String,
-    pub assets: f64,
-    pub liabilities: f64,
-    pub net_worth: f64,
+    pub assets: Money,
+    pub liabilities: Money,
+    pub net_worth: Money,
+    pub currency: String,
+    pub by_currency: Vec<CurrencyNetWorth>,
+    pub missing_rates: Vec<MissingRate>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct AccountBalanceHistory {
     pub date: String,
-    pub balance: f64,
+    pub balance: Money,
 }
 
 #[derive(Debug, Serialize)]
 pub struct SubCategorySpending {
     pub category_id: i64,
     pub category_name: String,
-    pub total_amount: f64,
+    pub total_amount: Money,
+    pub missing_rates: Vec<MissingRate>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct YearOverYearComparison {
     pub month: String,
-    pub current_year_amount: f64,
-    pub previous_year_amount: f64,
+    pub current_year_amount: Money,
+    pub previous_year_amount: Money,
     pub percentage_change: f64,
+    pub missing_rates: Vec<MissingRate>,
 }
@@ -0,0 +1,24 @@
+// File: src-tauri/src/models/audit.rs
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub entity_type: String, // TRANSACTION, ACCOUNT, CATEGORY, BUDGET, GOAL, RECURRING
+    pub entity_id: i64,
+    pub action: String, // CREATE, UPDATE, DELETE, REVERT
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+    pub reverted_entry_id: Option<i64>,
+    pub created_at: String,
+    pub is_reverted: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditFilter {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<i64>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub limit: Option<i64>,
+}
@@ -1,4 +1,5 @@
 // File: src-tauri/src/models/bill.rs
+use crate::models::money::Money;
 use serde::Serialize;
 
 /// Unified struct representing an upcoming bill from either
@@ -8,7 +9,7 @@ pub struct UpcomingBill {
     pub source: String,                    // "RECURRING" or "INSTALLMENT"
     pub source_id: i64,                    // recurring_transactions.id or installment_plans.id
     pub name: String,
-    pub amount: f64,
+    pub amount: Money,
     pub due_date: String,                  // YYYY-MM-DD
     pub days_until_due: i64,               // negative = overdue
     pub transaction_type: String,          // INCOME, EXPENSE, TRANSFER
@@ -1,13 +1,19 @@
 // File: src-tauri/src/models/budget.rs
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct Budget {
     pub id: i64,
     pub category_id: i64,
-    pub amount: f64,
-    pub period: String, // MONTHLY or YEARLY
+    pub amount: Money,
+    pub period: String, // WEEKLY, BIWEEKLY, MONTHLY, QUARTERLY, YEARLY or CUSTOM
     pub start_date: String,
+    pub end_date: Option<String>, // CUSTOM only, inclusive
+    pub rollover_enabled: bool,
+    pub rollover_cap: Option<Money>, // None = carry the full amount
+    pub saved_filter_id: Option<i64>, // Only the saved filter's transactions count as spending
 }
 
 #[derive(Debug, Deserialize)]
@@ -16,6 +22,10 @@ pub struct CreateBudgetInput {
     pub amount: f64,
     pub period: String,
     pub start_date: String,
+    pub end_date: Option<String>,       // required for CUSTOM
+    pub rollover_enabled: Option<bool>, // defaults to false
+    pub rollover_cap: Option<f64>,      // 0 or less = no cap
+    pub saved_filter_id: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -23,6 +33,23 @@ pub struct UpdateBudgetInput {
     pub id: i64,
     pub amount: Option<f64>,
     pub start_date: Option<String>,
+    pub end_date: Option<String>, // CUSTOM budgets only
+    pub rollover_enabled: Option<bool>,
+    pub rollover_cap: Option<f64>, // 0 or less removes the cap
+    pub saved_filter_id: Option<i64>, // 0 or less removes the saved filter
+}
+
+/// How one budget period played out. With rollover off, nothing is carried.
+#[derive(Debug, Serialize, Clone)]
+pub struct BudgetPeriod {
+    pub period_start: String,
+    pub period_end: String, // inclusive
+    pub budgeted: Money,
+    pub carried_in: Money,
+    pub available: Money,
+    pub spent: Money,
+    pub closing_balance: Money, // available - spent
+    pub carried_out: Money,     // closing balance after the cap
 }
 
 #[derive(Debug, Serialize)]
@@ -30,21 +57,27 @@ pub struct BudgetStatus {
     #[serde(flatten)]
     pub budget: Budget,
     pub category_name: String,
-    pub spent_amount: f64,
-    pub remaining_amount: f64,
+    pub period_start: String,
+    pub period_end: String,
+    pub carried_amount: Money,
+    pub available_amount: Money, // budget amount + carried amount
+    pub spent_amount: Money,
+    pub remaining_amount: Money,
     pub percentage_used: f64,
     pub days_remaining: i64,
-    pub daily_average_spent: f64,
-    pub daily_budget_remaining: f64,
+    pub daily_average_spent: Money,
+    pub daily_budget_remaining: Money,
     pub is_over_budget: bool,
+    pub history: Vec<BudgetPeriod>, // closed periods, oldest first
+    pub missing_rates: Vec<MissingRate>, // spending left out of the totals
 }
 
 #[derive(Debug, Serialize)]
 pub struct BudgetAlert {
     pub budget_id: i64,
     pub category_name: String,
-    pub budget_amount: f64,
-    pub spent_amount: f64,
+    pub budget_amount: Money,
+    pub spent_amount: Money,
     pub percentage_used: f64,
     pub alert_level: String, // WARNING (80%), DANGER (100%), CRITICAL (120%)
 }
@@ -1,12 +1,13 @@
 // File: src-tauri/src/models/credit_card.rs
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
 /// Credit card configuration linked to an account
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CreditCardSettings {
     pub id: i64,
     pub account_id: i64,
-    pub credit_limit: f64,
+    pub credit_limit: Money,
     pub statement_day: i32,   // 1-28, day of month statement is generated
     pub payment_due_day: i32, // 1-28, day of month payment is due
     pub minimum_payment_percentage: f64, // e.g., 5.0 = 5%
@@ -14,6 +15,13 @@ pub struct CreditCardSettings {
     pub settlement_account_id: Option<i64>, // Bank account for auto-pay
     pub created_at: String,
     pub updated_at: String,
+    pub apr: f64, // e.g., 24.0 = 24% a year
+    pub late_fee: Money,
+    pub annual_fee: Money,
+    pub annual_fee_month: Option<u32>, // 1-12, defaults to the month the card was added
+    pub fee_category_id: Option<i64>,  // Category for interest and fee charges
+    pub reward_type: String,           // CASHBACK, POINTS
+    pub point_value: f64,              // Card-currency value of one point
 }
 
 #[derive(Debug, Deserialize)]
@@ -25,6 +33,13 @@ pub struct CreateCreditCardSettingsInput {
     pub minimum_payment_percentage: Option<f64>,
     pub auto_settlement_enabled: Option<bool>,
     pub settlement_account_id: Option<i64>,
+    pub apr: Option<f64>,
+    pub late_fee: Option<f64>,
+    pub annual_fee: Option<f64>,
+    pub annual_fee_month: Option<u32>,
+    pub fee_category_id: Option<i64>,
+    pub reward_type: Option<String>,
+    pub point_value: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,6 +51,13 @@ pub struct UpdateCreditCardSettingsInput {
     pub minimum_payment_percentage: Option<f64>,
     pub auto_settlement_enabled: Option<bool>,
     pub settlement_account_id: Option<i64>,
+    pub apr: Option<f64>,
+    pub late_fee: Option<f64>,
+    pub annual_fee: Option<f64>,
+    pub annual_fee_month: Option<u32>,
+    pub fee_category_id: Option<i64>,
+    pub reward_type: Option<String>,
+    pub point_value: Option<f64>,
 }
 
 /// Full credit card info with account details and computed balances
@@ -44,16 +66,16 @@ pub struct CreditCardWithDetails {
     pub settings: CreditCardSettings,
     pub account_name: String,
     pub settlement_account_name: Option<String>,
-    pub total_balance: f64,       // Total liability (all-time unpaid)
-    pub outstanding_balance: f64, // Current cycle charges not yet paid
-    pub available_credit: f64,    // credit_limit - total_balance
-    pub current_cycle_charges: f64,
-    pub current_cycle_payments: f64,
+    pub total_balance: Money,       // Total liability (all-time unpaid)
+    pub outstanding_balance: Money, // Current cycle charges not yet paid
+    pub available_credit: Money,    // credit_limit - total_balance
+    pub current_cycle_charges: Money,
+    pub current_cycle_payments: Money,
     pub utilization_percentage: f64, // (total_balance / credit_limit) * 100
 }
 
 /// A closed billing statement
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CreditCardStatement {
     pub id: i64,
     pub credit_card_id: i64,
@@ -61,15 +83,18 @@ pub struct CreditCardStatement {
     pub due_date: String,
     pub cycle_start_date: String,
     pub cycle_end_date: String,
-    pub opening_balance: f64,
-    pub total_charges: f64,
-    pub total_payments: f64,
-    pub closing_balance: f64,
-    pub minimum_payment: f64,
+    pub opening_balance: Money,
+    pub total_charges: Money,
+    pub total_payments: Money,
+    pub closing_balance: Money,
+    pub minimum_payment: Money,
     pub status: String,
-    pub paid_amount: f64,
+    pub paid_amount: Money,
     pub paid_date: Option<String>,
     pub created_at: String,
+    pub finance_charge: Money,
+    pub late_fee: Money,
+    pub assessed_at: Option<String>, // Set once the due date has passed and charges were posted
 }
 
 /// Statement with transaction line items
@@ -77,6 +102,7 @@ pub struct CreditCardStatement {
 pub struct StatementWithTransactions {
     pub statement: CreditCardStatement,
     pub transactions: Vec<StatementTransaction>,
+    pub rewards: StatementRewards,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -84,7 +110,7 @@ pub struct StatementTransaction {
     pub id: i64,
     pub date: String,
     pub transaction_type: String,
-    pub amount: f64,
+    pub amount: Money,
     pub category_name: Option<String>,
     pub memo: Option<String>,
 }
@@ -103,10 +129,98 @@ pub struct SettlementInput {
 pub struct CreditCardSummary {
     pub account_id: i64,
     pub account_name: String,
-    pub total_balance: f64,
-    pub credit_limit: f64,
-    pub available_credit: f64,
+    pub total_balance: Money,
+    pub credit_limit: Money,
+    pub available_credit: Money,
     pub next_due_date: Option<String>,
-    pub next_due_amount: Option<f64>,
+    pub next_due_amount: Option<Money>,
     pub utilization_percentage: f64,
 }
+
+/// Month-by-month outcome of paying only the minimum on a card balance
+#[derive(Debug, Serialize)]
+pub struct MinimumPaymentProjection {
+    pub starting_balance: Money,
+    pub apr: f64,
+    pub minimum_payment_percentage: f64,
+    pub minimum_payment_floor: Money,
+    pub pays_off: bool, // false when the minimum never outpaces interest
+    pub months_to_payoff: i32,
+    pub total_interest: Money,
+    pub total_paid: Money,
+    pub months: Vec<ProjectionMonth>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectionMonth {
+    pub month: i32,
+    pub payment: Money,
+    pub interest: Money,
+    pub balance: Money,
+}
+
+/// A reward rate for one category (or the base rate when `category_id` is None).
+/// Rules sharing a category form tiers keyed by `tier_threshold`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RewardRule {
+    pub id: i64,
+    pub credit_card_id: i64,
+    pub category_id: Option<i64>,
+    pub category_name: Option<String>,
+    pub rate: f64,             // % of spend for CASHBACK, points per unit spent for POINTS
+    pub tier_threshold: Money, // Cycle spend above which this rate applies
+    pub cycle_cap: Option<f64>, // Max reward per cycle, in reward units
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRewardRuleInput {
+    pub credit_card_id: i64,
+    pub category_id: Option<i64>,
+    pub rate: f64,
+    pub tier_threshold: Option<f64>,
+    pub cycle_cap: Option<f64>,
+}
+
+/// Rewards earned in one billing cycle. Reward amounts are currency for
+/// CASHBACK cards and points for POINTS cards.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatementRewards {
+    pub reward_type: String,
+    pub total: f64,
+    pub value: Money,
+    pub lines: Vec<RewardLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RewardLine {
+    pub rule_id: i64,
+    pub category_id: Option<i64>,
+    pub category_name: Option<String>,
+    pub spend: Money, // Spend earning at this rule's rate
+    pub rate: f64,
+    pub reward: f64,
+    pub capped: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RedeemRewardsInput {
+    pub credit_card_settings_id: i64,
+    pub amount: f64,              // In reward units (currency or points)
+    pub account_id: Option<i64>,  // None = statement credit on the card itself
+    pub category_id: Option<i64>,
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreditCardRewardSummary {
+    pub credit_card_id: i64,
+    pub account_id: i64,
+    pub account_name: String,
+    pub reward_type: String,
+    pub earned: f64,
+    pub redeemed: f64,
+    pub balance: f64,
+    pub balance_value: Money,
+    pub current_cycle_accrual: f64, // Not yet on a statement
+}
@@ -1,4 +1,5 @@
 // File: src-tauri/src/models/currency.rs
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -10,6 +11,8 @@ pub struct ExchangeRate {
     pub effective_date: String,
     pub created_at: String,
     pub updated_at: String,
+    /// Base currency a cross rate was triangulated through; None for stored rates
+    pub via_currency: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,8 +27,8 @@ pub struct SetExchangeRateInput {
 pub struct CurrencyConversion {
     pub from_currency: String,
     pub to_currency: String,
-    pub original_amount: f64,
-    pub converted_amount: f64,
+    pub original_amount: Money,
+    pub converted_amount: Money,
     pub rate_used: f64,
     pub rate_date: String,
 }
@@ -35,6 +38,7 @@ pub struct SupportedCurrency {
     pub code: String,
     pub name: String,
     pub symbol: String,
+    pub decimals: u32,
 }
 
 #[derive(Debug, Serialize)]
@@ -44,4 +48,78 @@ pub struct ExchangeRateSummary {
     pub latest_rate: f64,
     pub latest_date: String,
     pub rate_count: i64,
+    pub days_since_update: i64,
+    pub is_stale: bool,
+}
+
+/// An amount in its original currency, reported alongside converted totals
+#[derive(Debug, Clone, Serialize)]
+pub struct CurrencyAmount {
+    pub currency: String,
+    pub amount: Money,
+}
+
+/// A currency that could not be converted because no rate was effective on
+/// some of the dates involved. Those amounts are left out of converted totals.
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingRate {
+    pub currency: String,
+    pub to_currency: String,
+    pub first_date: String,
+    pub last_date: String,
+    pub occurrences: i64,
+}
+
+/// FX gain or loss on a transfer between accounts in different currencies:
+/// the value received less the value sent, both at the market rate to the
+/// primary currency on the transfer date.
+#[derive(Debug, Clone, Serialize)]
+pub struct FxPosting {
+    pub transaction_id: i64,
+    pub date: String,
+    pub account_id: i64,
+    pub account_name: String,
+    pub amount: Money,
+    pub account_currency: String,
+    pub to_account_id: i64,
+    pub to_account_name: String,
+    pub to_amount: Money,
+    pub to_account_currency: String,
+    pub implied_rate: f64,
+    pub source_value: Money,
+    pub destination_value: Money,
+    pub gain: Money,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FxGainReport {
+    pub start_date: String,
+    pub end_date: String,
+    pub currency: String,
+    pub total_gain: Money,
+    pub postings: Vec<FxPosting>,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExchangeRateImportOptions {
+    /// CSV or ECB_XML; detected from the file when omitted
+    pub format: Option<String>,
+    /// Currency ECB files and one-column-per-currency CSVs quote against (EUR by default)
+    pub base_currency: Option<String>,
+    /// Only import rates between these currencies and the base
+    pub currencies: Option<Vec<String>>,
+    /// Replace rates already stored for the same pair and date
+    pub overwrite: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExchangeRateImportResult {
+    pub format: String,
+    pub imported: i64,
+    pub updated: i64,
+    pub skipped: i64,
+    pub errors: Vec<String>,
+    pub first_date: Option<String>,
+    pub last_date: Option<String>,
 }
@@ -1,11 +1,12 @@
 // File: src-tauri/src/models/goal.rs
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct SavingsGoal {
     pub id: i64,
     pub name: String,
-    pub target_amount: f64,
+    pub target_amount: Money,
     pub target_date: Option<String>,
     pub linked_account_id: Option<i64>,
     pub color: String,
@@ -17,8 +18,8 @@ pub struct SavingsGoal {
 
 #[derive(Debug, Serialize, Clone)]
 pub struct GoalProgress {
-    pub current_amount: f64,
-    pub target_amount: f64,
+    pub current_amount: Money,
+    pub target_amount: Money,
     pub percentage: f64,
     pub on_track: bool,
     pub projected_completion_date: Option<String>,
@@ -31,14 +32,14 @@ pub struct GoalWithProgress {
     pub goal: SavingsGoal,
     pub progress: GoalProgress,
     pub linked_account_name: Option<String>,
-    pub linked_account_balance: Option<f64>,
+    pub linked_account_balance: Option<Money>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct GoalContribution {
     pub id: i64,
     pub goal_id: i64,
-    pub amount: f64,
+    pub amount: Money,
     pub contribution_date: String,
     pub note: Option<String>,
     pub transaction_id: Option<i64>,
@@ -94,16 +95,16 @@ pub struct GoalWithdrawalInput {
 pub struct GoalAllocationSummary {
     pub goal_id: i64,
     pub goal_name: String,
-    pub allocated_amount: f64,
+    pub allocated_amount: Money,
     pub color: String,
-    pub target_amount: f64,
+    pub target_amount: Money,
 }
 
 #[derive(Debug, Serialize, Clone)]
 pub struct AccountUnallocatedBalance {
     pub account_id: i64,
-    pub total_balance: f64,
-    pub allocated_balance: f64,
-    pub unallocated_balance: f64,
+    pub total_balance: Money,
+    pub allocated_balance: Money,
+    pub unallocated_balance: Money,
     pub goals: Vec<GoalAllocationSummary>,
 }
@@ -1,4 +1,5 @@
 // File: src-tauri/src/models/import.rs
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -19,6 +20,7 @@ pub struct ColumnMapping {
     pub account_col: Option<usize>,
     pub category_col: Option<usize>,
     pub memo_col: Option<usize>,
+    pub payee_col: Option<usize>,
     pub date_format: String,
     pub negative_as_expense: bool,
 }
@@ -28,7 +30,7 @@ pub struct RowValidation {
     pub row_index: i64,
     pub status: String, // "valid", "warning", "error"
     pub date: String,
-    pub amount: f64,
+    pub amount: Money,
     pub transaction_type: String,
     pub account_name: String,
     pub category_name: String,
@@ -36,6 +38,8 @@ pub struct RowValidation {
     pub error: Option<String>,
     pub matched_account_id: Option<i64>,
     pub matched_category_id: Option<i64>,
+    /// Set when the category was suggested from history rather than matched
+    pub category_confidence: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -86,3 +90,69 @@ pub struct MatchSuggestion {
     pub matched_name: Option<String>,
     pub score: f64, // 0.0 to 1.0
 }
+
+#[derive(Debug, Serialize)]
+pub struct OfxTransactionPreview {
+    pub fitid: String,
+    pub date: String,
+    pub amount: Money,
+    pub transaction_type: String,
+    pub payee: String,
+    pub memo: String,
+    pub is_duplicate: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OfxStatementPreview {
+    pub account_key: String, // "BANKID:ACCTID" for bank statements, "ACCTID" for cards
+    pub account_number: String,
+    pub account_type: String,
+    pub currency: String,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub total_rows: i64,
+    pub duplicate_count: i64,
+    pub matched_account_id: Option<i64>,
+    pub matched_account_name: Option<String>,
+    pub transactions: Vec<OfxTransactionPreview>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OfxPreview {
+    pub version: String, // "1.x" (SGML) or "2.x" (XML)
+    pub statements: Vec<OfxStatementPreview>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OfxImportOptions {
+    pub account_mapping: HashMap<String, i64>, // account_key -> account id
+}
+
+#[derive(Debug, Serialize)]
+pub struct QifPreviewRow {
+    pub account_name: String,
+    pub date: String,
+    pub amount: String,
+    pub payee: String,
+    pub category: String, // "Parent:Child", or "[Account]" for transfers
+    pub memo: String,
+    pub split_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QifPreview {
+    pub account_types: Vec<String>, // Bank, CCard, Cash
+    pub accounts: Vec<String>,
+    pub categories: Vec<String>,
+    pub transfer_accounts: Vec<String>,
+    pub rows: Vec<QifPreviewRow>,
+    pub total_rows: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QifImportOptions {
+    pub skip_duplicates: bool,
+    pub default_account_id: i64,
+    pub account_mapping: HashMap<String, i64>, // QIF account name -> account id
+    pub date_format: Option<String>,           // "MM/DD/YYYY" (default) or "DD/MM/YYYY"
+}
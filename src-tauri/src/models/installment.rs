@@ -1,36 +1,46 @@
 // File: src-tauri/src/models/installment.rs
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct InstallmentPlan {
     pub id: i64,
     pub name: String,
-    pub total_amount: f64,
+    pub total_amount: Money,
     pub num_installments: i32,
-    pub amount_per_installment: f64,
+    pub amount_per_installment: Money,
     pub account_id: i64,
     pub category_id: i64,
     pub start_date: String,
     pub frequency: String,
     pub next_due_date: String,
     pub installments_paid: i32,
-    pub total_paid: f64,
+    pub total_paid: Money,
     pub status: String,
     pub memo: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub apr: f64,
+    pub interest_method: String, // FLAT, REDUCING_BALANCE
+    pub processing_fee: Money,
+    pub liability_account_id: Option<i64>,
+    pub interest_category_id: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct InstallmentPayment {
     pub id: i64,
     pub installment_plan_id: i64,
     pub transaction_id: i64,
     pub installment_number: i32,
-    pub amount: f64,
+    pub amount: Money,
     pub due_date: String,
     pub paid_date: String,
     pub created_at: String,
+    pub principal: Money,
+    pub interest: Money,
+    pub fee: Money,
+    pub interest_transaction_id: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -43,6 +53,25 @@ pub struct CreateInstallmentPlan {
     pub start_date: String,
     pub frequency: String,
     pub memo: Option<String>,
+    pub apr: Option<f64>,
+    pub interest_method: Option<String>,
+    pub processing_fee: Option<f64>,
+    pub liability_account_id: Option<i64>,
+    pub interest_category_id: Option<i64>,
+}
+
+/// One line of a plan's amortization schedule. `remaining_balance` is the
+/// principal still owed after this installment.
+#[derive(Debug, Clone, Serialize)]
+pub struct AmortizationRow {
+    pub installment_number: i32,
+    pub due_date: String,
+    pub payment: Money,
+    pub principal: Money,
+    pub interest: Money,
+    pub fee: Money,
+    pub remaining_balance: Money,
+    pub status: String, // PENDING, PAID, OVERDUE
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -51,16 +80,19 @@ pub struct InstallmentPlanWithDetails {
     pub payments: Vec<InstallmentPaymentDetails>,
     pub account_name: String,
     pub category_name: String,
-    pub remaining_amount: f64,
+    pub remaining_amount: Money,
     pub remaining_installments: i32,
-    pub next_payment_amount: f64,
+    pub next_payment_amount: Money,
+    pub total_interest: Money,
+    pub total_payable: Money,
+    pub schedule: Vec<AmortizationRow>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct InstallmentPaymentDetails {
     pub payment: InstallmentPayment,
     pub installment_number: i32,
-    pub amount: f64,
+    pub amount: Money,
     pub due_date: String,
     pub paid_date: Option<String>,
     pub status: String, // PENDING, PAID, OVERDUE
@@ -0,0 +1,142 @@
+// File: src-tauri/src/models/investment.rs
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Security {
+    pub id: i64,
+    pub symbol: String,
+    pub name: String,
+    pub currency: String,
+    pub latest_price: Option<f64>,
+    pub latest_price_date: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSecurityInput {
+    pub symbol: String,
+    pub name: String,
+    pub currency: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecurityPrice {
+    pub id: i64,
+    pub security_id: i64,
+    pub date: String,
+    pub price: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InvestmentAccount {
+    pub account_id: i64,
+    pub account_name: String,
+    pub currency: String,
+    pub cash_account_id: Option<i64>,
+    pub cost_basis_method: String, // FIFO, AVERAGE
+    pub income_category_id: Option<i64>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetInvestmentAccountInput {
+    pub account_id: i64,
+    pub cash_account_id: Option<i64>,
+    pub cost_basis_method: Option<String>,
+    pub income_category_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InvestmentTransaction {
+    pub id: i64,
+    pub account_id: i64,
+    pub security_id: i64,
+    pub symbol: String,
+    pub transaction_type: String, // BUY, SELL, DIVIDEND, SPLIT
+    pub date: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub fees: Money,
+    pub amount: Money,
+    pub split_ratio: Option<f64>,
+    pub realized_gain: Money,
+    pub cash_account_id: Option<i64>,
+    pub transaction_id: Option<i64>,
+    pub gain_transaction_id: Option<i64>,
+    pub memo: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInvestmentTransactionInput {
+    pub account_id: i64,
+    pub security_id: i64,
+    pub transaction_type: String,
+    pub date: String,
+    pub quantity: Option<f64>,
+    pub price: Option<f64>,
+    pub fees: Option<f64>,
+    /// Dividend payout; BUY and SELL amounts are derived from quantity and price
+    pub amount: Option<f64>,
+    pub split_ratio: Option<f64>,
+    pub memo: Option<String>,
+}
+
+/// An open purchase lot. Under average cost every lot carries the pooled unit cost.
+#[derive(Debug, Clone, Serialize)]
+pub struct Lot {
+    pub buy_id: i64,
+    pub date: String,
+    pub quantity: f64,
+    pub cost: Money,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Holding {
+    pub account_id: i64,
+    pub account_name: String,
+    pub security_id: i64,
+    pub symbol: String,
+    pub name: String,
+    pub quantity: f64,
+    pub cost_basis: Money,
+    pub average_cost: f64,
+    pub price: Option<f64>,
+    pub price_date: Option<String>,
+    /// Cost basis when the security has no price yet
+    pub market_value: Money,
+    pub unrealized_gain: Money,
+    pub unrealized_gain_percentage: f64,
+    pub realized_gain: Money,
+    pub dividends: Money,
+    pub lots: Vec<Lot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecurityGain {
+    pub account_id: i64,
+    pub security_id: i64,
+    pub symbol: String,
+    pub currency: String,
+    pub realized_gain: Money,
+    pub dividends: Money,
+    pub unrealized_gain: Money,
+}
+
+/// Realized gains and dividends within the period, unrealized gains as of its end.
+/// Totals are in the primary currency, converted at the rate on each sale or
+/// payout date (the end date for unrealized gains); per-security figures stay
+/// in the account currency.
+#[derive(Debug, Clone, Serialize)]
+pub struct InvestmentGainReport {
+    pub start_date: String,
+    pub end_date: String,
+    pub currency: String,
+    pub realized_gain: Money,
+    pub dividends: Money,
+    pub unrealized_gain: Money,
+    pub securities: Vec<SecurityGain>,
+    pub missing_rates: Vec<MissingRate>,
+}
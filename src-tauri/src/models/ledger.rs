@@ -0,0 +1,24 @@
+// File: src-tauri/src/models/ledger.rs
+use serde::Serialize;
+
+/// A single problem found in the double-entry ledger
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerIssue {
+    pub kind: String, // ORPHAN_JOURNAL_ENTRY, MISSING_JOURNAL_ENTRIES, JOURNAL_MISMATCH, UNBALANCED_TRANSFER, DANGLING_REFERENCE
+    pub transaction_id: Option<i64>,
+    pub journal_entry_id: Option<i64>,
+    pub message: String,
+    pub repairable: bool,
+}
+
+/// Result of `verify_ledger`. `issues` always describes the state before any repair.
+#[derive(Debug, Clone, Serialize)]
+pub struct LedgerReport {
+    pub transactions_checked: i64,
+    pub journal_entries_checked: i64,
+    pub issues: Vec<LedgerIssue>,
+    pub is_consistent: bool,
+    pub repaired: bool,
+    pub transactions_repaired: i64,
+    pub journal_entries_removed: i64,
+}
@@ -0,0 +1,97 @@
+// File: src-tauri/src/models/loan.rs
+use crate::models::money::Money;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Loan {
+    pub id: i64,
+    pub account_id: i64,
+    pub payment_account_id: i64,
+    pub interest_category_id: Option<i64>,
+    pub principal: Money,
+    pub annual_rate: f64,
+    pub term_months: i32,
+    pub start_date: String,
+    pub payment_day: u32,
+    pub emi: Money,
+    pub status: String, // ACTIVE, CLOSED
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLoanInput {
+    pub account_id: i64,
+    pub payment_account_id: i64,
+    pub interest_category_id: Option<i64>,
+    pub principal: f64,
+    pub annual_rate: f64,
+    pub term_months: i32,
+    pub start_date: String,
+    pub payment_day: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoanPayment {
+    pub id: i64,
+    pub loan_id: i64,
+    pub transaction_id: i64,
+    pub interest_transaction_id: Option<i64>,
+    pub kind: String, // SCHEDULED, PREPAYMENT
+    pub payment_number: Option<i32>,
+    pub payment_date: String,
+    pub principal: Money,
+    pub interest: Money,
+    pub strategy: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoanScheduleRow {
+    pub payment_number: i32,
+    pub due_date: String,
+    pub payment: Money,
+    pub principal: Money,
+    pub interest: Money,
+    pub remaining_balance: Money,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoanDetails {
+    pub loan: Loan,
+    pub account_name: String,
+    pub outstanding_principal: Money,
+    pub principal_paid: Money,
+    pub interest_paid: Money,
+    pub payments_made: i32,
+    pub payments: Vec<LoanPayment>,
+    pub schedule: Vec<LoanScheduleRow>,
+    pub remaining_payments: i32,
+    pub projected_payoff_date: Option<String>,
+    pub projected_interest: Money,
+    pub original_payoff_date: String,
+    pub original_total_interest: Money,
+    /// Interest avoided so far compared with the original schedule
+    pub interest_saved: Money,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoanPrepaymentInput {
+    pub loan_id: i64,
+    pub amount: f64,
+    pub strategy: String, // REDUCE_TERM, REDUCE_EMI
+    pub date: Option<String>,
+    pub from_account_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoanPrepaymentPreview {
+    pub strategy: String,
+    pub new_emi: Money,
+    pub remaining_payments: i32,
+    pub projected_payoff_date: Option<String>,
+    pub projected_interest: Money,
+    /// Interest saved relative to the schedule without this prepayment
+    pub interest_saved: Money,
+    pub payments_saved: i32,
+}
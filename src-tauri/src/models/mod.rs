@@ -1,5 +1,6 @@
 pub mod account;
 pub mod analytics;
+pub mod audit;
 pub mod bill;
 pub mod budget;
 pub mod category;
@@ -8,8 +9,16 @@ pub mod currency;
 pub mod goal;
 pub mod import;
 pub mod installment;
+pub mod investment;
+pub mod ledger;
+pub mod loan;
+pub mod money;
+pub mod reconciliation;
 pub mod networth;
+pub mod payee;
 pub mod recurring;
+pub mod saved_filter;
+pub mod suggestion;
 pub mod tag;
 pub mod template;
 pub mod transactions;
@@ -0,0 +1,373 @@
+// File: src-tauri/src/models/money.rs
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Exact monetary amount held as integer minor units (cents, fils, yen...).
+///
+/// `decimals` is the number of decimal places of the currency the amount is in
+/// (see `get_supported_currencies`), so 1234.50 LKR is `{ minor: 123450, decimals: 2 }`
+/// and 1234 JPY is `{ minor: 1234, decimals: 0 }`.
+///
+/// Amounts are stored in SQLite as INTEGER minor units and only become
+/// floating point on the way out to the frontend (see the `Serialize` impl).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Money {
+    minor: i64,
+    decimals: u32,
+}
+
+fn pow10(decimals: u32) -> i64 {
+    10_i64.pow(decimals)
+}
+
+/// Integer division rounding half away from zero.
+fn div_round(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.abs() * 2 >= denominator.abs() {
+        if (numerator < 0) != (denominator < 0) {
+            quotient - 1
+        } else {
+            quotient + 1
+        }
+    } else {
+        quotient
+    }
+}
+
+impl Money {
+    pub fn new(minor: i64, decimals: u32) -> Self {
+        Money { minor, decimals }
+    }
+
+    pub fn zero(decimals: u32) -> Self {
+        Money { minor: 0, decimals }
+    }
+
+    /// Convert a major-unit float (e.g. from the frontend) into minor units,
+    /// rounding half away from zero to the currency's precision.
+    pub fn from_major(amount: f64, decimals: u32) -> Self {
+        Money {
+            minor: (amount * pow10(decimals) as f64).round() as i64,
+            decimals,
+        }
+    }
+
+    /// Parse a decimal string ("1234.5", "-12", "0.075") exactly.
+    /// Extra fractional digits beyond the currency's precision are rounded.
+    pub fn parse(text: &str, decimals: u32) -> Result<Self, String> {
+        let trimmed = text.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((w, f)) => (w, f),
+            None => (digits, ""),
+        };
+
+        if (whole.is_empty() && fraction.is_empty())
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err(format!("Invalid amount: '{}'", text));
+        }
+
+        let invalid = || format!("Amount out of range: '{}'", text);
+        let whole_value: i128 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| invalid())?
+        };
+
+        let scale = pow10(decimals) as i128;
+        let minor = if fraction.len() as u32 <= decimals {
+            let padded = format!("{:0<width$}", fraction, width = decimals as usize);
+            let fraction_value: i128 = if padded.is_empty() { 0 } else { padded.parse().map_err(|_| invalid())? };
+            whole_value * scale + fraction_value
+        } else {
+            let fraction_value: i128 = fraction.parse().map_err(|_| invalid())?;
+            let fraction_scale = 10_i128
+                .checked_pow(fraction.len() as u32)
+                .ok_or_else(invalid)?;
+            whole_value * scale + div_round(fraction_value * scale, fraction_scale)
+        };
+
+        let minor = if negative { -minor } else { minor };
+        let minor = i64::try_from(minor).map_err(|_| invalid())?;
+
+        Ok(Money { minor, decimals })
+    }
+
+    pub fn minor(&self) -> i64 {
+        self.minor
+    }
+
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    /// Major-unit float for display and JSON. Never feed this back into arithmetic.
+    pub fn to_major(&self) -> f64 {
+        self.minor as f64 / pow10(self.decimals) as f64
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.minor == 0
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.minor > 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.minor < 0
+    }
+
+    pub fn abs(self) -> Self {
+        Money { minor: self.minor.abs(), decimals: self.decimals }
+    }
+
+    /// Re-express the amount with a different number of decimal places.
+    /// Adding decimals is exact; dropping them rounds half away from zero.
+    pub fn rescale(self, decimals: u32) -> Self {
+        match decimals.cmp(&self.decimals) {
+            Ordering::Equal => self,
+            Ordering::Greater => Money {
+                minor: self.minor * pow10(decimals - self.decimals),
+                decimals,
+            },
+            Ordering::Less => Money {
+                minor: div_round(self.minor as i128, pow10(self.decimals - decimals) as i128) as i64,
+                decimals,
+            },
+        }
+    }
+
+    /// Multiply by a floating factor (an exchange rate, a percentage / 100)
+    /// and round to the precision of `decimals`.
+    pub fn mul_f64(self, factor: f64, decimals: u32) -> Self {
+        Money::from_major(self.to_major() * factor, decimals)
+    }
+
+    /// `percent`% of this amount, rounded half away from zero.
+    /// The percentage is taken to 4 decimal places (e.g. 5.25 → 52500 / 1_000_000).
+    pub fn percent(self, percent: f64) -> Self {
+        let basis = (percent * 10_000.0).round() as i128;
+        Money {
+            minor: div_round(self.minor as i128 * basis, 1_000_000) as i64,
+            decimals: self.decimals,
+        }
+    }
+
+    /// Ratio of this amount to `other` as a float (for percentages and charts).
+    pub fn ratio(self, other: Money) -> f64 {
+        let (a, b) = Money::aligned(self, other);
+        if b == 0 {
+            0.0
+        } else {
+            a as f64 / b as f64
+        }
+    }
+
+    /// Split into `parts` amounts that add back up exactly to `self`.
+    /// The remainder is spread one minor unit at a time over the first parts.
+    pub fn allocate(self, parts: u32) -> Vec<Money> {
+        if parts == 0 {
+            return Vec::new();
+        }
+        let base = self.minor / parts as i64;
+        let remainder = self.minor % parts as i64;
+        (0..parts as i64)
+            .map(|i| Money {
+                minor: base + if i < remainder.abs() { remainder.signum() } else { 0 },
+                decimals: self.decimals,
+            })
+            .collect()
+    }
+
+    /// Divide into an average (e.g. spent per day), rounded to the currency precision.
+    pub fn div_round(self, divisor: i64) -> Self {
+        if divisor == 0 {
+            return Money::zero(self.decimals);
+        }
+        Money {
+            minor: div_round(self.minor as i128, divisor as i128) as i64,
+            decimals: self.decimals,
+        }
+    }
+
+    /// Add amounts that may have different decimals, widening to the larger
+    /// number so no precision is lost.
+    pub fn widening_add(self, other: Money) -> Money {
+        let decimals = self.decimals.max(other.decimals);
+        Money {
+            minor: self.rescale(decimals).minor + other.rescale(decimals).minor,
+            decimals,
+        }
+    }
+
+    /// Bring two amounts to a common scale and return their minor units.
+    fn aligned(a: Money, b: Money) -> (i64, i64) {
+        let decimals = a.decimals.max(b.decimals);
+        (a.rescale(decimals).minor, b.rescale(decimals).minor)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.minor < 0 { "-" } else { "" };
+        let scale = pow10(self.decimals).unsigned_abs();
+        let abs = self.minor.unsigned_abs();
+        if self.decimals == 0 {
+            write!(f, "{}{}", sign, abs)
+        } else {
+            write!(
+                f,
+                "{}{}.{:0width$}",
+                sign,
+                abs / scale,
+                abs % scale,
+                width = self.decimals as usize
+            )
+        }
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_major())
+    }
+}
+
+impl PartialEq for Money {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = Money::aligned(*self, *other);
+        a == b
+    }
+}
+
+impl Eq for Money {}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Money {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = Money::aligned(*self, *other);
+        a.cmp(&b)
+    }
+}
+
+// Amounts with different decimals are in different currencies, so adding
+// them is a bug unless one side is zero (as when a sum starts from
+// `Money::zero(0)`). Debug builds assert it; use `widening_add` when
+// mixing scales is intended.
+impl Add for Money {
+    type Output = Money;
+    fn add(self, other: Money) -> Money {
+        debug_assert!(
+            self.decimals == other.decimals || self.minor == 0 || other.minor == 0,
+            "adding amounts with different decimals ({} and {}); rescale one first",
+            self.decimals,
+            other.decimals
+        );
+        self.widening_add(other)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+    fn sub(self, other: Money) -> Money {
+        self + (-other)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+    fn neg(self) -> Money {
+        Money { minor: -self.minor, decimals: self.decimals }
+    }
+}
+
+impl Mul<i64> for Money {
+    type Output = Money;
+    fn mul(self, factor: i64) -> Money {
+        Money { minor: self.minor * factor, decimals: self.decimals }
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::zero(0), |acc, m| acc + m)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.fold(Money::zero(0), |acc, m| acc + *m)
+    }
+}
+
+impl rusqlite::ToSql for Money {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(rusqlite::types::ToSqlOutput::from(self.minor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        assert_eq!(Money::parse("1234.5", 2).unwrap().minor(), 123450);
+        assert_eq!(Money::parse("-0.07", 2).unwrap().minor(), -7);
+        assert_eq!(Money::parse("1234", 0).unwrap().to_string(), "1234");
+        assert_eq!(Money::parse("0.125", 2).unwrap().minor(), 13);
+        assert_eq!(Money::new(-5, 2).to_string(), "-0.05");
+        assert!(Money::parse("12a", 2).is_err());
+    }
+
+    #[test]
+    fn test_sums_are_exact() {
+        let total: Money = (0..10).map(|_| Money::from_major(0.1, 2)).sum();
+        assert_eq!(total, Money::new(100, 2));
+        assert_eq!(Money::new(100, 0).widening_add(Money::new(50, 2)), Money::new(10050, 2));
+        assert_eq!(Money::zero(0) + Money::new(50, 2), Money::new(50, 2));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "different decimals")]
+    fn test_adding_mismatched_decimals_is_caught() {
+        let _ = Money::new(100, 0) + Money::new(50, 2);
+    }
+
+    #[test]
+    fn test_allocate_and_percent() {
+        let parts = Money::new(10000, 2).allocate(3);
+        assert_eq!(parts.iter().sum::<Money>(), Money::new(10000, 2));
+        assert_eq!(parts[0].minor(), 3334);
+        assert_eq!(Money::new(12345, 2).percent(5.0).minor(), 617);
+    }
+}
@@ -1,22 +1,41 @@
 // File: src-tauri/src/models/networth.rs
-use serde::{Deserialize, Serialize};
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
+use serde::Serialize;
 
 /// Live net worth calculation with month-over-month change
 #[derive(Debug, Clone, Serialize)]
 pub struct NetWorthSummary {
-    pub assets: f64,
-    pub liabilities: f64,
-    pub net_worth: f64,
-    pub change_amount: f64,
+    pub assets: Money,
+    pub liabilities: Money,
+    pub net_worth: Money,
+    pub change_amount: Money,
     pub change_percentage: f64,
+    pub currency: String,
+    pub by_currency: Vec<CurrencyNetWorth>,
+    pub missing_rates: Vec<MissingRate>,
+}
+
+/// Net worth held in one currency, before conversion. `rate` is None when no
+/// rate to the primary currency was effective, in which case these amounts are
+/// left out of the converted totals.
+#[derive(Debug, Clone, Serialize)]
+pub struct CurrencyNetWorth {
+    pub currency: String,
+    pub assets: Money,
+    pub liabilities: Money,
+    pub net_worth: Money,
+    pub rate: Option<f64>,
 }
 
 /// Persisted monthly snapshot for historical chart
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NetWorthSnapshot {
     pub id: i64,
     pub snapshot_date: String,
-    pub total_assets: f64,
-    pub total_liabilities: f64,
-    pub net_worth: f64,
+    pub total_assets: Money,
+    pub total_liabilities: Money,
+    pub net_worth: Money,
+    pub by_currency: Vec<CurrencyNetWorth>,
+    pub missing_rates: Vec<MissingRate>,
 }
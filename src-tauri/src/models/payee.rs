@@ -0,0 +1,54 @@
+// File: src-tauri/src/models/payee.rs
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Payee {
+    pub id: i64,
+    pub name: String,
+    pub default_category_id: Option<i64>,
+    pub default_category_name: Option<String>,
+    pub aliases: Vec<String>,
+    pub tag_ids: Vec<i64>,
+    pub transaction_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePayeeInput {
+    pub name: String,
+    pub default_category_id: Option<i64>,
+    pub aliases: Option<Vec<String>>,
+    pub tag_ids: Option<Vec<i64>>,
+}
+
+/// Replaces every field; aliases and tags are replaced as whole lists
+#[derive(Debug, Deserialize)]
+pub struct UpdatePayeeInput {
+    pub id: i64,
+    pub name: String,
+    pub default_category_id: Option<i64>,
+    pub aliases: Vec<String>,
+    pub tag_ids: Vec<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergePayeesInput {
+    pub source_ids: Vec<i64>,
+    pub target_id: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PayeeSpending {
+    pub payee_id: i64,
+    pub payee_name: String,
+    pub total_income: Money,
+    pub total_expense: Money,
+    pub transaction_count: i64,
+    pub first_date: String,
+    pub last_date: String,
+    pub currency: String,
+    pub missing_rates: Vec<MissingRate>,
+}
@@ -0,0 +1,46 @@
+// File: src-tauri/src/models/reconciliation.rs
+use crate::models::money::Money;
+use serde::{Deserialize, Serialize};
+
+/// A reconciliation session of one account against a bank statement
+#[derive(Debug, Clone, Serialize)]
+pub struct Reconciliation {
+    pub id: i64,
+    pub account_id: i64,
+    pub statement_date: String,
+    pub statement_balance: Money,
+    pub status: String, // IN_PROGRESS, COMPLETED
+    pub cleared_balance: Option<Money>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StartReconciliationInput {
+    pub account_id: i64,
+    pub statement_date: String,
+    pub statement_balance: f64,
+}
+
+/// A transaction as it affects the account being reconciled
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconciliationTransaction {
+    pub transaction_id: i64,
+    pub date: String,
+    pub transaction_type: String,
+    pub memo: Option<String>,
+    pub amount: Money, // signed: positive increases the account balance
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconciliationSummary {
+    pub reconciliation: Reconciliation,
+    pub account_name: String,
+    pub opening_balance: Money, // balance as of the last completed reconciliation
+    pub cleared_balance: Money,
+    pub difference: Money, // statement_balance - cleared_balance
+    pub cleared_count: i64,
+    pub uncleared_count: i64,
+    pub transactions: Vec<ReconciliationTransaction>,
+}
@@ -1,13 +1,14 @@
 // File: src-tauri/src/models/recurring.rs
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct RecurringTransaction {
     pub id: i64,
     pub name: String,
     pub description: Option<String>,
     pub transaction_type: String, // INCOME, EXPENSE, TRANSFER
-    pub amount: f64,
+    pub amount: Money,
     pub account_id: i64,
     pub to_account_id: Option<i64>,
     pub category_id: Option<i64>,
@@ -75,7 +76,7 @@ pub struct UpcomingExecution {
     pub recurring_id: i64,
     pub name: String,
     pub transaction_type: String,
-    pub amount: f64,
+    pub amount: Money,
     pub next_execution_date: String,
     pub days_until_execution: i64,
 }
@@ -86,7 +87,7 @@ pub struct RecurringExecutionLog {
     pub recurring_id: i64,
     pub execution_date: String,
     pub status: String, // SUCCESS, SKIPPED, FAILED, VARIABLE_PENDING
-    pub amount: Option<f64>,
+    pub amount: Option<Money>,
     pub transaction_id: Option<i64>,
     pub notes: Option<String>,
     pub created_at: String,
@@ -0,0 +1,43 @@
+// File: src-tauri/src/models/saved_filter.rs
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
+use crate::models::transactions::TransactionFilter;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SavedFilter {
+    pub id: i64,
+    pub name: String,
+    pub filter: TransactionFilter,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSavedFilterInput {
+    pub name: String,
+    pub filter: TransactionFilter,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSavedFilterInput {
+    pub id: i64,
+    pub name: String,
+    pub filter: TransactionFilter,
+}
+
+/// Totals of a saved filter's transactions, for dashboard widgets
+#[derive(Debug, Serialize, Clone)]
+pub struct SavedFilterSummary {
+    pub saved_filter_id: i64,
+    pub name: String,
+    /// The dates the filter resolved to today
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub total_income: Money,
+    pub total_expense: Money,
+    pub net: Money,
+    pub transaction_count: i64,
+    pub currency: String,
+    pub missing_rates: Vec<MissingRate>,
+}
@@ -0,0 +1,40 @@
+// File: src-tauri/src/models/suggestion.rs
+use serde::{Deserialize, Serialize};
+
+/// A transaction being entered, before it is saved
+#[derive(Debug, Deserialize)]
+pub struct SuggestionInput {
+    pub transaction_type: String,
+    pub memo: Option<String>,
+    pub amount: Option<f64>,
+    pub account_id: Option<i64>,
+    pub date: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CategorySuggestion {
+    pub category_id: i64,
+    pub category_name: String,
+    /// Posterior probability among the categories of this transaction type, 0–1
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TagSuggestion {
+    pub tag_id: i64,
+    pub tag_name: String,
+    /// Probability that a transaction like this carries the tag, 0–1
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionSuggestions {
+    pub categories: Vec<CategorySuggestion>,
+    pub tags: Vec<TagSuggestion>,
+    /// Whether any memo word was seen in training; without one the
+    /// suggestions rest on amount, account and weekday alone
+    pub memo_recognized: bool,
+    /// Past transactions the suggestions were learned from
+    pub training_size: usize,
+}
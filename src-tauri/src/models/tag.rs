@@ -1,4 +1,6 @@
 // File: src-tauri/src/models/tag.rs
+use crate::models::currency::MissingRate;
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -35,7 +37,9 @@ pub struct TagSpending {
     pub tag_id: i64,
     pub tag_name: String,
     pub tag_color: String,
-    pub total_income: f64,
-    pub total_expense: f64,
+    pub total_income: Money,
+    pub total_expense: Money,
     pub transaction_count: i64,
+    pub currency: String,
+    pub missing_rates: Vec<MissingRate>,
 }
@@ -1,12 +1,13 @@
 // File: src-tauri/src/models/template.rs
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct TransactionTemplate {
     pub id: i64,
     pub name: String,
     pub transaction_type: String, // INCOME, EXPENSE, TRANSFER
-    pub amount: f64,
+    pub amount: Money,
     pub account_id: Option<i64>,
     pub to_account_id: Option<i64>,
     pub category_id: Option<i64>,
@@ -24,6 +25,8 @@ pub struct TransactionTemplateWithDetails {
     pub account_name: Option<String>,
     pub to_account_name: Option<String>,
     pub category_name: Option<String>,
+    /// Tags suggested by categorization rules when the template is used
+    pub tag_ids: Vec<i64>,
 }
 
 #[derive(Debug, Deserialize)]
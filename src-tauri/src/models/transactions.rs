@@ -1,20 +1,25 @@
 // File: src-tauri/src/models/transactions.rs
+use crate::models::currency::{CurrencyAmount, MissingRate};
 use crate::models::goal::{GoalAllocationInput, GoalWithdrawalInput};
 use crate::models::tag::TagInfo;
+use crate::models::money::Money;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct Transaction {
     pub id: i64,
     pub date: String,
     pub transaction_type: String, // INCOME, EXPENSE, TRANSFER
-    pub amount: f64,
+    pub amount: Money,
     pub account_id: i64,
     pub to_account_id: Option<i64>,
+    pub to_amount: Option<Money>, // Amount received by a transfer, in the destination currency
     pub category_id: Option<i64>,
+    pub payee_id: Option<i64>,
     pub memo: Option<String>,
     pub photo_path: Option<String>,
     pub created_at: String,
+    pub status: String, // UNCLEARED, CLEARED, RECONCILED on the source account
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,11 +29,15 @@ pub struct CreateTransactionInput {
     pub amount: f64,
     pub account_id: i64,
     pub to_account_id: Option<i64>,
+    pub to_amount: Option<f64>, // Required for transfers between currencies
     pub category_id: Option<i64>,
+    pub payee_id: Option<i64>,
+    pub payee: Option<String>, // Payee name or alias, resolved or created when payee_id is not given
     pub memo: Option<String>,
     pub tag_ids: Option<Vec<i64>>,
     pub goal_allocations: Option<Vec<GoalAllocationInput>>,   // For INCOME → allocate to goals
     pub goal_withdrawals: Option<Vec<GoalWithdrawalInput>>,   // For EXPENSE → reduce goals
+    pub splits: Option<Vec<TransactionSplitInput>>,           // Category lines, must sum to amount
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,9 +45,32 @@ pub struct UpdateTransactionInput {
     pub id: i64,
     pub date: Option<String>,
     pub amount: Option<f64>,
+    pub to_amount: Option<f64>, // Transfers only, in the destination currency
     pub category_id: Option<i64>,
+    pub payee_id: Option<i64>,
+    pub payee: Option<String>, // Resolved like on create; Some("") removes the payee
     pub memo: Option<String>,
     pub tag_ids: Option<Vec<i64>>,
+    pub splits: Option<Vec<TransactionSplitInput>>, // Some([]) removes existing splits
+}
+
+// ============ Split Models ============
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TransactionSplit {
+    pub id: i64,
+    pub transaction_id: i64,
+    pub category_id: i64,
+    pub category_name: Option<String>,
+    pub amount: Money,
+    pub memo: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TransactionSplitInput {
+    pub category_id: i64,
+    pub amount: f64,
+    pub memo: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -48,8 +80,11 @@ pub struct TransactionWithDetails {
     pub account_name: String,
     pub to_account_name: Option<String>,
     pub category_name: Option<String>,
+    pub payee_name: Option<String>,
     pub photo_count: i64,
     pub tags: Vec<TagInfo>,
+    pub splits: Vec<TransactionSplit>,
+    pub snippet: Option<String>, // Search hit with matches highlighted, when searching for words
 }
 
 #[derive(Debug, Serialize)]
@@ -57,51 +92,78 @@ pub struct JournalEntry {
     pub id: i64,
     pub transaction_id: i64,
     pub account_id: i64,
-    pub debit: f64,
-    pub credit: f64,
+    pub debit: Money,
+    pub credit: Money,
     pub created_at: String,
 }
 
 // ============ Filter Models ============
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct TransactionFilter {
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    pub date_range: Option<String>, // e.g. "last 3 months", used when no dates are given
+    pub saved_filter_id: Option<i64>, // Fills the fields left unset here
     pub transaction_type: Option<String>, // INCOME, EXPENSE, TRANSFER
     pub account_id: Option<i64>,
     pub category_id: Option<i64>,
+    pub payee_id: Option<i64>,
     pub search_query: Option<String>,
     pub include_subcategories: Option<bool>, // For category filtering
     pub tag_ids: Option<Vec<i64>>,
 }
 
+/// Position after the last transaction of a page; lists run newest first by date, then id
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransactionCursor {
+    pub date: String,
+    pub id: i64,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TransactionPage {
+    pub after: Option<TransactionCursor>, // None for the first page
+    pub limit: Option<i64>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct IncomeExpenseSummary {
-    pub total_income: f64,
-    pub total_expense: f64,
-    pub net_savings: f64,
+    pub total_income: Money,
+    pub total_expense: Money,
+    pub net_savings: Money,
     pub transaction_count: i64,
     pub start_date: String,
     pub end_date: String,
+    pub currency: String,
+    pub income_by_currency: Vec<CurrencyAmount>,
+    pub expense_by_currency: Vec<CurrencyAmount>,
+    pub missing_rates: Vec<MissingRate>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct CategorySpending {
     pub category_id: i64,
     pub category_name: String,
-    pub total_amount: f64,
+    pub total_amount: Money,
     pub transaction_count: i64,
     pub percentage: f64, // Of total spending
+    pub currency: String,
+    pub by_currency: Vec<CurrencyAmount>,
+    pub missing_rates: Vec<MissingRate>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct DailySummary {
     pub date: String,
-    pub total_income: f64,
-    pub total_expense: f64,
-    pub net: f64,
+    pub total_income: Money,
+    pub total_expense: Money,
+    pub net: Money,
     pub transaction_count: i64,
+    pub currency: String,
+    pub income_by_currency: Vec<CurrencyAmount>,
+    pub expense_by_currency: Vec<CurrencyAmount>,
+    pub missing_rates: Vec<MissingRate>,
 }
 
 // ============ NEW: Report Models ============
@@ -110,8 +172,12 @@ pub struct DailySummary {
 pub struct MonthlyTrend {
     pub month: String,      // e.g., "2026-01"
     pub month_name: String, // e.g., "January 2026"
-    pub income: f64,
-    pub expense: f64,
-    pub net: f64,
+    pub income: Money,
+    pub expense: Money,
+    pub net: Money,
     pub transaction_count: i64,
+    pub currency: String,
+    pub income_by_currency: Vec<CurrencyAmount>,
+    pub expense_by_currency: Vec<CurrencyAmount>,
+    pub missing_rates: Vec<MissingRate>,
 }
@@ -30,6 +30,7 @@ export default function BudgetForm({
     amount: "",
     period: "MONTHLY",
     start_date: new Date().toISOString().split("T")[0],
+    end_date: "",
   });
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -41,6 +42,7 @@ export default function BudgetForm({
         amount: budget.amount.toString(),
         period: budget.period,
         start_date: budget.start_date,
+        end_date: budget.end_date ?? "",
       });
     }
   }, [budget]);
@@ -62,6 +64,8 @@ export default function BudgetForm({
           id: budget.id,
           amount,
           start_date: formData.start_date,
+          end_date:
+            budget.period === "CUSTOM" ? formData.end_date : undefined,
         });
       } else {
         // Create new budget
@@ -74,6 +78,8 @@ export default function BudgetForm({
           amount,
           period: formData.period,
           start_date: formData.start_date,
+          end_date:
+            formData.period === "CUSTOM" ? formData.end_date : undefined,
         });
       }
     } catch (err) {
@@ -89,8 +95,12 @@ export default function BudgetForm({
   );
 
   const periodOptions = [
+    { value: "WEEKLY", label: "Weekly" },
+    { value: "BIWEEKLY", label: "Every 2 weeks" },
     { value: "MONTHLY", label: "Monthly" },
+    { value: "QUARTERLY", label: "Quarterly" },
     { value: "YEARLY", label: "Yearly" },
+    { value: "CUSTOM", label: "Custom range" },
   ];
 
   return (
@@ -172,6 +182,19 @@ export default function BudgetForm({
             required
           />
 
+          {formData.period === "CUSTOM" && (
+            <Input
+              label="End Date"
+              type="date"
+              value={formData.end_date}
+              min={formData.start_date}
+              onChange={(e) =>
+                setFormData({ ...formData, end_date: e.target.value })
+              }
+              required
+            />
+          )}
+
           <div className="flex gap-3 pt-4">
             <Button
               type="button"
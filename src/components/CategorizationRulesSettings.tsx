@@ -1,29 +1,30 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { Plus, Trash2, Edit2, Check, X } from "lucide-react";
+import { Plus, Trash2, Edit2, Check, X, RefreshCw } from "lucide-react";
 import Button from "./Button";
 import Input from "./Input";
 import Select from "./Select";
 import { useToast } from "./Toast";
+import type {
+  CategorizationRule,
+  RuleApplicationResult,
+} from "../types/advanced";
 
 interface Category {
   id: number;
   name: string;
 }
 
-export interface CategorizationRule {
-  id: string;
-  match_pattern: string;
-  match_type: string; // 'exact', 'contains', 'starts_with', 'regex'
-  category_id: string;
-  priority: number;
-  created_at: string;
-  updated_at: string;
+interface Tag {
+  id: number;
+  name: string;
 }
 
 export default function CategorizationRulesSettings() {
   const [rules, setRules] = useState<CategorizationRule[]>([]);
   const [categories, setCategories] = useState<Category[]>([]);
+  const [tags, setTags] = useState<Tag[]>([]);
+  const [preview, setPreview] = useState<RuleApplicationResult | null>(null);
   const [isLoading, setIsLoading] = useState(true);
   const [isEditing, setIsEditing] = useState<string | null>(null);
   const { success, error } = useToast();
@@ -33,12 +34,14 @@ export default function CategorizationRulesSettings() {
   const loadData = async () => {
     setIsLoading(true);
     try {
-      const [fetchedRules, fetchedCats] = await Promise.all([
+      const [fetchedRules, fetchedCats, fetchedTags] = await Promise.all([
         invoke<CategorizationRule[]>("get_categorization_rules"),
         invoke<{ id: number; name: string }[]>("get_categories"),
+        invoke<Tag[]>("get_tags"),
       ]);
       setRules(fetchedRules);
       setCategories(fetchedCats.map((c) => ({ id: c.id, name: c.name })));
+      setTags(fetchedTags.map((t) => ({ id: t.id, name: t.name })));
     } catch (err) {
       error(`Failed to load data: ${err}`);
     } finally {
@@ -58,13 +61,27 @@ export default function CategorizationRulesSettings() {
       match_type: "contains",
       category_id: categories.length > 0 ? categories[0].id.toString() : "",
       priority: 0,
+      min_amount: null,
+      max_amount: null,
+      transaction_type: null,
+      memo_rewrite: null,
+      tag_ids: [],
     });
     setIsEditing(newId);
   };
 
   const handleSave = async () => {
-    if (!editForm.match_pattern || !editForm.category_id) {
-      error("Match pattern and category are required.");
+    const hasCondition =
+      !!editForm.match_pattern ||
+      editForm.min_amount != null ||
+      editForm.max_amount != null ||
+      !!editForm.transaction_type;
+    const hasAction =
+      !!editForm.category_id ||
+      !!editForm.memo_rewrite ||
+      (editForm.tag_ids?.length ?? 0) > 0;
+    if (!hasCondition || !hasAction) {
+      error("A rule needs a condition and a category, tags or a memo to set.");
       return;
     }
 
@@ -95,17 +112,77 @@ export default function CategorizationRulesSettings() {
     }
   };
 
+  const handleRerun = async (dryRun: boolean) => {
+    try {
+      const result = await invoke<RuleApplicationResult>("rerun_categorization_rules", {
+        input: { dry_run: dryRun },
+      });
+      if (dryRun) {
+        setPreview(result);
+      } else {
+        setPreview(null);
+        success(`Rules applied to ${result.changes.length} transaction(s).`);
+      }
+    } catch (err) {
+      error(`Failed to run rules: ${err}`);
+    }
+  };
+
+  const categoryName = (id: string | number | null) =>
+    categories.find((c) => c.id.toString() === id?.toString())?.name || "Unknown";
+
   return (
     <div className="space-y-4">
       <div className="flex items-center justify-between">
         <p className="text-sm text-gray-500 dark:text-gray-400">
-          Automatically categorize imported transactions based on memo or description keywords.
+          Automatically categorize new and imported transactions by memo, amount and type.
         </p>
-        <Button onClick={handleCreateNew} size="sm" icon={<Plus className="w-4 h-4" />}>
-          Add Rule
-        </Button>
+        <div className="flex items-center gap-2">
+          <Button
+            onClick={() => handleRerun(true)}
+            size="sm"
+            variant="ghost"
+            icon={<RefreshCw className="w-4 h-4" />}
+          >
+            Re-run on History
+          </Button>
+          <Button onClick={handleCreateNew} size="sm" icon={<Plus className="w-4 h-4" />}>
+            Add Rule
+          </Button>
+        </div>
       </div>
 
+      {preview && (
+        <div className="p-4 bg-gray-50 dark:bg-gray-800/50 rounded-xl border border-gray-200 dark:border-gray-700 space-y-3">
+          <p className="text-sm text-gray-700 dark:text-gray-300">
+            {preview.changes.length} of {preview.scanned} uncategorized transaction(s) would change
+            {preview.locked > 0 && ` (${preview.locked} reconciled and skipped)`}.
+          </p>
+          {preview.changes.length > 0 && (
+            <ul className="max-h-48 overflow-y-auto text-xs text-gray-600 dark:text-gray-400 space-y-1">
+              {preview.changes.map((c) => (
+                <li key={c.transaction_id}>
+                  {c.date} · {c.memo || "(no memo)"}
+                  {c.new_category_name && ` → ${c.new_category_name}`}
+                  {c.new_memo && ` · memo "${c.new_memo}"`}
+                  {c.added_tag_ids.length > 0 && ` · +${c.added_tag_ids.length} tag(s)`}
+                </li>
+              ))}
+            </ul>
+          )}
+          <div className="flex justify-end gap-2">
+            <Button variant="ghost" size="sm" onClick={() => setPreview(null)}>
+              Close
+            </Button>
+            {preview.changes.length > 0 && (
+              <Button size="sm" onClick={() => handleRerun(false)}>
+                Apply Changes
+              </Button>
+            )}
+          </div>
+        </div>
+      )}
+
       {isLoading ? (
         <div className="animate-pulse flex flex-col space-y-4">
           <div className="h-10 bg-gray-200 dark:bg-gray-700 rounded-lg w-full"></div>
@@ -123,6 +200,7 @@ export default function CategorizationRulesSettings() {
             <RuleEditor
               form={editForm}
               categories={categories}
+              tags={tags}
               onChange={setEditForm}
               onSave={handleSave}
               onCancel={() => setIsEditing(null)}
@@ -135,6 +213,7 @@ export default function CategorizationRulesSettings() {
                 key={rule.id}
                 form={editForm}
                 categories={categories}
+                tags={tags}
                 onChange={setEditForm}
                 onSave={handleSave}
                 onCancel={() => setIsEditing(null)}
@@ -150,10 +229,16 @@ export default function CategorizationRulesSettings() {
                   </div>
                   <div>
                     <p className="text-sm font-medium text-gray-900 dark:text-white">
-                      "{rule.match_pattern}"
+                      {rule.match_pattern ? `"${rule.match_pattern}"` : "Any memo"}
+                      {rule.transaction_type && ` · ${rule.transaction_type}`}
+                      {(rule.min_amount != null || rule.max_amount != null) &&
+                        ` · ${rule.min_amount ?? "0"}–${rule.max_amount ?? "∞"}`}
                     </p>
                     <p className="text-xs text-gray-500 dark:text-gray-400">
-                      → {categories.find((c) => c.id.toString() === rule.category_id)?.name || "Unknown"}
+                      → {rule.category_id ? categoryName(rule.category_id) : "No category"}
+                      {rule.memo_rewrite && ` · memo "${rule.memo_rewrite}"`}
+                      {rule.tag_ids.length > 0 &&
+                        ` · ${rule.tag_ids.map((id) => tags.find((t) => t.id === id)?.name).filter(Boolean).join(", ")}`}
                     </p>
                   </div>
                 </div>
@@ -187,12 +272,14 @@ export default function CategorizationRulesSettings() {
 function RuleEditor({
   form,
   categories,
+  tags,
   onChange,
   onSave,
   onCancel,
 }: {
   form: Partial<CategorizationRule>;
   categories: Category[];
+  tags: Tag[];
   onChange: (f: Partial<CategorizationRule>) => void;
   onSave: () => void;
   onCancel: () => void;
@@ -226,8 +313,62 @@ function RuleEditor({
           <label className="block text-xs font-medium text-gray-700 dark:text-gray-300 mb-1">Assign Category</label>
           <Select
             value={form.category_id || ""}
-            onChange={(e) => onChange({ ...form, category_id: e.target.value })}
-            options={categories.map((c) => ({ value: c.id.toString(), label: c.name }))}
+            onChange={(e) => onChange({ ...form, category_id: e.target.value || null })}
+            options={[
+              { value: "", label: "Don't change" },
+              ...categories.map((c) => ({ value: c.id.toString(), label: c.name })),
+            ]}
+          />
+        </div>
+        <div>
+          <label className="block text-xs font-medium text-gray-700 dark:text-gray-300 mb-1">Transaction Type</label>
+          <Select
+            value={form.transaction_type || ""}
+            onChange={(e) =>
+              onChange({
+                ...form,
+                transaction_type: (e.target.value || null) as CategorizationRule["transaction_type"],
+              })
+            }
+            options={[
+              { value: "", label: "Any" },
+              { value: "EXPENSE", label: "Expense" },
+              { value: "INCOME", label: "Income" },
+              { value: "TRANSFER", label: "Transfer" },
+            ]}
+          />
+        </div>
+        <div className="grid grid-cols-2 gap-2">
+          <div>
+            <label className="block text-xs font-medium text-gray-700 dark:text-gray-300 mb-1">Min Amount</label>
+            <Input
+              type="number"
+              value={form.min_amount?.toString() ?? ""}
+              onChange={(e) =>
+                onChange({ ...form, min_amount: e.target.value === "" ? null : parseFloat(e.target.value) })
+              }
+              className="w-full"
+            />
+          </div>
+          <div>
+            <label className="block text-xs font-medium text-gray-700 dark:text-gray-300 mb-1">Max Amount</label>
+            <Input
+              type="number"
+              value={form.max_amount?.toString() ?? ""}
+              onChange={(e) =>
+                onChange({ ...form, max_amount: e.target.value === "" ? null : parseFloat(e.target.value) })
+              }
+              className="w-full"
+            />
+          </div>
+        </div>
+        <div>
+          <label className="block text-xs font-medium text-gray-700 dark:text-gray-300 mb-1">Rewrite Memo</label>
+          <Input
+            value={form.memo_rewrite || ""}
+            onChange={(e) => onChange({ ...form, memo_rewrite: e.target.value || null })}
+            placeholder="e.g. Uber $1"
+            className="w-full"
           />
         </div>
         <div>
@@ -240,6 +381,37 @@ function RuleEditor({
           />
         </div>
       </div>
+      {tags.length > 0 && (
+        <div>
+          <label className="block text-xs font-medium text-gray-700 dark:text-gray-300 mb-1">Add Tags</label>
+          <div className="flex flex-wrap gap-2">
+            {tags.map((tag) => {
+              const selected = form.tag_ids?.includes(tag.id) ?? false;
+              return (
+                <button
+                  key={tag.id}
+                  type="button"
+                  onClick={() =>
+                    onChange({
+                      ...form,
+                      tag_ids: selected
+                        ? (form.tag_ids ?? []).filter((id) => id !== tag.id)
+                        : [...(form.tag_ids ?? []), tag.id],
+                    })
+                  }
+                  className={`px-2.5 py-1 text-xs rounded-full border transition-colors ${
+                    selected
+                      ? "bg-amber-100 border-amber-300 text-amber-800 dark:bg-amber-900/30 dark:border-amber-700 dark:text-amber-300"
+                      : "border-gray-200 text-gray-600 dark:border-gray-700 dark:text-gray-400"
+                  }`}
+                >
+                  {tag.name}
+                </button>
+              );
+            })}
+          </div>
+        </div>
+      )}
       <div className="flex items-center justify-end gap-2 pt-2 border-t border-amber-200/50 dark:border-amber-800/50">
         <Button variant="ghost" size="sm" onClick={onCancel} icon={<X className="w-4 h-4" />}>
           Cancel
@@ -1,13 +1,13 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { save } from "@tauri-apps/plugin-dialog";
-import { writeTextFile, writeFile } from "@tauri-apps/plugin-fs";
 import { X, Download, Save, Trash2, Check, AlertTriangle } from "lucide-react";
 import Button from "./Button";
 import Input from "./Input";
 import Select from "./Select";
 import { useToast } from "./Toast";
 import type { ExportTemplate } from "../types/advanced";
+import type { SavedFilter } from "../types/savedFilter";
 
 interface ExportFilter {
   start_date: string | null;
@@ -15,6 +15,7 @@ interface ExportFilter {
   transaction_type: string | null;
   account_id: number | null;
   category_id: number | null;
+  saved_filter_id: number | null;
   columns: string[] | null;
   include_pie_chart: boolean | null;
   include_histogram: boolean | null;
@@ -55,6 +56,7 @@ export default function ExportModal({ onClose }: { onClose: () => void }) {
   const [transactionType, setTransactionType] = useState("ALL");
   const [accountId, setAccountId] = useState("");
   const [categoryId, setCategoryId] = useState("");
+  const [savedFilterId, setSavedFilterId] = useState("");
   
   // Charts
   const [includePieChart, setIncludePieChart] = useState(false);
@@ -72,6 +74,7 @@ export default function ExportModal({ onClose }: { onClose: () => void }) {
   // Options
   const [accounts, setAccounts] = useState<{ id: number; name: string }[]>([]);
   const [categories, setCategories] = useState<{ id: number; name: string }[]>([]);
+  const [savedFilters, setSavedFilters] = useState<SavedFilter[]>([]);
 
   useEffect(() => {
     loadData();
@@ -79,14 +82,16 @@ export default function ExportModal({ onClose }: { onClose: () => void }) {
 
   const loadData = async () => {
     try {
-      const [accs, cats, tmpls] = await Promise.all([
+      const [accs, cats, tmpls, views] = await Promise.all([
         invoke<{ id: number; name: string }[]>("get_accounts"),
         invoke<{ id: number; name: string }[]>("get_categories"),
         invoke<ExportTemplate[]>("get_export_templates"),
+        invoke<SavedFilter[]>("get_saved_filters"),
       ]);
       setAccounts(accs);
       setCategories(cats);
       setTemplates(tmpls);
+      setSavedFilters(views);
     } catch (err) {
       error(`Failed to load data: ${err}`);
     }
@@ -114,6 +119,7 @@ export default function ExportModal({ onClose }: { onClose: () => void }) {
         setTransactionType(filters.transaction_type || "ALL");
         setAccountId(filters.account_id ? filters.account_id.toString() : "");
         setCategoryId(filters.category_id ? filters.category_id.toString() : "");
+        setSavedFilterId(tmpl.saved_filter_id ? tmpl.saved_filter_id.toString() : "");
         setIncludePieChart(filters.include_pie_chart || false);
         setIncludeHistogram(filters.include_histogram || false);
         
@@ -177,6 +183,7 @@ export default function ExportModal({ onClose }: { onClose: () => void }) {
           name: newTemplateName,
           columns: JSON.stringify(selectedColumns),
           filters: JSON.stringify(filters),
+          saved_filter_id: savedFilterId ? parseInt(savedFilterId) : null,
           format: format,
         }
       });
@@ -219,6 +226,7 @@ export default function ExportModal({ onClose }: { onClose: () => void }) {
         transaction_type: transactionType === "ALL" ? null : transactionType,
         account_id: accountId ? parseInt(accountId) : null,
         category_id: categoryId ? parseInt(categoryId) : null,
+        saved_filter_id: savedFilterId ? parseInt(savedFilterId) : null,
         columns: selectedColumns,
         include_pie_chart: format === "excel" ? includePieChart : false,
         include_histogram: format === "excel" ? includeHistogram : false,
@@ -239,16 +247,7 @@ export default function ExportModal({ onClose }: { onClose: () => void }) {
         return;
       }
 
-      if (format === "csv") {
-        const content = await invoke<string>("export_transactions_csv", { filter });
-        await writeTextFile(savePath, content);
-      } else if (format === "json") {
-        const content = await invoke<string>("export_transactions_json", { filter });
-        await writeTextFile(savePath, content);
-      } else if (format === "excel") {
-        const content = await invoke<Uint8Array>("export_transactions_excel", { filter });
-        await writeFile(savePath, content);
-      }
+      await invoke("export_transactions_to_file", { filter, format, path: savePath });
 
       success("Export Complete", `Saved to ${savePath.split(/[/\\]/).pop()}`);
       onClose();
@@ -353,6 +352,20 @@ export default function ExportModal({ onClose }: { onClose: () => void }) {
                   ]}
                 />
               </div>
+
+              {savedFilters.length > 0 && (
+                <div>
+                  <label className="block text-xs font-medium text-gray-700 dark:text-gray-300 mb-1">Saved View</label>
+                  <Select
+                    value={savedFilterId}
+                    onChange={e => setSavedFilterId(e.target.value)}
+                    options={[
+                      { value: "", label: "None" },
+                      ...savedFilters.map(v => ({ value: v.id.toString(), label: v.name }))
+                    ]}
+                  />
+                </div>
+              )}
             </div>
             
             {/* Right Column: Columns & Format */}
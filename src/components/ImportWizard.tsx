@@ -16,6 +16,7 @@ const TYPE_HEADERS = ["type", "transaction type", "kind", "category type"];
 const CATEGORY_HEADERS = ["category", "group", "class", "tag"];
 const MEMO_HEADERS = ["memo", "description", "note", "notes", "details", "narration", "reference", "particulars"];
 const ACCOUNT_HEADERS = ["account", "account name", "bank", "source"];
+const PAYEE_HEADERS = ["payee", "merchant", "vendor", "payee name", "merchant name"];
 
 function autoDetectColumn(headers: string[], knownNames: string[]): number | null {
   for (let i = 0; i < headers.length; i++) {
@@ -45,6 +46,7 @@ export default function ImportWizard({ onClose, onComplete }: { onClose: () => v
     account_col: null,
     category_col: null,
     memo_col: null,
+    payee_col: null,
     date_format: "YYYY-MM-DD",
     negative_as_expense: true,
   });
@@ -89,6 +91,7 @@ export default function ImportWizard({ onClose, onComplete }: { onClose: () => v
       const detectedCategory = autoDetectColumn(headers, CATEGORY_HEADERS);
       const detectedMemo = autoDetectColumn(headers, MEMO_HEADERS);
       const detectedAccount = autoDetectColumn(headers, ACCOUNT_HEADERS);
+      const detectedPayee = autoDetectColumn(headers, PAYEE_HEADERS);
 
       setMapping(prev => ({
         ...prev,
@@ -99,6 +102,7 @@ export default function ImportWizard({ onClose, onComplete }: { onClose: () => v
         category_col: detectedCategory ?? null,
         memo_col: detectedMemo ?? null,
         account_col: detectedAccount ?? null,
+        payee_col: detectedPayee ?? null,
       }));
     } catch (err) {
       console.error("Failed to parse CSV:", err);
@@ -406,6 +410,7 @@ function StepMapping({ headers, sampleRows, mapping, onMappingChange }: {
     { key: "account_col", label: "Account", required: false, icon: "🏦" },
     { key: "category_col", label: "Category", required: false, icon: "📂" },
     { key: "memo_col", label: "Memo / Description", required: false, icon: "📝" },
+    { key: "payee_col", label: "Payee / Merchant", required: false, icon: "🏪" },
   ];
 
   return (
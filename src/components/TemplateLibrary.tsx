@@ -118,8 +118,11 @@ export default function TemplateLibrary({
 
   const handleUse = async (template: TransactionTemplateWithDetails) => {
     try {
-      await invoke("use_template", { templateId: template.id });
-      onUseTemplate(template);
+      // Uncategorized templates come back with suggestions from the categorization rules
+      const applied = await invoke<TransactionTemplateWithDetails>("use_template", {
+        templateId: template.id,
+      });
+      onUseTemplate(applied);
       success(
         "Template Applied",
         `"${template.name}" loaded into transaction form.`,
@@ -4,6 +4,8 @@ import {
   MagnifyingGlassIcon,
   FunnelIcon,
   XMarkIcon,
+  BookmarkIcon,
+  TrashIcon,
 } from "@heroicons/react/24/outline";
 import Select from "./Select";
 import CascadingCategorySelect from "./CascadingCategorySelect";
@@ -14,6 +16,7 @@ import type { TransactionFilter } from "../types/transaction";
 import type { AccountWithBalance } from "../types/account";
 import type { CategoryWithChildren } from "../types/category";
 import type { Tag } from "../types/tag";
+import type { SavedFilter } from "../types/savedFilter";
 
 interface TransactionFilterBarProps {
   accounts: AccountWithBalance[];
@@ -23,6 +26,13 @@ interface TransactionFilterBarProps {
 
 type DatePreset = "all" | "today" | "this_week" | "this_month" | "custom";
 
+// Presets saved with a view as date range expressions, so they stay relative
+const PRESET_RANGES: Partial<Record<DatePreset, string>> = {
+  today: "today",
+  this_week: "this week",
+  this_month: "this month",
+};
+
 export default function TransactionFilterBar({
   accounts,
   categories,
@@ -38,10 +48,13 @@ export default function TransactionFilterBar({
   const [tagIds, setTagIds] = useState<number[]>([]);
   const [allTags, setAllTags] = useState<Tag[]>([]);
   const [showAdvanced, setShowAdvanced] = useState(false);
+  const [views, setViews] = useState<SavedFilter[]>([]);
+  const [viewId, setViewId] = useState<string>("");
 
-  // Load tags on mount
+  // Load tags and saved views on mount
   useEffect(() => {
     invoke<Tag[]>("get_tags").then(setAllTags).catch(console.error);
+    invoke<SavedFilter[]>("get_saved_filters").then(setViews).catch(console.error);
   }, []);
 
   const buildFilter = (
@@ -54,6 +67,7 @@ export default function TransactionFilterBar({
       accountId: string;
       categoryId: string;
       tagIds: number[];
+      viewId: string;
     }> = {},
   ): TransactionFilter => {
     const s = overrides.search ?? search;
@@ -64,8 +78,11 @@ export default function TransactionFilterBar({
     const a = overrides.accountId ?? accountId;
     const c = overrides.categoryId ?? categoryId;
     const tags = overrides.tagIds ?? tagIds;
+    const v = overrides.viewId ?? viewId;
 
+    // With a view selected, "All Time" leaves its own date range in effect
     const filter: TransactionFilter = {};
+    if (v) filter.saved_filter_id = Number(v);
 
     // Date range
     const now = new Date();
@@ -149,6 +166,45 @@ export default function TransactionFilterBar({
     applyFilter({ tagIds: ids });
   };
 
+  const handleViewChange = (value: string) => {
+    setViewId(value);
+    setDatePreset("all");
+    applyFilter({ viewId: value, datePreset: "all" });
+  };
+
+  const handleSaveView = async () => {
+    const name = window.prompt("Name this view");
+    if (!name?.trim()) return;
+
+    const filter = buildFilter({ viewId: "" });
+    const range = PRESET_RANGES[datePreset];
+    if (range) {
+      delete filter.start_date;
+      delete filter.end_date;
+      filter.date_range = range;
+    }
+    try {
+      const view = await invoke<SavedFilter>("create_saved_filter", {
+        input: { name: name.trim(), filter },
+      });
+      setViews([...views, view].sort((a, b) => a.name.localeCompare(b.name)));
+    } catch (err) {
+      window.alert(`Failed to save view: ${err}`);
+    }
+  };
+
+  const handleDeleteView = async () => {
+    const view = views.find((v) => v.id.toString() === viewId);
+    if (!view || !confirm(`Delete the view "${view.name}"?`)) return;
+    try {
+      await invoke("delete_saved_filter", { id: view.id });
+      setViews(views.filter((v) => v.id !== view.id));
+      handleViewChange("");
+    } catch (err) {
+      window.alert(`Failed to delete view: ${err}`);
+    }
+  };
+
   const handleCustomDateChange = (start: string, end: string) => {
     setCustomStart(start);
     setCustomEnd(end);
@@ -164,6 +220,7 @@ export default function TransactionFilterBar({
     setAccountId("");
     setCategoryId("");
     setTagIds([]);
+    setViewId("");
     setShowAdvanced(false);
     onFilterChange(
       buildFilter({
@@ -175,12 +232,18 @@ export default function TransactionFilterBar({
         accountId: "",
         categoryId: "",
         tagIds: [],
+        viewId: "",
       }),
     );
   };
 
   const hasActiveFilters =
-    type !== "" || accountId !== "" || categoryId !== "" || tagIds.length > 0 || search !== "";
+    type !== "" ||
+    accountId !== "" ||
+    categoryId !== "" ||
+    tagIds.length > 0 ||
+    search !== "" ||
+    viewId !== "";
 
   const accountOptions = [
     { value: "", label: "All Accounts" },
@@ -208,7 +271,7 @@ export default function TransactionFilterBar({
             type="text"
             value={search}
             onChange={(e) => handleSearchChange(e.target.value)}
-            placeholder="Search by memo or amount..."
+            placeholder='Search, e.g. keells amount>5000 tag:travel -category:Rent'
             className="w-full pl-9 pr-3 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white placeholder-gray-400 dark:placeholder-gray-500 focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent"
           />
         </div>
@@ -237,6 +300,42 @@ export default function TransactionFilterBar({
             </button>
           ))}
 
+          {/* Saved views */}
+          {views.length > 0 && (
+            <select
+              value={viewId}
+              onChange={(e) => handleViewChange(e.target.value)}
+              className="px-2 py-1.5 text-xs font-medium rounded-lg border border-gray-300 dark:border-gray-600 bg-white dark:bg-gray-800 text-gray-700 dark:text-gray-300 focus:outline-none focus:ring-2 focus:ring-blue-500"
+              title="Saved views"
+            >
+              <option value="">All transactions</option>
+              {views.map((view) => (
+                <option key={view.id} value={view.id}>
+                  {view.name}
+                </option>
+              ))}
+            </select>
+          )}
+          {viewId ? (
+            <button
+              onClick={handleDeleteView}
+              className="p-1.5 rounded-lg bg-gray-100 text-gray-500 dark:bg-gray-700 dark:text-gray-400 hover:bg-gray-200 dark:hover:bg-gray-600 transition-colors"
+              title="Delete this view"
+            >
+              <TrashIcon className="h-4 w-4" />
+            </button>
+          ) : (
+            hasActiveFilters && (
+              <button
+                onClick={handleSaveView}
+                className="p-1.5 rounded-lg bg-gray-100 text-gray-500 dark:bg-gray-700 dark:text-gray-400 hover:bg-gray-200 dark:hover:bg-gray-600 transition-colors"
+                title="Save these filters as a view"
+              >
+                <BookmarkIcon className="h-4 w-4" />
+              </button>
+            )
+          )}
+
           {/* Toggle advanced filters */}
           <button
             onClick={() => setShowAdvanced(!showAdvanced)}
@@ -9,10 +9,14 @@ import Calculator from "./Calculator";
 import { PhotoPicker } from "./PhotoAttachment";
 import TagPicker from "./TagPicker";
 import { invoke } from "@tauri-apps/api/core";
-import type { CreateTransactionInput } from "../types/transaction";
+import type {
+  CreateTransactionInput,
+  TransactionSuggestions,
+} from "../types/transaction";
 import type { AccountWithBalance } from "../types/account";
 import type { CategoryWithChildren } from "../types/category";
 import type { Tag } from "../types/tag";
+import type { Payee } from "../types/payee";
 
 interface GoalAllocationSummary {
   goal_id: number;
@@ -47,6 +51,7 @@ interface TransactionFormProps {
     to_account_id?: number;
     amount?: number;
     memo?: string;
+    tag_ids?: number[];
   } | null;
 }
 
@@ -67,8 +72,10 @@ export default function TransactionForm({
     account_id: accounts[0]?.id || 0,
     to_account_id: accounts[1]?.id || 0,
     category_id: 0,
+    payee: "",
     memo: "",
   });
+  const [toAmount, setToAmount] = useState("");
   const [pendingPhotoPaths, setPendingPhotoPaths] = useState<string[]>([]);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -76,6 +83,8 @@ export default function TransactionForm({
   const [showQuickCategory, setShowQuickCategory] = useState(false);
   const [allTags, setAllTags] = useState<Tag[]>([]);
   const [selectedTagIds, setSelectedTagIds] = useState<number[]>([]);
+  const [payees, setPayees] = useState<Payee[]>([]);
+  const [suggestions, setSuggestions] = useState<TransactionSuggestions | null>(null);
 
   // Virtual Envelope state
   const [accountGoalSummary, setAccountGoalSummary] = useState<AccountGoalSummary | null>(null);
@@ -87,6 +96,7 @@ export default function TransactionForm({
   // Load tags on mount
   useEffect(() => {
     invoke<Tag[]>("get_tags").then(setAllTags).catch(console.error);
+    invoke<Payee[]>("get_payees").then(setPayees).catch(console.error);
   }, []);
 
   // React to prefillData changes (template usage or initial load)
@@ -106,8 +116,10 @@ export default function TransactionForm({
           0,
         to_account_id: prefillData.to_account_id || accounts[1]?.id || 0,
         category_id: prefillData.category_id || 0,
+        payee: "",
         memo: prefillData.memo || "",
       }));
+      setSelectedTagIds(prefillData.tag_ids ?? []);
     } else {
       // Only set these if they aren't set yet (initial load)
       setFormData((prev) => {
@@ -121,6 +133,38 @@ export default function TransactionForm({
     }
   }, [prefillData]); // Only re-run when prefillData changes
 
+  // Learned category and tag suggestions for the draft, once there is a memo to go on
+  useEffect(() => {
+    if (!formData.memo.trim()) {
+      setSuggestions(null);
+      return;
+    }
+    const timer = setTimeout(() => {
+      invoke<TransactionSuggestions>("suggest_transaction_details", {
+        input: {
+          transaction_type: type,
+          memo: formData.memo,
+          amount: parseFloat(formData.amount) || undefined,
+          account_id: formData.account_id || undefined,
+          date: formData.date,
+        },
+      })
+        .then((result) => setSuggestions(result.memo_recognized ? result : null))
+        .catch(console.error);
+    }, 300);
+    return () => clearTimeout(timer);
+  }, [type, formData.memo, formData.amount, formData.account_id, formData.date]);
+
+  const categorySuggestions =
+    type !== "TRANSFER"
+      ? (suggestions?.categories ?? []).filter(
+          (s) => s.category_id !== formData.category_id && s.confidence >= 0.2,
+        )
+      : [];
+  const tagSuggestions = (suggestions?.tags ?? []).filter(
+    (s) => !selectedTagIds.includes(s.tag_id),
+  );
+
   // Fetch goal summary when account or type changes
   useEffect(() => {
     const fetchGoalSummary = async () => {
@@ -174,6 +218,12 @@ export default function TransactionForm({
     0,
   );
 
+  // Transfers between currencies record the amount received as well
+  const fromCurrency = accounts.find((a) => a.id === formData.account_id)?.currency;
+  const toCurrency = accounts.find((a) => a.id === formData.to_account_id)?.currency;
+  const isCrossCurrency =
+    type === "TRANSFER" && !!fromCurrency && !!toCurrency && fromCurrency !== toCurrency;
+
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
     setLoading(true);
@@ -198,10 +248,12 @@ export default function TransactionForm({
         amount,
         account_id: formData.account_id,
         to_account_id: type === "TRANSFER" ? formData.to_account_id : null,
+        to_amount: isCrossCurrency ? parseFloat(toAmount) : null,
         category_id:
           type !== "TRANSFER" && formData.category_id
             ? formData.category_id
             : null,
+        payee: formData.payee.trim() || null,
         memo: formData.memo || null,
         tag_ids: selectedTagIds.length > 0 ? selectedTagIds : undefined,
       };
@@ -237,6 +289,10 @@ export default function TransactionForm({
         throw new Error("Cannot transfer to the same account");
       }
 
+      if (isCrossCurrency && !(input.to_amount && input.to_amount > 0)) {
+        throw new Error(`Please enter the amount received in ${toCurrency}`);
+      }
+
       if (type !== "TRANSFER" && !input.category_id) {
         throw new Error("Please select a category");
       }
@@ -257,8 +313,10 @@ export default function TransactionForm({
         account_id: accounts[0]?.id || 0,
         to_account_id: accounts[1]?.id || 0,
         category_id: 0,
+        payee: "",
         memo: "",
       });
+      setToAmount("");
       setPendingPhotoPaths([]);
       setSelectedTagIds([]);
       setGoalAllocations({});
@@ -276,6 +334,25 @@ export default function TransactionForm({
     setFormData({ ...formData, amount: value });
   };
 
+  // A known payee fills in its default category and tags
+  const handlePayeeChange = (value: string) => {
+    const name = value.trim().toLowerCase();
+    const payee = payees.find(
+      (p) => p.name.toLowerCase() === name || p.aliases.some((a) => a.toLowerCase() === name),
+    );
+    setFormData({
+      ...formData,
+      payee: value,
+      category_id:
+        payee?.default_category_id && !formData.category_id
+          ? payee.default_category_id
+          : formData.category_id,
+    });
+    if (payee && payee.tag_ids.length > 0) {
+      setSelectedTagIds((prev) => [...prev, ...payee.tag_ids.filter((id) => !prev.includes(id))]);
+    }
+  };
+
   // Filter categories by type
   const filteredCategories =
     type !== "TRANSFER"
@@ -399,6 +476,17 @@ export default function TransactionForm({
                 options={accountOptions}
                 required
               />
+              {isCrossCurrency && (
+                <Input
+                  label={`Amount Received (${toCurrency})`}
+                  type="number"
+                  step="0.01"
+                  min="0"
+                  value={toAmount}
+                  onChange={(e) => setToAmount(e.target.value)}
+                  required
+                />
+              )}
             </>
           ) : (
             <>
@@ -598,6 +686,25 @@ export default function TransactionForm({
           </div>
         )}
 
+        {/* Payee */}
+        {type !== "TRANSFER" && (
+          <div>
+            <Input
+              label="Payee (Optional)"
+              type="text"
+              list="payee-options"
+              value={formData.payee}
+              onChange={(e) => handlePayeeChange(e.target.value)}
+              placeholder="Who was paid, or who paid you"
+            />
+            <datalist id="payee-options">
+              {payees.map((p) => (
+                <option key={p.id} value={p.name} />
+              ))}
+            </datalist>
+          </div>
+        )}
+
         {/* Memo */}
         <div>
           <label className="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
@@ -610,6 +717,33 @@ export default function TransactionForm({
             className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-800 text-gray-900 dark:text-white placeholder-gray-400 dark:placeholder-gray-500 focus:outline-none focus:ring-2 focus:ring-blue-500"
             placeholder="Add a note about this transaction..."
           />
+          {(categorySuggestions.length > 0 || tagSuggestions.length > 0) && (
+            <div className="flex flex-wrap items-center gap-2 mt-2">
+              <span className="text-xs text-gray-500 dark:text-gray-400">Suggested:</span>
+              {categorySuggestions.map((s) => (
+                <button
+                  key={`category-${s.category_id}`}
+                  type="button"
+                  onClick={() => setFormData({ ...formData, category_id: s.category_id })}
+                  className="px-2.5 py-1 text-xs rounded-full border border-blue-200 dark:border-blue-800 text-blue-700 dark:text-blue-300 hover:bg-blue-50 dark:hover:bg-blue-900/20"
+                  title={`${Math.round(s.confidence * 100)}% confidence`}
+                >
+                  {s.category_name}
+                </button>
+              ))}
+              {tagSuggestions.map((s) => (
+                <button
+                  key={`tag-${s.tag_id}`}
+                  type="button"
+                  onClick={() => setSelectedTagIds([...selectedTagIds, s.tag_id])}
+                  className="px-2.5 py-1 text-xs rounded-full border border-gray-200 dark:border-gray-700 text-gray-600 dark:text-gray-300 hover:bg-gray-50 dark:hover:bg-gray-800"
+                  title={`${Math.round(s.confidence * 100)}% confidence`}
+                >
+                  #{s.tag_name}
+                </button>
+              ))}
+            </div>
+          )}
         </div>
 
         {/* Receipt Photos */}
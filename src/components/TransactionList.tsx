@@ -16,6 +16,23 @@ import type { TransactionWithDetails } from "../types/transaction";
 
 const PAGE_SIZE = 25;
 
+// Renders a search snippet, highlighting the parts wrapped in <mark> tags
+function SearchSnippet({ snippet }: { snippet: string }) {
+  return (
+    <>
+      {snippet.split(/(<mark>.*?<\/mark>)/g).map((part, i) =>
+        part.startsWith("<mark>") ? (
+          <mark key={i} className="bg-yellow-200 dark:bg-yellow-700 dark:text-white rounded-sm">
+            {part.slice(6, -7)}
+          </mark>
+        ) : (
+          part
+        )
+      )}
+    </>
+  );
+}
+
 interface TransactionListProps {
   transactions: TransactionWithDetails[];
   onEdit: (transaction: TransactionWithDetails) => void;
@@ -166,7 +183,18 @@ export default function TransactionList({
                     {txn.amount.toFixed(2)}
                   </td>
                   <td className="px-6 py-4 text-sm text-gray-600 dark:text-gray-400 max-w-xs">
-                    <span className="truncate block">{txn.memo || "-"}</span>
+                    {txn.payee_name && (
+                      <span className="truncate block font-medium text-gray-900 dark:text-white">
+                        {txn.payee_name}
+                      </span>
+                    )}
+                    <span className="truncate block">
+                      {txn.snippet ? (
+                        <SearchSnippet snippet={txn.snippet} />
+                      ) : (
+                        txn.memo || (txn.payee_name ? "" : "-")
+                      )}
+                    </span>
                   </td>
                   <td className="px-6 py-4 whitespace-nowrap text-right text-sm">
                     <div className="flex items-center justify-end gap-2">
@@ -268,9 +296,14 @@ export default function TransactionList({
                   ))}
                 </div>
               )}
-              {txn.memo && (
+              {txn.payee_name && (
+                <p className="text-sm font-medium text-gray-900 dark:text-white mb-1">
+                  {txn.payee_name}
+                </p>
+              )}
+              {(txn.snippet || txn.memo) && (
                 <p className="text-sm text-gray-600 dark:text-gray-400 mb-2">
-                  {txn.memo}
+                  {txn.snippet ? <SearchSnippet snippet={txn.snippet} /> : txn.memo}
                 </p>
               )}
               <div className="flex items-center gap-2">
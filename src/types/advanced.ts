@@ -2,17 +2,53 @@ export interface CategorizationRule {
   id: string;
   match_pattern: string;
   match_type: string; // 'exact', 'contains', 'starts_with', 'regex'
-  category_id: string;
+  category_id: string | null;
   priority: number;
+  min_amount: number | null;
+  max_amount: number | null;
+  account_id: number | null;
+  transaction_type: "INCOME" | "EXPENSE" | "TRANSFER" | null;
+  /** Replacement memo; regex rules may use $1-style capture references */
+  memo_rewrite: string | null;
+  tag_ids: number[];
   created_at: string;
   updated_at: string;
 }
 
+export interface ApplyRulesInput {
+  start_date?: string;
+  end_date?: string;
+  account_id?: number;
+  include_categorized?: boolean;
+  dry_run: boolean;
+}
+
+export interface RuleChange {
+  transaction_id: number;
+  date: string;
+  amount: number;
+  memo: string | null;
+  rule_ids: string[];
+  old_category_id: number | null;
+  new_category_id: number | null;
+  new_category_name: string | null;
+  new_memo: string | null;
+  added_tag_ids: number[];
+}
+
+export interface RuleApplicationResult {
+  dry_run: boolean;
+  scanned: number;
+  locked: number;
+  changes: RuleChange[];
+}
+
 export interface ExportTemplate {
   id: string;
   name: string;
   columns: string; // JSON array of column names
   filters: string; // JSON object representing filters
+  saved_filter_id: number | null;
   format: string; // 'csv' or 'json'
   created_at: string;
   updated_at: string;
@@ -1,10 +1,23 @@
 // File: src/types/analytics.ts
+import type { MissingRate } from "./currency";
+
 export interface NetWorthSummary {
   assets: number;
   liabilities: number;
   net_worth: number;
   change_amount: number;
   change_percentage: number;
+  currency: string;
+  by_currency: CurrencyNetWorth[];
+  missing_rates: MissingRate[];
+}
+
+export interface CurrencyNetWorth {
+  currency: string;
+  assets: number;
+  liabilities: number;
+  net_worth: number;
+  rate: number | null;
 }
 
 export interface NetWorthSnapshot {
@@ -13,4 +26,6 @@ export interface NetWorthSnapshot {
   total_assets: number;
   total_liabilities: number;
   net_worth: number;
+  by_currency: CurrencyNetWorth[];
+  missing_rates: MissingRate[];
 }
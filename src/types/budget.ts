@@ -1,10 +1,16 @@
 // File: src/types/budget.ts
+import type { MissingRate } from "./currency";
+
 export interface Budget {
   id: number;
   category_id: number;
   amount: number;
-  period: string; // "MONTHLY" | "YEARLY"
+  period: string; // "WEEKLY" | "BIWEEKLY" | "MONTHLY" | "QUARTERLY" | "YEARLY" | "CUSTOM"
   start_date: string;
+  end_date: string | null; // CUSTOM only
+  rollover_enabled: boolean;
+  rollover_cap: number | null;
+  saved_filter_id: number | null; // only the saved filter's transactions count
 }
 
 export interface CreateBudgetInput {
@@ -12,12 +18,31 @@ export interface CreateBudgetInput {
   amount: number;
   period: string;
   start_date: string;
+  end_date?: string; // required for CUSTOM
+  rollover_enabled?: boolean;
+  rollover_cap?: number; // 0 or less = no cap
+  saved_filter_id?: number;
 }
 
 export interface UpdateBudgetInput {
   id: number;
   amount?: number;
   start_date?: string;
+  end_date?: string; // CUSTOM only
+  rollover_enabled?: boolean;
+  rollover_cap?: number; // 0 or less removes the cap
+  saved_filter_id?: number; // 0 or less removes the saved filter
+}
+
+export interface BudgetPeriod {
+  period_start: string;
+  period_end: string;
+  budgeted: number;
+  carried_in: number;
+  available: number;
+  spent: number;
+  closing_balance: number;
+  carried_out: number;
 }
 
 export interface BudgetStatus {
@@ -26,7 +51,15 @@ export interface BudgetStatus {
   amount: number;
   period: string;
   start_date: string;
+  end_date: string | null;
+  rollover_enabled: boolean;
+  rollover_cap: number | null;
+  saved_filter_id: number | null;
   category_name: string;
+  period_start: string;
+  period_end: string;
+  carried_amount: number;
+  available_amount: number;
   spent_amount: number;
   remaining_amount: number;
   percentage_used: number;
@@ -34,6 +67,8 @@ export interface BudgetStatus {
   daily_average_spent: number;
   daily_budget_remaining: number;
   is_over_budget: boolean;
+  history: BudgetPeriod[];
+  missing_rates: MissingRate[];
 }
 
 export interface BudgetAlert {
@@ -1,5 +1,7 @@
 // File: src/types/credit_card.ts
 
+export type RewardType = "CASHBACK" | "POINTS";
+
 export interface CreditCardSettings {
   id: number;
   account_id: number;
@@ -11,6 +13,13 @@ export interface CreditCardSettings {
   settlement_account_id: number | null;
   created_at: string;
   updated_at: string;
+  apr: number;
+  late_fee: number;
+  annual_fee: number;
+  annual_fee_month: number | null;
+  fee_category_id: number | null;
+  reward_type: RewardType;
+  point_value: number;
 }
 
 export interface CreateCreditCardSettingsInput {
@@ -21,6 +30,13 @@ export interface CreateCreditCardSettingsInput {
   minimum_payment_percentage?: number;
   auto_settlement_enabled?: boolean;
   settlement_account_id?: number;
+  apr?: number;
+  late_fee?: number;
+  annual_fee?: number;
+  annual_fee_month?: number;
+  fee_category_id?: number;
+  reward_type?: RewardType;
+  point_value?: number;
 }
 
 export interface UpdateCreditCardSettingsInput {
@@ -31,6 +47,13 @@ export interface UpdateCreditCardSettingsInput {
   minimum_payment_percentage?: number;
   auto_settlement_enabled?: boolean;
   settlement_account_id?: number;
+  apr?: number;
+  late_fee?: number;
+  annual_fee?: number;
+  annual_fee_month?: number;
+  fee_category_id?: number;
+  reward_type?: RewardType;
+  point_value?: number;
 }
 
 export interface CreditCardWithDetails {
@@ -61,6 +84,9 @@ export interface CreditCardStatement {
   paid_amount: number;
   paid_date: string | null;
   created_at: string;
+  finance_charge: number;
+  late_fee: number;
+  assessed_at: string | null;
 }
 
 export interface StatementTransaction {
@@ -75,6 +101,7 @@ export interface StatementTransaction {
 export interface StatementWithTransactions {
   statement: CreditCardStatement;
   transactions: StatementTransaction[];
+  rewards: StatementRewards;
 }
 
 export interface SettlementInput {
@@ -101,3 +128,78 @@ export interface CreditCardSummary {
   next_due_amount: number | null;
   utilization_percentage: number;
 }
+
+export interface ProjectionMonth {
+  month: number;
+  payment: number;
+  interest: number;
+  balance: number;
+}
+
+export interface MinimumPaymentProjection {
+  starting_balance: number;
+  apr: number;
+  minimum_payment_percentage: number;
+  minimum_payment_floor: number;
+  pays_off: boolean;
+  months_to_payoff: number;
+  total_interest: number;
+  total_paid: number;
+  months: ProjectionMonth[];
+}
+
+export interface RewardRule {
+  id: number;
+  credit_card_id: number;
+  category_id: number | null;
+  category_name: string | null;
+  rate: number;
+  tier_threshold: number;
+  cycle_cap: number | null;
+  created_at: string;
+}
+
+export interface CreateRewardRuleInput {
+  credit_card_id: number;
+  category_id?: number;
+  rate: number;
+  tier_threshold?: number;
+  cycle_cap?: number;
+}
+
+export interface RewardLine {
+  rule_id: number;
+  category_id: number | null;
+  category_name: string | null;
+  spend: number;
+  rate: number;
+  reward: number;
+  capped: boolean;
+}
+
+export interface StatementRewards {
+  reward_type: RewardType;
+  total: number;
+  value: number;
+  lines: RewardLine[];
+}
+
+export interface RedeemRewardsInput {
+  credit_card_settings_id: number;
+  amount: number;
+  account_id?: number;
+  category_id?: number;
+  date?: string;
+}
+
+export interface CreditCardRewardSummary {
+  credit_card_id: number;
+  account_id: number;
+  account_name: string;
+  reward_type: RewardType;
+  earned: number;
+  redeemed: number;
+  balance: number;
+  balance_value: number;
+  current_cycle_accrual: number;
+}
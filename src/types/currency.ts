@@ -8,6 +8,8 @@ export interface ExchangeRate {
   effective_date: string;
   created_at: string;
   updated_at: string;
+  /** Set when the rate was triangulated through this base currency */
+  via_currency: string | null;
 }
 
 export interface SetExchangeRateInput {
@@ -30,6 +32,7 @@ export interface SupportedCurrency {
   code: string;
   name: string;
   symbol: string;
+  decimals: number;
 }
 
 export interface ExchangeRateSummary {
@@ -38,6 +41,27 @@ export interface ExchangeRateSummary {
   latest_rate: number;
   latest_date: string;
   rate_count: number;
+  days_since_update: number;
+  is_stale: boolean;
+}
+
+export interface ExchangeRateImportOptions {
+  /** CSV or ECB_XML; detected from the file when omitted */
+  format?: string;
+  /** Currency the file quotes against, EUR by default */
+  base_currency?: string;
+  currencies?: string[];
+  overwrite?: boolean;
+}
+
+export interface ExchangeRateImportResult {
+  format: string;
+  imported: number;
+  updated: number;
+  skipped: number;
+  errors: string[];
+  first_date: string | null;
+  last_date: string | null;
 }
 
 export interface ConvertedBalance {
@@ -49,3 +73,43 @@ export interface ConvertedBalance {
   converted_balance: number;
   rate_used: number; // 0.0 means no rate found
 }
+
+export interface CurrencyAmount {
+  currency: string;
+  amount: number;
+}
+
+/** Amounts left out of a converted total because no rate was effective */
+export interface MissingRate {
+  currency: string;
+  to_currency: string;
+  first_date: string;
+  last_date: string;
+  occurrences: number;
+}
+
+export interface FxPosting {
+  transaction_id: number;
+  date: string;
+  account_id: number;
+  account_name: string;
+  amount: number;
+  account_currency: string;
+  to_account_id: number;
+  to_account_name: string;
+  to_amount: number;
+  to_account_currency: string;
+  implied_rate: number;
+  source_value: number;
+  destination_value: number;
+  gain: number;
+}
+
+export interface FxGainReport {
+  start_date: string;
+  end_date: string;
+  currency: string;
+  total_gain: number;
+  postings: FxPosting[];
+  missing_rates: MissingRate[];
+}
@@ -15,6 +15,7 @@ export interface ColumnMapping {
   account_col: number | null;
   category_col: number | null;
   memo_col: number | null;
+  payee_col: number | null;
   date_format: string;
   negative_as_expense: boolean;
 }
@@ -31,6 +32,8 @@ export interface RowValidation {
   error: string | null;
   matched_account_id: number | null;
   matched_category_id: number | null;
+  /** Set when the category was suggested from history rather than matched */
+  category_confidence: number | null;
 }
 
 export interface ImportValidationResult {
@@ -1,4 +1,6 @@
 // File: src/types/installment.ts
+export type InterestMethod = "FLAT" | "REDUCING_BALANCE";
+
 export interface InstallmentPlan {
   id: number;
   name: string;
@@ -16,6 +18,11 @@ export interface InstallmentPlan {
   memo: string | null;
   created_at: string;
   updated_at: string;
+  apr: number;
+  interest_method: InterestMethod;
+  processing_fee: number;
+  liability_account_id: number | null;
+  interest_category_id: number | null;
 }
 
 export interface InstallmentPayment {
@@ -27,6 +34,10 @@ export interface InstallmentPayment {
   due_date: string;
   paid_date: string;
   created_at: string;
+  principal: number;
+  interest: number;
+  fee: number;
+  interest_transaction_id: number | null;
 }
 
 export interface InstallmentPaymentDetails {
@@ -46,6 +57,20 @@ export interface InstallmentPlanWithDetails {
   remaining_amount: number;
   remaining_installments: number;
   next_payment_amount: number;
+  total_interest: number;
+  total_payable: number;
+  schedule: AmortizationRow[];
+}
+
+export interface AmortizationRow {
+  installment_number: number;
+  due_date: string;
+  payment: number;
+  principal: number;
+  interest: number;
+  fee: number;
+  remaining_balance: number;
+  status: string;
 }
 
 export interface CreateInstallmentPlan {
@@ -57,4 +82,9 @@ export interface CreateInstallmentPlan {
   start_date: string;
   frequency: string;
   memo: string | null;
+  apr: number | null;
+  interest_method: InterestMethod | null;
+  processing_fee: number | null;
+  liability_account_id: number | null;
+  interest_category_id: number | null;
 }
@@ -0,0 +1,46 @@
+// File: src/types/payee.ts
+import type { MissingRate } from "./currency";
+
+export interface Payee {
+  id: number;
+  name: string;
+  default_category_id: number | null;
+  default_category_name: string | null;
+  aliases: string[];
+  tag_ids: number[];
+  transaction_count: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface CreatePayeeInput {
+  name: string;
+  default_category_id?: number | null;
+  aliases?: string[];
+  tag_ids?: number[];
+}
+
+export interface UpdatePayeeInput {
+  id: number;
+  name: string;
+  default_category_id: number | null;
+  aliases: string[];
+  tag_ids: number[];
+}
+
+export interface MergePayeesInput {
+  source_ids: number[];
+  target_id: number;
+}
+
+export interface PayeeSpending {
+  payee_id: number;
+  payee_name: string;
+  total_income: number;
+  total_expense: number;
+  transaction_count: number;
+  first_date: string;
+  last_date: string;
+  currency: string;
+  missing_rates: MissingRate[];
+}
@@ -1,4 +1,6 @@
 // File: src/types/report.ts
+import type { CurrencyAmount, MissingRate } from "./currency";
+
 export interface ReportFilters {
   startDate: string;
   endDate: string;
@@ -15,6 +17,10 @@ export interface MonthlyTrend {
   expense: number;
   net: number;
   transaction_count: number;
+  currency: string;
+  income_by_currency: CurrencyAmount[];
+  expense_by_currency: CurrencyAmount[];
+  missing_rates: MissingRate[];
 }
 
 export interface PeriodSummary {
@@ -0,0 +1,24 @@
+// File: src/types/savedFilter.ts
+import type { TransactionFilter } from "./transaction";
+import type { MissingRate } from "./currency";
+
+export interface SavedFilter {
+  id: number;
+  name: string;
+  filter: TransactionFilter;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface SavedFilterSummary {
+  saved_filter_id: number;
+  name: string;
+  start_date: string | null;
+  end_date: string | null;
+  total_income: number;
+  total_expense: number;
+  net: number;
+  transaction_count: number;
+  currency: string;
+  missing_rates: MissingRate[];
+}
@@ -1,4 +1,5 @@
 // File: src/types/tag.ts
+import type { MissingRate } from "./currency";
 
 export interface Tag {
   id: number;
@@ -32,4 +33,6 @@ export interface TagSpending {
   total_expense: number;
   transaction_count: number;
   percentage?: number;
+  currency: string;
+  missing_rates: MissingRate[];
 }
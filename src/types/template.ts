@@ -19,6 +19,8 @@ export interface TransactionTemplateWithDetails extends TransactionTemplate {
   account_name: string | null;
   to_account_name: string | null;
   category_name: string | null;
+  /** Tags suggested by categorization rules when the template is used */
+  tag_ids: number[];
 }
 
 export interface CreateTemplateInput {
@@ -1,4 +1,6 @@
 // File: src/types/transaction.ts
+import type { CurrencyAmount, MissingRate } from "./currency";
+
 export interface Transaction {
   id: number;
   date: string;
@@ -6,10 +8,13 @@ export interface Transaction {
   amount: number;
   account_id: number;
   to_account_id: number | null;
+  to_amount: number | null; // amount received by a transfer, in the destination currency
   category_id: number | null;
+  payee_id: number | null;
   memo: string | null;
   photo_path: string | null;
   created_at: string;
+  status: "UNCLEARED" | "CLEARED" | "RECONCILED";
 }
 
 export interface CreateTransactionInput {
@@ -18,20 +23,43 @@ export interface CreateTransactionInput {
   amount: number;
   account_id: number;
   to_account_id: number | null;
+  to_amount?: number | null; // required for transfers between currencies
   category_id: number | null;
+  payee_id?: number | null;
+  payee?: string | null; // name or alias; unknown names create a payee
   memo: string | null;
   tag_ids?: number[];
   goal_allocations?: { goal_id: number; amount: number }[];
   goal_withdrawals?: { goal_id: number; amount: number }[];
+  splits?: TransactionSplitInput[];
+}
+
+export interface TransactionSplitInput {
+  category_id: number;
+  amount: number;
+  memo: string | null;
+}
+
+export interface TransactionSplit {
+  id: number;
+  transaction_id: number;
+  category_id: number;
+  category_name: string | null;
+  amount: number;
+  memo: string | null;
 }
 
 export interface UpdateTransactionInput {
   id: number;
   date?: string;
   amount?: number;
+  to_amount?: number;
   category_id?: number;
+  payee_id?: number;
+  payee?: string; // "" removes the payee
   memo?: string;
   tag_ids?: number[];
+  splits?: TransactionSplitInput[];
 }
 
 export interface TransactionWithDetails {
@@ -41,15 +69,21 @@ export interface TransactionWithDetails {
   amount: number;
   account_id: number;
   to_account_id: number | null;
+  to_amount: number | null;
   category_id: number | null;
+  payee_id: number | null;
   memo: string | null;
   photo_path: string | null;
   created_at: string;
   account_name: string;
   to_account_name: string | null;
   category_name: string | null;
+  payee_name: string | null;
+  /** Matched text with <mark> around hits, when searching for words */
+  snippet: string | null;
   photo_count: number;
   tags: { id: number; name: string; color: string }[];
+  splits: TransactionSplit[];
 }
 
 export interface PhotoInfo {
@@ -64,14 +98,28 @@ export interface PhotoInfo {
 export interface TransactionFilter {
   start_date?: string;
   end_date?: string;
+  date_range?: string; // e.g. "last 3 months", used when no dates are given
+  saved_filter_id?: number; // fills the fields left unset here
   transaction_type?: string; // INCOME, EXPENSE, TRANSFER
   account_id?: number;
   category_id?: number;
+  payee_id?: number;
   search_query?: string;
   include_subcategories?: boolean;
   tag_ids?: number[];
 }
 
+// Position after the last transaction of a page; lists run newest first by date, then id
+export interface TransactionCursor {
+  date: string;
+  id: number;
+}
+
+export interface TransactionPage {
+  after?: TransactionCursor; // omitted for the first page
+  limit?: number;
+}
+
 export interface IncomeExpenseSummary {
   total_income: number;
   total_expense: number;
@@ -79,6 +127,10 @@ export interface IncomeExpenseSummary {
   transaction_count: number;
   start_date: string;
   end_date: string;
+  currency: string;
+  income_by_currency: CurrencyAmount[];
+  expense_by_currency: CurrencyAmount[];
+  missing_rates: MissingRate[];
 }
 
 export interface CategorySpending {
@@ -87,6 +139,9 @@ export interface CategorySpending {
   total_amount: number;
   transaction_count: number;
   percentage: number;
+  currency: string;
+  by_currency: CurrencyAmount[];
+  missing_rates: MissingRate[];
 }
 
 export interface DailySummary {
@@ -95,4 +150,36 @@ export interface DailySummary {
   total_expense: number;
   net: number;
   transaction_count: number;
+  currency: string;
+  income_by_currency: CurrencyAmount[];
+  expense_by_currency: CurrencyAmount[];
+  missing_rates: MissingRate[];
+}
+
+export interface SuggestionInput {
+  transaction_type: "INCOME" | "EXPENSE" | "TRANSFER";
+  memo?: string;
+  amount?: number;
+  account_id?: number;
+  date?: string;
+  limit?: number;
+}
+
+export interface CategorySuggestion {
+  category_id: number;
+  category_name: string;
+  confidence: number;
+}
+
+export interface TagSuggestion {
+  tag_id: number;
+  tag_name: string;
+  confidence: number;
+}
+
+export interface TransactionSuggestions {
+  categories: CategorySuggestion[];
+  tags: TagSuggestion[];
+  memo_recognized: boolean;
+  training_size: number;
 }
@@ -10,7 +10,11 @@ import Input from "../components/Input";
 import Select from "../components/Select";
 import { useToast } from "../components/Toast";
 import type { CreateRecurringTransactionInput } from "../types/recurring";
-import type { CreateInstallmentPlan } from "../types/installment";
+import type {
+  CreateInstallmentPlan,
+  InstallmentPlan,
+  InterestMethod,
+} from "../types/installment";
 import type { Account } from "../types/account";
 import type { CategoryWithChildren } from "../types/category";
 import type { TransactionTemplateWithDetails } from "../types/template";
@@ -43,6 +47,11 @@ const INITIAL_INSTALLMENT: CreateInstallmentPlan = {
   start_date: new Date().toISOString().split("T")[0],
   frequency: "MONTHLY",
   memo: null,
+  apr: null,
+  interest_method: "FLAT",
+  processing_fee: null,
+  liability_account_id: null,
+  interest_category_id: null,
 };
 
 export default function AdvancedView() {
@@ -158,10 +167,13 @@ export default function AdvancedView() {
 
     setInstallmentSubmitting(true);
     try {
-      await invoke("create_installment_plan", { plan: installmentForm });
+      const created = await invoke<InstallmentPlan>(
+        "create_installment_plan",
+        { plan: installmentForm },
+      );
       success(
         "Installment Created",
-        `"${installmentForm.name}" — ${installmentForm.num_installments} payments of LKR ${(installmentForm.total_amount / installmentForm.num_installments).toFixed(2)}`,
+        `"${created.name}" — ${created.num_installments} payments of LKR ${created.amount_per_installment.toFixed(2)}`,
       );
       setShowInstallmentForm(false);
       setInstallmentForm({ ...INITIAL_INSTALLMENT });
@@ -202,6 +214,16 @@ export default function AdvancedView() {
 
   const expenseCategoryOptions = getCategoryOptions("EXPENSE");
 
+  const optionalAccountOptions = [
+    { value: 0, label: "None" },
+    ...accounts.map((acc) => ({ value: acc.id, label: acc.name })),
+  ];
+
+  const optionalCategoryOptions = [
+    { value: 0, label: "Same as plan category" },
+    ...expenseCategoryOptions.slice(1),
+  ];
+
   // Helper for updating recurring form
   const updateR = (patch: Partial<CreateRecurringTransactionInput>) =>
     setRecurringForm((prev) => ({ ...prev, ...patch }));
@@ -637,6 +659,61 @@ export default function AdvancedView() {
                   value={installmentForm.start_date}
                   onChange={(e) => updateI({ start_date: e.target.value })}
                 />
+                <Input
+                  label="APR % (Optional)"
+                  type="number"
+                  value={installmentForm.apr ?? ""}
+                  onChange={(e) =>
+                    updateI({
+                      apr: e.target.value ? Number(e.target.value) : null,
+                    })
+                  }
+                />
+                <Select
+                  label="Interest Method"
+                  value={installmentForm.interest_method ?? "FLAT"}
+                  onChange={(e) =>
+                    updateI({
+                      interest_method: e.target.value as InterestMethod,
+                    })
+                  }
+                  options={[
+                    { value: "FLAT", label: "Flat rate" },
+                    { value: "REDUCING_BALANCE", label: "Reducing balance" },
+                  ]}
+                />
+                <Input
+                  label="Processing Fee (Optional)"
+                  type="number"
+                  value={installmentForm.processing_fee ?? ""}
+                  onChange={(e) =>
+                    updateI({
+                      processing_fee: e.target.value
+                        ? Number(e.target.value)
+                        : null,
+                    })
+                  }
+                />
+                <Select
+                  label="Liability Account (Optional)"
+                  value={installmentForm.liability_account_id ?? 0}
+                  onChange={(e) =>
+                    updateI({
+                      liability_account_id: Number(e.target.value) || null,
+                    })
+                  }
+                  options={optionalAccountOptions}
+                />
+                <Select
+                  label="Interest Category"
+                  value={installmentForm.interest_category_id ?? 0}
+                  onChange={(e) =>
+                    updateI({
+                      interest_category_id: Number(e.target.value) || null,
+                    })
+                  }
+                  options={optionalCategoryOptions}
+                />
                 <div className="md:col-span-2">
                   <Input
                     label="Memo (Optional)"
@@ -658,6 +735,7 @@ export default function AdvancedView() {
                         ).toFixed(2)
                       : "0.00"}
                   </span>
+                  {(installmentForm.apr ?? 0) > 0 && " + interest"}
                 </p>
               </div>
               <div className="flex justify-end mt-5">
@@ -208,13 +208,15 @@ export default function DashboardView() {
             startDate: prevStartDate,
             endDate: prevEndDate,
           }),
-          invoke<TransactionWithDetails[]>("get_transactions_with_details"),
+          invoke<TransactionWithDetails[]>("get_transactions_with_details", {
+            page: { limit: 10 },
+          }),
         ]);
 
       setAccounts(accountsData);
       setSummary(summaryData);
       setPrevSummary(prevSummaryData);
-      setRecentTransactions(transactionsData.slice(0, 10));
+      setRecentTransactions(transactionsData);
     } catch (error) {
       console.error("Failed to load dashboard data:", error);
     } finally {
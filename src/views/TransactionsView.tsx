@@ -37,6 +37,7 @@ export default function TransactionsView() {
     to_account_id?: number;
     amount?: number;
     memo?: string;
+    tag_ids?: number[];
   } | null>(null);
 
   // Filter state — default to current month